@@ -1,7 +1,8 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use tantalum_unit::c;
 use tantalum_unit::quantity::Quantity;
-use tantalum_unit::unit::Unit::{Coulomb, Joule, Kilo, Meter, Milli, Newton, Second, Volt, Watt};
+use tantalum_unit::scalable_integer::{BigRational, ScalableInteger};
+use tantalum_unit::unit::Unit::{Coulomb, Day, Gallon, Hour, Joule, Kelvin, Kilo, Liter, Meter, Mile, Milli, Newton, Pound, Second, Volt, Watt};
 use tantalum_unit::unit::Unit;
 
 fn mul(i: i64) -> Quantity {
@@ -36,12 +37,85 @@ fn convert(i: i64) -> (Quantity, Quantity, Quantity) {
     (result_a, result_b, result_c)
 }
 
+fn convert_repeated(quantity: &Quantity, targets: &[Unit]) -> Vec<Quantity> {
+    targets.iter().map(|to| quantity.clone().convert_to(to.clone()).unwrap()).collect()
+}
+
+fn convert_many(quantity: &Quantity, targets: &[Unit]) -> Vec<Quantity> {
+    quantity.convert_to_many(targets).into_iter().map(Result::unwrap).collect()
+}
+
+fn convert_incompatible(quantity: &Quantity, to: &Unit) -> usize {
+    (0..100).filter(|_| quantity.clone().convert_to(to.clone()).is_err()).count()
+}
+
+fn sum_naive(volumes: &[Quantity]) -> Quantity {
+    volumes.iter().fold(Quantity::from_i64_with_unit(0, Liter), |sum, volume| sum + volume.clone())
+}
+
+fn sum_in(volumes: &[Quantity]) -> Quantity {
+    Quantity::sum_in(&Liter, volumes).unwrap()
+}
+
+fn nested_si_units(unit: Unit) -> (BigRational, BigRational, Unit) {
+    unit.to_si_units()
+}
+
+fn flatten(units: &[Unit]) -> Vec<Unit> {
+    units.iter().map(|u| u.clone().flatten()).collect()
+}
+
+fn simplify(units: &[Unit]) -> Vec<Unit> {
+    units.iter().map(|u| u.clone().simplify()).collect()
+}
+
+fn format(quantity: &Quantity) -> String {
+    quantity.to_string()
+}
+
+fn scalable_add(n: i64) -> ScalableInteger {
+    let mut sum = ScalableInteger::from(0);
+    for i in 0..n {
+        sum += ScalableInteger::from(i);
+    }
+    sum
+}
+
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("mul", |b| b.iter(|| mul(black_box(20))));
     c.bench_function("div", |b| b.iter(|| div(black_box(20))));
     c.bench_function("add", |b| b.iter(|| add(black_box(20))));
     c.bench_function("sub", |b| b.iter(|| sub(black_box(20))));
     c.bench_function("convert", |b| b.iter(|| convert(black_box(20))));
+    let nested = c!(c!(Kilo, Watt, Hour; Meter, Meter); c!(Kelvin, Day; Mile, Gallon, Pound));
+    let quantity = Quantity::from_i64_with_unit(35, c!(Joule; Second));
+    let targets = [c!(Milli, Watt;), c!(Kilo, Coulomb, Volt; Second), c!(Newton, Meter; Second)];
+    c.bench_function("convert_repeated", |b| b.iter(|| convert_repeated(black_box(&quantity), black_box(&targets))));
+    c.bench_function("convert_many", |b| b.iter(|| convert_many(black_box(&quantity), black_box(&targets))));
+    let incompatible = Quantity::from_i64_with_unit(35, c!(Kilo, Watt, Hour; Meter, Meter));
+    let target = c!(Newton, Meter; Second, Second);
+    c.bench_function("convert_incompatible", |b| b.iter(|| convert_incompatible(black_box(&incompatible), black_box(&target))));
+    let volumes: Vec<Quantity> = (0..100_000)
+        .map(|i| Quantity::from_i64_with_unit(i % 100, if i % 2 == 0 { Liter } else { Gallon }))
+        .collect();
+    assert_eq!(sum_naive(&volumes), sum_in(&volumes));
+    let mut group = c.benchmark_group("sum");
+    group.sample_size(10);
+    group.bench_function("sum_naive", |b| b.iter(|| sum_naive(black_box(&volumes))));
+    group.bench_function("sum_in", |b| b.iter(|| sum_in(black_box(&volumes))));
+    group.finish();
+    c.bench_function("nested_si_units", |b| b.iter(|| nested_si_units(black_box(nested.clone()))));
+    let nested_units = [
+        c!(c!(Meter; Second); Second),
+        c!(Second; c!(Meter; Second)),
+        c!(c!(Meter; Second); c!(Meter; Second)),
+        c!(c!(c!(Watt; Joule); Second); c!(Meter; Second)),
+    ];
+    c.bench_function("flatten", |b| b.iter(|| flatten(black_box(&nested_units))));
+    c.bench_function("simplify", |b| b.iter(|| simplify(black_box(&nested_units))));
+    let quantity = Quantity::from_i64_with_unit(15, c!(Kilo, Watt, Hour; Meter, Meter));
+    c.bench_function("format", |b| b.iter(|| format(black_box(&quantity))));
+    c.bench_function("scalable_add", |b| b.iter(|| scalable_add(black_box(1000))));
 }
 
 criterion_group!(benches, criterion_benchmark);
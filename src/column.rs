@@ -0,0 +1,159 @@
+//! Parsing columns of tables, whose header names the unit and whose cells are bare numbers.
+//!
+//! The unit is taken from the end of the header, in parentheses like ```distance (km)```, in
+//! brackets like ```flow [gal/min]``` or as the last word like ```distance km```.
+//!
+//! # Example:
+//! ```
+//! use tantalum_unit::prelude::*;
+//! use tantalum_unit::column::{detect_unit_in_header, parse_column};
+//!
+//! assert_eq!(detect_unit_in_header("flow [gal/min]"), Some(Gallon / Minute));
+//!
+//! let distances = parse_column("distance (km)", ["1.5", "42"].into_iter()).unwrap();
+//! assert_eq!(distances, [Quantity::new(ratio!(3, 2), Kilo * Meter), Quantity::from_i64_with_unit(42, Kilo * Meter)]);
+//!
+//! let error = parse_column("distance (km)", ["1.5", "x"].into_iter()).unwrap_err();
+//! assert_eq!(error.row(), Some(1));
+//! ```
+
+use std::ops::Range;
+use crate::format::NumberLocale;
+use crate::parse::{parse_number, parse_unit_in, ParseQuantityError};
+use crate::quantity::Quantity;
+use crate::unit::{Unit, UNITLESS};
+
+/// Parses the cells of a column in the unit named by its header, with numbers written in the
+/// ```NumberLocale::EN_US``` conventions. See ```parse_column_with_locale```.
+pub fn parse_column<'a>(header: &str, values: impl Iterator<Item = &'a str>) -> Result<Vec<Quantity>, ParseQuantityError> {
+    parse_column_with_locale(header, values, &NumberLocale::EN_US)
+}
+
+/// Parses the cells of a column in the unit named by its header, with numbers written in the
+/// conventions of ```locale```.
+///
+/// A unit in parentheses or brackets must be valid. A last word that isn't a unit, like in
+/// ```count```, makes the column unitless. Each cell must be a number, exactly converted like in
+/// ```Quantity::from_str_with_locale```, so empty cells are errors too. Errors in a cell carry
+/// the index of the cell in ```ParseQuantityError::row```, starting at zero.
+///
+/// # Example:
+/// ```
+/// use tantalum_unit::prelude::*;
+/// use tantalum_unit::column::parse_column_with_locale;
+/// use tantalum_unit::format::NumberLocale;
+///
+/// let masses = parse_column_with_locale("Masse [kg]", ["1,5", "2"].into_iter(), &NumberLocale::DE_DE);
+/// assert_eq!(masses.unwrap()[0], Quantity::new(ratio!(3, 2), Kilo * Gram));
+/// ```
+pub fn parse_column_with_locale<'a>(header: &str, values: impl Iterator<Item = &'a str>, locale: &NumberLocale) -> Result<Vec<Quantity>, ParseQuantityError> {
+    let unit = match enclosed_unit(header) {
+        Some(range) => parse_unit_in(header, range)?,
+        None => trailing_unit(header).unwrap_or(UNITLESS),
+    };
+
+    values.enumerate()
+        .map(|(row, value)| {
+            let magnitude = parse_number(value, locale).map_err(|e| e.in_row(row))?;
+            Ok(Quantity::new(magnitude, unit.clone()))
+        })
+        .collect()
+}
+
+/// The unit named at the end of a header, in parentheses, in brackets or as the last word.
+///
+/// # Example:
+/// ```
+/// use tantalum_unit::prelude::*;
+/// use tantalum_unit::column::detect_unit_in_header;
+///
+/// assert_eq!(detect_unit_in_header("speed (m/s)"), Some(Meter / Second));
+/// assert_eq!(detect_unit_in_header("length in"), Some(Inch));
+/// assert_eq!(detect_unit_in_header("count"), None);
+/// ```
+pub fn detect_unit_in_header(header: &str) -> Option<Unit> {
+    match enclosed_unit(header) {
+        Some(range) => header[range].parse().ok(),
+        None => trailing_unit(header),
+    }
+}
+
+/// The range of the unit in parentheses or brackets at the end of a header.
+fn enclosed_unit(header: &str) -> Option<Range<usize>> {
+    let trimmed = header.trim_end();
+    let open = match trimmed.as_bytes().last()? {
+        b')' => '(',
+        b']' => '[',
+        _ => return None,
+    };
+
+    let start = trimmed.rfind(open)? + 1;
+    Some(start..trimmed.len() - 1)
+}
+
+/// The unit that is the last word of a header, if it is one.
+fn trailing_unit(header: &str) -> Option<Unit> {
+    header.split_whitespace().last()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{int, ratio};
+    use crate::parse::ErrorKind;
+    use crate::scalable_integer::BigRational;
+    use crate::unit::Unit::*;
+    use super::*;
+
+    #[test]
+    fn header_styles() {
+        assert_eq!(detect_unit_in_header("distance (km)"), Some(Kilo * Meter));
+        assert_eq!(detect_unit_in_header("flow [gal/min]"), Some(Gallon / Minute));
+        assert_eq!(detect_unit_in_header("area (m^2)  "), Some(Meter * Meter));
+        assert_eq!(detect_unit_in_header("rate (per year) [1/s]"), Some(UNITLESS / Second));
+        assert_eq!(detect_unit_in_header("temperature °C"), Some(Celsius));
+        assert_eq!(detect_unit_in_header("mass (foo)"), None);
+        assert_eq!(detect_unit_in_header("rows"), None);
+        assert_eq!(detect_unit_in_header(""), None);
+    }
+
+    #[test]
+    fn parse_columns() {
+        let cells = ["1", " 2.5 ", "-3e2", "1/4"];
+        let column = parse_column("flow [gal/min]", cells.into_iter()).unwrap_err();
+        assert_eq!((column.row(), column.kind()), (Some(3), ErrorKind::InvalidNumber));
+
+        let column = parse_column("flow [gal/min]", cells[..3].iter().copied()).unwrap();
+        let expected = [int!(1), ratio!(5, 2), int!(-300)].map(|m| Quantity::new(m, Gallon / Minute));
+        assert_eq!(column, expected);
+
+        let column = parse_column("distance (km)", ["0.1"].into_iter()).unwrap();
+        assert_eq!(column, [Quantity::new(ratio!(1, 10), Kilo * Meter)]);
+
+        // Without a unit in the header the column is unitless
+        assert_eq!(parse_column("count", ["7"].into_iter()).unwrap(), [Quantity::from_i64(7)]);
+    }
+
+    #[test]
+    fn parse_with_locale() {
+        let cells = ["1.234,5", "0,25"];
+        let column = parse_column_with_locale("Strecke (km)", cells.into_iter(), &NumberLocale::DE_DE).unwrap();
+        assert_eq!(column, [ratio!(12345, 10), ratio!(1, 4)].map(|m| Quantity::new(m, Kilo * Meter)));
+
+        let error = parse_column("distance (km)", cells.into_iter()).unwrap_err();
+        assert_eq!(error.row(), Some(0));
+    }
+
+    #[test]
+    fn row_errors() {
+        let error = parse_column("distance (km)", ["1", "", "3"].into_iter()).unwrap_err();
+        assert_eq!((error.row(), error.kind(), error.span()), (Some(1), ErrorKind::InvalidNumber, 0..0));
+
+        let error = parse_column("distance (km)", ["1", "2", "3 m"].into_iter()).unwrap_err();
+        assert_eq!((error.row(), error.span()), (Some(2), 2..3));
+        assert_eq!(error.to_string(), "row 2: invalid number at `m` in `3 m`");
+
+        // An invalid unit in the header isn't in any row
+        let error = parse_column("mass (foo)", ["1"].into_iter()).unwrap_err();
+        assert_eq!((error.row(), error.kind(), error.span()), (None, ErrorKind::UnknownSymbol, 6..9));
+    }
+}
@@ -0,0 +1,131 @@
+//! Irrational constants, approximated to a given number of decimal places.
+//!
+//! Like the functions in ```math```, the constant for a precision of ```p``` is its exact value
+//! rounded half to even to a multiple of ```10^-p```, so the error is strictly less than
+//! ```10^-p```. The most precise approximation of each constant computed so far is kept, so
+//! asking for the same or fewer digits again only rounds it, and the results for different
+//! precisions agree with each other.
+//!
+//! # Example:
+//! ```
+//! use tantalum_unit::prelude::*;
+//! use tantalum_unit::constants::{e, ln2, pi, sqrt2};
+//!
+//! assert_eq!(pi(4), ratio!(31416, 10000));
+//! assert_eq!(e(4), ratio!(27183, 10000));
+//! assert_eq!(ln2(4), ratio!(6931, 10000));
+//! assert_eq!(sqrt2(4), ratio!(14142, 10000));
+//! ```
+
+use std::sync::{Mutex, PoisonError};
+use num::{BigInt, One};
+use crate::math::{exp_fixed, from_fixed, ln2_fixed, pi_fixed, power_of_ten, GUARD_DIGITS};
+use crate::scalable_integer::BigRational;
+
+/// The most precise approximation of a constant so far: a number of decimal places, and the
+/// constant as a fixed point number with ```GUARD_DIGITS``` more places.
+type Cache = Mutex<Option<(u32, BigInt)>>;
+
+static PI: Cache = Mutex::new(None);
+static E: Cache = Mutex::new(None);
+static LN2: Cache = Mutex::new(None);
+static SQRT2: Cache = Mutex::new(None);
+
+/// pi rounded to ```precision_digits``` decimal places.
+pub fn pi(precision_digits: u32) -> BigRational {
+    cached(&PI, precision_digits, pi_fixed)
+}
+
+/// Euler's number rounded to ```precision_digits``` decimal places.
+pub fn e(precision_digits: u32) -> BigRational {
+    cached(&E, precision_digits, |scale| exp_fixed(&BigRational::one(), scale))
+}
+
+/// The natural logarithm of two rounded to ```precision_digits``` decimal places.
+pub fn ln2(precision_digits: u32) -> BigRational {
+    cached(&LN2, precision_digits, ln2_fixed)
+}
+
+/// The square root of two rounded to ```precision_digits``` decimal places.
+pub fn sqrt2(precision_digits: u32) -> BigRational {
+    cached(&SQRT2, precision_digits, |scale| (scale * scale * 2u32).sqrt())
+}
+
+/// Rounds the cached approximation of a constant, computing a more precise one first if needed.
+fn cached(cache: &Cache, digits: u32, compute: fn(&BigInt) -> BigInt) -> BigRational {
+    let mut cache = cache.lock().unwrap_or_else(PoisonError::into_inner);
+    let (cached_digits, value) = match &*cache {
+        Some((cached_digits, value)) if *cached_digits >= digits => (*cached_digits, value.clone()),
+        _ => {
+            let value = compute(&power_of_ten(digits + GUARD_DIGITS));
+            *cache = Some((digits, value.clone()));
+            (digits, value)
+        }
+    };
+    from_fixed(value, cached_digits - digits + GUARD_DIGITS, digits).magnitude
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigInt;
+    use crate::{int, ratio};
+    use crate::math::power_of_ten;
+    use crate::rounding::{round, RoundingMode};
+    use crate::scalable_integer::BigRational;
+    use super::*;
+
+    fn decimal(digits: &str, places: u32) -> BigRational {
+        BigRational::new(digits.parse::<BigInt>().unwrap().into(), power_of_ten(places).into())
+    }
+
+    #[test]
+    fn pi_digits() {
+        assert_eq!(pi(0), int!(3));
+        assert_eq!(pi(2), ratio!(314, 100));
+        assert_eq!(pi(4), ratio!(31416, 10000));
+
+        // The digits after the 50th decimal place are 5820..., so pi(50) rounds up
+        assert_eq!(pi(50), decimal("314159265358979323846264338327950288419716939937511", 50));
+    }
+
+    #[test]
+    fn e_digits() {
+        assert_eq!(e(30), decimal("2718281828459045235360287471353", 30));
+        assert_eq!(e(100), decimal("27182818284590452353602874713526624977572470936999595749669676277240766303535475945713821785251664274", 100));
+        assert_eq!(e(0), int!(3));
+    }
+
+    #[test]
+    fn ln2_digits() {
+        assert_eq!(ln2(30), decimal("693147180559945309417232121458", 30));
+        assert_eq!(ln2(100), decimal("6931471805599453094172321214581765680755001343602552541206800094933936219696947156058633269964186875", 100));
+        assert_eq!(ln2(0), int!(1));
+    }
+
+    #[test]
+    fn sqrt2_digits() {
+        assert_eq!(sqrt2(30), decimal("1414213562373095048801688724210", 30));
+        assert_eq!(sqrt2(100), decimal("14142135623730950488016887242096980785696718753769480731766797379907324784621070388503875343276415727", 100));
+        assert_eq!(sqrt2(0), int!(1));
+    }
+
+    #[test]
+    fn memoized_prefixes() {
+        let cache: Cache = Mutex::new(None);
+        let sqrt = |digits| cached(&cache, digits, |scale| (scale * scale * 2u32).sqrt());
+        let precise = sqrt(60);
+        assert_eq!(cache.lock().unwrap().as_ref().map(|(digits, _)| *digits), Some(60));
+
+        // Fewer digits round the cached value instead of computing a new one
+        for digits in 0..=60 {
+            let scale = BigRational::from_integer(power_of_ten(digits).into());
+            let rounded = BigRational::new(round(&(&precise * &scale), RoundingMode::HalfEven), scale.to_integer());
+            assert_eq!(sqrt(digits), rounded);
+        }
+        assert_eq!(cache.lock().unwrap().as_ref().map(|(digits, _)| *digits), Some(60));
+
+        assert_eq!(sqrt(80), sqrt2(80));
+        assert_eq!(cache.lock().unwrap().as_ref().map(|(digits, _)| *digits), Some(80));
+        assert_eq!(sqrt(30), sqrt2(30));
+    }
+}
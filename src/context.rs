@@ -0,0 +1,160 @@
+//! Opt-in bounding of the magnitudes computed by arithmetic and conversions.
+//!
+//! Exact arithmetic on rationals can produce huge numerators and denominators over a long chain
+//! of operations. Within ```with_context``` and a ```MathContext``` with
+//! ```max_denominator_digits``` set, the results of ```+```, ```-```, ```*``` and ```/``` between
+//! quantities and of conversions are replaced by the closest rational whose denominator has at
+//! most that many digits, see ```scalable_integer::best_approximation```. Each such result is off
+//! by at most ```10^-d / 2``` for ```d``` digits. Outside of a context, results are exact.
+//!
+//! # Example:
+//! ```
+//! use tantalum_unit::prelude::*;
+//! use tantalum_unit::context::{with_context, MathContext};
+//!
+//! let meter = Quantity::from_i64_with_unit(1, Meter);
+//! assert_eq!(meter.clone().convert_to(Inch).unwrap().magnitude, ratio!(5000, 127));
+//!
+//! let inches = with_context(MathContext::bounded(2), || meter.convert_to(Inch).unwrap());
+//! assert_eq!(inches.magnitude, ratio!(3937, 100));
+//! ```
+
+use std::cell::Cell;
+use num::BigInt;
+use crate::quantity::Quantity;
+use crate::scalable_integer::{best_approximation, BigRational};
+
+thread_local! {
+    static CURRENT: Cell<MathContext> = const { Cell::new(MathContext::EXACT) };
+}
+
+/// How the results of arithmetic and conversions are bounded on the current thread.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MathContext {
+    /// The largest number of decimal digits of the denominator of a result, or ```None``` to
+    /// keep results exact.
+    pub max_denominator_digits: Option<u32>,
+}
+
+impl MathContext {
+    /// The context outside of ```with_context```, which keeps results exact.
+    pub const EXACT: MathContext = MathContext { max_denominator_digits: None };
+
+    /// A context that limits denominators to ```max_denominator_digits``` decimal digits.
+    pub const fn bounded(max_denominator_digits: u32) -> MathContext {
+        MathContext { max_denominator_digits: Some(max_denominator_digits) }
+    }
+
+    /// The context of the current thread.
+    pub fn current() -> MathContext {
+        CURRENT.with(Cell::get)
+    }
+
+    /// The value closest to ```value``` that the context allows.
+    pub fn bound(&self, value: BigRational) -> BigRational {
+        let Some(digits) = self.max_denominator_digits else {
+            return value;
+        };
+        let max_denominator = num::pow(BigInt::from(10), digits as usize).into();
+        if *value.denom() <= max_denominator {
+            return value;
+        }
+        best_approximation(&value, &max_denominator)
+    }
+}
+
+/// Runs ```f``` with ```context``` as the context of the current thread, restoring the previous
+/// context afterwards, even if ```f``` panics.
+pub fn with_context<T>(context: MathContext, f: impl FnOnce() -> T) -> T {
+    struct Restore(MathContext);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CURRENT.with(|current| current.set(self.0));
+        }
+    }
+
+    let _restore = Restore(CURRENT.with(|current| current.replace(context)));
+    f()
+}
+
+impl Quantity {
+    /// The Quantity with its magnitude bounded by the context of the current thread.
+    pub(crate) fn bounded(mut self) -> Quantity {
+        let context = MathContext::current();
+        if context.max_denominator_digits.is_some() {
+            self.magnitude = context.bound(self.magnitude);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::Zero;
+    use crate::ratio;
+    use crate::context::{with_context, MathContext};
+    use crate::quantity::Quantity;
+    use crate::scalable_integer::{BigRational, ScalableInteger};
+    use crate::unit::Unit::*;
+    use crate::unit::Unit;
+
+    /// Converts a volume back and forth between units 100 times.
+    fn conversion_chain() -> Quantity {
+        let units = [Gallon, Liter, CubicInch, Pint, CubicFeet];
+        let mut quantity = Quantity::new(ratio!(7, 3), Liter);
+        for i in 0..100 {
+            let unit: Unit = units[i % units.len()].clone();
+            quantity = quantity.convert_to(unit).unwrap();
+        }
+        quantity.convert_to(Liter).unwrap()
+    }
+
+    #[test]
+    fn exact_by_default() {
+        assert_eq!(MathContext::current(), MathContext::EXACT);
+        assert_eq!(conversion_chain(), Quantity::new(ratio!(7, 3), Liter));
+        let quotient = Quantity::from_i64(1) / Quantity::from_i64(7);
+        assert_eq!(quotient.magnitude, ratio!(1, 7));
+    }
+
+    #[test]
+    fn bounded_conversion_chain() {
+        let exact = conversion_chain().magnitude;
+        let bounded = with_context(MathContext::bounded(20), conversion_chain).magnitude;
+        assert!(*bounded.denom() <= ScalableInteger::from(10u128.pow(20)));
+
+        // Each step is off by at most 10^-20 / 2, scaled by the later conversions
+        let difference = &bounded - &exact;
+        let error = if difference < BigRational::zero() { -difference } else { difference };
+        assert!(error < ratio!(1, 10u128.pow(17)), "{error}");
+        assert_eq!(MathContext::current(), MathContext::EXACT);
+    }
+
+    #[test]
+    fn bounded_arithmetic() {
+        with_context(MathContext::bounded(1), || {
+            assert_eq!((Quantity::from_i64(1) / Quantity::from_i64(7)).magnitude, ratio!(1, 7));
+            assert_eq!((Quantity::from_i64(1) / Quantity::from_i64(11)).magnitude, ratio!(1, 10));
+            let sum = Quantity::new(ratio!(1, 3), Meter) + Quantity::new(ratio!(1, 7), Meter);
+            assert_eq!(sum.magnitude, ratio!(1, 2));
+            let product = Quantity::new(ratio!(1, 3), Meter) * Quantity::new(ratio!(1, 7), Meter);
+            assert_eq!(product.magnitude, ratio!(0, 1));
+            let difference = Quantity::new(ratio!(1, 3), Meter) - Quantity::new(ratio!(1, 7), Meter);
+            assert_eq!(difference.magnitude, ratio!(1, 5));
+        });
+        assert_eq!(MathContext::bounded(3).bound(ratio!(355, 113)), ratio!(355, 113));
+        assert_eq!(MathContext::EXACT.bound(ratio!(1, 12345)), ratio!(1, 12345));
+    }
+
+    #[test]
+    fn nested_contexts() {
+        with_context(MathContext::bounded(5), || {
+            with_context(MathContext::EXACT, || assert_eq!(MathContext::current(), MathContext::EXACT));
+            assert_eq!(MathContext::current(), MathContext::bounded(5));
+        });
+        let panicked = std::panic::catch_unwind(|| with_context(MathContext::bounded(2), || panic!("in context")));
+        assert!(panicked.is_err());
+        assert_eq!(MathContext::current(), MathContext::EXACT);
+    }
+}
@@ -0,0 +1,136 @@
+//! Conversions between ```Quantity``` and ```rust_decimal::Decimal```.
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use rust_decimal::Decimal;
+use crate::quantity::Quantity;
+use crate::rounding::{round_to_scale, RoundingMode};
+use crate::scalable_integer::{BigRational, ScalableInteger};
+use crate::unit::Unit;
+
+/// An error converting a ```Quantity``` into a ```Decimal```.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecimalError {
+    /// The requested scale is larger than the 28 decimal places a ```Decimal``` can represent.
+    ScaleTooLarge(u32),
+    /// The rounded value doesn't fit into the 96 bit mantissa of a ```Decimal```.
+    Overflow,
+}
+
+impl Display for DecimalError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecimalError::ScaleTooLarge(scale) => write!(f, "a scale of {scale} exceeds the maximum decimal scale of {}", Decimal::MAX_SCALE),
+            DecimalError::Overflow => write!(f, "the value doesn't fit into a decimal"),
+        }
+    }
+}
+
+impl Error for DecimalError {}
+
+impl Quantity {
+    /// Constructs a Quantity from a ```Decimal```. The conversion is exact.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    /// use rust_decimal::Decimal;
+    ///
+    /// let price = Quantity::from_decimal(Decimal::new(1999, 2), Liter); // 19.99L
+    /// assert_eq!(price, Quantity::new(ratio!(1999, 100), Liter));
+    /// ```
+    pub fn from_decimal(value: Decimal, unit: Unit) -> Self {
+        let denominator = num::pow(ScalableInteger::from(10), value.scale() as usize);
+        Self::new(BigRational::new(value.mantissa().into(), denominator), unit)
+    }
+
+    /// Rounds the magnitude to ```scale``` decimal places and returns it as a ```Decimal```.
+    ///
+    /// Returns an error if the scale is larger than ```Decimal::MAX_SCALE``` or if the rounded
+    /// value doesn't fit into a ```Decimal```.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    /// use rust_decimal::Decimal;
+    /// use tantalum_unit::rounding::RoundingMode;
+    ///
+    /// let length = Quantity::new(ratio!(2, 3), Meter);
+    /// let decimal = length.to_decimal(3, RoundingMode::HalfEven).unwrap();
+    ///
+    /// assert_eq!(decimal, Decimal::new(667, 3)); // 0.667
+    /// ```
+    pub fn to_decimal(&self, scale: u32, mode: RoundingMode) -> Result<Decimal, DecimalError> {
+        if scale > Decimal::MAX_SCALE {
+            return Err(DecimalError::ScaleTooLarge(scale));
+        }
+
+        let mantissa = match round_to_scale(&self.magnitude, scale, mode) {
+            ScalableInteger::Single(n) => n.into(),
+            ScalableInteger::Double(n) => n,
+            ScalableInteger::Big(_) => return Err(DecimalError::Overflow),
+        };
+
+        Decimal::try_from_i128_with_scale(mantissa, scale).map_err(|_| DecimalError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use crate::ratio;
+    use crate::unit::Unit::*;
+    use super::*;
+
+    #[test]
+    fn from_decimal_is_exact() {
+        let value = Decimal::from_str("0.0000000000000000000000000001").unwrap();
+        let result = Quantity::from_decimal(value, Meter);
+        assert_eq!(result.magnitude, BigRational::new(1.into(), num::pow(ScalableInteger::from(10), 28)));
+
+        let result = Quantity::from_decimal(Decimal::MAX, Gram);
+        assert_eq!(result.magnitude, BigRational::from_integer(79_228_162_514_264_337_593_543_950_335i128.into()));
+
+        let result = Quantity::from_decimal(Decimal::new(-12345, 3), Second);
+        assert_eq!(result, Quantity::new(ratio!(-12345, 1000), Second));
+    }
+
+    #[test]
+    fn to_decimal_rounds() {
+        let a = Quantity::new(ratio!(-5, 8), Meter);
+        assert_eq!(a.to_decimal(2, RoundingMode::HalfEven).unwrap(), Decimal::new(-62, 2));
+        assert_eq!(a.to_decimal(2, RoundingMode::HalfUp).unwrap(), Decimal::new(-63, 2));
+        assert_eq!(a.to_decimal(0, RoundingMode::Floor).unwrap(), Decimal::new(-1, 0));
+
+        let a = Quantity::new(ratio!(1, 3), Meter);
+        let result = a.to_decimal(28, RoundingMode::HalfUp).unwrap();
+        assert_eq!(result, Decimal::from_str("0.3333333333333333333333333333").unwrap());
+    }
+
+    #[test]
+    fn to_decimal_round_trip() {
+        for text in ["79228162514264337593543950335", "-7.9228162514264337593543950335", "0.0000000000000000000000000001"] {
+            let value = Decimal::from_str(text).unwrap();
+            let result = Quantity::from_decimal(value, Liter).to_decimal(value.scale(), RoundingMode::HalfEven);
+            assert_eq!(result, Ok(value));
+        }
+    }
+
+    #[test]
+    fn to_decimal_overflow() {
+        // One more than the largest 96 bit mantissa
+        let a = Quantity::new(BigRational::from_integer(79_228_162_514_264_337_593_543_950_336i128.into()), Meter);
+        assert_eq!(a.to_decimal(0, RoundingMode::HalfEven), Err(DecimalError::Overflow));
+
+        // Fits without decimal places, but not with 10 of them
+        let a = Quantity::new(BigRational::from_integer(10_000_000_000_000_000_000i128.into()), Meter);
+        assert!(a.to_decimal(0, RoundingMode::HalfEven).is_ok());
+        assert_eq!(a.to_decimal(10, RoundingMode::HalfEven), Err(DecimalError::Overflow));
+
+        let a = Quantity::new(BigRational::from_integer(num::pow(ScalableInteger::from(10), 40)), Meter);
+        assert_eq!(a.to_decimal(0, RoundingMode::HalfEven), Err(DecimalError::Overflow));
+
+        let a = Quantity::from_i64_with_unit(1, Meter);
+        assert_eq!(a.to_decimal(29, RoundingMode::HalfEven), Err(DecimalError::ScaleTooLarge(29)));
+    }
+}
@@ -0,0 +1,229 @@
+//! The dimension of a unit, like length or mass per volume, independent of the unit itself.
+//!
+//! A dimension holds the exponents of the base dimensions a unit is made of, so formulas can be
+//! checked without converting any quantity. Angles and pixels are counts in the SI, so they are
+//! dimensionless here.
+//!
+//! # Example:
+//! ```
+//! use tantalum_unit::prelude::*;
+//! use tantalum_unit::dimension::Dimension;
+//!
+//! let energy = Dimension::MASS * Dimension::LENGTH.pow(2) / Dimension::TIME.pow(2);
+//! assert_eq!(Joule.dimension(), energy);
+//! assert_eq!((Kilo * Watt * Hour).dimension(), energy);
+//! assert_eq!(Watt.dimension().to_string(), "L^2 M T^-3");
+//! ```
+
+use std::fmt::{Display, Formatter};
+use std::ops::{Div, Mul};
+use num::traits::Pow;
+use crate::quantity::Quantity;
+use crate::unit::Unit;
+use crate::unit::Unit::*;
+
+/// The exponents of the base dimensions of a unit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Dimension {
+    pub length: i32,
+    pub mass: i32,
+    pub time: i32,
+    pub current: i32,
+    pub temperature: i32,
+    pub amount: i32,
+    pub luminous_intensity: i32,
+    pub information: i32,
+}
+
+impl Dimension {
+    /// The dimension of unitless values.
+    pub const DIMENSIONLESS: Dimension = Dimension {
+        length: 0,
+        mass: 0,
+        time: 0,
+        current: 0,
+        temperature: 0,
+        amount: 0,
+        luminous_intensity: 0,
+        information: 0,
+    };
+    pub const LENGTH: Dimension = Dimension { length: 1, ..Self::DIMENSIONLESS };
+    pub const MASS: Dimension = Dimension { mass: 1, ..Self::DIMENSIONLESS };
+    pub const TIME: Dimension = Dimension { time: 1, ..Self::DIMENSIONLESS };
+    pub const CURRENT: Dimension = Dimension { current: 1, ..Self::DIMENSIONLESS };
+    pub const TEMPERATURE: Dimension = Dimension { temperature: 1, ..Self::DIMENSIONLESS };
+    pub const AMOUNT: Dimension = Dimension { amount: 1, ..Self::DIMENSIONLESS };
+    pub const LUMINOUS_INTENSITY: Dimension = Dimension { luminous_intensity: 1, ..Self::DIMENSIONLESS };
+    pub const INFORMATION: Dimension = Dimension { information: 1, ..Self::DIMENSIONLESS };
+
+    /// Raises the dimension to an integer power, multiplying every exponent. Like ```Pow```, but
+    /// usable without importing the trait.
+    pub fn pow(self, exponent: i32) -> Dimension {
+        self.zip_with(Self::DIMENSIONLESS, |a, _| a * exponent)
+    }
+
+    /// Checks if every exponent is zero.
+    pub fn is_dimensionless(&self) -> bool {
+        *self == Self::DIMENSIONLESS
+    }
+
+    /// The exponents with their symbols, in the order they are displayed.
+    fn exponents(&self) -> [(&'static str, i32); 8] {
+        [
+            ("L", self.length),
+            ("M", self.mass),
+            ("T", self.time),
+            ("I", self.current),
+            ("Θ", self.temperature),
+            ("N", self.amount),
+            ("J", self.luminous_intensity),
+            ("D", self.information),
+        ]
+    }
+
+    /// Combines the exponents of two dimensions.
+    fn zip_with(self, other: Dimension, f: impl Fn(i32, i32) -> i32) -> Dimension {
+        Dimension {
+            length: f(self.length, other.length),
+            mass: f(self.mass, other.mass),
+            time: f(self.time, other.time),
+            current: f(self.current, other.current),
+            temperature: f(self.temperature, other.temperature),
+            amount: f(self.amount, other.amount),
+            luminous_intensity: f(self.luminous_intensity, other.luminous_intensity),
+            information: f(self.information, other.information),
+        }
+    }
+}
+
+impl Mul for Dimension {
+    type Output = Dimension;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a + b)
+    }
+}
+
+impl Div for Dimension {
+    type Output = Dimension;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self.zip_with(rhs, |a, b| a - b)
+    }
+}
+
+impl Pow<i32> for Dimension {
+    type Output = Dimension;
+
+    fn pow(self, rhs: i32) -> Self::Output {
+        Dimension::pow(self, rhs)
+    }
+}
+
+/// Displays the base dimensions with their exponents, like ```L^2 M T^-3```, using the ISQ symbols
+/// ```L M T I Θ N J``` and ```D``` for information. The dimension of unitless values is ```1```.
+impl Display for Dimension {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_dimensionless() {
+            return f.write_str("1");
+        }
+
+        let parts: Vec<String> = self.exponents()
+            .into_iter()
+            .filter(|(_, exponent)| *exponent != 0)
+            .map(|(symbol, exponent)| match exponent {
+                1 => symbol.to_owned(),
+                _ => format!("{symbol}^{exponent}"),
+            })
+            .collect();
+        f.write_str(&parts.join(" "))
+    }
+}
+
+impl Unit {
+    /// The dimension of the unit, derived from the SI units it is made of.
+    ///
+    /// Prefixes, slopes and offsets don't matter, so ```Celsius``` is a temperature like
+    /// ```Kelvin``` and modifiers are dimensionless.
+    pub fn dimension(&self) -> Dimension {
+        let exponents = self.si_exponents();
+        let exponent = |unit: Unit| exponents[unit.index().expect("base units are named")];
+        Dimension {
+            length: exponent(Meter),
+            mass: exponent(Gram),
+            time: exponent(Second),
+            current: exponent(Ampere),
+            temperature: exponent(Kelvin),
+            amount: exponent(Mole),
+            luminous_intensity: exponent(Candela),
+            information: exponent(Bit),
+        }
+    }
+}
+
+impl Quantity {
+    /// The dimension of the unit of the Quantity, see ```Unit::dimension```.
+    pub fn dimension(&self) -> Dimension {
+        self.unit.dimension()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::unit::UNITLESS;
+    use super::*;
+
+    #[test]
+    fn base_units() {
+        assert_eq!(Meter.dimension(), Dimension::LENGTH);
+        assert_eq!(Gram.dimension(), Dimension::MASS);
+        assert_eq!(Hour.dimension(), Dimension::TIME);
+        assert_eq!(Ampere.dimension(), Dimension::CURRENT);
+        assert_eq!(Celsius.dimension(), Dimension::TEMPERATURE);
+        assert_eq!(Mole.dimension(), Dimension::AMOUNT);
+        assert_eq!(Candela.dimension(), Dimension::LUMINOUS_INTENSITY);
+        assert_eq!(Byte.dimension(), Dimension::INFORMATION);
+    }
+
+    #[test]
+    fn dimensionless_units() {
+        assert!(UNITLESS.dimension().is_dimensionless());
+        assert!(Kilo.dimension().is_dimensionless());
+        assert!(Degree.dimension().is_dimensionless());
+        assert!(Pixel.dimension().is_dimensionless());
+        assert!((Meter / Meter).dimension().is_dimensionless());
+        assert_eq!(Percent.dimension(), Dimension::default());
+    }
+
+    #[test]
+    fn derived_units() {
+        let force = Dimension::MASS * Dimension::LENGTH / Dimension::TIME.pow(2);
+        assert_eq!(Newton.dimension(), force);
+        assert_eq!((Pound * Feet / (Second * Second)).dimension(), force);
+        assert_eq!(Joule.dimension(), force * Dimension::LENGTH);
+        assert_eq!(Hertz.dimension(), Dimension::TIME.pow(-1));
+        assert_eq!(Liter.dimension(), Dimension::LENGTH.pow(3));
+        assert_eq!(Unit::Power(Box::new(Second), -2).dimension(), Dimension::TIME.pow(-2));
+        assert_eq!(Ohm.dimension() * Siemens.dimension(), Dimension::DIMENSIONLESS);
+        assert_eq!(Joule.dimension().pow(0), Dimension::DIMENSIONLESS);
+        assert_eq!(Pow::pow(Meter.dimension(), 3), Liter.dimension());
+        assert_eq!((Mega * Bit / Second).dimension(), Dimension::INFORMATION / Dimension::TIME);
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(Watt.dimension().to_string(), "L^2 M T^-3");
+        assert_eq!(Meter.dimension().to_string(), "L");
+        assert_eq!(Volt.dimension().to_string(), "L^2 M T^-3 I^-1");
+        assert_eq!((Mole / Liter).dimension().to_string(), "L^-3 N");
+        assert_eq!(Kelvin.dimension().to_string(), "Θ");
+        assert_eq!(UNITLESS.dimension().to_string(), "1");
+    }
+
+    #[test]
+    fn quantity_dimension() {
+        let energy = Quantity::from_i64_with_unit(3, Kilo * Watt * Hour);
+        assert_eq!(energy.dimension(), Joule.dimension());
+        assert!(Quantity::from_i64(3).dimension().is_dimensionless());
+    }
+}
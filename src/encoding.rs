@@ -0,0 +1,389 @@
+//! A compact binary encoding for ```Quantity```.
+//!
+//! An encoded quantity is laid out as:
+//!
+//! | Field       | Encoding                                                                  |
+//! |-------------|---------------------------------------------------------------------------|
+//! | version     | one byte, currently ```FORMAT_VERSION```                                  |
+//! | numerator   | signed varint                                                             |
+//! | denominator | signed varint                                                             |
+//! | unit        | a unit                                                                    |
+//!
+//! Integers are stored as LEB128 varints of ```|n| << 1 | sign```, so small values of either sign
+//! take a single byte and ```Big``` values of any size are supported.
+//!
+//! A named unit is stored as its little endian ```u16``` tag, which is its position in
+//! ```Unit::NAMED_UNITS```. New units must therefore only ever be appended to ```define_units!```.
+//! A compound unit is stored as the tag ```COMPOUND_TAG```, followed by the varint length and
+//! the units of its numerator and then of its denominator. A power of a unit is stored as the
+//! tag ```POWER_TAG```, followed by the exponent as a signed varint and the unit. Converting a
+//! power raises the slopes of its units to the exponent, so decoders reject nested powers whose
+//! exponents multiply to more than the exponents the parser accepts.
+//!
+//! Decoders reject versions they don't know, so the layout can change in the future by bumping
+//! the version byte.
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use num::{BigInt, BigUint, ToPrimitive, Zero};
+use num::bigint::Sign;
+use crate::quantity::Quantity;
+use crate::scalable_integer::{BigRational, ScalableInteger};
+use crate::parse::MAX_EXPONENT;
+use crate::unit::Unit;
+use crate::unit::Unit::{Compound, Power};
+
+/// The version byte written at the start of every encoded quantity.
+pub const FORMAT_VERSION: u8 = 1;
+
+/// The unit tag marking a compound unit.
+pub const COMPOUND_TAG: u16 = u16::MAX;
+
+/// The unit tag marking a power of a unit.
+pub const POWER_TAG: u16 = u16::MAX - 1;
+
+/// How deeply compound units may be nested before decoding gives up.
+const MAX_DEPTH: usize = 32;
+
+/// Varints of up to this many bytes hold at most 126 bits and are decoded without a ```BigUint```.
+const SMALL_VARINT_BYTES: usize = 18;
+
+/// An error decoding a ```Quantity``` from bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The input ended in the middle of a quantity.
+    UnexpectedEnd,
+    /// The quantity was encoded with a format version this version of the crate can't read.
+    UnsupportedVersion(u8),
+    /// A unit tag doesn't correspond to any known unit.
+    UnknownUnit(u16),
+    /// The denominator of the magnitude is zero.
+    ZeroDenominator,
+    /// The length of a compound unit doesn't fit into a ```usize```.
+    LengthOverflow,
+    /// The exponents of nested powers multiply to more than a parsed unit may have.
+    ExponentOverflow,
+    /// Compound units are nested more deeply than the decoder allows.
+    TooDeeplyNested,
+    /// The input continues after the quantity.
+    TrailingBytes(usize),
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd => write!(f, "unexpected end of input"),
+            DecodeError::UnsupportedVersion(version) => write!(f, "unsupported format version {version}"),
+            DecodeError::UnknownUnit(tag) => write!(f, "unknown unit tag {tag}"),
+            DecodeError::ZeroDenominator => write!(f, "the denominator is zero"),
+            DecodeError::LengthOverflow => write!(f, "the length of a compound unit is too large"),
+            DecodeError::ExponentOverflow => write!(f, "the exponent of a power is too large"),
+            DecodeError::TooDeeplyNested => write!(f, "compound units are nested more than {MAX_DEPTH} levels deep"),
+            DecodeError::TrailingBytes(count) => write!(f, "{count} unexpected bytes after the quantity"),
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+impl Quantity {
+    /// Encodes the quantity in the compact binary format described in the ```encoding``` module.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let speed = Quantity::new(ratio!(27, 2), Kilo * Meter / Hour);
+    /// let bytes = speed.to_bytes();
+    ///
+    /// assert_eq!(bytes.len(), 13);
+    /// assert_eq!(Quantity::from_bytes(&bytes), Ok(speed));
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        self.write_bytes(&mut bytes);
+        bytes
+    }
+
+    /// Appends the encoded quantity to ```out```, e.g. to store many quantities in one buffer.
+    pub fn write_bytes(&self, out: &mut Vec<u8>) {
+        out.push(FORMAT_VERSION);
+        write_integer(self.magnitude.numer(), out);
+        write_integer(self.magnitude.denom(), out);
+        write_unit(&self.unit, out);
+    }
+
+    /// Decodes a quantity encoded with ```to_bytes```, requiring the input to hold exactly one quantity.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut input = bytes;
+        let quantity = Self::read_bytes(&mut input)?;
+
+        if input.is_empty() {
+            Ok(quantity)
+        } else {
+            Err(DecodeError::TrailingBytes(input.len()))
+        }
+    }
+
+    /// Decodes the quantity at the start of ```input``` and advances it past the quantity.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let mut buffer = Vec::new();
+    /// Quantity::from_i64_with_unit(3, Meter).write_bytes(&mut buffer);
+    /// Quantity::from_i64_with_unit(5, Second).write_bytes(&mut buffer);
+    ///
+    /// let mut input = &buffer[..];
+    /// assert_eq!(Quantity::read_bytes(&mut input), Ok(Quantity::from_i64_with_unit(3, Meter)));
+    /// assert_eq!(Quantity::read_bytes(&mut input), Ok(Quantity::from_i64_with_unit(5, Second)));
+    /// assert!(input.is_empty());
+    /// ```
+    pub fn read_bytes(input: &mut &[u8]) -> Result<Self, DecodeError> {
+        let version = read_byte(input)?;
+        if version != FORMAT_VERSION {
+            return Err(DecodeError::UnsupportedVersion(version));
+        }
+
+        let numerator = read_integer(input)?;
+        let denominator = read_integer(input)?;
+        if denominator.is_zero() {
+            return Err(DecodeError::ZeroDenominator);
+        }
+
+        let unit = read_unit(input, 0, 1)?;
+        Ok(Quantity::new(BigRational::new(numerator, denominator), unit))
+    }
+}
+
+fn write_integer(value: &ScalableInteger, out: &mut Vec<u8>) {
+    match value {
+        ScalableInteger::Single(n) => write_varint(u128::from(n.unsigned_abs()) << 1 | u128::from(*n < 0), out),
+        // i128::MIN is the only Double whose shifted magnitude doesn't fit into a u128
+        ScalableInteger::Double(n) if *n != i128::MIN => write_varint(n.unsigned_abs() << 1 | u128::from(*n < 0), out),
+        _ => {
+            let (sign, magnitude) = value.clone().to_big_int().into_parts();
+            let shifted = magnitude << 1u8 | BigUint::from(u8::from(sign == Sign::Minus));
+
+            let digits = shifted.to_radix_le(128);
+            let last = digits.len() - 1;
+            out.extend(digits.iter().enumerate().map(|(i, digit)| if i == last { *digit } else { digit | 0x80 }));
+        }
+    }
+}
+
+fn write_varint(mut value: u128, out: &mut Vec<u8>) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn write_unit(unit: &Unit, out: &mut Vec<u8>) {
+    match unit {
+        Compound(numerator, denominator) => {
+            out.extend_from_slice(&COMPOUND_TAG.to_le_bytes());
+            for list in [numerator, denominator] {
+                write_varint(list.len() as u128, out);
+                for u in list {
+                    write_unit(u, out);
+                }
+            }
+        }
+        Power(base, exponent) => {
+            out.extend_from_slice(&POWER_TAG.to_le_bytes());
+            write_integer(&ScalableInteger::from(*exponent), out);
+            write_unit(base, out);
+        }
+        named => {
+            let tag = named.index().expect("named units have an index") as u16;
+            out.extend_from_slice(&tag.to_le_bytes());
+        }
+    }
+}
+
+fn read_byte(input: &mut &[u8]) -> Result<u8, DecodeError> {
+    let (&byte, rest) = input.split_first().ok_or(DecodeError::UnexpectedEnd)?;
+    *input = rest;
+    Ok(byte)
+}
+
+/// Splits the bytes of the varint at the start of the input off the input.
+fn read_varint_bytes<'a>(input: &mut &'a [u8]) -> Result<&'a [u8], DecodeError> {
+    let end = input.iter().position(|byte| byte & 0x80 == 0).ok_or(DecodeError::UnexpectedEnd)?;
+    let (bytes, rest) = input.split_at(end + 1);
+    *input = rest;
+    Ok(bytes)
+}
+
+fn read_integer(input: &mut &[u8]) -> Result<ScalableInteger, DecodeError> {
+    let bytes = read_varint_bytes(input)?;
+
+    if bytes.len() <= SMALL_VARINT_BYTES {
+        let value = bytes.iter().rev().fold(0u128, |value, byte| value << 7 | u128::from(byte & 0x7f));
+        let magnitude = (value >> 1) as i128;
+        Ok(if value & 1 == 1 { -magnitude } else { magnitude }.into())
+    } else {
+        let digits: Vec<u8> = bytes.iter().map(|byte| byte & 0x7f).collect();
+        let value = BigUint::from_radix_le(&digits, 128).expect("digits are below the radix");
+        let sign = if value.bit(0) { Sign::Minus } else { Sign::Plus };
+        Ok(BigInt::from_biguint(sign, value >> 1u8).into())
+    }
+}
+
+fn read_length(input: &mut &[u8]) -> Result<usize, DecodeError> {
+    let bytes = read_varint_bytes(input)?;
+    bytes.iter().rev().try_fold(0usize, |length, byte| {
+        length.checked_mul(0x80)
+            .map(|length| length | usize::from(byte & 0x7f))
+            .ok_or(DecodeError::LengthOverflow)
+    })
+}
+
+/// Reads a unit nested ```depth``` levels deep, inside powers whose exponents multiply to ```scale```.
+fn read_unit(input: &mut &[u8], depth: usize, scale: u32) -> Result<Unit, DecodeError> {
+    let tag = u16::from_le_bytes([read_byte(input)?, read_byte(input)?]);
+    if tag != COMPOUND_TAG && tag != POWER_TAG {
+        return Unit::NAMED_UNITS.get(usize::from(tag)).cloned().ok_or(DecodeError::UnknownUnit(tag));
+    }
+
+    if depth == MAX_DEPTH {
+        return Err(DecodeError::TooDeeplyNested);
+    }
+
+    if tag == POWER_TAG {
+        // Slopes are raised to the exponent when a unit is used, so it is bounded like in the parser
+        let exponent = read_integer(input)?.to_i32().ok_or(DecodeError::ExponentOverflow)?;
+        let scale = scale.checked_mul(exponent.unsigned_abs().max(1))
+            .filter(|scale| *scale <= MAX_EXPONENT)
+            .ok_or(DecodeError::ExponentOverflow)?;
+        return Ok(Power(Box::new(read_unit(input, depth + 1, scale)?), exponent));
+    }
+
+    let read_list = |input: &mut &[u8]| -> Result<Vec<Unit>, DecodeError> {
+        let length = read_length(input)?;
+        // Every unit takes at least two bytes, which bounds the allocation for corrupt lengths
+        let mut units = Vec::with_capacity(length.min(input.len() / 2));
+        for _ in 0..length {
+            units.push(read_unit(input, depth + 1, scale)?);
+        }
+        Ok(units)
+    };
+
+    let numerator = read_list(input)?;
+    let denominator = read_list(input)?;
+    Ok(Compound(numerator.into(), denominator.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigInt;
+    use proptest::prelude::*;
+    use crate::{int, ratio};
+    use crate::unit::UNITLESS;
+    use crate::unit::Unit::*;
+    use super::*;
+
+    fn round_trip(quantity: Quantity) {
+        assert_eq!(Quantity::from_bytes(&quantity.to_bytes()), Ok(quantity));
+    }
+
+    #[test]
+    fn every_named_unit() {
+        for unit in Unit::NAMED_UNITS {
+            round_trip(Quantity::new(ratio!(-7, 3), unit.clone()));
+        }
+    }
+
+    #[test]
+    fn compound_units() {
+        round_trip(Quantity::new(int!(1), UNITLESS));
+        round_trip(Quantity::new(int!(1), UNITLESS / Second));
+        round_trip(Quantity::new(int!(1), Kilo * Meter / (Hour * Hour)));
+
+        let nested = Compound(vec![Compound(vec![Kilo, Meter].into(), vec![Second].into()), Gram].into(), vec![Compound(vec![].into(), vec![Hour].into())].into());
+        round_trip(Quantity::new(int!(2), nested));
+    }
+
+    #[test]
+    fn powers() {
+        round_trip(Quantity::new(int!(3), Power(Box::new(Meter), 3)));
+        round_trip(Quantity::new(int!(3), Power(Box::new(Kilo * Meter / Second), -2)));
+        round_trip(Quantity::new(int!(3), Compound(vec![Meter].into(), vec![Power(Box::new(Second), -1024)].into())));
+
+        // Larger powers are encoded, but rejected when decoding
+        let huge = Quantity::new(int!(3), Compound(vec![Meter].into(), vec![Power(Box::new(Second), i32::MIN)].into()));
+        assert_eq!(Quantity::from_bytes(&huge.to_bytes()).err(), Some(DecodeError::ExponentOverflow));
+
+        let [low, high] = POWER_TAG.to_le_bytes();
+        let [meter_low, meter_high] = (Meter.index().unwrap() as u16).to_le_bytes();
+        assert_eq!(Quantity::new(int!(1), Power(Box::new(Meter), -2)).to_bytes(), [FORMAT_VERSION, 2, 2, low, high, 5, meter_low, meter_high]);
+    }
+
+    #[test]
+    fn big_magnitudes() {
+        round_trip(Quantity::new(int!(i64::MIN), Meter));
+        round_trip(Quantity::new(int!(i128::MIN), Meter));
+        round_trip(Quantity::new(ratio!(i128::MAX, 7), Meter));
+
+        let big = BigInt::from(3).pow(200);
+        round_trip(Quantity::new(BigRational::new((-big.clone()).into(), (big + BigInt::from(1)).into()), Meter));
+    }
+
+    #[test]
+    fn layout() {
+        let tag = Meter.index().unwrap() as u16;
+        let [low, high] = tag.to_le_bytes();
+        assert_eq!(Quantity::new(ratio!(-3, 2), Meter).to_bytes(), [FORMAT_VERSION, 7, 4, low, high]);
+
+        let [low, high] = COMPOUND_TAG.to_le_bytes();
+        assert_eq!(Quantity::new(int!(64), UNITLESS).to_bytes(), [FORMAT_VERSION, 0x80, 1, 2, low, high, 0, 0]);
+    }
+
+    #[test]
+    fn invalid_input() {
+        let bytes = Quantity::new(ratio!(1, 3), Meter / Second).to_bytes();
+
+        assert_eq!(Quantity::from_bytes(&[]), Err(DecodeError::UnexpectedEnd));
+        assert_eq!(Quantity::from_bytes(&bytes[..bytes.len() - 1]), Err(DecodeError::UnexpectedEnd));
+        assert_eq!(Quantity::from_bytes(&[2, 0, 2, 0, 0]), Err(DecodeError::UnsupportedVersion(2)));
+        assert_eq!(Quantity::from_bytes(&[1, 2, 0, 0, 0]), Err(DecodeError::ZeroDenominator));
+        assert_eq!(Quantity::from_bytes(&[1, 2, 2, 0xfd, 0xff]), Err(DecodeError::UnknownUnit(0xfffd)));
+        assert_eq!(Quantity::from_bytes(&[&bytes[..], &[0]].concat()), Err(DecodeError::TrailingBytes(1)));
+
+        let mut nested = vec![1, 2, 2];
+        for _ in 0..=MAX_DEPTH {
+            nested.extend([0xff, 0xff, 1]);
+        }
+        assert_eq!(Quantity::from_bytes(&nested), Err(DecodeError::TooDeeplyNested));
+
+        let mut long = vec![1, 2, 2, 0xff, 0xff];
+        long.extend([0xff; 12]);
+        long.push(1);
+        assert_eq!(Quantity::from_bytes(&long), Err(DecodeError::LengthOverflow));
+
+        let [low, high] = POWER_TAG.to_le_bytes();
+        let mut huge_exponent = vec![1, 2, 2, low, high];
+        huge_exponent.extend([0xff; 5]);
+        huge_exponent.push(1);
+        assert_eq!(Quantity::from_bytes(&huge_exponent), Err(DecodeError::ExponentOverflow));
+
+        // Exponents of nested powers multiply
+        let power = |exponent: i32, unit: Unit| Power(Box::new(unit), exponent);
+        let bounded = Quantity::from_i64_with_unit(1, power(-32, power(32, Meter)));
+        assert_eq!(Quantity::from_bytes(&bounded.to_bytes()), Ok(bounded));
+        for unit in [power(i32::MAX, Meter), power(1025, Meter), power(-33, power(32, Meter)), Compound(vec![Meter].into(), vec![power(64, power(0, power(32, Meter)))].into())] {
+            let bytes = Quantity::from_i64_with_unit(1, unit).to_bytes();
+            assert_eq!(Quantity::from_bytes(&bytes).err(), Some(DecodeError::ExponentOverflow));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn arbitrary_quantities_round_trip(quantity: Quantity) {
+            prop_assert_eq!(Quantity::from_bytes(&quantity.to_bytes()), Ok(quantity));
+        }
+    }
+}
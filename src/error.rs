@@ -0,0 +1,317 @@
+//! Errors shared by the conversion APIs.
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use crate::pow::MAX_ROOT_DIGITS;
+use crate::scalable_integer::BigRational;
+use crate::unit::Unit;
+
+/// An error converting a ```Quantity``` between units.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConversionError {
+    /// The units measure different dimensions, like a length and a time.
+    IncompatibleUnits {
+        /// The unit that was converted from.
+        from: Unit,
+        /// The unit that was converted to.
+        to: Unit,
+    },
+    /// One of the units is a compound unit of a temperature containing an affine unit, like
+    /// ```Kilo * Celsius```, which could be an absolute temperature or a difference.
+    AmbiguousOffset {
+        /// The unit that was converted from.
+        from: Unit,
+        /// The unit that was converted to.
+        to: Unit,
+    },
+    /// The converted value doesn't fit into the requested integer type.
+    Overflow,
+    /// The value isn't an integer, but only an exact conversion was requested.
+    NotAnInteger,
+    /// The reference value of a relative comparison is zero.
+    ZeroReference,
+}
+
+/// The most units ```ConversionError::suggestions``` returns.
+const MAX_SUGGESTIONS: usize = 3;
+
+impl ConversionError {
+    /// Units related to a failed conversion between incompatible units, which are also listed by
+    /// ```Display```.
+    ///
+    /// If the source unit is off by one power of time from the target, like ```Joule``` and
+    /// ```Watt```, the suggestions start with the source unit divided or multiplied by
+    /// ```Second```, followed by named units of the target dimension. Otherwise they are other
+    /// named units the source unit converts to. Other errors have no suggestions.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    /// use tantalum_unit::error::ConversionError;
+    ///
+    /// let error = ConversionError::IncompatibleUnits { from: Joule, to: Watt };
+    /// assert_eq!(error.suggestions(), [Joule / Second, Watt]);
+    /// assert_eq!(error.to_string(), "cannot convert J to W, did you mean J/s or W?");
+    /// ```
+    pub fn suggestions(&self) -> Vec<Unit> {
+        match self {
+            ConversionError::IncompatibleUnits { from, to } => from.conversion_suggestions(to, MAX_SUGGESTIONS),
+            _ => vec![],
+        }
+    }
+
+    /// The SI units of both units of a failed conversion between incompatible units, which show
+    /// how their dimensions differ. Other errors have none.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let error = Quantity::from_unit(Gallon).convert_to(Hertz).unwrap_err();
+    /// assert_eq!(error.si_units(), Some((Meter * Meter * Meter, UNITLESS / Second)));
+    /// ```
+    pub fn si_units(&self) -> Option<(Unit, Unit)> {
+        match self {
+            ConversionError::IncompatibleUnits { from, to } => Some((from.clone().to_si_units().2, to.clone().to_si_units().2)),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConversionError::IncompatibleUnits { from, to } => {
+                write!(f, "cannot convert {} to {}", from.symbol(), to.symbol())?;
+                let suggestions = self.suggestions();
+                if let Some((last, rest)) = suggestions.split_last() {
+                    f.write_str(", did you mean ")?;
+                    if !rest.is_empty() {
+                        let rest: Vec<_> = rest.iter().map(Unit::symbol).collect();
+                        write!(f, "{} or ", rest.join(", "))?;
+                    }
+                    write!(f, "{}?", last.symbol())?;
+                }
+                Ok(())
+            }
+            ConversionError::AmbiguousOffset { from, to } => {
+                write!(f, "cannot convert {} to {}, as it's unclear whether they are temperatures or differences", from.symbol(), to.symbol())
+            }
+            ConversionError::Overflow => f.write_str("the value doesn't fit into the integer type"),
+            ConversionError::NotAnInteger => f.write_str("the value isn't an integer"),
+            ConversionError::ZeroReference => f.write_str("the reference value is zero"),
+        }
+    }
+}
+
+impl Error for ConversionError {}
+
+/// An error adding or subtracting two quantities.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ArithmeticError {
+    /// The units measure different dimensions, like a length and a time.
+    IncompatibleUnits {
+        /// The unit of the left operand.
+        left: Unit,
+        /// The unit of the right operand.
+        right: Unit,
+    },
+    /// The units measure the same dimension but differ, and strict arithmetic doesn't convert
+    /// between them.
+    ImplicitConversion {
+        /// The unit of the left operand.
+        left: Unit,
+        /// The unit of the right operand.
+        right: Unit,
+    },
+}
+
+impl Display for ArithmeticError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArithmeticError::IncompatibleUnits { left, right } => write!(f, "cannot combine {} and {}", left.symbol(), right.symbol()),
+            ArithmeticError::ImplicitConversion { left, right } => write!(f, "combining {} and {} requires an explicit conversion", left.symbol(), right.symbol()),
+        }
+    }
+}
+
+impl Error for ArithmeticError {}
+
+/// An error splitting a ```Quantity``` into parts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DivisionError {
+    /// The Quantity was to be split into zero parts.
+    ZeroParts,
+    /// The granularity is zero or negative.
+    NonPositiveGranularity,
+    /// The granularity can't be converted to the unit of the Quantity.
+    IncompatibleUnits {
+        /// The unit of the Quantity.
+        quantity: Unit,
+        /// The unit of the granularity.
+        granularity: Unit,
+    },
+}
+
+impl Display for DivisionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DivisionError::ZeroParts => f.write_str("cannot split into zero parts"),
+            DivisionError::NonPositiveGranularity => f.write_str("the granularity isn't positive"),
+            DivisionError::IncompatibleUnits { quantity, granularity } => write!(f, "cannot split {} into multiples of {}", quantity.symbol(), granularity.symbol()),
+        }
+    }
+}
+
+impl Error for DivisionError {}
+
+/// An error re-expressing a ```Quantity``` with a prefix.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PrefixError {
+    /// The unit given as the prefix isn't an SI or binary modifier.
+    NotAPrefix(Unit),
+    /// The unit of the Quantity consists of more than one unit apart from its prefixes, like ```km/h```.
+    CompoundUnit(Unit),
+}
+
+impl Display for PrefixError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrefixError::NotAPrefix(unit) => write!(f, "{} is not a prefix", unit.symbol()),
+            PrefixError::CompoundUnit(unit) => write!(f, "cannot prefix the compound unit {}", unit.symbol()),
+        }
+    }
+}
+
+impl Error for PrefixError {}
+
+/// An error raising a ```Quantity``` to the power of another.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PowError {
+    /// The exponent isn't unitless.
+    UnitExponent(Unit),
+    /// The exponent doesn't fit into an ```i32```.
+    ExponentTooLarge,
+    /// The result isn't rational, or has a fractional power of a unit like ```m^(1/2)```.
+    InexactResult,
+    /// An even root of a negative base, which isn't real.
+    NegativeBase,
+    /// Zero raised to a negative power.
+    DivisionByZero,
+    /// The root's degree times the requested decimal places exceeds ```MAX_ROOT_DIGITS```.
+    PrecisionTooLarge,
+}
+
+impl Display for PowError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PowError::UnitExponent(unit) => write!(f, "the exponent has the unit {}", unit.symbol()),
+            PowError::ExponentTooLarge => f.write_str("the exponent is too large"),
+            PowError::InexactResult => f.write_str("the power can't be represented exactly"),
+            PowError::NegativeBase => f.write_str("an even root of a negative base isn't real"),
+            PowError::DivisionByZero => f.write_str("zero raised to a negative power"),
+            PowError::PrecisionTooLarge => write!(f, "approximating the root needs more than {MAX_ROOT_DIGITS} digits"),
+        }
+    }
+}
+
+impl Error for PowError {}
+
+/// An error evaluating a function like a logarithm on a ```Quantity```.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MathError {
+    /// The function is only defined for unitless quantities.
+    NotUnitless(Unit),
+    /// The argument of a logarithm is zero or negative.
+    NonPositive,
+    /// The result is too large to be computed.
+    Overflow,
+    /// The function is only defined for angles and unitless quantities.
+    NotAngle(Unit),
+    /// The function has no value at the argument, like the tangent of 90°.
+    Undefined,
+    /// The arguments of a function of two quantities measure different dimensions.
+    IncompatibleUnits(Unit, Unit),
+}
+
+impl Display for MathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MathError::NotUnitless(unit) => write!(f, "expected a unitless quantity, found the unit {}", unit.symbol()),
+            MathError::NonPositive => f.write_str("the logarithm of a non-positive number is undefined"),
+            MathError::Overflow => f.write_str("the result is too large"),
+            MathError::NotAngle(unit) => write!(f, "expected an angle or a unitless quantity, found the unit {}", unit.symbol()),
+            MathError::Undefined => f.write_str("the function is undefined at the argument"),
+            MathError::IncompatibleUnits(a, b) => write!(f, "the units {} and {} are incompatible", a.symbol(), b.symbol()),
+        }
+    }
+}
+
+impl Error for MathError {}
+
+/// An error registering or looking up a conversion in a ```UnitRegistry```.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RegistryError {
+    /// The custom unit has no registered conversions.
+    UnknownUnit(String),
+    /// No chain of registered conversions connects the units.
+    NoPath {
+        /// The name or symbol of the unit that was converted from.
+        from: String,
+        /// The name or symbol of the unit that was converted to.
+        to: String,
+    },
+    /// The conversion contradicts the conversions registered before.
+    Inconsistent {
+        /// The name or symbol of the unit that was converted from.
+        from: String,
+        /// The name or symbol of the unit that was converted to.
+        to: String,
+        /// The factor following from the registered conversions.
+        existing: BigRational,
+    },
+    /// The conversion factor is zero or negative.
+    InvalidFactor(BigRational),
+    /// Conversions between two built-in units are fixed and can't be registered.
+    BuiltInUnits,
+    /// The built-in unit has an offset like ```Celsius```, so it can't be converted by a factor.
+    AffineUnit(Unit),
+}
+
+impl Display for RegistryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegistryError::UnknownUnit(name) => write!(f, "the unit {} has no registered conversions", name),
+            RegistryError::NoPath { from, to } => write!(f, "no conversion from {} to {} is registered", from, to),
+            RegistryError::Inconsistent { from, to, existing } => write!(f, "1 {} is already registered as {} {}", from, existing, to),
+            RegistryError::InvalidFactor(factor) => write!(f, "the conversion factor {} isn't positive", factor),
+            RegistryError::BuiltInUnits => f.write_str("conversions between built-in units can't be registered"),
+            RegistryError::AffineUnit(unit) => write!(f, "the unit {} has an offset and can't be converted by a factor", unit.symbol()),
+        }
+    }
+}
+
+impl Error for RegistryError {}
+
+/// An error parsing a ```ScalableInteger``` with ```Num::from_str_radix```.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseIntegerError {
+    /// The input has no digits, apart from an optional sign.
+    Empty,
+    /// The input contains a character that isn't a digit of the radix.
+    InvalidDigit,
+    /// The radix isn't between 2 and 36.
+    InvalidRadix(u32),
+}
+
+impl Display for ParseIntegerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseIntegerError::Empty => f.write_str("cannot parse an integer from an empty string"),
+            ParseIntegerError::InvalidDigit => f.write_str("invalid digit found in string"),
+            ParseIntegerError::InvalidRadix(radix) => write!(f, "the radix {radix} isn't between 2 and 36"),
+        }
+    }
+}
+
+impl Error for ParseIntegerError {}
@@ -0,0 +1,204 @@
+//! Step-by-step explanations of unit conversions.
+//!
+//! A conversion goes from the source unit to SI base units and from there to the target unit.
+//! Derived units like ```Joule``` are defined in terms of kilograms, so the prefixes left over in
+//! the SI units get their own steps. Every step adds an offset and then multiplies by a factor.
+//!
+//! # Example:
+//! ```
+//! use tantalum_unit::prelude::*;
+//!
+//! let explanation = Quantity::from_unit(Gallon).explain_conversion(&Liter).unwrap();
+//!
+//! assert_eq!(explanation.factor(), ratio!(473176473, 125000000));
+//! assert_eq!(explanation.to_string(), "\
+//! 1 gal = 473176473/125000000000 m^3 (gal to SI units: multiply by 473176473/125000000000)
+//! 473176473/125000000000 m^3 = 473176473/125000000 L (SI units to L: multiply by 1000)
+//! 1 gal = 473176473/125000000 L (combined factor 473176473/125000000)");
+//! ```
+
+use std::fmt::{Display, Formatter};
+use num::{One, Zero};
+use crate::error::ConversionError;
+use crate::quantity::Quantity;
+use crate::scalable_integer::BigRational;
+use crate::unit::Unit;
+
+/// One step of a conversion, which turns a value ```v``` into ```(v + offset) * factor```.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConversionStep {
+    pub description: String,
+    pub offset: BigRational,
+    pub factor: BigRational,
+    /// The unit of the value after the step.
+    pub unit: Unit,
+}
+
+impl ConversionStep {
+    fn new(description: String, offset: BigRational, factor: BigRational, unit: Unit) -> Self {
+        Self { description, offset, factor, unit }
+    }
+
+    /// The step that undoes ```v -> (v + offset) * factor```.
+    fn inverse(description: String, offset: BigRational, factor: BigRational, unit: Unit) -> Self {
+        Self::new(description, -(offset * &factor), factor.recip(), unit)
+    }
+
+    /// Whether the step doesn't change the value.
+    fn is_identity(&self) -> bool {
+        self.offset.is_zero() && self.factor.is_one()
+    }
+
+    pub fn apply(&self, value: &BigRational) -> BigRational {
+        (value + &self.offset) * &self.factor
+    }
+}
+
+/// The steps converting a Quantity to another unit, as returned by ```Quantity::explain_conversion```.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConversionExplanation {
+    pub from: Quantity,
+    pub steps: Vec<ConversionStep>,
+}
+
+impl ConversionExplanation {
+    /// The product of the factors of all steps, which converts differences between the units.
+    pub fn factor(&self) -> BigRational {
+        self.steps.iter().map(|step| &step.factor).fold(BigRational::one(), |product, factor| product * factor)
+    }
+
+    /// The converted Quantity, obtained by applying all steps to the source.
+    pub fn result(&self) -> Quantity {
+        let magnitude = self.steps.iter().fold(self.from.magnitude.clone(), |value, step| step.apply(&value));
+        let unit = self.steps.last().map_or_else(|| self.from.unit.clone(), |step| step.unit.clone());
+        Quantity::new(magnitude, unit)
+    }
+}
+
+impl Display for ConversionExplanation {
+    /// Writes one line per step with the value before and after it, followed by the overall result.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut value = self.from.magnitude.clone();
+        let mut unit = &self.from.unit;
+        for step in &self.steps {
+            let next = step.apply(&value);
+            write!(f, "{} = {} ({}: ", exact(&value, unit), exact(&next, &step.unit), step.description)?;
+            if !step.offset.is_zero() {
+                write!(f, "add {}, then ", step.offset)?;
+            }
+            writeln!(f, "multiply by {})", step.factor)?;
+            value = next;
+            unit = &step.unit;
+        }
+        write!(f, "{} = {} (combined factor {})", exact(&self.from.magnitude, &self.from.unit), exact(&value, unit), self.factor())
+    }
+}
+
+/// Writes a magnitude exactly, followed by the unit symbol if there is one.
+fn exact(magnitude: &BigRational, unit: &Unit) -> String {
+    if unit.is_unitless() {
+        magnitude.to_string()
+    } else {
+        format!("{magnitude} {unit}")
+    }
+}
+
+impl Quantity {
+    /// Explains how the Quantity is converted to ```to```, step by step with exact factors.
+    ///
+    /// The steps go through SI base units, reducing the prefixes left in the SI units of derived
+    /// units like ```Joule``` in steps of their own. Returns an error if the units measure
+    /// different dimensions, including reciprocal ones like ```Ohm``` and ```Siemens```.
+    pub fn explain_conversion(&self, to: &Unit) -> Result<ConversionExplanation, ConversionError> {
+        if !self.unit.has_same_dimension(to) {
+            return Err(ConversionError::IncompatibleUnits { from: self.unit.clone(), to: to.clone() });
+        }
+
+        let (offset, slope, si_unit) = self.unit.clone().to_si_units();
+        let (prefix_offset, prefix_slope, base_unit) = si_unit.clone().to_si_units();
+        let (offset_to, slope_to, si_unit_to) = to.clone().to_si_units();
+        let (prefix_offset_to, prefix_slope_to, _) = si_unit_to.clone().to_si_units();
+
+        let steps = vec![
+            ConversionStep::new(format!("{} to SI units", self.unit), offset, slope, si_unit.clone()),
+            ConversionStep::new(format!("prefixes of {si_unit}"), prefix_offset, prefix_slope, base_unit),
+            ConversionStep::inverse(format!("prefixes of {si_unit_to}"), prefix_offset_to, prefix_slope_to, si_unit_to),
+            ConversionStep::inverse(format!("SI units to {to}"), offset_to, slope_to, to.clone()),
+        ];
+        // The first and last step are kept even if they are trivial, so the units are always mentioned
+        let last = steps.len() - 1;
+        let steps = steps.into_iter()
+            .enumerate()
+            .filter(|(i, step)| *i == 0 || *i == last || !step.is_identity())
+            .map(|(_, step)| step)
+            .collect();
+        Ok(ConversionExplanation { from: self.clone(), steps })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{int, ratio};
+    use crate::unit::Unit::*;
+    use super::*;
+
+    /// Checks that the product of the factors converts differences and the steps convert values
+    /// like ```convert_to```.
+    fn check(from: Quantity, to: Unit) -> ConversionExplanation {
+        let explanation = from.explain_conversion(&to).unwrap();
+        let product = explanation.steps.iter().fold(int!(1), |product, step| product * step.factor.clone());
+        assert_eq!(explanation.factor(), product);
+
+        let converted = from.clone().convert_to(to.clone()).unwrap();
+        assert_eq!(explanation.result(), converted);
+        let difference = Quantity::new(&from.magnitude + int!(1), from.unit.clone()).convert_to(to).unwrap();
+        assert_eq!(difference.magnitude - converted.magnitude, product);
+        explanation
+    }
+
+    #[test]
+    fn gallon_to_liter() {
+        let explanation = check(Quantity::from_i64_with_unit(3, Gallon), Liter);
+        assert_eq!(explanation.factor(), ratio!(473176473, 125000000));
+        assert_eq!(explanation.steps.len(), 2);
+        assert_eq!(explanation.steps[0].unit, Meter * Meter * Meter);
+    }
+
+    #[test]
+    fn fahrenheit_to_kelvin() {
+        let explanation = check(Quantity::from_i64_with_unit(212, Fahrenheit), Kelvin);
+        assert_eq!(explanation.steps[0].offset, ratio!(45967, 100));
+        assert_eq!(explanation.factor(), ratio!(5, 9));
+        assert_eq!(explanation.to_string(), "\
+212 °F = 7463/20 K (°F to SI units: add 45967/100, then multiply by 5/9)
+7463/20 K = 7463/20 K (SI units to K: multiply by 1)
+212 °F = 7463/20 K (combined factor 5/9)");
+    }
+
+    #[test]
+    fn power_to_kilowatt() {
+        let explanation = check(Quantity::from_i64_with_unit(3800, Joule / Second), Kilo * Watt);
+        assert_eq!(explanation.factor(), ratio!(1, 1000));
+        assert_eq!(explanation.result(), Quantity::new(ratio!(19, 5), Kilo * Watt));
+    }
+
+    #[test]
+    fn celsius_to_fahrenheit() {
+        check(Quantity::from_i64_with_unit(100, Celsius), Fahrenheit);
+    }
+
+    #[test]
+    fn prefixes_of_derived_units() {
+        let from = Quantity::from_i64_with_unit(2, Kilo * Gram * Meter * Meter / (Second * Second));
+        let explanation = from.explain_conversion(&Joule).unwrap();
+        assert_eq!(explanation.result(), Quantity::from_i64_with_unit(2, Joule));
+        let descriptions: Vec<&str> = explanation.steps.iter().map(|step| step.description.as_str()).collect();
+        assert_eq!(descriptions, ["kg*m^2/s^2 to SI units", "prefixes of kg*m^2/s^2", "SI units to J"]);
+    }
+
+    #[test]
+    fn incompatible_units() {
+        let error = Quantity::from_unit(Ohm).explain_conversion(&Siemens).unwrap_err();
+        assert_eq!(error, ConversionError::IncompatibleUnits { from: Ohm, to: Siemens });
+    }
+}
@@ -0,0 +1,156 @@
+//! Extension methods for constructing quantities from numbers, e.g. ```5.kilometers()```.
+//!
+//! The methods are only available after importing ```QuantityExt```.
+//!
+//! # Example:
+//! ```
+//! use tantalum_unit::prelude::*;
+//! use tantalum_unit::ext::QuantityExt;
+//! use tantalum_unit::unit::KILOMETER_PER_HOUR;
+//!
+//! let d = 5.kilometers() / 30.0.minutes();
+//! assert_eq!(d.convert_to(KILOMETER_PER_HOUR).unwrap(), 10.with_unit(KILOMETER_PER_HOUR));
+//! ```
+
+use crate::float::{rational_from_f64, FloatConversionError};
+use crate::quantity::Quantity;
+use crate::unit::Unit::*;
+use crate::unit::{Unit, CENTIMETER, KILOGRAM, KILOMETER, KILOMETER_PER_HOUR, KILOWATT, KILOWATT_HOUR, METER_PER_SECOND, MILLILITER, MILLIMETER};
+
+macro_rules! quantity_ext {
+    ($($method:ident, $try_method:ident, $unit:expr);* $(;)?) => {
+        /// Constructs quantities from numbers.
+        ///
+        /// Integers always convert. Floats convert exactly, which fails for NaN and infinity:
+        /// the plain methods panic in that case and the ```try_``` methods return an error.
+        pub trait QuantityExt: Sized {
+            /// Constructs a Quantity with the given unit, returning an error if the number has no rational value.
+            fn try_with_unit(self, unit: Unit) -> Result<Quantity, FloatConversionError>;
+
+            /// Constructs a Quantity with the given unit. Panics if the number has no rational value.
+            fn with_unit(self, unit: Unit) -> Quantity {
+                self.try_with_unit(unit).unwrap_or_else(|e| panic!("{e}"))
+            }
+
+            $(
+                #[doc = concat!("Constructs a Quantity in ```", stringify!($unit), "```.")]
+                fn $method(self) -> Quantity {
+                    self.with_unit($unit)
+                }
+
+                #[doc = concat!("Constructs a Quantity in ```", stringify!($unit), "```, returning an error if the number has no rational value.")]
+                fn $try_method(self) -> Result<Quantity, FloatConversionError> {
+                    self.try_with_unit($unit)
+                }
+            )*
+        }
+    };
+}
+
+quantity_ext!(
+    // Length
+    meters, try_meters, Meter;
+    kilometers, try_kilometers, KILOMETER;
+    centimeters, try_centimeters, CENTIMETER;
+    millimeters, try_millimeters, MILLIMETER;
+    miles, try_miles, Mile;
+
+    // Time
+    seconds, try_seconds, Second;
+    minutes, try_minutes, Minute;
+    hours, try_hours, Hour;
+    days, try_days, Day;
+
+    // Mass
+    grams, try_grams, Gram;
+    kilograms, try_kilograms, KILOGRAM;
+
+    // Volume
+    liters, try_liters, Liter;
+    milliliters, try_milliliters, MILLILITER;
+
+    // Speed
+    meters_per_second, try_meters_per_second, METER_PER_SECOND;
+    kilometers_per_hour, try_kilometers_per_hour, KILOMETER_PER_HOUR;
+
+    // Energy and power
+    joules, try_joules, Joule;
+    watts, try_watts, Watt;
+    kilowatts, try_kilowatts, KILOWATT;
+    kilowatt_hours, try_kilowatt_hours, KILOWATT_HOUR;
+
+    // Electricity
+    amperes, try_amperes, Ampere;
+    volts, try_volts, Volt;
+
+    // Force and pressure
+    newtons, try_newtons, Newton;
+    pascals, try_pascals, Pascal;
+
+    // Temperature
+    kelvin, try_kelvin, Kelvin;
+    celsius, try_celsius, Celsius;
+    fahrenheit, try_fahrenheit, Fahrenheit;
+
+    // Information
+    bits, try_bits, Bit;
+    bytes, try_bytes, Byte;
+    nibbles, try_nibbles, Nibble;
+
+    // Angle
+    radians, try_radians, Radian;
+    degrees, try_degrees, Degree;
+
+    // Ratio
+    percent, try_percent, Percent;
+);
+
+impl QuantityExt for i64 {
+    fn try_with_unit(self, unit: Unit) -> Result<Quantity, FloatConversionError> {
+        Ok(Quantity::from_i64_with_unit(self, unit))
+    }
+}
+
+impl QuantityExt for f64 {
+    fn try_with_unit(self, unit: Unit) -> Result<Quantity, FloatConversionError> {
+        Ok(Quantity::new(rational_from_f64(self)?, unit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_quantity_eq, ratio};
+    use crate::scalable_integer::BigRational;
+    use super::*;
+
+    #[test]
+    fn integers() {
+        assert_eq!(5.meters(), Quantity::from_i64_with_unit(5, Meter));
+        assert_eq!((-3).kilometers(), Quantity::from_i64_with_unit(-3, Kilo * Meter));
+        assert_eq!(20.celsius(), Quantity::from_i64_with_unit(20, Celsius));
+        assert_eq!(4096.try_bytes(), Ok(Quantity::from_i64_with_unit(4096, Byte)));
+    }
+
+    #[test]
+    fn floats_are_exact() {
+        assert_eq!(2.5.kilometers(), Quantity::new(ratio!(5, 2), Kilo * Meter));
+        assert_eq!(0.1.liters(), Quantity::new(ratio!(3602879701896397i64, 36028797018963968i64), Liter));
+        assert_eq!(f64::NAN.try_hours(), Err(FloatConversionError::NotANumber));
+        assert_eq!(f64::INFINITY.try_with_unit(Meter), Err(FloatConversionError::Infinite));
+    }
+
+    #[test]
+    #[should_panic]
+    fn nan_panics() {
+        f64::NAN.seconds();
+    }
+
+    #[test]
+    fn arithmetic() {
+        let energy = 2.kilowatts() * 3.hours();
+        assert_quantity_eq!(6.kilowatt_hours(), energy);
+
+        let speed = 100.meters() / 9.58.seconds();
+        assert_eq!(speed.to_f32_in(METER_PER_SECOND), Ok(10.438414));
+    }
+}
@@ -0,0 +1,385 @@
+//! Exact conversions between ```Quantity``` and binary floating point numbers.
+//!
+//! Every finite float is a dyadic rational ```m * 2^e```, so it can be converted into a
+//! ```BigRational``` without any loss. The way back rounds to the nearest float, ties to even.
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use num::{BigInt, Integer, Signed, Zero};
+use crate::quantity::Quantity;
+use crate::scalable_integer::BigRational;
+use crate::unit::Unit;
+
+/// The number of explicitly stored mantissa bits of an ```f32```.
+const F32_MANTISSA_BITS: u32 = 23;
+/// The exponent of the smallest subnormal ```f32```, ```2^-149```.
+const F32_MIN_EXPONENT: i64 = -149;
+/// The exponent bias of an ```f32```.
+const F32_BIAS: i64 = 127;
+
+/// An error converting between a ```Quantity``` and a floating point number.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FloatConversionError {
+    /// The float is NaN, which has no rational value.
+    NotANumber,
+    /// The float is positive or negative infinity, which has no rational value.
+    Infinite,
+    /// The magnitude is too large to be represented by the float type.
+    Overflow,
+    /// The quantity can't be converted to the requested unit.
+    IncompatibleUnit,
+}
+
+impl Display for FloatConversionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FloatConversionError::NotANumber => write!(f, "NaN can't be converted to a quantity"),
+            FloatConversionError::Infinite => write!(f, "an infinite value can't be converted to a quantity"),
+            FloatConversionError::Overflow => write!(f, "the magnitude is too large for the float type"),
+            FloatConversionError::IncompatibleUnit => write!(f, "the quantity can't be converted to the requested unit"),
+        }
+    }
+}
+
+impl Error for FloatConversionError {}
+
+impl Quantity {
+    /// Constructs a unitless Quantity from an ```f32```. The conversion is exact.
+    ///
+    /// Panics if the value is NaN or infinite, see ```try_from_f32``` for a fallible version.
+    pub fn from_f32(value: f32) -> Self {
+        Self::from_f32_with_unit(value, crate::unit::UNITLESS)
+    }
+
+    /// Constructs a Quantity from an ```f32``` and a unit. The conversion is exact.
+    ///
+    /// Panics if the value is NaN or infinite, see ```try_from_f32_with_unit``` for a fallible version.
+    pub fn from_f32_with_unit(value: f32, unit: Unit) -> Self {
+        Self::try_from_f32_with_unit(value, unit).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Constructs a unitless Quantity from an ```f32```, returning an error if the value is NaN or infinite.
+    pub fn try_from_f32(value: f32) -> Result<Self, FloatConversionError> {
+        Self::try_from_f32_with_unit(value, crate::unit::UNITLESS)
+    }
+
+    /// Constructs a Quantity from an ```f32``` and a unit, returning an error if the value is NaN or infinite.
+    ///
+    /// The conversion is exact and doesn't go through ```f64```.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    /// use tantalum_unit::float::FloatConversionError;
+    ///
+    /// let reading = Quantity::try_from_f32_with_unit(0.1, Volt).unwrap();
+    /// assert_eq!(reading, Quantity::new(ratio!(13421773, 134217728), Volt));
+    ///
+    /// assert_eq!(Quantity::try_from_f32_with_unit(f32::NAN, Volt), Err(FloatConversionError::NotANumber));
+    /// ```
+    pub fn try_from_f32_with_unit(value: f32, unit: Unit) -> Result<Self, FloatConversionError> {
+        Ok(Self::new(rational_from_f32(value)?, unit))
+    }
+
+    /// Constructs a unitless Quantity from an ```f64```. The conversion is exact.
+    ///
+    /// Panics if the value is NaN or infinite, see ```try_from_f64``` for a fallible version.
+    pub fn from_f64(value: f64) -> Self {
+        Self::from_f64_with_unit(value, crate::unit::UNITLESS)
+    }
+
+    /// Constructs a Quantity from an ```f64``` and a unit. The conversion is exact.
+    ///
+    /// Panics if the value is NaN or infinite, see ```try_from_f64_with_unit``` for a fallible version.
+    pub fn from_f64_with_unit(value: f64, unit: Unit) -> Self {
+        Self::try_from_f64_with_unit(value, unit).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Constructs a unitless Quantity from an ```f64```, returning an error if the value is NaN or
+    /// infinite. Also available as ```Quantity::try_from```.
+    pub fn try_from_f64(value: f64) -> Result<Self, FloatConversionError> {
+        Self::try_from_f64_with_unit(value, crate::unit::UNITLESS)
+    }
+
+    /// Constructs a Quantity from an ```f64``` and a unit, returning an error if the value is NaN or infinite.
+    ///
+    /// Every finite value converts exactly, including subnormals, and ```-0.0``` is zero.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    /// use tantalum_unit::float::FloatConversionError;
+    ///
+    /// let length = Quantity::try_from_f64_with_unit(2.5, Meter).unwrap();
+    /// assert_eq!(length, Quantity::new(ratio!(5, 2), Meter));
+    ///
+    /// assert_eq!(Quantity::try_from_f64_with_unit(f64::INFINITY, Meter), Err(FloatConversionError::Infinite));
+    /// ```
+    pub fn try_from_f64_with_unit(value: f64, unit: Unit) -> Result<Self, FloatConversionError> {
+        Ok(Self::new(rational_from_f64(value)?, unit))
+    }
+
+    /// Converts the Quantity to ```unit``` and returns its magnitude as the nearest ```f32```, ties to even.
+    ///
+    /// Returns an error if the Quantity can't be converted to the unit or if the magnitude is
+    /// too large for an ```f32```. Magnitudes too small for an ```f32``` round to zero.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let distance = Quantity::from_i64_with_unit(3, Kilo * Meter);
+    /// assert_eq!(distance.to_f32_in(Meter), Ok(3000.0));
+    /// ```
+    pub fn to_f32_in(&self, unit: Unit) -> Result<f32, FloatConversionError> {
+        let converted = self.clone().convert_to(unit).map_err(|_| FloatConversionError::IncompatibleUnit)?;
+        let (negative, mantissa, exponent) = round_to_binary(&converted.magnitude, F32_MANTISSA_BITS + 1, F32_MIN_EXPONENT)
+            .ok_or(FloatConversionError::Overflow)?;
+
+        let sign = u32::from(negative) << 31;
+        if mantissa >> F32_MANTISSA_BITS == 0 {
+            // Subnormal or zero, the exponent is the minimum exponent
+            return Ok(f32::from_bits(sign | mantissa as u32));
+        }
+
+        let biased_exponent = exponent + F32_BIAS + i64::from(F32_MANTISSA_BITS);
+        if biased_exponent >= 0xff {
+            return Err(FloatConversionError::Overflow);
+        }
+        let fraction = mantissa as u32 & ((1 << F32_MANTISSA_BITS) - 1);
+        Ok(f32::from_bits(sign | (biased_exponent as u32) << F32_MANTISSA_BITS | fraction))
+    }
+}
+
+impl TryFrom<f64> for Quantity {
+    type Error = FloatConversionError;
+
+    /// Constructs a unitless Quantity like ```Quantity::try_from_f64```.
+    fn try_from(value: f64) -> Result<Self, Self::Error> {
+        Quantity::try_from_f64(value)
+    }
+}
+
+/// Converts an ```f32``` into the exact rational it represents.
+pub(crate) fn rational_from_f32(value: f32) -> Result<BigRational, FloatConversionError> {
+    rational_from_bits(u64::from(value.to_bits()), F32_MANTISSA_BITS, 8)
+}
+
+/// Converts an ```f64``` into the exact rational it represents.
+pub(crate) fn rational_from_f64(value: f64) -> Result<BigRational, FloatConversionError> {
+    rational_from_bits(value.to_bits(), 52, 11)
+}
+
+/// Converts the bits of an IEEE 754 binary float with the given field widths into an exact rational.
+fn rational_from_bits(bits: u64, mantissa_bits: u32, exponent_bits: u32) -> Result<BigRational, FloatConversionError> {
+    let max_biased_exponent = (1 << exponent_bits) - 1;
+    let bias = max_biased_exponent / 2;
+
+    let negative = bits >> (mantissa_bits + exponent_bits) & 1 == 1;
+    let biased_exponent = (bits >> mantissa_bits & max_biased_exponent) as i64;
+    let fraction = bits & ((1 << mantissa_bits) - 1);
+
+    if biased_exponent == max_biased_exponent as i64 {
+        return Err(if fraction == 0 { FloatConversionError::Infinite } else { FloatConversionError::NotANumber });
+    }
+
+    let (mantissa, exponent) = if biased_exponent == 0 {
+        // Subnormal numbers have no implicit leading bit
+        (fraction, 1 - bias as i64 - i64::from(mantissa_bits))
+    } else {
+        (fraction | 1 << mantissa_bits, biased_exponent - bias as i64 - i64::from(mantissa_bits))
+    };
+
+    Ok(dyadic(negative, mantissa, exponent))
+}
+
+/// Returns ```(-1)^negative * mantissa * 2^exponent``` as a reduced rational.
+fn dyadic(negative: bool, mantissa: u64, exponent: i64) -> BigRational {
+    if mantissa == 0 {
+        return BigRational::zero();
+    }
+
+    // Cancel common factors of two so the result is reduced without computing a gcd
+    let shift = i64::from(mantissa.trailing_zeros()).min(-exponent.min(0));
+    let (mantissa, exponent) = (mantissa >> shift, exponent + shift);
+
+    let mut numerator = BigInt::from(mantissa);
+    if negative {
+        numerator = -numerator;
+    }
+
+    if exponent >= 0 {
+        BigRational::new_raw((numerator << exponent).into(), 1.into())
+    } else {
+        BigRational::new_raw(numerator.into(), (BigInt::from(1) << -exponent).into())
+    }
+}
+
+/// Rounds a rational to a binary float with ```precision``` mantissa bits, ties to even.
+///
+/// Returns ```(negative, mantissa, exponent)``` such that the result is ```mantissa * 2^exponent```
+/// with ```exponent >= min_exponent```. The mantissa has exactly ```precision``` bits unless the
+/// exponent is ```min_exponent```. Returns ```None``` if the exponent doesn't fit into an ```i64```.
+fn round_to_binary(value: &BigRational, precision: u32, min_exponent: i64) -> Option<(bool, u64, i64)> {
+    let numerator = value.numer().clone().to_big_int();
+    let denominator = value.denom().clone().to_big_int();
+    if numerator.is_zero() {
+        return Some((false, 0, min_exponent));
+    }
+
+    let negative = numerator.is_negative() != denominator.is_negative();
+    let (numerator, denominator) = (numerator.abs(), denominator.abs());
+
+    // floor(log2(value)) is either the difference of the bit lengths or one less
+    let mut log2 = i64::try_from(numerator.bits()).ok()? - i64::try_from(denominator.bits()).ok()?;
+    let (scaled_numerator, scaled_denominator) = shifted(&numerator, &denominator, -log2);
+    if scaled_numerator < scaled_denominator {
+        log2 -= 1;
+    }
+
+    let mut exponent = (log2 - i64::from(precision) + 1).max(min_exponent);
+    let (numerator, denominator) = shifted(&numerator, &denominator, -exponent);
+    let (mut mantissa, remainder) = numerator.div_rem(&denominator);
+
+    let twice_remainder: BigInt = remainder << 1;
+    if twice_remainder > denominator || (twice_remainder == denominator && mantissa.is_odd()) {
+        mantissa += 1;
+    }
+
+    // Rounding up may carry into a new bit, e.g. 0b111.1 to 0b1000
+    if mantissa.bits() > u64::from(precision) {
+        mantissa >>= 1;
+        exponent += 1;
+    }
+
+    Some((negative, u64::try_from(mantissa).ok()?, exponent))
+}
+
+/// Returns ```(numerator * 2^shift, denominator)``` with the power of two moved to the denominator if ```shift``` is negative.
+fn shifted(numerator: &BigInt, denominator: &BigInt, shift: i64) -> (BigInt, BigInt) {
+    if shift >= 0 {
+        (numerator << shift, denominator.clone())
+    } else {
+        (numerator.clone(), denominator << -shift)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigInt;
+    use crate::{int, ratio};
+    use crate::unit::Unit::*;
+    use super::*;
+
+    fn power_of_two(exponent: u32) -> BigInt {
+        BigInt::from(1) << exponent
+    }
+
+    fn round_trip(value: f32) {
+        let quantity = Quantity::from_f32_with_unit(value, Meter);
+        assert_eq!(quantity.to_f32_in(Meter).map(f32::to_bits), Ok(value.to_bits()), "{value:e}");
+    }
+
+    #[test]
+    fn exact_construction() {
+        assert_eq!(Quantity::from_f32(1.5), Quantity::new(ratio!(3, 2), crate::unit::UNITLESS));
+        assert_eq!(Quantity::from_f32(-0.0), Quantity::from_f32(0.0));
+        assert_eq!(Quantity::from_f32(f32::from_bits(1)).magnitude, ratio!(1, power_of_two(149)));
+
+        let max = (BigInt::from((1 << 24) - 1) * power_of_two(104)).into();
+        assert_eq!(Quantity::from_f32(f32::MAX).magnitude, BigRational::from_integer(max));
+    }
+
+    #[test]
+    fn exact_f64() {
+        assert_eq!(rational_from_f64(0.1), Ok(ratio!(3602879701896397i64, 36028797018963968i64)));
+        assert_eq!(rational_from_f64(-2.5e10), Ok(int!(-25_000_000_000i64)));
+        assert_eq!(rational_from_f64(f64::from_bits(1)), Ok(ratio!(1, power_of_two(1074))));
+        assert_eq!(rational_from_f64(f64::NAN), Err(FloatConversionError::NotANumber));
+        assert_eq!(rational_from_f64(f64::NEG_INFINITY), Err(FloatConversionError::Infinite));
+    }
+
+    #[test]
+    fn non_finite() {
+        assert_eq!(Quantity::try_from_f32(f32::NAN), Err(FloatConversionError::NotANumber));
+        assert_eq!(Quantity::try_from_f32(f32::INFINITY), Err(FloatConversionError::Infinite));
+        assert_eq!(Quantity::try_from_f32_with_unit(f32::NEG_INFINITY, Meter), Err(FloatConversionError::Infinite));
+    }
+
+    #[test]
+    fn from_f64() {
+        assert_eq!(Quantity::try_from(1.5), Ok(Quantity::new(ratio!(3, 2), crate::unit::UNITLESS)));
+        assert_eq!(Quantity::try_from_f64(f64::NAN), Err(FloatConversionError::NotANumber));
+        assert_eq!(Quantity::try_from(f64::INFINITY), Err(FloatConversionError::Infinite));
+        assert_eq!(Quantity::try_from_f64_with_unit(f64::NEG_INFINITY, Meter), Err(FloatConversionError::Infinite));
+        assert_eq!(Quantity::try_from(-f64::NAN), Err(FloatConversionError::NotANumber));
+
+        // Subnormals and negative zero are exact
+        assert_eq!(Quantity::try_from_f64(f64::from_bits(1)).unwrap().magnitude, ratio!(1, power_of_two(1074)));
+        let largest_subnormal = f64::MIN_POSITIVE - f64::from_bits(1);
+        assert_eq!(Quantity::try_from_f64(-largest_subnormal).unwrap().magnitude,
+                   ratio!(BigInt::from(1) - power_of_two(52), power_of_two(1074)));
+        assert_eq!(Quantity::try_from_f64_with_unit(-0.0, Meter), Ok(Quantity::from_i64_with_unit(0, Meter)));
+        assert_eq!(Quantity::from_f64(-0.0).to_string(), Quantity::from_f64(0.0).to_string());
+
+        assert_eq!(Quantity::from_f64_with_unit(0.1, Meter), Quantity::new(ratio!(3602879701896397i64, 36028797018963968i64), Meter));
+    }
+
+    #[test]
+    #[should_panic(expected = "NaN")]
+    fn from_f64_nan_panics() {
+        Quantity::from_f64(f64::NAN);
+    }
+
+    #[test]
+    #[should_panic]
+    fn from_nan_panics() {
+        Quantity::from_f32(f32::NAN);
+    }
+
+    #[test]
+    fn round_trips() {
+        for value in [0.0, 1.0, -1.0, 0.1, -123.456, 3.0e38, f32::MAX, f32::MIN, f32::EPSILON,
+                      f32::MIN_POSITIVE, f32::MIN_POSITIVE / 3.0, -f32::from_bits(1), f32::from_bits(0x007f_ffff)] {
+            round_trip(value);
+        }
+    }
+
+    #[test]
+    fn rounding() {
+        assert_eq!(Quantity::new(ratio!(1, 3), Meter).to_f32_in(Meter), Ok(1.0 / 3.0));
+        assert_eq!(Quantity::new(ratio!(-2, 3), Meter).to_f32_in(Meter), Ok(-2.0 / 3.0));
+
+        // 2^24 + 1 lies halfway between two floats and rounds to the even one
+        assert_eq!(Quantity::new(int!((1 << 24) + 1), Meter).to_f32_in(Meter), Ok(16777216.0));
+        assert_eq!(Quantity::new(int!((1 << 24) + 3), Meter).to_f32_in(Meter), Ok(16777220.0));
+
+        // Halfway between the largest subnormal and the smallest normal number
+        let halfway = ratio!((1 << 24) - 1, power_of_two(150));
+        assert_eq!(Quantity::new(halfway, Meter).to_f32_in(Meter), Ok(f32::MIN_POSITIVE));
+
+        let tiny = ratio!(1, power_of_two(151));
+        assert_eq!(Quantity::new(tiny, Meter).to_f32_in(Meter), Ok(0.0));
+    }
+
+    #[test]
+    fn converts_units() {
+        let speed = Quantity::from_i64_with_unit(36, Kilo * Meter / Hour);
+        assert_eq!(speed.to_f32_in(Meter / Second), Ok(10.0));
+        assert_eq!(speed.to_f32_in(Kelvin), Err(FloatConversionError::IncompatibleUnit));
+    }
+
+    #[test]
+    fn overflow() {
+        let too_large = Quantity::from_f32_with_unit(f32::MAX, Meter) * int!(2);
+        assert_eq!(too_large.to_f32_in(Meter), Err(FloatConversionError::Overflow));
+        assert_eq!((-too_large).to_f32_in(Meter), Err(FloatConversionError::Overflow));
+
+        // Rounds up to 2^128, which is no longer finite
+        let rounds_up = int!(power_of_two(128) - BigInt::from(1));
+        assert_eq!(Quantity::new(rounds_up, Meter).to_f32_in(Meter), Err(FloatConversionError::Overflow));
+
+        let huge = Quantity::new(int!(1), Kilo * Meter) * int!(BigInt::from(10).pow(36));
+        assert_eq!(huge.to_f32_in(Meter), Err(FloatConversionError::Overflow));
+    }
+}
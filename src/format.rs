@@ -0,0 +1,982 @@
+//! Rendering quantities as decimal numbers.
+//!
+//! The exact ```Display``` of a ```Quantity``` isn't affected by anything in this module; only the
+//! decimal renderers here honor a ```NumberLocale```.
+//!
+//! # Example:
+//! ```
+//! use tantalum_unit::prelude::*;
+//! use tantalum_unit::format::NumberLocale;
+//! use tantalum_unit::rounding::RoundingMode;
+//!
+//! let length = Quantity::new(ratio!(123456, 100), Meter);
+//! assert_eq!(length.to_decimal_string(2, RoundingMode::HalfEven, &NumberLocale::EN_US), "1,234.56 m");
+//! assert_eq!(length.to_decimal_string(2, RoundingMode::HalfEven, &NumberLocale::DE_DE), "1.234,56 m");
+//! ```
+//!
+//! A ```QuantityFormatter``` combines all formatting options, so it can be configured once and
+//! used to format many quantities consistently.
+
+use num::{Integer, One, Zero};
+use crate::error::ConversionError;
+use crate::quantity::Quantity;
+use crate::rounding::{decimal_exponent, round, round_to_scale, round_to_significant, significant_string, terminating_decimal_places, RoundingMode};
+use crate::scalable_integer::{BigRational, ScalableInteger};
+use crate::unit::Unit;
+use crate::unit::Unit::*;
+use crate::unit_list::UnitList;
+
+/// The number of decimal places of the seconds in an ISO 8601 duration.
+const DURATION_SCALE: u32 = 9;
+
+/// How a locale writes numbers: the decimal separator and how the digits of the integer part are grouped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct NumberLocale {
+    pub decimal_separator: char,
+    /// Separates groups of digits in the integer part, or ```None``` to not group digits.
+    pub group_separator: Option<char>,
+    /// The sizes of the digit groups, starting at the decimal separator. The last size repeats,
+    /// so ```[3]``` groups thousands and ```[3, 2]``` produces the Indian ```12,34,567```.
+    pub grouping: &'static [u8],
+}
+
+impl NumberLocale {
+    /// ```1,234.56```
+    pub const EN_US: NumberLocale = NumberLocale { decimal_separator: '.', group_separator: Some(','), grouping: &[3] };
+    /// ```1.234,56```
+    pub const DE_DE: NumberLocale = NumberLocale { decimal_separator: ',', group_separator: Some('.'), grouping: &[3] };
+    /// ```1 234,56```, grouped with a narrow no-break space.
+    pub const FR_FR: NumberLocale = NumberLocale { decimal_separator: ',', group_separator: Some('\u{202F}'), grouping: &[3] };
+    /// ```1234.56```, without grouping.
+    pub const PLAIN: NumberLocale = NumberLocale { decimal_separator: '.', group_separator: None, grouping: &[] };
+
+    /// Rewrites a number written with a ```.``` decimal separator and without grouping, like
+    /// ```-1234.5``` or ```1.5e-8```, in the conventions of this locale.
+    pub fn localize(&self, number: &str) -> String {
+        let (sign, unsigned) = match number.strip_prefix('-') {
+            Some(unsigned) => ("-", unsigned),
+            None => ("", number),
+        };
+        let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+            Some(index) => unsigned.split_at(index),
+            None => (unsigned, ""),
+        };
+        let (whole, fraction) = match mantissa.split_once('.') {
+            Some((whole, fraction)) => (whole, Some(fraction)),
+            None => (mantissa, None),
+        };
+
+        let mut result = String::with_capacity(number.len() + whole.len() / 2);
+        result.push_str(sign);
+        result.push_str(&self.group(whole));
+        if let Some(fraction) = fraction {
+            result.push(self.decimal_separator);
+            result.push_str(fraction);
+        }
+        result.push_str(exponent);
+        result
+    }
+
+    /// Inserts the group separator into a string of digits.
+    pub(crate) fn group(&self, digits: &str) -> String {
+        let Some(separator) = self.group_separator else {
+            return digits.to_owned();
+        };
+
+        // Split off groups from the right, then reverse them
+        let mut groups = vec![];
+        let mut rest = digits;
+        let mut sizes = self.grouping.iter().copied();
+        let mut size = 0;
+        while !rest.is_empty() {
+            size = sizes.next().unwrap_or(size);
+            if size == 0 || rest.len() <= size as usize {
+                break;
+            }
+            let (head, tail) = rest.split_at(rest.len() - size as usize);
+            groups.push(tail);
+            rest = head;
+        }
+        groups.push(rest);
+
+        let mut result = String::with_capacity(digits.len() + groups.len());
+        for (i, group) in groups.iter().rev().enumerate() {
+            if i > 0 {
+                result.push(separator);
+            }
+            result.push_str(group);
+        }
+        result
+    }
+}
+
+impl Default for NumberLocale {
+    fn default() -> Self {
+        NumberLocale::EN_US
+    }
+}
+
+/// Renders a rational as a decimal with exactly ```scale``` decimal places.
+///
+/// A value that rounds to zero is written without a sign.
+///
+/// # Example:
+/// ```
+/// use tantalum_unit::prelude::*;
+/// use tantalum_unit::format::{format_decimal, NumberLocale};
+/// use tantalum_unit::rounding::RoundingMode;
+///
+/// assert_eq!(format_decimal(&ratio!(-2, 3), 3, RoundingMode::HalfEven, &NumberLocale::DE_DE), "-0,667");
+/// assert_eq!(format_decimal(&ratio!(1234567, 1), 0, RoundingMode::HalfEven, &NumberLocale::EN_US), "1,234,567");
+/// ```
+pub fn format_decimal(value: &BigRational, scale: u32, mode: RoundingMode, locale: &NumberLocale) -> String {
+    let rounded = round_to_scale(value, scale, mode);
+    let negative = rounded < ScalableInteger::from(0);
+    let digits = if negative { -rounded } else { rounded }.to_string();
+
+    let scale = scale as usize;
+    let digits = format!("{}{digits}", "0".repeat((scale + 1).saturating_sub(digits.len())));
+    let (whole, fraction) = digits.split_at(digits.len() - scale);
+
+    let sign = if negative { "-" } else { "" };
+    let point = if fraction.is_empty() { "" } else { "." };
+    locale.localize(&format!("{sign}{whole}{point}{fraction}"))
+}
+
+impl Quantity {
+    /// Renders the Quantity as a decimal with exactly ```scale``` decimal places, followed by the unit symbol.
+    pub fn to_decimal_string(&self, scale: u32, mode: RoundingMode, locale: &NumberLocale) -> String {
+        let number = format_decimal(&self.magnitude, scale, mode, locale);
+        if self.is_unitless() {
+            number
+        } else {
+            format!("{number} {}", self.unit)
+        }
+    }
+
+    /// Converts the Quantity to ```unit``` and renders it like ```Display```, without consuming it.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let power = Quantity::from_i64_with_unit(3000, Joule / Second);
+    /// assert_eq!(power.to_string_in(&(Kilo * Watt)), Ok("3kW".to_owned()));
+    /// assert!(power.to_string_in(&Meter).is_err());
+    /// ```
+    pub fn to_string_in(&self, unit: &Unit) -> Result<String, ConversionError> {
+        Ok(self.converted(unit)?.to_string())
+    }
+
+    /// Converts the Quantity to ```unit``` and renders it like ```to_decimal_string```.
+    pub fn to_decimal_string_in(&self, unit: &Unit, scale: u32, mode: RoundingMode, locale: &NumberLocale) -> Result<String, ConversionError> {
+        Ok(self.converted(unit)?.to_decimal_string(scale, mode, locale))
+    }
+
+    /// Renders the Quantity in engineering notation with ```sig_digits``` significant digits,
+    /// like ```12.3e3 m```: the exponent is a multiple of three and the mantissa lies in
+    /// ```[1, 1000)```. The exponent is left out when it's zero.
+    ///
+    /// The mantissa is rounded half to even, which may carry into the next power of a thousand,
+    /// e.g. ```999.96``` to ```1.00e3``` with three digits.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// assert_eq!(Quantity::from_i64_with_unit(12345, Meter).to_engineering_string(3), "12.3e3 m");
+    /// assert_eq!(Quantity::new(ratio!(47, 1000000), Farad).to_engineering_string(2), "47e-6 F");
+    /// assert_eq!(Quantity::new(ratio!(9999, 10), Meter).to_engineering_string(3), "1.00e3 m");
+    /// ```
+    pub fn to_engineering_string(&self, sig_digits: usize) -> String {
+        let (mantissa, exponent) = engineering_parts(&self.magnitude, sig_digits);
+        let exponent = if exponent == 0 { String::new() } else { format!("e{exponent}") };
+        with_symbol(format!("{mantissa}{exponent}"), &self.unit)
+    }
+
+    /// Like ```to_engineering_string```, but writes the exponent as an SI prefix of the unit, like
+    /// ```12.3 km```.
+    ///
+    /// Falls back to an exponent for units that don't take prefixes and for exponents beyond the
+    /// SI prefixes. A prefix of the unit is replaced, so ```12345 mm``` is ```12.3 m```.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// assert_eq!(Quantity::from_i64_with_unit(12345, Meter).to_engineering_string_prefixed(3), "12.3 km");
+    /// assert_eq!(Quantity::new(ratio!(47, 1000000), Farad).to_engineering_string_prefixed(2), "47 µF");
+    /// assert_eq!(Quantity::from_i64_with_unit(12345, Inch).to_engineering_string_prefixed(3), "12.3e3 in");
+    /// ```
+    pub fn to_engineering_string_prefixed(&self, sig_digits: usize) -> String {
+        let Some((prefix_exponent, base)) = split_prefix(&self.unit) else {
+            return self.to_engineering_string(sig_digits);
+        };
+        let base_magnitude = scale(&self.magnitude, prefix_exponent);
+        let (mantissa, exponent) = engineering_parts(&base_magnitude, sig_digits);
+        if !(-30..=30).contains(&exponent) {
+            return Quantity::new(base_magnitude, base).to_engineering_string(sig_digits);
+        }
+        let unit = match &PREFIXES[(exponent / 3 + 10) as usize] {
+            Some(prefix) => Compound(vec![prefix.clone(), base].into(), UnitList::new()),
+            None => base,
+        };
+        with_symbol(mantissa, &unit)
+    }
+
+    fn converted(&self, unit: &Unit) -> Result<Quantity, ConversionError> {
+        self.clone()
+            .convert_to(unit.clone())
+    }
+
+    /// Renders a time as an ISO 8601 duration like ```P1DT2H30M0.5S```, or returns an error if the
+    /// Quantity isn't a time.
+    ///
+    /// The duration only uses days, hours, minutes and seconds, since years and months don't have
+    /// a fixed length in ISO 8601. The seconds are rounded to at most nine decimal places, half to
+    /// even. Negative durations start with a ```-```, an extension of ISO 8601 that is also used by
+    /// XML Schema and understood by ```Quantity::from_iso8601_duration```.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// assert_eq!(q!(90 min).to_iso8601_duration(), Ok("PT1H30M".to_owned()));
+    /// assert_eq!(q!(-1.5 d).to_iso8601_duration(), Ok("-P1DT12H".to_owned()));
+    /// assert!(q!(1 m).to_iso8601_duration().is_err());
+    /// ```
+    pub fn to_iso8601_duration(&self) -> Result<String, ConversionError> {
+        if !self.unit.has_same_dimension(&Second) {
+            return Err(ConversionError::IncompatibleUnits { from: self.unit.clone(), to: Second });
+        }
+        let seconds = self.clone().convert_to(Second).expect("times are convertible to seconds").magnitude;
+
+        let nanoseconds = round_to_scale(&seconds, DURATION_SCALE, RoundingMode::HalfEven);
+        let negative = nanoseconds < ScalableInteger::zero();
+        let mut remainder = if negative { -nanoseconds } else { nanoseconds };
+
+        let per_second = num::pow(ScalableInteger::from(10), DURATION_SCALE as usize);
+        let mut split = |seconds: i64| {
+            let (count, rest) = remainder.div_rem(&(ScalableInteger::from(seconds) * per_second.clone()));
+            remainder = rest;
+            count
+        };
+        let (days, hours, minutes) = (split(86_400), split(3600), split(60));
+
+        let mut duration = String::from(if negative { "-P" } else { "P" });
+        if !days.is_zero() {
+            duration.push_str(&format!("{days}D"));
+        }
+        // A zero duration is written as PT0S
+        let zero = days.is_zero() && hours.is_zero() && minutes.is_zero() && remainder.is_zero();
+        if !(hours.is_zero() && minutes.is_zero() && remainder.is_zero()) || zero {
+            duration.push('T');
+        }
+        if !hours.is_zero() {
+            duration.push_str(&format!("{hours}H"));
+        }
+        if !minutes.is_zero() {
+            duration.push_str(&format!("{minutes}M"));
+        }
+        if !remainder.is_zero() || zero {
+            let digits = format_decimal(&BigRational::new(remainder, per_second), DURATION_SCALE, RoundingMode::HalfEven, &NumberLocale::PLAIN);
+            let digits = if digits.contains('.') { digits.trim_end_matches('0').trim_end_matches('.') } else { &digits };
+            duration.push_str(&format!("{digits}S"));
+        }
+        Ok(duration)
+    }
+}
+
+/// How many digits a ```QuantityFormatter``` shows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Precision {
+    /// A fixed number of decimal places.
+    Decimals(u32),
+    /// At most this many significant digits, switching to scientific notation for very large
+    /// and very small values like ```to_significant_string```.
+    SignificantDigits(u32),
+    /// The exact value, as a decimal if it terminates within this many decimal places and as a
+    /// reduced fraction like ```1/3``` otherwise.
+    Exact(u32),
+}
+
+/// The SI prefixes ```auto_prefix``` chooses from, indexed by ```exponent / 3 + 10```.
+const PREFIXES: [Option<Unit>; 21] = [
+    Some(Quecto), Some(Ronto), Some(Yocto), Some(Zepto), Some(Atto), Some(Femto), Some(Pico), Some(Nano),
+    Some(Micro), Some(Milli), None, Some(Kilo), Some(Mega), Some(Giga), Some(Tera), Some(Peta), Some(Exa),
+    Some(Zetta), Some(Yotta), Some(Ronna), Some(Quetta),
+];
+
+/// Formats quantities as decimal numbers followed by their unit.
+///
+/// The formatter is configured with builder methods and is ```Copy```, so it can be shared
+/// freely between threads.
+///
+/// # Example:
+/// ```
+/// use tantalum_unit::prelude::*;
+/// use tantalum_unit::format::QuantityFormatter;
+///
+/// let formatter = QuantityFormatter::new().precision(2).auto_prefix(true).long_names(true);
+/// assert_eq!(formatter.format(&Quantity::from_i64_with_unit(1500, Meter)), "1.50 kilometers");
+/// assert_eq!(formatter.format(&Quantity::new(ratio!(1, 1000), Kilo * Meter)), "1.00 meter");
+///
+/// let formatter = QuantityFormatter::new().significant_digits(3).space(true).unicode_exponents(true);
+/// assert_eq!(formatter.format(&Quantity::new(ratio!(98, 10), Meter / (Second * Second))), "9.8 m/s²");
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct QuantityFormatter {
+    precision: Precision,
+    rounding: RoundingMode,
+    locale: NumberLocale,
+    long_names: bool,
+    auto_prefix: bool,
+    space: bool,
+    unicode_exponents: bool,
+    parenthesize_prefixes: bool,
+    sort_units: bool,
+    mixed_fractions: bool,
+    snap_denominator: Option<u32>,
+}
+
+impl QuantityFormatter {
+    /// A formatter showing six significant digits in the ```NumberLocale::EN_US``` locale, with
+    /// unit symbols directly after the number.
+    pub const fn new() -> Self {
+        Self {
+            precision: Precision::SignificantDigits(6),
+            rounding: RoundingMode::HalfEven,
+            locale: NumberLocale::EN_US,
+            long_names: false,
+            auto_prefix: false,
+            space: false,
+            unicode_exponents: false,
+            parenthesize_prefixes: false,
+            sort_units: false,
+            mixed_fractions: false,
+            snap_denominator: None,
+        }
+    }
+
+    /// Shows exactly ```decimals``` decimal places.
+    pub const fn precision(mut self, decimals: u32) -> Self {
+        self.precision = Precision::Decimals(decimals);
+        self
+    }
+
+    /// Shows at most ```digits``` significant digits.
+    pub const fn significant_digits(mut self, digits: u32) -> Self {
+        self.precision = Precision::SignificantDigits(digits);
+        self
+    }
+
+    /// Shows the exact value, as a decimal if it has at most ```max_decimals``` decimal places,
+    /// like ```0.25```, and as a fraction otherwise, like ```1/3```.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    /// use tantalum_unit::format::QuantityFormatter;
+    ///
+    /// let formatter = QuantityFormatter::new().exact(6);
+    /// assert_eq!(formatter.format(&Quantity::new(ratio!(1, 4), Meter)), "0.25m");
+    /// assert_eq!(formatter.format(&Quantity::new(ratio!(1, 3), Meter)), "1/3m");
+    /// assert_eq!(formatter.format(&Quantity::new(ratio!(1, 1024), Meter)), "1/1024m");
+    /// ```
+    pub const fn exact(mut self, max_decimals: u32) -> Self {
+        self.precision = Precision::Exact(max_decimals);
+        self
+    }
+
+    pub const fn rounding(mut self, rounding: RoundingMode) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    pub const fn locale(mut self, locale: NumberLocale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Writes the unit's name, e.g. ```kilometers per hour```, instead of its symbol.
+    ///
+    /// The name is pluralized unless the number is one, and is always separated from the number by a space.
+    pub const fn long_names(mut self, long_names: bool) -> Self {
+        self.long_names = long_names;
+        self
+    }
+
+    /// Chooses the SI prefix that puts the number between 1 and 1000, e.g. ```1.5 km``` instead of ```1500 m```.
+    ///
+    /// Only applies to quantities whose unit is a single SI unit, optionally with a prefix.
+    pub const fn auto_prefix(mut self, auto_prefix: bool) -> Self {
+        self.auto_prefix = auto_prefix;
+        self
+    }
+
+    /// Separates the number and the unit symbol with a space.
+    pub const fn space(mut self, space: bool) -> Self {
+        self.space = space;
+        self
+    }
+
+    /// Writes exponents as superscripts, e.g. ```m/s²``` instead of ```m/s^2```.
+    pub const fn unicode_exponents(mut self, unicode_exponents: bool) -> Self {
+        self.unicode_exponents = unicode_exponents;
+        self
+    }
+
+    /// Writes the exact value as a mixed number, like ```3 5/8``` instead of ```29/8```, in place
+    /// of the configured precision.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    /// use tantalum_unit::format::QuantityFormatter;
+    ///
+    /// let formatter = QuantityFormatter::new().mixed_fractions(true).space(true);
+    /// assert_eq!(formatter.format(&Quantity::new(ratio!(29, 8), Inch)), "3 5/8 in");
+    /// assert_eq!(formatter.format(&Quantity::new(ratio!(-5, 4), Inch)), "-1 1/4 in");
+    /// ```
+    pub const fn mixed_fractions(mut self, mixed_fractions: bool) -> Self {
+        self.mixed_fractions = mixed_fractions;
+        self
+    }
+
+    /// Rounds mixed numbers to the nearest multiple of ```1/denominator```, like sixteenths of an
+    /// inch, and marks rounded values with ```≈```. Only applies with ```mixed_fractions```.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    /// use tantalum_unit::format::QuantityFormatter;
+    ///
+    /// let formatter = QuantityFormatter::new().mixed_fractions(true).snap_denominator(Some(16)).space(true);
+    /// assert_eq!(formatter.format(&Quantity::new(ratio!(33, 100), Inch)), "≈5/16 in");
+    /// assert_eq!(formatter.format(&Quantity::new(ratio!(3, 4), Inch)), "3/4 in");
+    /// ```
+    pub const fn snap_denominator(mut self, denominator: Option<u32>) -> Self {
+        self.snap_denominator = denominator;
+        self
+    }
+
+    /// Converts the Quantity to ```unit``` and formats it.
+    pub fn format_in(&self, quantity: &Quantity, unit: &Unit) -> Result<String, ConversionError> {
+        Ok(self.format(&quantity.converted(unit)?))
+    }
+
+    /// Writes powers of prefixed units in parentheses, e.g. ```(km)^2``` instead of ```km^2```.
+    pub const fn parenthesize_prefixes(mut self, parenthesize_prefixes: bool) -> Self {
+        self.parenthesize_prefixes = parenthesize_prefixes;
+        self
+    }
+
+    /// Simplifies the unit and writes its components in a fixed order, see ```Unit::canonical_symbol```.
+    pub const fn sort_units(mut self, sort_units: bool) -> Self {
+        self.sort_units = sort_units;
+        self
+    }
+
+    pub fn format(&self, quantity: &Quantity) -> String {
+        let (mut magnitude, mut unit) = (quantity.magnitude.clone(), quantity.unit.clone());
+        if self.auto_prefix {
+            (magnitude, unit) = self.choose_prefix(magnitude, unit);
+        }
+
+        let rounded = self.round(&magnitude);
+        let number = if self.mixed_fractions {
+            let approximate = if rounded == magnitude { "" } else { "≈" };
+            format!("{approximate}{}", mixed_number(&rounded))
+        } else {
+            self.format_number(&rounded)
+        };
+
+        if unit.is_unitless() {
+            number
+        } else if self.long_names {
+            let name = unit.name();
+            let one = rounded == BigRational::one() || rounded == -BigRational::one();
+            format!("{number} {}", if one { name.into_owned() } else { pluralize(&name) })
+        } else {
+            let symbol = if self.sort_units {
+                unit.simplify().symbol_with(self.parenthesize_prefixes, true).into_owned()
+            } else if self.parenthesize_prefixes {
+                unit.parenthesized_symbol().into_owned()
+            } else {
+                unit.to_string()
+            };
+            let symbol = if self.unicode_exponents { superscript_exponents(&symbol) } else { symbol };
+            let space = if self.space { " " } else { "" };
+            format!("{number}{space}{symbol}")
+        }
+    }
+
+    /// Renders a rounded magnitude with the configured precision.
+    fn format_number(&self, rounded: &BigRational) -> String {
+        match self.precision {
+            Precision::Decimals(decimals) => format_decimal(rounded, decimals, self.rounding, &self.locale),
+            Precision::SignificantDigits(digits) => self.locale.localize(&significant_string(rounded, digits, self.rounding)),
+            Precision::Exact(max_decimals) => match terminating_decimal_places(rounded) {
+                Some(places) if places <= max_decimals => format_decimal(rounded, places, self.rounding, &self.locale),
+                // Without group separators, which would be confused with decimal separators in fractions
+                _ => format!("{}/{}", rounded.numer(), rounded.denom()),
+            },
+        }
+    }
+
+    /// Rounds a magnitude the way it will be shown.
+    fn round(&self, value: &BigRational) -> BigRational {
+        if self.mixed_fractions {
+            return match self.snap_denominator {
+                Some(denominator) if denominator > 0 => {
+                    let denominator = ScalableInteger::from(denominator);
+                    let multiple = round(&(value * BigRational::from_integer(denominator.clone())), self.rounding);
+                    BigRational::new(multiple, denominator)
+                }
+                _ => value.clone(),
+            };
+        }
+        match self.precision {
+            Precision::Decimals(decimals) => BigRational::new(round_to_scale(value, decimals, self.rounding), power_of_ten(decimals as i64)),
+            Precision::SignificantDigits(digits) => {
+                let (mantissa, exponent) = round_to_significant(value, digits.max(1), self.rounding);
+                if exponent >= 0 {
+                    BigRational::from_integer(mantissa * power_of_ten(exponent))
+                } else {
+                    BigRational::new(mantissa, power_of_ten(-exponent))
+                }
+            }
+            Precision::Exact(_) => value.clone(),
+        }
+    }
+
+    /// Moves powers of a thousand between the magnitude and the prefix of a prefixable unit.
+    fn choose_prefix(&self, magnitude: BigRational, unit: Unit) -> (BigRational, Unit) {
+        let Some((prefix_exponent, base)) = split_prefix(&unit) else {
+            return (magnitude, unit);
+        };
+        if magnitude.is_zero() {
+            return (magnitude, unit);
+        }
+
+        let base_magnitude = scale(&magnitude, prefix_exponent);
+        let mut exponent = (decimal_exponent(&base_magnitude).div_euclid(3) * 3).clamp(-30, 30);
+
+        // Rounding may carry into the next power of a thousand, e.g. 999.999 m to 1000.00 m
+        let limit = BigRational::from_integer(1000.into());
+        let rounded = self.round(&scale(&base_magnitude, -exponent));
+        if exponent < 30 && (rounded >= limit || rounded <= -limit) {
+            exponent += 3;
+        }
+
+        let prefixed = match &PREFIXES[(exponent / 3 + 10) as usize] {
+            Some(prefix) => Compound(vec![prefix.clone(), base].into(), UnitList::new()),
+            None => base,
+        };
+        (scale(&base_magnitude, -exponent), prefixed)
+    }
+}
+
+impl Default for QuantityFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Rounds a value to ```digits``` significant digits and renders its mantissa in ```[1, 1000)```,
+/// returning the mantissa and its exponent, a multiple of three.
+fn engineering_parts(value: &BigRational, digits: usize) -> (String, i64) {
+    let digits = u32::try_from(digits).unwrap_or(u32::MAX).max(1);
+    let (mantissa, exponent) = round_to_significant(value, digits, RoundingMode::HalfEven);
+    if mantissa.is_zero() {
+        return ("0".to_owned(), 0);
+    }
+
+    let sign = if mantissa < ScalableInteger::zero() { "-" } else { "" };
+    let mut digits = mantissa.to_string().trim_start_matches('-').to_owned();
+    let leading_exponent = exponent + digits.len() as i64 - 1;
+    let engineering_exponent = leading_exponent.div_euclid(3) * 3;
+
+    // One to three digits go before the decimal point, padded with zeros if there are fewer digits
+    let whole_digits = (leading_exponent - engineering_exponent + 1) as usize;
+    if digits.len() < whole_digits {
+        digits.push_str(&"0".repeat(whole_digits - digits.len()));
+    }
+    let (whole, fraction) = digits.split_at(whole_digits);
+    let point = if fraction.is_empty() { "" } else { "." };
+    (format!("{sign}{whole}{point}{fraction}"), engineering_exponent)
+}
+
+/// Renders a rational as a whole number followed by a proper fraction, like ```-1 1/4```.
+fn mixed_number(value: &BigRational) -> String {
+    let sign = if *value < BigRational::zero() { "-" } else { "" };
+    let value = if *value < BigRational::zero() { -value } else { value.clone() };
+    let (whole, rest) = value.numer().div_rem(value.denom());
+    match (whole.is_zero(), rest.is_zero()) {
+        (_, true) => format!("{sign}{whole}"),
+        (true, false) => format!("{sign}{rest}/{}", value.denom()),
+        (false, false) => format!("{sign}{whole} {rest}/{}", value.denom()),
+    }
+}
+
+/// Appends the symbol of the unit to a number, separated by a space, unless it's unitless.
+fn with_symbol(number: String, unit: &Unit) -> String {
+    if unit.is_unitless() {
+        number
+    } else {
+        format!("{number} {unit}")
+    }
+}
+
+/// Splits a unit into the decimal exponent of its SI prefix, or zero, and a named unit that can take a prefix.
+fn split_prefix(unit: &Unit) -> Option<(i64, Unit)> {
+    let (exponent, base) = match unit.clone().flatten() {
+        Compound(numerator, denominator) => match (&numerator[..], &denominator[..]) {
+            ([prefix, base], []) => (prefix_exponent(prefix)?, base.clone()),
+            ([base], []) => (0, base.clone()),
+            _ => return None,
+        },
+        base => (0, base),
+    };
+
+    let prefixable = matches!(base, Meter | Gram | Second | Ampere | Kelvin | Mole | Candela | Newton | Joule | Ohm | Hertz
+        | Volt | Tesla | Siemens | Watt | Liter | Pascal | Henry | Weber | Coulomb | Farad | Bit | Byte | Radian | Baud);
+    prefixable.then_some((exponent, base))
+}
+
+/// The decimal exponent of an SI prefix like ```Kilo```, or ```None``` for other units.
+fn prefix_exponent(prefix: &Unit) -> Option<i64> {
+    if !prefix.is_modifier() {
+        return None;
+    }
+    let (_, slope, _) = prefix.clone().to_si_units();
+    let exponent = decimal_exponent(&slope);
+    (scale(&BigRational::one(), exponent) == slope).then_some(exponent)
+}
+
+fn power_of_ten(exponent: i64) -> ScalableInteger {
+    num::pow(ScalableInteger::from(10), exponent as usize)
+}
+
+/// Multiplies a rational by ```10^exponent```.
+fn scale(value: &BigRational, exponent: i64) -> BigRational {
+    if exponent >= 0 {
+        value * BigRational::from_integer(power_of_ten(exponent))
+    } else {
+        value / BigRational::from_integer(power_of_ten(-exponent))
+    }
+}
+
+/// Pluralizes a unit name like ```kilometer per hour``` or ```square meter```.
+fn pluralize(name: &str) -> String {
+    let (numerator, rest) = match name.find(" per ") {
+        Some(index) => name.split_at(index),
+        None => (name, ""),
+    };
+    // In "meter to the 4" the unit is the word before the exponent
+    let (head, tail) = match numerator.find(" to the ") {
+        Some(index) => numerator.split_at(index),
+        None => (numerator, ""),
+    };
+
+    let plural = if head.ends_with(['s', 'x', 'z']) || head.ends_with("feet") || head.ends_with("celsius")
+        || head.ends_with("fahrenheit") || head.ends_with("herzt") {
+        head.to_owned()
+    } else if head.ends_with("ch") || head.ends_with("sh") {
+        format!("{head}es")
+    } else {
+        format!("{head}s")
+    };
+    format!("{plural}{tail}{rest}")
+}
+
+/// Replaces exponents like ```^2``` or ```^-1``` with superscript digits.
+fn superscript_exponents(symbol: &str) -> String {
+    const DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+    let mut result = String::with_capacity(symbol.len());
+    let mut in_exponent = false;
+    for c in symbol.chars() {
+        match c {
+            '^' => in_exponent = true,
+            '-' if in_exponent => result.push('⁻'),
+            '0'..='9' if in_exponent => result.push(DIGITS[c as usize - '0' as usize]),
+            _ => {
+                in_exponent = false;
+                result.push(c);
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ratio;
+    use crate::unit::UNITLESS;
+    use super::*;
+
+    fn format(value: BigRational, scale: u32, locale: &NumberLocale) -> String {
+        format_decimal(&value, scale, RoundingMode::HalfEven, locale)
+    }
+
+    #[test]
+    fn locales() {
+        let value = ratio!(-123456789, 100);
+        assert_eq!(format(value.clone(), 2, &NumberLocale::EN_US), "-1,234,567.89");
+        assert_eq!(format(value.clone(), 2, &NumberLocale::DE_DE), "-1.234.567,89");
+        assert_eq!(format(value.clone(), 2, &NumberLocale::FR_FR), "-1\u{202F}234\u{202F}567,89");
+        assert_eq!(format(value.clone(), 2, &NumberLocale::PLAIN), "-1234567.89");
+
+        let indian = NumberLocale { grouping: &[3, 2], ..NumberLocale::EN_US };
+        assert_eq!(format(value, 2, &indian), "-12,34,567.89");
+    }
+
+    #[test]
+    fn small_values() {
+        assert_eq!(format(ratio!(1, 2), 2, &NumberLocale::EN_US), "0.50");
+        assert_eq!(format(ratio!(1, 2), 2, &NumberLocale::DE_DE), "0,50");
+        assert_eq!(format(ratio!(-1, 8), 3, &NumberLocale::DE_DE), "-0,125");
+        assert_eq!(format(ratio!(1, 1000), 2, &NumberLocale::EN_US), "0.00");
+        assert_eq!(format(ratio!(-1, 1000), 2, &NumberLocale::EN_US), "0.00");
+        assert_eq!(format(ratio!(999, 1), 0, &NumberLocale::DE_DE), "999");
+        assert_eq!(format(ratio!(1000, 1), 0, &NumberLocale::DE_DE), "1.000");
+    }
+
+    #[test]
+    fn localize() {
+        assert_eq!(NumberLocale::DE_DE.localize("1.5e-8"), "1,5e-8");
+        assert_eq!(NumberLocale::DE_DE.localize("-12345"), "-12.345");
+        assert_eq!(NumberLocale::EN_US.localize("123456.789"), "123,456.789");
+        assert_eq!(NumberLocale::default(), NumberLocale::EN_US);
+    }
+
+    #[test]
+    fn quantities() {
+        let speed = Quantity::new(ratio!(12345, 10), Kilo * Meter / Hour);
+        assert_eq!(speed.to_decimal_string(1, RoundingMode::HalfEven, &NumberLocale::EN_US), "1,234.5 km/h");
+        assert_eq!(speed.to_decimal_string(3, RoundingMode::HalfEven, &NumberLocale::DE_DE), "1.234,500 km/h");
+        assert_eq!(speed.to_decimal_string(0, RoundingMode::Floor, &NumberLocale::DE_DE), "1.234 km/h");
+
+        let ratio = Quantity::new(ratio!(-3, 4), UNITLESS);
+        assert_eq!(ratio.to_decimal_string(2, RoundingMode::HalfEven, &NumberLocale::DE_DE), "-0,75");
+    }
+
+    #[test]
+    fn formatter_defaults() {
+        let formatter = QuantityFormatter::default();
+        assert_eq!(formatter.format(&Quantity::new(ratio!(2, 3), Meter)), "0.666667m");
+        assert_eq!(formatter.format(&Quantity::new(ratio!(1234567, 1), Meter)), "1,234,570m");
+        assert_eq!(formatter.format(&Quantity::new(ratio!(3, 1), UNITLESS)), "3");
+    }
+
+    #[test]
+    fn formatter_options() {
+        let kilometers = Quantity::from_i64_with_unit(1500, Meter);
+        let formatter = QuantityFormatter::new().auto_prefix(true).precision(2).long_names(true);
+        assert_eq!(formatter.format(&kilometers), "1.50 kilometers");
+
+        let formatter = formatter.locale(NumberLocale::DE_DE).long_names(false).space(true);
+        assert_eq!(formatter.format(&kilometers), "1,50 km");
+        assert_eq!(formatter.format(&Quantity::from_i64_with_unit(-1_500_000, Gram)), "-1,50 Mg");
+
+        let formatter = QuantityFormatter::new().precision(1).rounding(RoundingMode::Floor);
+        assert_eq!(formatter.format(&Quantity::new(ratio!(-1, 20), Second)), "-0.1s");
+
+        let formatter = QuantityFormatter::new().significant_digits(3).space(true).unicode_exponents(true);
+        assert_eq!(formatter.format(&Quantity::new(ratio!(1, 3), Meter * Meter / (Second * Second * Second))), "0.333 m²/s³");
+        assert_eq!(formatter.format(&Quantity::from_i64_with_unit(2, UNITLESS / Second)), "2 1/s");
+        assert_eq!(formatter.format(&Quantity::from_i64_with_unit(2, Hertz)), "2 Hz");
+    }
+
+    #[test]
+    fn formatter_exact() {
+        let formatter = QuantityFormatter::new().exact(20).space(true);
+        assert_eq!(formatter.format(&Quantity::new(ratio!(1, 4), Meter)), "0.25 m");
+        assert_eq!(formatter.format(&Quantity::new(ratio!(1, 3), Meter)), "1/3 m");
+        assert_eq!(formatter.format(&Quantity::new(ratio!(-7, 8), Meter)), "-0.875 m");
+        assert_eq!(formatter.format(&Quantity::new(ratio!(-1, 3), Meter)), "-1/3 m");
+        assert_eq!(formatter.format(&Quantity::from_i64_with_unit(1500, Meter)), "1,500 m");
+        assert_eq!(formatter.format(&Quantity::new(ratio!(473176473, 125000000000i64), Meter)), "0.003785411784 m");
+        assert_eq!(formatter.format(&Quantity::new(ratio!(1, 2u64.pow(21) as i64), Meter)), "1/2097152 m");
+
+        let formatter = QuantityFormatter::new().exact(2).locale(NumberLocale::DE_DE);
+        assert_eq!(formatter.format(&Quantity::new(ratio!(5, 4), Meter)), "1,25m");
+        assert_eq!(formatter.format(&Quantity::new(ratio!(1, 8), Meter)), "1/8m");
+    }
+
+    #[test]
+    fn formatter_auto_prefix() {
+        let formatter = QuantityFormatter::new().auto_prefix(true).precision(2).space(true);
+        let format = |magnitude: BigRational, unit: Unit| formatter.format(&Quantity::new(magnitude, unit));
+
+        assert_eq!(format(ratio!(150, 1), Centi * Meter), "1.50 m");
+        assert_eq!(format(ratio!(1, 2000), Second), "500.00 µs");
+        assert_eq!(format(ratio!(9999999, 10000), Meter), "1.00 km");
+        assert_eq!(format(ratio!(2048, 1), Kibi * Byte), "2,048.00 KiB");
+        assert_eq!(format(ratio!(1500, 1), Mile), "1,500.00 mi");
+        assert_eq!(format(ratio!(1500, 1), Meter / Second), "1,500.00 m/s");
+        assert_eq!(format(ratio!(0, 1), Kilo * Meter), "0.00 km");
+    }
+
+    #[test]
+    fn plural_names() {
+        let formatter = QuantityFormatter::new().precision(0).long_names(true);
+        let format = |magnitude: i64, unit: Unit| formatter.format(&Quantity::from_i64_with_unit(magnitude, unit));
+
+        assert_eq!(format(1, Inch), "1 inch");
+        assert_eq!(format(-1, Inch), "-1 inch");
+        assert_eq!(format(2, Inch), "2 inches");
+        assert_eq!(format(3, Feet), "3 feet");
+        assert_eq!(format(3, Siemens), "3 siemens");
+        assert_eq!(format(50, Kilo * Meter / Hour), "50 kilometers per hour");
+        assert_eq!(format(3, Meter * Meter), "3 square meters");
+        assert_eq!(format(3, LightYear), "3 light years");
+    }
+
+    #[test]
+    fn formatter_is_shareable() {
+        fn assert_send_sync<T: Send + Sync + Copy>() {}
+        assert_send_sync::<QuantityFormatter>();
+    }
+
+    #[test]
+    fn iso8601_durations() {
+        let duration = |quantity: Quantity| quantity.to_iso8601_duration().unwrap();
+
+        assert_eq!(duration(Quantity::from_i64_with_unit(0, Second)), "PT0S");
+        assert_eq!(duration(Quantity::from_i64_with_unit(45, Second)), "PT45S");
+        assert_eq!(duration(Quantity::from_i64_with_unit(90, Minute)), "PT1H30M");
+        assert_eq!(duration(Quantity::from_i64_with_unit(2, Day)), "P2D");
+        assert_eq!(duration(Quantity::from_i64_with_unit(1, Year)), "P365DT6H");
+        assert_eq!(duration(Quantity::from_i64_with_unit(-1, Hour)), "-PT1H");
+        assert_eq!(duration(Quantity::new(ratio!(183_601, 1), Second)), "P2DT3H1S");
+        assert_eq!(duration(Quantity::new(ratio!(1, 2), Second)), "PT0.5S");
+        assert_eq!(duration(Quantity::new(ratio!(1, 3), Second)), "PT0.333333333S");
+        assert_eq!(duration(Quantity::new(ratio!(1, 3), Nano * Second)), "PT0S");
+        assert_eq!(duration(Quantity::new(ratio!(1, 2), Kilo * Second / (Meter / Meter))), "PT8M20S");
+
+        assert_eq!(Quantity::from_i64_with_unit(1, Meter).to_iso8601_duration(), Err(ConversionError::IncompatibleUnits { from: Meter, to: Second }));
+        assert!(Quantity::from_i64_with_unit(1, Hertz).to_iso8601_duration().is_err());
+    }
+
+    #[test]
+    fn iso8601_round_trip() {
+        let values = [
+            Quantity::from_i64_with_unit(0, Second),
+            Quantity::from_i64_with_unit(86_399, Second),
+            Quantity::from_i64_with_unit(-21, Day),
+            Quantity::new(ratio!(123_456_789, 1_000), Second),
+            Quantity::new(ratio!(-1, 1_000_000_000), Second),
+            Quantity::new(ratio!(7, 4), Minute),
+            Quantity::new(ratio!(10i64.pow(15) + 1, 1_000), Second),
+        ];
+        for value in values {
+            let duration = value.to_iso8601_duration().unwrap();
+            let parsed = Quantity::from_iso8601_duration(&duration).unwrap();
+            assert_eq!(parsed, value.clone().convert_to(Second).unwrap(), "{duration}");
+        }
+    }
+
+    #[test]
+    fn format_in_unit() {
+        let power = Quantity::from_i64_with_unit(3000, Joule / Second);
+        assert_eq!(power.to_string_in(&(Kilo * Watt)), Ok("3kW".to_owned()));
+        assert_eq!(power.to_decimal_string_in(&(Mega * Watt), 4, RoundingMode::HalfEven, &NumberLocale::DE_DE), Ok("0,0030 MW".to_owned()));
+        assert_eq!(QuantityFormatter::new().space(true).format_in(&power, &(Kilo * Watt)), Ok("3 kW".to_owned()));
+        assert_eq!(power, Quantity::from_i64_with_unit(3000, Joule / Second));
+
+        let error = ConversionError::IncompatibleUnits { from: Joule / Second, to: Meter };
+        assert_eq!(power.to_string_in(&Meter), Err(error.clone()));
+        assert_eq!(power.to_decimal_string_in(&Meter, 2, RoundingMode::HalfEven, &NumberLocale::EN_US), Err(error.clone()));
+        assert_eq!(QuantityFormatter::new().format_in(&power, &Meter), Err(error));
+    }
+
+    #[test]
+    fn parenthesized_prefixes() {
+        let area = Quantity::from_i64_with_unit(3, Kilo * Meter * Kilo * Meter);
+        assert_eq!(QuantityFormatter::new().format(&area), "3km^2");
+        assert_eq!(QuantityFormatter::new().parenthesize_prefixes(true).format(&area), "3(km)^2");
+        assert_eq!(QuantityFormatter::new().parenthesize_prefixes(true).unicode_exponents(true).space(true).format(&area), "3 (km)²");
+    }
+
+    #[test]
+    fn sorted_units() {
+        let power = Quantity::from_i64_with_unit(3, Meter * Meter * Kilo * Gram / (Second * Second * Second));
+        assert_eq!(QuantityFormatter::new().format(&power), "3m^2*kg/s^3");
+        assert_eq!(QuantityFormatter::new().sort_units(true).format(&power), "3kg*m^2/s^3");
+    }
+
+    #[test]
+    fn engineering_notation() {
+        let engineering = |value: BigRational, unit: Unit, digits| Quantity::new(value, unit).to_engineering_string(digits);
+        assert_eq!(engineering(ratio!(12345, 1), Meter, 3), "12.3e3 m");
+        assert_eq!(engineering(ratio!(123456, 1), Meter, 3), "123e3 m");
+        assert_eq!(engineering(ratio!(1234567, 1), Meter, 3), "1.23e6 m");
+        assert_eq!(engineering(ratio!(12, 1), Meter, 4), "12.00 m");
+        assert_eq!(engineering(ratio!(12345, 1), Meter, 1), "10e3 m");
+        assert_eq!(engineering(ratio!(-12345, 1), Meter, 3), "-12.3e3 m");
+        assert_eq!(engineering(ratio!(0, 1), Meter, 3), "0 m");
+        assert_eq!(engineering(ratio!(5, 2), UNITLESS, 3), "2.50");
+
+        // Around the boundary between two exponents
+        assert_eq!(engineering(ratio!(9999, 10), Meter, 4), "999.9 m");
+        assert_eq!(engineering(ratio!(9999, 10), Meter, 3), "1.00e3 m");
+        assert_eq!(engineering(ratio!(1000, 1), Meter, 3), "1.00e3 m");
+        assert_eq!(engineering(ratio!(999, 1), Meter, 3), "999 m");
+
+        // Negative exponents
+        assert_eq!(engineering(ratio!(47, 1000000), Farad, 2), "47e-6 F");
+        assert_eq!(engineering(ratio!(1, 1000), Second, 3), "1.00e-3 s");
+        assert_eq!(engineering(ratio!(1, 10000), Second, 2), "100e-6 s");
+        assert_eq!(engineering(ratio!(9999, 10000000), Second, 3), "1.00e-3 s");
+        assert_eq!(engineering(ratio!(1, 3), Second, 3), "333e-3 s");
+    }
+
+    #[test]
+    fn engineering_notation_with_prefixes() {
+        let prefixed = |value: BigRational, unit: Unit, digits| Quantity::new(value, unit).to_engineering_string_prefixed(digits);
+        assert_eq!(prefixed(ratio!(12345, 1), Meter, 3), "12.3 km");
+        assert_eq!(prefixed(ratio!(12345, 1), Milli * Meter, 3), "12.3 m");
+        assert_eq!(prefixed(ratio!(9999, 10), Meter, 3), "1.00 km");
+        assert_eq!(prefixed(ratio!(9999, 10), Meter, 4), "999.9 m");
+        assert_eq!(prefixed(ratio!(47, 1000000), Farad, 2), "47 µF");
+        assert_eq!(prefixed(ratio!(1, 3), Second, 3), "333 ms");
+        assert_eq!(prefixed(ratio!(12345, 1), Inch, 3), "12.3e3 in");
+        assert_eq!(prefixed(ratio!(10i128.pow(34), 1), Meter, 2), "10e33 m");
+        assert_eq!(prefixed(ratio!(3, 1), Meter / Second, 2), "3.0 m/s");
+    }
+
+    #[test]
+    fn mixed_fractions() {
+        let formatter = QuantityFormatter::new().mixed_fractions(true).space(true);
+        let format = |value: BigRational, unit: Unit| formatter.format(&Quantity::new(value, unit));
+        assert_eq!(format(ratio!(29, 8), Inch), "3 5/8 in");
+        assert_eq!(format(ratio!(3, 2), Liter), "1 1/2 L");
+        assert_eq!(format(ratio!(-5, 4), Inch), "-1 1/4 in");
+        assert_eq!(format(ratio!(-1, 4), Inch), "-1/4 in");
+        assert_eq!(format(ratio!(1, 3), Inch), "1/3 in");
+        assert_eq!(format(ratio!(3, 1), Inch), "3 in");
+        assert_eq!(format(ratio!(0, 1), Inch), "0 in");
+        assert_eq!(format(ratio!(5, 2), UNITLESS), "2 1/2");
+        assert_eq!(formatter.long_names(true).format(&Quantity::new(ratio!(3, 2), Inch)), "1 1/2 inches");
+    }
+
+    #[test]
+    fn snapped_mixed_fractions() {
+        let formatter = QuantityFormatter::new().mixed_fractions(true).snap_denominator(Some(16)).space(true);
+        let format = |value: BigRational| formatter.format(&Quantity::new(value, Inch));
+        assert_eq!(format(ratio!(33, 100)), "≈5/16 in");
+        assert_eq!(format(ratio!(-33, 100)), "≈-5/16 in");
+        assert_eq!(format(ratio!(29, 8)), "3 5/8 in");
+        assert_eq!(format(ratio!(2, 1)), "2 in");
+        assert_eq!(format(ratio!(199, 100)), "≈2 in");
+        assert_eq!(format(ratio!(1, 100)), "≈0 in");
+
+        let halves = formatter.snap_denominator(Some(2));
+        assert_eq!(halves.format(&Quantity::new(ratio!(7, 4), Inch)), "≈2 in");
+        assert_eq!(halves.format(&Quantity::new(ratio!(13, 10), Inch)), "≈1 1/2 in");
+        // Snapping only applies to mixed numbers
+        assert_eq!(formatter.mixed_fractions(false).format(&Quantity::new(ratio!(33, 100), Inch)), "0.33 in");
+    }
+}
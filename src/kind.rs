@@ -0,0 +1,177 @@
+//! The physical kind of a unit, like a length or a velocity, derived from its dimension.
+//!
+//! The kind only depends on the SI units a unit is made of, so compounds that never mention a
+//! named derived unit have a kind as well.
+//!
+//! # Example:
+//! ```
+//! use tantalum_unit::prelude::*;
+//! use tantalum_unit::kind::Kind;
+//!
+//! assert_eq!(Meter.kind(), Some(Kind::Length));
+//! assert_eq!((Kilo * Meter / Hour).kind(), Some(Kind::Velocity));
+//! assert_eq!((Kilo * Gram * Meter / (Second * Second)).kind(), Some(Kind::Force));
+//! assert_eq!((Meter * Kelvin).kind(), None);
+//! ```
+
+use std::sync::OnceLock;
+use crate::quantity::Quantity;
+use crate::unit::Unit;
+use crate::unit::Unit::*;
+
+/// A physical kind of quantity.
+///
+/// Kinds that share a dimension can't be told apart, so torque is an ```Energy``` and a
+/// frequency is never an angular velocity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Kind {
+    Length,
+    Area,
+    Volume,
+    Mass,
+    Time,
+    Frequency,
+    Velocity,
+    Acceleration,
+    Jerk,
+    Force,
+    Pressure,
+    Energy,
+    Power,
+    Momentum,
+    Density,
+    Temperature,
+    Current,
+    Charge,
+    Voltage,
+    Resistance,
+    Conductance,
+    Capacitance,
+    Inductance,
+    MagneticFlux,
+    MagneticFluxDensity,
+    AmountOfSubstance,
+    Concentration,
+    LuminousIntensity,
+    Information,
+    Angle,
+}
+
+impl Kind {
+    /// Every kind with a unit of its dimension.
+    fn references() -> [(Kind, Unit); 30] {
+        [
+            (Kind::Length, Meter),
+            (Kind::Area, Meter * Meter),
+            (Kind::Volume, Meter * Meter * Meter),
+            (Kind::Mass, Gram),
+            (Kind::Time, Second),
+            (Kind::Frequency, Hertz),
+            (Kind::Velocity, Meter / Second),
+            (Kind::Acceleration, Meter / (Second * Second)),
+            (Kind::Jerk, Meter / (Second * Second * Second)),
+            (Kind::Force, Newton),
+            (Kind::Pressure, Pascal),
+            (Kind::Energy, Joule),
+            (Kind::Power, Watt),
+            (Kind::Momentum, Gram * Meter / Second),
+            (Kind::Density, Gram / (Meter * Meter * Meter)),
+            (Kind::Temperature, Kelvin),
+            (Kind::Current, Ampere),
+            (Kind::Charge, Coulomb),
+            (Kind::Voltage, Volt),
+            (Kind::Resistance, Ohm),
+            (Kind::Conductance, Siemens),
+            (Kind::Capacitance, Farad),
+            (Kind::Inductance, Henry),
+            (Kind::MagneticFlux, Weber),
+            (Kind::MagneticFluxDensity, Tesla),
+            (Kind::AmountOfSubstance, Mole),
+            (Kind::Concentration, Mole / (Meter * Meter * Meter)),
+            (Kind::LuminousIntensity, Candela),
+            (Kind::Information, Bit),
+            (Kind::Angle, Radian),
+        ]
+    }
+}
+
+impl Unit {
+    /// The kind of quantity the unit measures, or ```None``` if no kind has its dimension.
+    ///
+    /// Dimensionless units like modifiers have no kind.
+    pub fn kind(&self) -> Option<Kind> {
+        static TABLE: OnceLock<Vec<(Kind, Vec<i32>)>> = OnceLock::new();
+        let table = TABLE.get_or_init(|| {
+            Kind::references().into_iter().map(|(kind, unit)| (kind, unit.si_exponents())).collect()
+        });
+
+        let exponents = self.si_exponents();
+        table.iter().find(|(_, reference)| *reference == exponents).map(|(kind, _)| *kind)
+    }
+}
+
+impl Quantity {
+    /// The kind of quantity this is, see ```Unit::kind```.
+    pub fn kind(&self) -> Option<Kind> {
+        self.unit.kind()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::unit::{KILOWATT_HOUR, UNITLESS};
+    use super::*;
+
+    #[test]
+    fn named_units() {
+        for (kind, unit) in Kind::references() {
+            assert_eq!(unit.kind(), Some(kind), "{unit}");
+        }
+        assert_eq!(Celsius.kind(), Some(Kind::Temperature));
+        assert_eq!(Gallon.kind(), Some(Kind::Volume));
+        assert_eq!(Hectare.kind(), Some(Kind::Area));
+        assert_eq!(Byte.kind(), Some(Kind::Information));
+        assert_eq!(Year.kind(), Some(Kind::Time));
+        assert_eq!(Degree.kind(), Some(Kind::Angle));
+    }
+
+    #[test]
+    fn derived_dimensions() {
+        assert_eq!((Meter / Second).kind(), Some(Kind::Velocity));
+        assert_eq!((Mile / Hour).kind(), Some(Kind::Velocity));
+        assert_eq!((Meter / (Second * Second)).kind(), Some(Kind::Acceleration));
+        assert_eq!((Kilo * Gram * Meter / (Second * Second)).kind(), Some(Kind::Force));
+        assert_eq!((Newton / (Meter * Meter)).kind(), Some(Kind::Pressure));
+        assert_eq!((Kilo * Gram / (Meter * Second * Second)).kind(), Some(Kind::Pressure));
+        assert_eq!((Newton * Meter).kind(), Some(Kind::Energy));
+        assert_eq!(KILOWATT_HOUR.kind(), Some(Kind::Energy));
+        assert_eq!((Joule / Second).kind(), Some(Kind::Power));
+        assert_eq!((Volt * Ampere).kind(), Some(Kind::Power));
+        assert_eq!((Ampere * Second).kind(), Some(Kind::Charge));
+        assert_eq!((Coulomb / Volt).kind(), Some(Kind::Capacitance));
+        assert_eq!((Volt / Ampere).kind(), Some(Kind::Resistance));
+        assert_eq!((UNITLESS / Ohm).kind(), Some(Kind::Conductance));
+        assert_eq!((Volt * Second).kind(), Some(Kind::MagneticFlux));
+        assert_eq!((Weber / (Meter * Meter)).kind(), Some(Kind::MagneticFluxDensity));
+        assert_eq!((UNITLESS / Second).kind(), Some(Kind::Frequency));
+        assert_eq!((Kilo * Gram / Liter).kind(), Some(Kind::Density));
+        assert_eq!((Milli * Mole / Liter).kind(), Some(Kind::Concentration));
+        assert_eq!((Pound * Feet / Second).kind(), Some(Kind::Momentum));
+        assert_eq!((Liter / Meter).kind(), Some(Kind::Area));
+    }
+
+    #[test]
+    fn unknown_dimensions() {
+        assert_eq!(UNITLESS.kind(), None);
+        assert_eq!(Kilo.kind(), None);
+        assert_eq!((Meter / Meter).kind(), None);
+        assert_eq!((Meter * Kelvin).kind(), None);
+        assert_eq!((Bit / Second).kind(), None);
+    }
+
+    #[test]
+    fn quantity_kind() {
+        assert_eq!(Quantity::from_i64_with_unit(3, Meter / Second).kind(), Some(Kind::Velocity));
+        assert_eq!(Quantity::from_i64(3).kind(), None);
+    }
+}
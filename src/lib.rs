@@ -1,5 +1,31 @@
 pub mod quantity;
 pub mod unit;
+pub mod unit_list;
 pub mod util;
 pub mod scalable_integer;
+pub mod rounding;
+pub mod format;
+pub mod error;
+pub mod encoding;
+pub mod float;
+pub mod ext;
+pub mod parse;
+pub mod column;
+pub mod explain;
+pub mod kind;
+pub mod dimension;
+pub mod pow;
+pub mod math;
+pub mod constants;
+pub mod registry;
+pub mod split;
+pub mod policy;
+pub mod context;
+pub mod typed;
+pub mod prelude;
+pub mod testing;
+#[cfg(feature = "rust_decimal")]
+pub mod decimal;
+#[cfg(any(test, feature = "proptest"))]
+pub mod strategies;
 mod unit_macro;
\ No newline at end of file
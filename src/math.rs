@@ -0,0 +1,747 @@
+//! Logarithms, the exponential function and trigonometric functions.
+//!
+//! Logarithms and ```exp``` take unitless quantities, while the trigonometric functions take angles,
+//! with unitless quantities counting as radians.
+//!
+//! The results are irrational in general, so they are approximated to a given number of decimal
+//! places: the result for a precision of ```p``` is the exact value rounded half to even to a
+//! multiple of ```10^-p```, except that it may be off by one in the last place when the exact
+//! value is very close to a rounding boundary. Results that are detected to be rational are exact,
+//! like ```exp(0) = 1```, ```log10(1000) = 3``` or ```sin(30°) = 1/2```.
+//!
+//! Modifiers are applied before evaluating a function, so ```ln(2 k)``` is ```ln(2000)```.
+//!
+//! # Example:
+//! ```
+//! use tantalum_unit::prelude::*;
+//! use tantalum_unit::error::MathError;
+//!
+//! assert_eq!(Quantity::from_i64(2).ln(6), Ok(Quantity::new(ratio!(693147, 1000000), UNITLESS)));
+//! assert_eq!(Quantity::from_i64(1000).log10(6), Ok(Quantity::from_i64(3)));
+//! assert_eq!(q!(2 m).exp(6), Err(MathError::NotUnitless(Meter)));
+//! assert_eq!(Quantity::from_i64_with_unit(30, Degree).sin(6), Ok(Quantity::new(ratio!(1, 2), UNITLESS)));
+//! ```
+
+use num::{BigInt, Integer, One, ToPrimitive, Zero};
+use crate::{int, ratio};
+use crate::error::MathError;
+use crate::quantity::Quantity;
+use crate::rounding::{round, RoundingMode};
+use crate::pow::magnitude_pow;
+use crate::scalable_integer::{BigRational, ScalableInteger};
+use crate::constants::pi;
+use crate::unit::Unit;
+use crate::unit::Unit::{Compound, Degree, Radian};
+
+/// Extra decimal places of the intermediate results, which absorb the truncation of each step.
+pub(crate) const GUARD_DIGITS: u32 = 10;
+
+/// The largest argument of ```exp```, whose result has about 4343 digits before the decimal point.
+const MAX_EXP_ARGUMENT: i64 = 10_000;
+
+impl Quantity {
+    /// The natural logarithm, rounded to ```precision``` decimal places.
+    pub fn ln(&self, precision: u32) -> Result<Quantity, MathError> {
+        let x = positive_argument(self)?;
+        if x.is_one() {
+            return Ok(Quantity::from_i64(0));
+        }
+        let guard = GUARD_DIGITS + digits(binary_exponent(&x).unsigned_abs());
+        let scale = power_of_ten(precision + guard);
+        Ok(from_fixed(ln_fixed(&x, &scale), guard, precision))
+    }
+
+    /// The decimal logarithm, rounded to ```precision``` decimal places, and exact for powers of ten.
+    pub fn log10(&self, precision: u32) -> Result<Quantity, MathError> {
+        self.log_base(10, precision)
+    }
+
+    /// The binary logarithm, rounded to ```precision``` decimal places, and exact for powers of two.
+    pub fn log2(&self, precision: u32) -> Result<Quantity, MathError> {
+        self.log_base(2, precision)
+    }
+
+    /// The exponential function, rounded to ```precision``` decimal places.
+    ///
+    /// Returns an error for arguments above ```10000```, whose results have thousands of digits.
+    pub fn exp(&self, precision: u32) -> Result<Quantity, MathError> {
+        let x = unitless_argument(self)?;
+        if x.is_zero() {
+            return Ok(Quantity::from_i64(1));
+        }
+        if x > BigRational::from_integer(MAX_EXP_ARGUMENT.into()) {
+            return Err(MathError::Overflow);
+        }
+
+        // Results below half of the last place round to zero, and exp(-3n) < 10^-n
+        let smallest = -BigRational::from_integer((3 * (i64::from(precision) + 1)).into());
+        if x < smallest {
+            return Ok(Quantity::from_i64(0));
+        }
+
+        // Large results need more digits, since the error of ln(2) is multiplied by 2^k
+        let estimate = to_big_int(x.numer()).to_f64().unwrap_or(0.0) / to_big_int(x.denom()).to_f64().unwrap_or(1.0);
+        let guard = GUARD_DIGITS + (estimate.max(0.0) * std::f64::consts::LOG10_E).ceil() as u32;
+        let scale = power_of_ten(precision + guard);
+        Ok(from_fixed(exp_fixed(&x, &scale), guard, precision))
+    }
+
+    fn log_base(&self, base: u32, precision: u32) -> Result<Quantity, MathError> {
+        let x = positive_argument(self)?;
+        if let Some(exponent) = exact_log(&x, base) {
+            return Ok(Quantity::from_i64(exponent));
+        }
+
+        let guard = GUARD_DIGITS + digits(binary_exponent(&x).unsigned_abs());
+        let scale = power_of_ten(precision + guard);
+        let ln_base = ln_fixed(&BigRational::from_integer(base.into()), &scale);
+        Ok(from_fixed(ln_fixed(&x, &scale) * &scale / ln_base, guard, precision))
+    }
+
+    /// The sine of an angle, rounded to ```precision``` decimal places.
+    ///
+    /// The result is exact for multiples of 30° where it is rational.
+    pub fn sin(&self, precision: u32) -> Result<Quantity, MathError> {
+        let angle = angle_argument(self)?;
+        if let Angle::HalfTurns(turns) = &angle {
+            if let Some(exact) = exact_sin(turns) {
+                return Ok(Quantity::from_rational(exact));
+            }
+        }
+        let guard = angle.guard_digits();
+        let scale = power_of_ten(precision + guard);
+        Ok(from_fixed(sin_cos_fixed(&angle, &scale).0, guard, precision))
+    }
+
+    /// The cosine of an angle, rounded to ```precision``` decimal places.
+    ///
+    /// The result is exact for multiples of 30° where it is rational.
+    pub fn cos(&self, precision: u32) -> Result<Quantity, MathError> {
+        let angle = angle_argument(self)?;
+        if let Angle::HalfTurns(turns) = &angle {
+            if let Some(exact) = exact_sin(&(turns + ratio!(1, 2))) {
+                return Ok(Quantity::from_rational(exact));
+            }
+        }
+        let guard = angle.guard_digits();
+        let scale = power_of_ten(precision + guard);
+        Ok(from_fixed(sin_cos_fixed(&angle, &scale).1, guard, precision))
+    }
+
+    /// The tangent of an angle, rounded to ```precision``` decimal places.
+    ///
+    /// The result is exact for multiples of 45°, and an error for odd multiples of 90°.
+    pub fn tan(&self, precision: u32) -> Result<Quantity, MathError> {
+        let angle = angle_argument(self)?;
+        if let Angle::HalfTurns(turns) = &angle {
+            let quarters = turns * int!(4);
+            if quarters.is_integer() {
+                return match quarters.to_integer().to_big_int().mod_floor(&BigInt::from(4)).to_i64() {
+                    Some(0) => Ok(Quantity::from_i64(0)),
+                    Some(1) => Ok(Quantity::from_i64(1)),
+                    Some(2) => Err(MathError::Undefined),
+                    _ => Ok(Quantity::from_i64(-1)),
+                };
+            }
+        }
+
+        // Dividing by a small cosine magnifies its error, so each of its leading zeros costs two digits
+        let mut guard = angle.guard_digits();
+        loop {
+            let scale = power_of_ten(precision + guard);
+            let (sin, cos) = sin_cos_fixed(&angle, &scale);
+            let leading_zeros = (precision + guard).saturating_sub(fixed_digits(&cos));
+            if guard >= angle.guard_digits() + 2 * leading_zeros {
+                return Ok(from_fixed(sin * &scale / cos, guard, precision));
+            }
+            guard = angle.guard_digits() + 2 * leading_zeros + 2;
+        }
+    }
+
+    /// The angle between the positive x axis and the point ```(x, self)``` in ```Radian```, like
+    /// ```f64::atan2```, rounded to ```precision``` decimal places.
+    ///
+    /// Both coordinates must measure the same dimension, and the result lies in ```(-pi, pi]```.
+    pub fn atan2(&self, x: &Quantity, precision: u32) -> Result<Quantity, MathError> {
+        if !self.unit.has_same_dimension(&x.unit) {
+            return Err(MathError::IncompatibleUnits(self.unit.clone(), x.unit.clone()));
+        }
+        let (y, x) = (si_magnitude(self), si_magnitude(x));
+        let zero = BigRational::zero();
+        if y.is_zero() && x >= zero {
+            return Ok(Quantity::new(BigRational::zero(), Radian));
+        }
+
+        let scale = power_of_ten(precision + GUARD_DIGITS);
+        let pi = pi_fixed(&scale);
+        // Reduce to atan(z) with z in [0, 1] and undo the reduction with the symmetries of atan
+        let (y_abs, x_abs) = (abs(&y), abs(&x));
+        let z = if y_abs <= x_abs { &y_abs / &x_abs } else { &x_abs / &y_abs };
+        let mut angle = atan_fixed(&to_fixed(&z, &scale), &scale);
+        if y_abs > x_abs {
+            angle = &pi / 2 - angle;
+        }
+        if x < zero {
+            angle = &pi - angle;
+        }
+        if y < zero {
+            angle = -angle;
+        }
+        Ok(Quantity::new(from_fixed(angle, GUARD_DIGITS, precision).magnitude, Radian))
+    }
+
+    /// Converts an angle to the angle unit ```to``` with pi rounded to ```precision``` decimal
+    /// places, unlike ```convert_to```, which uses the fixed approximation of ```Degree```.
+    ///
+    /// Conversions between degrees and radians are rounded to ```precision``` decimal places,
+    /// other conversions are exact. Returns an error if either unit isn't an angle.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let angle = Quantity::from_i64_with_unit(180, Degree);
+    /// assert_eq!(angle.convert_angle(Radian, 5), Ok(Quantity::new(ratio!(314159, 100000), Radian)));
+    /// assert_eq!(angle.convert_angle(Milli * Degree, 5), Ok(Quantity::from_i64_with_unit(180000, Milli * Degree)));
+    /// ```
+    pub fn convert_angle(&self, to: Unit, precision: u32) -> Result<Quantity, MathError> {
+        let (from_factor, from_pi) = radian_factor(&self.unit)?;
+        let (to_factor, to_pi) = radian_factor(&to)?;
+        let magnitude = &self.magnitude * from_factor / to_factor;
+        let magnitude = match from_pi - to_pi {
+            0 => return Ok(Quantity::new(magnitude, to)),
+            powers => {
+                // The error of pi is multiplied by the magnitude, so larger angles need more digits
+                let guard = GUARD_DIGITS + fixed_digits(&to_big_int(&magnitude.to_integer())) * powers.unsigned_abs();
+                let pi = pi(precision + guard);
+                let mut result = magnitude;
+                for _ in 0..powers.unsigned_abs() {
+                    result = if powers > 0 { result * &pi } else { result / &pi };
+                }
+                let scale = BigRational::from_integer(power_of_ten(precision).into());
+                BigRational::new(round(&(result * &scale), RoundingMode::HalfEven), scale.to_integer())
+            }
+        };
+        Ok(Quantity::new(magnitude, to))
+    }
+}
+
+/// The magnitude of a unitless Quantity with its modifiers applied.
+fn unitless_argument(quantity: &Quantity) -> Result<BigRational, MathError> {
+    let stripped = quantity.clone().apply_modifiers();
+    if stripped.is_unitless() {
+        Ok(stripped.magnitude)
+    } else {
+        Err(MathError::NotUnitless(quantity.unit.clone()))
+    }
+}
+
+fn positive_argument(quantity: &Quantity) -> Result<BigRational, MathError> {
+    let x = unitless_argument(quantity)?;
+    if x <= BigRational::zero() {
+        return Err(MathError::NonPositive);
+    }
+    Ok(x)
+}
+
+/// The integer ```n``` with ```x = base^n```, if there is one.
+fn exact_log(x: &BigRational, base: u32) -> Option<i64> {
+    let (numer, denom) = (to_big_int(x.numer()), to_big_int(x.denom()));
+    let (power, negative) = if denom.is_one() { (numer, false) } else if numer.is_one() { (denom, true) } else { return None };
+
+    let base = BigInt::from(base);
+    let mut exponent = 0;
+    let mut rest = power;
+    while !rest.is_one() {
+        let (quotient, remainder) = rest.div_rem(&base);
+        if !remainder.is_zero() {
+            return None;
+        }
+        rest = quotient;
+        exponent += 1;
+    }
+    Some(if negative { -exponent } else { exponent })
+}
+
+/// The ```k``` for which ```x / 2^k``` lies in ```[1, 2)```.
+fn binary_exponent(x: &BigRational) -> i64 {
+    let (numer, denom) = (to_big_int(x.numer()), to_big_int(x.denom()));
+    let mut k = numer.bits() as i64 - denom.bits() as i64;
+    if shift(&numer, -k) < denom {
+        k -= 1;
+    }
+    k
+}
+
+/// ```ln(x)``` for a positive ```x```, as a fixed point number with the given scale.
+fn ln_fixed(x: &BigRational, scale: &BigInt) -> BigInt {
+    let k = binary_exponent(x);
+    let (numer, denom) = (shift(&to_big_int(x.numer()), -k.min(0)), shift(&to_big_int(x.denom()), k.max(0)));
+
+    // ln(y) = 2 atanh((y - 1) / (y + 1)) with y = numer / denom in [1, 2)
+    let z = (&numer - &denom) * scale / (&numer + &denom);
+    ln2_fixed(scale) * BigInt::from(k) + atanh_fixed(&z, scale) * 2
+}
+
+pub(crate) fn ln2_fixed(scale: &BigInt) -> BigInt {
+    atanh_fixed(&(scale / 3), scale) * 2
+}
+
+/// ```atanh(z)``` for a fixed point ```z``` with ```|z| <= 1/3```.
+fn atanh_fixed(z: &BigInt, scale: &BigInt) -> BigInt {
+    let z_squared = z * z / scale;
+    let mut power = z.clone();
+    let mut sum = z.clone();
+    let mut n = 1u32;
+    loop {
+        power = power * &z_squared / scale;
+        if power.is_zero() {
+            return sum;
+        }
+        sum += &power / (2 * n + 1);
+        n += 1;
+    }
+}
+
+/// ```exp(x)``` as a fixed point number with the given scale.
+pub(crate) fn exp_fixed(x: &BigRational, scale: &BigInt) -> BigInt {
+    // exp(x) = 2^k exp(r) with r = x - k ln(2) in [0, ln(2))
+    let ln2 = ln2_fixed(scale);
+    let x = to_fixed(x, scale);
+    let k = x.div_floor(&ln2);
+    let r = x - &k * &ln2;
+
+    let mut term = scale.clone();
+    let mut sum = scale.clone();
+    let mut n = 1u32;
+    loop {
+        term = term * &r / scale / n;
+        if term.is_zero() {
+            break;
+        }
+        sum += &term;
+        n += 1;
+    }
+    shift(&sum, k.to_i64().expect("the argument of exp is bounded"))
+}
+
+/// An angle, in radians or as an exact multiple of pi.
+enum Angle {
+    Radians(BigRational),
+    /// A multiple of pi in ```[0, 2)```.
+    HalfTurns(BigRational),
+}
+
+impl Angle {
+    /// The guard digits for the angle, which grow with the multiple of pi that is subtracted from it.
+    fn guard_digits(&self) -> u32 {
+        match self {
+            Angle::Radians(x) => GUARD_DIGITS + fixed_digits(&to_big_int(&x.to_integer())),
+            Angle::HalfTurns(_) => GUARD_DIGITS,
+        }
+    }
+}
+
+/// The angle of a Quantity with its modifiers applied, where unitless quantities are in radians.
+///
+/// Degrees are kept as multiples of pi, so they don't depend on the approximation of ```Degree```.
+fn angle_argument(quantity: &Quantity) -> Result<Angle, MathError> {
+    let stripped = quantity.clone().apply_modifiers();
+    let angle = match &stripped.unit {
+        Degree => {
+            let turns = stripped.magnitude / int!(180);
+            let two = int!(2);
+            Angle::HalfTurns(&turns - (&turns / &two).floor() * two)
+        }
+        unit if unit.is_unitless() || *unit == Radian => Angle::Radians(stripped.magnitude),
+        unit if unit.has_same_dimension(&Radian) => Angle::Radians(stripped.magnitude * unit.si_affine().1),
+        _ => return Err(MathError::NotAngle(quantity.unit.clone())),
+    };
+    Ok(match angle {
+        Angle::Radians(x) if x.is_zero() => Angle::HalfTurns(x),
+        angle => angle,
+    })
+}
+
+/// The factor converting an angle unit to radians, as a rational and a power of pi.
+///
+/// A degree is exactly pi/180 radians, so its factor is ```(1/180, 1)```.
+fn radian_factor(unit: &Unit) -> Result<(BigRational, i32), MathError> {
+    if !unit.has_same_dimension(&Radian) {
+        return Err(MathError::NotAngle(unit.clone()));
+    }
+    let factor = |component: &Unit| {
+        let (named, exponent) = component.as_power();
+        let (rational, pi) = match named {
+            Degree => (ratio!(1, 180), 1i32),
+            named => (named.si_affine().1, 0),
+        };
+        (magnitude_pow(rational, exponent), pi.saturating_mul(exponent))
+    };
+    Ok(match unit.clone().flatten() {
+        Compound(numerator, denominator) => {
+            let (mut rational, mut powers) = (BigRational::one(), 0i32);
+            for (rational_part, pi_part) in numerator.iter().map(factor) {
+                rational *= rational_part;
+                powers = powers.saturating_add(pi_part);
+            }
+            for (rational_part, pi_part) in denominator.iter().map(factor) {
+                rational /= rational_part;
+                powers = powers.saturating_sub(pi_part);
+            }
+            (rational, powers)
+        }
+        named => factor(&named),
+    })
+}
+
+/// ```sin(turns * pi)``` for the multiples of 30° where it is rational.
+fn exact_sin(turns: &BigRational) -> Option<BigRational> {
+    let sixths = turns * int!(6);
+    if !sixths.is_integer() {
+        return None;
+    }
+    match sixths.to_integer().to_big_int().mod_floor(&BigInt::from(12)).to_i64()? {
+        0 | 6 => Some(BigRational::zero()),
+        1 | 5 => Some(ratio!(1, 2)),
+        3 => Some(BigRational::one()),
+        7 | 11 => Some(ratio!(-1, 2)),
+        9 => Some(-BigRational::one()),
+        _ => None,
+    }
+}
+
+/// The magnitude of a Quantity in SI units.
+fn si_magnitude(quantity: &Quantity) -> BigRational {
+    let (offset, slope) = quantity.unit.si_affine();
+    (&quantity.magnitude + offset) * slope
+}
+
+/// ```sin``` and ```cos``` of an angle as fixed point numbers with the given scale.
+fn sin_cos_fixed(angle: &Angle, scale: &BigInt) -> (BigInt, BigInt) {
+    // angle = k pi/2 + r with |r| <= pi/4
+    let (k, r) = match angle {
+        Angle::HalfTurns(turns) => {
+            let k = round(&(turns * int!(2)), RoundingMode::HalfEven);
+            let rest = turns - BigRational::new(k.clone(), 2.into());
+            (k.to_big_int(), to_fixed(&rest, scale) * pi_fixed(scale) / scale)
+        }
+        Angle::Radians(x) => {
+            let half_pi: BigInt = pi_fixed(scale) / 2u32;
+            let x = to_fixed(x, scale);
+            let k = (&x + &half_pi / 2u32).div_floor(&half_pi);
+            let r = x - &k * half_pi;
+            (k, r)
+        }
+    };
+
+    let (sin, cos) = sin_cos_series(&r, scale);
+    match k.mod_floor(&BigInt::from(4)).to_i64() {
+        Some(0) => (sin, cos),
+        Some(1) => (cos, -sin),
+        Some(2) => (-sin, -cos),
+        _ => (-cos, sin),
+    }
+}
+
+/// The Taylor series of ```sin``` and ```cos``` for a small fixed point ```r```.
+fn sin_cos_series(r: &BigInt, scale: &BigInt) -> (BigInt, BigInt) {
+    let mut sin = r.clone();
+    let mut cos = scale.clone();
+    // r^n / n!
+    let mut term = r.clone();
+    let mut n = 1u32;
+    loop {
+        n += 1;
+        term = term * r / scale / n;
+        if term.is_zero() {
+            return (sin, cos);
+        }
+        match n % 4 {
+            0 => cos += &term,
+            1 => sin += &term,
+            2 => cos -= &term,
+            _ => sin -= &term,
+        }
+    }
+}
+
+/// ```atan(z)``` for a fixed point ```z``` in ```[0, 1]```.
+fn atan_fixed(z: &BigInt, scale: &BigInt) -> BigInt {
+    // Each atan(z) = 2 atan(z / (1 + sqrt(1 + z^2))) halves the angle, so the series converges quickly
+    const HALVINGS: usize = 3;
+    let mut z = z.clone();
+    for _ in 0..HALVINGS {
+        z = &z * scale / (scale + (scale * scale + &z * &z).sqrt());
+    }
+
+    let z_squared = &z * &z / scale;
+    let mut power = z.clone();
+    let mut sum = z;
+    let mut n = 1u32;
+    loop {
+        power = -(power * &z_squared / scale);
+        if power.is_zero() {
+            return sum << HALVINGS;
+        }
+        sum += &power / (2 * n + 1);
+        n += 1;
+    }
+}
+
+/// pi as a fixed point number, from Machin's formula ```pi = 16 atan(1/5) - 4 atan(1/239)```.
+pub(crate) fn pi_fixed(scale: &BigInt) -> BigInt {
+    atan_inverse_fixed(5, scale) * 16 - atan_inverse_fixed(239, scale) * 4
+}
+
+/// ```atan(1/n)``` as a fixed point number.
+fn atan_inverse_fixed(n: u32, scale: &BigInt) -> BigInt {
+    let n_squared = BigInt::from(n * n);
+    let mut power = scale / n;
+    let mut sum = power.clone();
+    let mut k = 1u32;
+    loop {
+        power /= &n_squared;
+        if power.is_zero() {
+            return sum;
+        }
+        let term = &power / (2 * k + 1);
+        if k % 2 == 1 {
+            sum -= term;
+        } else {
+            sum += term;
+        }
+        k += 1;
+    }
+}
+
+fn abs(x: &BigRational) -> BigRational {
+    if *x < BigRational::zero() { -x } else { x.clone() }
+}
+
+/// A rational as a fixed point number with the given scale, rounded toward zero.
+fn to_fixed(x: &BigRational, scale: &BigInt) -> BigInt {
+    to_big_int(x.numer()) * scale / to_big_int(x.denom())
+}
+
+/// Rounds a fixed point number with ```precision + guard``` decimal places to ```precision``` places.
+pub(crate) fn from_fixed(value: BigInt, guard: u32, precision: u32) -> Quantity {
+    let rounded = round(&BigRational::new(value.into(), power_of_ten(guard).into()), RoundingMode::HalfEven);
+    Quantity::from_rational(BigRational::new(rounded, power_of_ten(precision).into()))
+}
+
+/// Multiplies by ```2^k```, rounding toward zero.
+fn shift(value: &BigInt, k: i64) -> BigInt {
+    if k >= 0 { value << k as usize } else { value >> (-k) as usize }
+}
+
+pub(crate) fn power_of_ten(exponent: u32) -> BigInt {
+    num::pow(BigInt::from(10), exponent as usize)
+}
+
+/// The number of decimal digits of a non-negative integer.
+fn digits(value: u64) -> u32 {
+    value.checked_ilog10().map_or(1, |log| log + 1)
+}
+
+/// The number of decimal digits of a fixed point number.
+fn fixed_digits(value: &BigInt) -> u32 {
+    value.magnitude().to_string().len() as u32
+}
+
+fn to_big_int(value: &ScalableInteger) -> BigInt {
+    value.clone().to_big_int()
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use crate::unit::Unit::*;
+    use crate::unit::UNITLESS;
+    use super::*;
+
+    fn unitless(numer: i64, denom: i64) -> Quantity {
+        Quantity::new(BigRational::new(numer.into(), denom.into()), UNITLESS)
+    }
+
+    #[test]
+    fn natural_logarithm() {
+        assert!(unitless(2_718_281_828, 1_000_000_000).ln(20).unwrap().magnitude < BigRational::one());
+        assert!(unitless(2_718_281_829, 1_000_000_000).ln(20).unwrap().magnitude > BigRational::one());
+
+        let ln2 = Quantity::from_i64(2).ln(30).unwrap();
+        assert_eq!(ln2.magnitude, BigRational::new("693147180559945309417232121458".parse::<BigInt>().unwrap().into(), power_of_ten(30).into()));
+        assert_eq!(unitless(1, 2).ln(10), Ok(unitless(-6_931_471_806, 10_000_000_000)));
+        assert_eq!(Quantity::from_i64(10).ln(10), Ok(unitless(23_025_850_930, 10_000_000_000)));
+        assert_eq!(unitless(1, 3).ln(5), Ok(unitless(-109_861, 100_000)));
+        assert_eq!(Quantity::from_i64(1).ln(5), Ok(Quantity::from_i64(0)));
+        assert_eq!(Quantity::from_i64(1_000_000_007).ln(8), Ok(unitless(2_072_326_584, 100_000_000)));
+        assert_eq!(Quantity::from_i64(5).ln(0), Ok(Quantity::from_i64(2)));
+    }
+
+    #[test]
+    fn other_logarithms() {
+        assert_eq!(Quantity::from_i64(1000).log10(5), Ok(Quantity::from_i64(3)));
+        assert_eq!(unitless(1, 100).log10(5), Ok(Quantity::from_i64(-2)));
+        assert_eq!(Quantity::from_i64(1).log10(5), Ok(Quantity::from_i64(0)));
+        assert_eq!(Quantity::from_i64(2).log10(10), Ok(unitless(3_010_299_957, 10_000_000_000)));
+        assert_eq!(Quantity::from_i64(1024).log2(5), Ok(Quantity::from_i64(10)));
+        assert_eq!(unitless(1, 8).log2(5), Ok(Quantity::from_i64(-3)));
+        assert_eq!(Quantity::from_i64(3).log2(10), Ok(unitless(15_849_625_007, 10_000_000_000)));
+        assert_eq!(Quantity::from_i64(3).log10(10), Ok(unitless(4_771_212_547, 10_000_000_000)));
+        assert_eq!(Quantity::from_i64_with_unit(1, Kilo).log10(3), Ok(Quantity::from_i64(3)));
+    }
+
+    #[test]
+    fn exponential() {
+        assert_eq!(Quantity::from_i64(0).exp(5), Ok(Quantity::from_i64(1)));
+        assert_eq!(Quantity::from_i64(1).exp(15), Ok(unitless(2_718_281_828_459_045, 1_000_000_000_000_000)));
+        assert_eq!(Quantity::from_i64(-1).exp(10), Ok(unitless(3_678_794_412, 10_000_000_000)));
+        assert_eq!(Quantity::from_i64(10).exp(5), Ok(unitless(2_202_646_579, 100_000)));
+        assert_eq!(unitless(1, 2).exp(10), Ok(unitless(16_487_212_707, 10_000_000_000)));
+        assert_eq!(Quantity::from_i64(-6).exp(6), Ok(unitless(2479, 1_000_000)));
+        assert_eq!(Quantity::from_i64(-30).exp(5), Ok(Quantity::from_i64(0)));
+        assert_eq!(Quantity::from_i64(-100_000).exp(5), Ok(Quantity::from_i64(0)));
+
+        let big = Quantity::from_i64(1000).exp(0).unwrap();
+        assert_eq!(big.magnitude.numer().to_string().len(), 435);
+        assert!(big.magnitude.numer().to_string().starts_with("197007111401704699388887935224332312531693798532384578995280299138506385078244"));
+        assert_eq!(Quantity::from_i64(10_001).exp(5), Err(MathError::Overflow));
+
+        let x = Quantity::new(ratio!(7, 3), UNITLESS);
+        let round_trip = x.exp(30).unwrap().ln(20).unwrap();
+        assert_eq!(round_trip.magnitude, BigRational::new("233333333333333333333".parse::<BigInt>().unwrap().into(), power_of_ten(20).into()));
+    }
+
+    #[test]
+    fn domain_errors() {
+        assert_eq!(Quantity::from_i64_with_unit(2, Meter).ln(5), Err(MathError::NotUnitless(Meter)));
+        assert_eq!(Quantity::from_i64_with_unit(2, Meter).exp(5), Err(MathError::NotUnitless(Meter)));
+        assert_eq!(Quantity::from_i64_with_unit(2, Kilo * Gram).log2(5), Err(MathError::NotUnitless(Kilo * Gram)));
+        assert_eq!(Quantity::from_i64(0).ln(5), Err(MathError::NonPositive));
+        assert_eq!(Quantity::from_i64(-3).log10(5), Err(MathError::NonPositive));
+        assert_eq!(Quantity::from_i64_with_unit(3, Meter / Meter).ln(3), Ok(unitless(1099, 1000)));
+        assert_eq!(MathError::NonPositive.to_string(), "the logarithm of a non-positive number is undefined");
+    }
+
+    fn degrees(value: i64) -> Quantity {
+        Quantity::from_i64_with_unit(value, Degree)
+    }
+
+    /// The decimal places of a fixed point magnitude as a float.
+    fn to_f64(quantity: &Quantity) -> f64 {
+        to_big_int(quantity.magnitude.numer()).to_f64().unwrap() / to_big_int(quantity.magnitude.denom()).to_f64().unwrap()
+    }
+
+    #[test]
+    fn special_angles() {
+        let half = Quantity::new(ratio!(1, 2), UNITLESS);
+        assert_eq!(degrees(0).sin(5), Ok(Quantity::from_i64(0)));
+        assert_eq!(degrees(30).sin(5), Ok(half.clone()));
+        assert_eq!(degrees(90).sin(5), Ok(Quantity::from_i64(1)));
+        assert_eq!(degrees(150).sin(5), Ok(half.clone()));
+        assert_eq!(degrees(-30).sin(5), Ok(-half.clone()));
+        assert_eq!(degrees(390).sin(5), Ok(half.clone()));
+        assert_eq!(degrees(270).sin(5), Ok(Quantity::from_i64(-1)));
+        assert_eq!(degrees(60).cos(5), Ok(half.clone()));
+        assert_eq!(degrees(90).cos(5), Ok(Quantity::from_i64(0)));
+        assert_eq!(degrees(180).cos(5), Ok(Quantity::from_i64(-1)));
+        assert_eq!(degrees(45).tan(5), Ok(Quantity::from_i64(1)));
+        assert_eq!(degrees(135).tan(5), Ok(Quantity::from_i64(-1)));
+        assert_eq!(degrees(-180).tan(5), Ok(Quantity::from_i64(0)));
+        assert_eq!(degrees(90).tan(5), Err(MathError::Undefined));
+        assert_eq!(degrees(-90).tan(5), Err(MathError::Undefined));
+        assert_eq!(Quantity::from_i64_with_unit(300, Deci * Degree).sin(5), Ok(half));
+        assert_eq!(Quantity::from_i64_with_unit(0, Radian).cos(5), Ok(Quantity::from_i64(1)));
+        assert_eq!(Quantity::from_i64(0).tan(5), Ok(Quantity::from_i64(0)));
+    }
+
+    #[test]
+    fn irrational_angles() {
+        assert_eq!(degrees(45).sin(10), Ok(unitless(7_071_067_812, 10_000_000_000)));
+        assert_eq!(degrees(60).sin(10), Ok(unitless(8_660_254_038, 10_000_000_000)));
+        assert_eq!(Quantity::new(ratio!(180, 7), Degree).sin(10), Ok(unitless(4_338_837_391, 10_000_000_000)));
+        assert_eq!(Quantity::from_i64(1).sin(15), Ok(unitless(841_470_984_807_897, 1_000_000_000_000_000)));
+        assert_eq!(Quantity::from_i64_with_unit(1, Radian).cos(10), Ok(unitless(5_403_023_059, 10_000_000_000)));
+        assert_eq!(Quantity::from_i64_with_unit(1000, Milli * Radian).tan(10), Ok(unitless(15_574_077_247, 10_000_000_000)));
+        assert_eq!(Quantity::from_i64(100).sin(10), Ok(unitless(-5_063_656_411, 10_000_000_000)));
+        assert_eq!(Quantity::new(int!(100_000_000_000_000_000_000i128), Radian).sin(10), Ok(unitless(-6_452_512_853, 10_000_000_000)));
+        assert_eq!(Quantity::from_i64_with_unit(1, Milli * Radian).cos(12), Ok(unitless(999_999_500_000, 1_000_000_000_000)));
+    }
+
+    #[test]
+    fn tangent_near_poles() {
+        assert_eq!(Quantity::new(ratio!(11, 7), UNITLESS).tan(10), Ok(unitless(-15_816_660_411_070, 10_000_000_000)));
+        assert_eq!(Quantity::new(ratio!(355, 226), UNITLESS).tan(5), Ok(unitless(-749_725_818_533, 100_000)));
+    }
+
+    #[test]
+    fn arc_tangent() {
+        let radians = |numer, denom| Quantity::new(unitless(numer, denom).magnitude, Radian);
+        let meters = |value| Quantity::from_i64_with_unit(value, Meter);
+        assert_eq!(meters(1).atan2(&meters(1), 10), Ok(radians(7_853_981_634, 10_000_000_000)));
+        assert_eq!(meters(1).atan2(&meters(-1), 10), Ok(radians(23_561_944_902, 10_000_000_000)));
+        assert_eq!(meters(-1).atan2(&meters(0), 10), Ok(radians(-15_707_963_268, 10_000_000_000)));
+        assert_eq!(meters(0).atan2(&meters(-1), 10), Ok(radians(31_415_926_536, 10_000_000_000)));
+        assert_eq!(meters(0).atan2(&meters(0), 10), Ok(radians(0, 1)));
+        assert_eq!(Quantity::from_i64_with_unit(3, Kilo * Meter).atan2(&meters(4000), 10), Ok(radians(6_435_011_088, 10_000_000_000)));
+        assert_eq!(meters(1).atan2(&Quantity::from_i64_with_unit(1, Second), 10), Err(MathError::IncompatibleUnits(Meter, Second)));
+    }
+
+    #[test]
+    fn trigonometric_errors() {
+        assert_eq!(Quantity::from_i64_with_unit(2, Meter).sin(5), Err(MathError::NotAngle(Meter)));
+        assert_eq!(Quantity::from_i64_with_unit(2, Meter / Second).tan(5), Err(MathError::NotAngle(Meter / Second)));
+        assert_eq!(MathError::Undefined.to_string(), "the function is undefined at the argument");
+    }
+
+    #[test]
+    fn degree_conversion() {
+        let radians = degrees(180).convert_to(Radian).unwrap();
+        assert_eq!(crate::rounding::round_to_scale(&radians.magnitude, 30, RoundingMode::HalfEven), "3141592653589793238462643383280".parse::<BigInt>().unwrap().into());
+    }
+
+    #[test]
+    fn convert_angle() {
+        // pi is 3.14159265..., so rounding to 5 and 6 places brackets it
+        let below = degrees(180).convert_angle(Radian, 5).unwrap().magnitude;
+        let above = degrees(180).convert_angle(Radian, 6).unwrap().magnitude;
+        assert_eq!(below, ratio!(314159, 100000));
+        assert_eq!(above, ratio!(3141593, 1000000));
+        let pi = crate::constants::pi(40);
+        assert!(below < pi && pi < above);
+
+        assert_eq!(degrees(45).convert_angle(Radian, 20), Ok(Quantity::new(ratio!(78539816339744830962i128, 100000000000000000000i128), Radian)));
+        assert_eq!(degrees(-90).convert_angle(Milli * Radian, 3), Ok(Quantity::new(ratio!(-1570796, 1000), Milli * Radian)));
+        assert_eq!(Quantity::from_i64_with_unit(1, Radian).convert_angle(Degree, 10), Ok(Quantity::new(ratio!(572957795131i64, 10000000000i64), Degree)));
+        assert_eq!(degrees(1_000_000_000).convert_angle(Radian, 3), Ok(Quantity::new(ratio!(17453292520i64, 1000), Radian)));
+
+        // Conversions without a change in the power of pi are exact
+        assert_eq!(degrees(3).convert_angle(Kilo * Degree, 0), Ok(Quantity::new(ratio!(3, 1000), Kilo * Degree)));
+        assert_eq!(Quantity::from_i64_with_unit(2, Radian).convert_angle(Milli * Radian, 0), Ok(Quantity::from_i64_with_unit(2000, Milli * Radian)));
+
+        assert_eq!(degrees(1).convert_angle(Meter, 5), Err(MathError::NotAngle(Meter)));
+        assert_eq!(Quantity::from_i64_with_unit(1, Second).convert_angle(Radian, 5), Err(MathError::NotAngle(Second)));
+    }
+
+    proptest! {
+        #[test]
+        fn matches_f64(numer in -1_000_000i64..1_000_000, denom in 1i64..1000) {
+            let x = numer as f64 / denom as f64;
+            let radians = unitless(numer, denom);
+            prop_assert!((to_f64(&radians.sin(12).unwrap()) - x.sin()).abs() < 1e-9);
+            prop_assert!((to_f64(&radians.cos(12).unwrap()) - x.cos()).abs() < 1e-9);
+            let tan = to_f64(&radians.tan(12).unwrap());
+            prop_assert!((tan - x.tan()).abs() < 1e-9 * x.tan().abs().max(1.0) * x.tan().abs().max(1.0));
+
+            let angle = Quantity::new(BigRational::new(numer.into(), denom.into()), Degree);
+            prop_assert!((to_f64(&angle.sin(12).unwrap()) - x.to_radians().sin()).abs() < 1e-9);
+        }
+
+        #[test]
+        fn atan2_matches_f64(y in -1_000_000i64..1_000_000, x in -1_000_000i64..1_000_000) {
+            let angle = Quantity::from_i64_with_unit(y, Meter).atan2(&Quantity::from_i64_with_unit(x, Meter), 12).unwrap();
+            prop_assert!((to_f64(&angle) - (y as f64).atan2(x as f64)).abs() < 1e-9);
+        }
+    }
+}
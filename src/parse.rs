@@ -0,0 +1,1321 @@
+//! Parsing of unit expressions like ```km/h``` or ```kg*m/s^2```, usable in ```const``` contexts.
+//!
+//! A unit expression is a sequence of unit symbols separated by ```*```, ```/``` or whitespace.
+//! A ```/``` divides by the symbol directly after it, so ```J/kg/K``` is ```J/(kg*K)```.
+//! Each symbol may be raised to an integer power with ```^```, e.g. ```s^2``` or ```s^-1```, and
+//! an expression may start with ```1/``` to denote a reciprocal unit.
+//!
+//! A symbol is either the symbol of a named unit or the symbol of a modifier followed by the
+//! symbol of a named unit, e.g. ```km```. Exact matches are preferred, so ```m``` is a meter
+//! and ```cd``` is a candela rather than a centiday.
+//!
+//! The parser works on indices into ```Unit::NAMED_UNITS``` so that it can run at compile time,
+//! either through the ```unit!``` and ```q!``` macros or at runtime through ```FromStr```.
+//!
+//! # Example:
+//! ```
+//! use tantalum_unit::prelude::*;
+//! use tantalum_unit::parse::ErrorKind;
+//!
+//! let unit: Unit = "kg*m/s^2".parse().unwrap();
+//! assert_eq!(unit, Kilo * Gram * Meter / (Second * Second));
+//!
+//! let error = "m * foo".parse::<Unit>().unwrap_err();
+//! assert_eq!(error.kind(), ErrorKind::UnknownSymbol);
+//! assert_eq!(error.span(), 4..7);
+//! assert_eq!(error.to_string(), "unknown unit symbol `foo` in `m * foo`");
+//! ```
+//!
+//! A quantity is a number followed by a unit expression. The number may have a sign, a decimal
+//! point and an exponent, or be a fraction of two such numbers without a space around the
+//! ```/```, like ```7/8```. Mixed numbers like ```1 1/2``` or ```2-3/8``` are read as a whole
+//! number plus a proper fraction. The number is converted exactly. ```FromStr``` expects
+//! numbers in the conventions of ```NumberLocale::EN_US```, while
+//! ```Quantity::from_str_with_locale``` parses numbers like ```1.234,5``` in other locales.
+//! ```
+//! use tantalum_unit::prelude::*;
+//!
+//! let speed: Quantity = "12.5 km/h".parse().unwrap();
+//! assert_eq!(speed, Quantity::new(ratio!(25, 2), Kilo * Meter / Hour));
+//! assert_eq!("7/8 in".parse(), Ok(Quantity::new(ratio!(7, 8), Inch)));
+//! assert_eq!("2-3/8 in".parse(), Ok(Quantity::new(ratio!(19, 8), Inch)));
+//! assert_eq!("-40 °C".parse(), Ok(Quantity::from_i64_with_unit(-40, Celsius)));
+//! ```
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+use std::str::FromStr;
+use num::{Num, Zero};
+use crate::format::NumberLocale;
+use crate::quantity::Quantity;
+use crate::scalable_integer::{BigRational, ScalableInteger};
+use crate::unit::{Unit, UNITLESS};
+use crate::unit_list::UnitList;
+
+/// Marks a factor without a modifier.
+const NO_PREFIX: usize = usize::MAX;
+
+/// The largest exponent of a factor, which bounds the size of a parsed unit.
+pub(crate) const MAX_EXPONENT: u32 = 1024;
+
+/// The largest absolute decimal exponent of a number, which bounds the size of a parsed magnitude.
+const MAX_DECIMAL_EXPONENT: u32 = 10_000;
+
+/// The size of the buffer compile time error messages are assembled in.
+const MESSAGE_CAPACITY: usize = 256;
+
+/// What went wrong while parsing a unit expression.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// A unit symbol was expected, e.g. after an operator or at the start of the expression.
+    ExpectedSymbol,
+    /// The symbol isn't the symbol of a named unit, with or without a modifier.
+    UnknownSymbol,
+    /// The exponent after a ```^``` is missing, zero or too large.
+    InvalidExponent,
+    /// The number of a quantity is missing or malformed, or its exponent is too large.
+    InvalidNumber,
+    /// The denominator of a fraction is zero.
+    ZeroDenominator,
+    /// A designator of an ISO 8601 duration is missing, unknown or out of order.
+    InvalidDesignator,
+    /// The suffix of a bare number is the symbol of a modifier and of a unit, like ```m```.
+    AmbiguousSuffix,
+}
+
+impl ErrorKind {
+    const fn description(self) -> &'static str {
+        match self {
+            ErrorKind::ExpectedSymbol => "expected a unit symbol at",
+            ErrorKind::UnknownSymbol => "unknown unit symbol",
+            ErrorKind::InvalidExponent => "invalid exponent at",
+            ErrorKind::InvalidNumber => "invalid number at",
+            ErrorKind::ZeroDenominator => "zero denominator at",
+            ErrorKind::InvalidDesignator => "invalid duration designator at",
+            ErrorKind::AmbiguousSuffix => "ambiguous suffix",
+        }
+    }
+}
+
+/// A unit symbol raised to a power, e.g. ```km^2```.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Factor {
+    /// The index of the modifier in ```Unit::NAMED_UNITS```, or ```NO_PREFIX```.
+    pub(crate) prefix: usize,
+    /// The index of the unit in ```Unit::NAMED_UNITS```.
+    pub(crate) unit: usize,
+    /// How many times the factor is repeated.
+    pub(crate) count: u32,
+    /// Whether the factor belongs to the denominator.
+    pub(crate) inverted: bool,
+}
+
+impl Factor {
+    /// The number of units the factor expands to.
+    const fn len(&self) -> usize {
+        let units = if self.prefix == NO_PREFIX { 1 } else { 2 };
+        units * self.count as usize
+    }
+}
+
+/// The result of scanning for the next factor of a unit expression.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Step {
+    /// The expression has no more factors.
+    End,
+    /// A factor, and the position after it.
+    Factor(Factor, usize),
+    /// An error between the given byte positions.
+    Error(ErrorKind, usize, usize),
+}
+
+/// Scans the factor starting at byte ```position``` of ```source```.
+pub(crate) const fn next_factor(source: &str, mut position: usize) -> Step {
+    let bytes = source.as_bytes();
+    let first = position == 0;
+    let mut inverted = false;
+
+    position = skip_whitespace(bytes, position);
+    if position == bytes.len() {
+        return Step::End;
+    }
+
+    if first && bytes[position] == b'1' {
+        // A reciprocal unit like 1/s
+        position = skip_whitespace(bytes, position + 1);
+        if position == bytes.len() || bytes[position] != b'/' {
+            return Step::Error(ErrorKind::ExpectedSymbol, position - 1, position);
+        }
+    }
+
+    if bytes[position] == b'/' {
+        inverted = true;
+        position = skip_whitespace(bytes, position + 1);
+    } else if bytes[position] == b'*' && !first {
+        position = skip_whitespace(bytes, position + 1);
+    }
+
+    let start = position;
+    while position < bytes.len() && !is_delimiter(bytes[position]) {
+        position += 1;
+    }
+    if start == position {
+        return Step::Error(ErrorKind::ExpectedSymbol, start, next_char_boundary(bytes, start));
+    }
+
+    let (prefix, unit) = match lookup(slice(bytes, start, position)) {
+        Some(found) => found,
+        None => return Step::Error(ErrorKind::UnknownSymbol, start, position),
+    };
+
+    let mut count = 1;
+    let after_symbol = skip_whitespace(bytes, position);
+    if after_symbol < bytes.len() && bytes[after_symbol] == b'^' {
+        position = skip_whitespace(bytes, after_symbol + 1);
+        if position < bytes.len() && bytes[position] == b'-' {
+            inverted = !inverted;
+            position = skip_whitespace(bytes, position + 1);
+        }
+
+        let digits = position;
+        count = 0u32;
+        while position < bytes.len() && bytes[position].is_ascii_digit() {
+            count = count * 10 + (bytes[position] - b'0') as u32;
+            position += 1;
+            if count > MAX_EXPONENT {
+                return Step::Error(ErrorKind::InvalidExponent, digits, position);
+            }
+        }
+        if count == 0 {
+            return Step::Error(ErrorKind::InvalidExponent, digits, next_char_boundary(bytes, position));
+        }
+    }
+
+    Step::Factor(Factor { prefix, unit, count, inverted }, position)
+}
+
+/// An error from parsing a unit expression at runtime.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseUnitError {
+    kind: ErrorKind,
+    span: Range<usize>,
+    source: String,
+}
+
+impl ParseUnitError {
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The byte range of the offending part of the input, which is empty at the end of the input.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+}
+
+impl Display for ParseUnitError {
+    /// Formats the error like the compile time errors of ```unit!```, e.g. ```unknown unit symbol `foo` in `m * foo` ```.
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let token = if self.span.is_empty() { "end of input" } else { &self.source[self.span.clone()] };
+        write!(f, "{} `{}` in `{}`", self.kind.description(), token, self.source)
+    }
+}
+
+impl Error for ParseUnitError {}
+
+impl FromStr for Unit {
+    type Err = ParseUnitError;
+
+    /// Parses a unit expression, producing the same unit as ```unit!``` for the same input.
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let (mut numerator, mut denominator) = (vec![], vec![]);
+        let mut position = 0;
+        loop {
+            match next_factor(source, position) {
+                Step::End => break,
+                Step::Factor(factor, next) => {
+                    let units = if factor.inverted { &mut denominator } else { &mut numerator };
+                    for _ in 0..factor.count {
+                        if factor.prefix != NO_PREFIX {
+                            units.push(Unit::from_index(factor.prefix));
+                        }
+                        units.push(Unit::from_index(factor.unit));
+                    }
+                    position = next;
+                }
+                Step::Error(kind, start, end) => {
+                    return Err(ParseUnitError { kind, span: start..end, source: source.to_owned() });
+                }
+            }
+        }
+
+        if let ([_], []) = (numerator.as_slice(), denominator.as_slice()) {
+            return Ok(numerator.pop().unwrap());
+        }
+        Ok(Unit::Compound(numerator.into(), denominator.into()))
+    }
+}
+
+/// An error from parsing a quantity, either in its number or in its unit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseQuantityError {
+    kind: ErrorKind,
+    span: Range<usize>,
+    source: String,
+    row: Option<usize>,
+}
+
+impl ParseQuantityError {
+    fn new(kind: ErrorKind, span: Range<usize>, source: &str) -> Self {
+        Self { kind, span, source: source.to_owned(), row: None }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The byte range of the offending part of the input, which is empty at the end of the input.
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// The index of the offending cell when parsing a column, see ```column::parse_column```.
+    pub fn row(&self) -> Option<usize> {
+        self.row
+    }
+
+    /// Attaches the index of the cell the input was taken from.
+    pub(crate) fn in_row(self, row: usize) -> Self {
+        Self { row: Some(row), ..self }
+    }
+}
+
+impl Display for ParseQuantityError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(row) = self.row {
+            write!(f, "row {row}: ")?;
+        }
+        let token = if self.span.is_empty() { "end of input" } else { &self.source[self.span.clone()] };
+        write!(f, "{} `{}` in `{}`", self.kind.description(), token, self.source)
+    }
+}
+
+impl Error for ParseQuantityError {}
+
+impl FromStr for Quantity {
+    type Err = ParseQuantityError;
+
+    /// Parses a number followed by an optional unit expression, like ```12.5 km/h``` or ```42```,
+    /// with the number written in the ```NumberLocale::EN_US``` conventions.
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Quantity::from_str_with_locale(source, &NumberLocale::EN_US)
+    }
+}
+
+impl Quantity {
+    /// Parses a quantity whose number is written in the conventions of ```locale```, like ```1.234,5 km```
+    /// for ```NumberLocale::DE_DE```.
+    ///
+    /// The number must use the decimal separator of the locale. Group separators are optional,
+    /// but if there are any, all digits of the integer part must be grouped as the locale
+    /// groups them. So ```1.234``` is ```1234/1000``` in en-US but ```1234``` in de-DE, where
+    /// ```1.23``` is rejected.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    /// use tantalum_unit::format::NumberLocale;
+    ///
+    /// let length = Quantity::from_str_with_locale("1.234,5 km", &NumberLocale::DE_DE);
+    /// assert_eq!(length, Ok(Quantity::new(ratio!(12345, 10), Kilo * Meter)));
+    /// assert_eq!("1,234.5 km".parse(), length);
+    /// assert!(Quantity::from_str_with_locale("12.34,5 km", &NumberLocale::DE_DE).is_err());
+    /// ```
+    pub fn from_str_with_locale(source: &str, locale: &NumberLocale) -> Result<Quantity, ParseQuantityError> {
+        let bytes = source.as_bytes();
+        let start = skip_whitespace(bytes, 0);
+        let (mut magnitude, mut position) = scan_number(source, start, true, locale)?;
+
+        if let Some((fraction, end)) = scan_mixed_fraction(source, start, position)? {
+            // The sign of the whole part applies to the fraction too
+            if bytes[start] == b'-' {
+                magnitude -= fraction;
+            } else {
+                magnitude += fraction;
+            }
+            position = end;
+        } else if is_fraction_bar(bytes, position) {
+            // A fraction, which mustn't be confused with a unit like /s
+            let (denominator, end) = scan_number(source, position + 1, false, locale)?;
+            if denominator.numer().is_zero() {
+                return Err(ParseQuantityError::new(ErrorKind::ZeroDenominator, position + 1..end, source));
+            }
+            magnitude /= denominator;
+            position = end;
+        }
+
+        // A second fraction bar, like in 1/2/3, is ambiguous
+        if is_fraction_bar(bytes, position) {
+            let end = digits_end(bytes, position + 1);
+            return Err(ParseQuantityError::new(ErrorKind::InvalidNumber, start..end, source));
+        }
+
+        let unit = parse_unit_in(source, position..source.len())?;
+        Ok(Quantity::new(magnitude, unit))
+    }
+}
+
+impl Quantity {
+    /// Parses a number followed directly by the symbol of an SI or binary modifier, like ```5k```,
+    /// ```1.2M``` or ```3Gi```, into a unitless Quantity with the factor of the modifier applied.
+    ///
+    /// The number is written like in ```FromStr``` and may also stand alone. Symbols that are
+    /// also the symbol of a unit are rejected as ```ErrorKind::AmbiguousSuffix```, so ```5m```,
+    /// ```5h```, ```5d``` and ```5T``` are errors rather than milli, hecto, deci or tera. Use
+    /// ```from_suffixed_str_with_unit``` to attach a unit, which makes them unambiguous.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    /// use tantalum_unit::parse::ErrorKind;
+    ///
+    /// assert_eq!(Quantity::from_suffixed_str("5k"), Ok(Quantity::from_i64(5000)));
+    /// assert_eq!(Quantity::from_suffixed_str("3Gi"), Ok(Quantity::from_i64(3 << 30)));
+    /// assert_eq!(Quantity::from_suffixed_str("5m").unwrap_err().kind(), ErrorKind::AmbiguousSuffix);
+    /// ```
+    pub fn from_suffixed_str(source: &str) -> Result<Quantity, ParseQuantityError> {
+        Quantity::parse_suffixed(source, UNITLESS, true)
+    }
+
+    /// Parses a number with a modifier suffix like ```from_suffixed_str```, attaching ```unit```.
+    ///
+    /// Since the unit is given, every modifier symbol is accepted, so ```5m``` is ```5/1000```
+    /// of the unit.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// assert_eq!(Quantity::from_suffixed_str_with_unit("5m", Second), Ok(Quantity::new(ratio!(1, 200), Second)));
+    /// assert_eq!(Quantity::from_suffixed_str_with_unit("2Ki", Byte), Ok(Quantity::from_i64_with_unit(2048, Byte)));
+    /// ```
+    pub fn from_suffixed_str_with_unit(source: &str, unit: Unit) -> Result<Quantity, ParseQuantityError> {
+        Quantity::parse_suffixed(source, unit, false)
+    }
+
+    fn parse_suffixed(source: &str, unit: Unit, reject_ambiguous: bool) -> Result<Quantity, ParseQuantityError> {
+        let bytes = source.as_bytes();
+        let start = skip_whitespace(bytes, 0);
+        let (magnitude, position) = scan_number(source, start, true, &NumberLocale::EN_US)?;
+        let suffix = source[position..].trim_end();
+        if suffix.is_empty() {
+            return Ok(Quantity::new(magnitude, unit));
+        }
+
+        let span = position..position + suffix.len();
+        let Some(modifier) = find(suffix.as_bytes(), true) else {
+            return Err(ParseQuantityError::new(ErrorKind::UnknownSymbol, span, source));
+        };
+        if reject_ambiguous && find(suffix.as_bytes(), false).is_some() {
+            return Err(ParseQuantityError::new(ErrorKind::AmbiguousSuffix, span, source));
+        }
+        let (_, factor, _) = Unit::from_index(modifier).to_si_units();
+        Ok(Quantity::new(magnitude * factor, unit))
+    }
+}
+
+/// Parses the unit expression in ```source[range]```, reporting errors with spans into ```source```.
+pub(crate) fn parse_unit_in(source: &str, range: Range<usize>) -> Result<Unit, ParseQuantityError> {
+    source[range.clone()].parse::<Unit>().map_err(|e| {
+        let span = e.span.start + range.start..e.span.end + range.start;
+        ParseQuantityError::new(e.kind, span, source)
+    })
+}
+
+/// Parses a decimal number like ```-12.5e3``` that makes up all of ```source``` apart from
+/// surrounding whitespace.
+pub(crate) fn parse_number(source: &str, locale: &NumberLocale) -> Result<BigRational, ParseQuantityError> {
+    let bytes = source.as_bytes();
+    let (number, end) = scan_number(source, skip_whitespace(bytes, 0), true, locale)?;
+    let position = skip_whitespace(bytes, end);
+    if position < bytes.len() {
+        return Err(ParseQuantityError::new(ErrorKind::InvalidNumber, position..source.len(), source));
+    }
+    Ok(number)
+}
+
+/// Scans a decimal number like ```-12.5e3``` starting at byte ```start```, returning it and the position after it.
+fn scan_number(source: &str, start: usize, signed: bool, locale: &NumberLocale) -> Result<(BigRational, usize), ParseQuantityError> {
+    let bytes = source.as_bytes();
+    let digits_end = |mut position: usize| {
+        while position < bytes.len() && bytes[position].is_ascii_digit() {
+            position += 1;
+        }
+        position
+    };
+    let error = |end: usize| ParseQuantityError::new(ErrorKind::InvalidNumber, start..next_char_boundary(bytes, end), source);
+
+    let mut position = start;
+    let negative = signed && position < bytes.len() && bytes[position] == b'-';
+    if signed && position < bytes.len() && (bytes[position] == b'-' || bytes[position] == b'+') {
+        position += 1;
+    }
+
+    // A group separator only belongs to the number if a digit follows it
+    let whole_start = position;
+    let mut digits = String::new();
+    loop {
+        let end = digits_end(position);
+        digits.push_str(&source[position..end]);
+        position = end;
+        match locale.group_separator {
+            Some(separator) if source[position..].starts_with(separator) && bytes.get(position + separator.len_utf8()).is_some_and(u8::is_ascii_digit) => {
+                position += separator.len_utf8();
+            }
+            _ => break,
+        }
+    }
+    let whole = &source[whole_start..position];
+    if whole.len() != digits.len() && locale.group(&digits) != whole {
+        return Err(ParseQuantityError::new(ErrorKind::InvalidNumber, start..position, source));
+    }
+
+    let mut exponent: i64 = 0;
+    if source[position..].starts_with(locale.decimal_separator) {
+        let fraction_start = position + locale.decimal_separator.len_utf8();
+        let fraction_end = digits_end(fraction_start);
+        digits.push_str(&source[fraction_start..fraction_end]);
+        exponent -= (fraction_end - fraction_start) as i64;
+        position = fraction_end;
+    }
+    if digits.is_empty() {
+        return Err(error(position));
+    }
+
+    // Only an e followed by digits is an exponent, so 2Em is two exameters
+    if position < bytes.len() && (bytes[position] == b'e' || bytes[position] == b'E') {
+        let sign = position + 1;
+        let digits_start = if sign < bytes.len() && (bytes[sign] == b'-' || bytes[sign] == b'+') { sign + 1 } else { sign };
+        let end = digits_end(digits_start);
+        if end > digits_start {
+            match source[digits_start..end].parse::<u32>() {
+                Ok(value) if value <= MAX_DECIMAL_EXPONENT => {
+                    exponent += if bytes[sign] == b'-' { -(value as i64) } else { value as i64 };
+                }
+                _ => return Err(ParseQuantityError::new(ErrorKind::InvalidNumber, start..end, source)),
+            }
+            position = end;
+        }
+    }
+
+    let mantissa = ScalableInteger::from_str_radix(&digits, 10).expect("the mantissa only contains digits");
+    let power = num::pow(ScalableInteger::from(10), exponent.unsigned_abs() as usize);
+    let mut value = if exponent >= 0 {
+        BigRational::from_integer(mantissa * power)
+    } else {
+        BigRational::new(mantissa, power)
+    };
+    if negative {
+        value = -value;
+    }
+    Ok((value, position))
+}
+
+/// The date and time designators of an ISO 8601 duration with the seconds per component.
+const DATE_DESIGNATORS: [(u8, i64); 4] = [(b'Y', 31_557_600), (b'M', 2_629_746), (b'W', 604_800), (b'D', 86_400)];
+const TIME_DESIGNATORS: [(u8, i64); 3] = [(b'H', 3600), (b'M', 60), (b'S', 1)];
+
+impl Quantity {
+    /// Parses an ISO 8601 duration like ```PT1H30M``` or ```P2DT3H``` into an exact quantity of seconds.
+    ///
+    /// Years and months have the fixed lengths of ```Unit::Year``` and ```Unit::Month```, which
+    /// are 365.25 days and a twelfth of that. The components must appear in the order
+    /// ```PnYnMnWnDTnHnMnS```, each at most once. Only the last component may have a fraction,
+    /// with either ```.``` or ```,``` as the decimal separator. A leading ```-``` negates the
+    /// duration, as in ```-PT5M```.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// assert_eq!(Quantity::from_iso8601_duration("PT1H30M"), Ok(q!(5400 s)));
+    /// assert_eq!(Quantity::from_iso8601_duration("PT0.5S"), Ok(q!(0.5 s)));
+    /// assert!(Quantity::from_iso8601_duration("P1.5Y2D").is_err());
+    /// ```
+    pub fn from_iso8601_duration(source: &str) -> Result<Quantity, ParseQuantityError> {
+        let bytes = source.as_bytes();
+        let designator_error = |position: usize| {
+            ParseQuantityError::new(ErrorKind::InvalidDesignator, position..next_char_boundary(bytes, position), source)
+        };
+        // A leading minus is an extension of ISO 8601 for negative durations
+        let negative = bytes.first() == Some(&b'-');
+        let start = usize::from(negative);
+        if bytes.get(start) != Some(&b'P') {
+            return Err(designator_error(start));
+        }
+
+        let mut seconds = BigRational::zero();
+        let mut designators = &DATE_DESIGNATORS[..];
+        let mut position = start + 1;
+        let mut components = 0;
+        let mut time = false;
+        let mut fraction: Option<Range<usize>> = None;
+        while position < bytes.len() {
+            if bytes[position] == b'T' && !time {
+                // The time part must have at least one component
+                if !bytes.get(position + 1).is_some_and(u8::is_ascii_digit) {
+                    return Err(designator_error(position + 1));
+                }
+                designators = &TIME_DESIGNATORS;
+                time = true;
+                position += 1;
+                continue;
+            }
+            if let Some(span) = fraction {
+                return Err(ParseQuantityError::new(ErrorKind::InvalidNumber, span, source));
+            }
+
+            let (value, end, fractional) = scan_duration_number(source, position)?;
+            let Some(index) = designators.iter().position(|&(designator, _)| bytes.get(end) == Some(&designator)) else {
+                return Err(designator_error(end));
+            };
+            seconds += value * BigRational::from_integer(designators[index].1.into());
+            if fractional {
+                fraction = Some(position..end);
+            }
+            designators = &designators[index + 1..];
+            components += 1;
+            position = end + 1;
+        }
+
+        if components == 0 {
+            return Err(designator_error(position));
+        }
+        Ok(Quantity::new(if negative { -seconds } else { seconds }, Unit::Second))
+    }
+}
+
+/// Scans the unsigned decimal number of a duration component, returning it, the position after it
+/// and whether it has a fraction.
+fn scan_duration_number(source: &str, start: usize) -> Result<(BigRational, usize, bool), ParseQuantityError> {
+    let bytes = source.as_bytes();
+    let digits_end = |mut position: usize| {
+        while position < bytes.len() && bytes[position].is_ascii_digit() {
+            position += 1;
+        }
+        position
+    };
+
+    let whole_end = digits_end(start);
+    if whole_end == start {
+        return Err(ParseQuantityError::new(ErrorKind::InvalidNumber, start..next_char_boundary(bytes, start), source));
+    }
+    let mut digits = source[start..whole_end].to_owned();
+    let mut end = whole_end;
+    if end < bytes.len() && (bytes[end] == b'.' || bytes[end] == b',') {
+        end = digits_end(whole_end + 1);
+        if end == whole_end + 1 {
+            return Err(ParseQuantityError::new(ErrorKind::InvalidNumber, start..next_char_boundary(bytes, end), source));
+        }
+        digits.push_str(&source[whole_end + 1..end]);
+    }
+
+    let mantissa = ScalableInteger::from_str_radix(&digits, 10).expect("the mantissa only contains digits");
+    let power = num::pow(ScalableInteger::from(10), end.saturating_sub(whole_end + 1));
+    Ok((BigRational::new(mantissa, power), end, end > whole_end))
+}
+
+/// Returns the number of units in the numerator and denominator of a unit expression.
+///
+/// Panics with a descriptive message if the expression is invalid, which turns into a compile
+/// error when called in a ```const``` item.
+#[doc(hidden)]
+pub const fn __unit_sizes(source: &str) -> (usize, usize) {
+    let (mut numerator, mut denominator) = (0, 0);
+    let mut position = 0;
+    loop {
+        match next_factor(source, position) {
+            Step::End => return (numerator, denominator),
+            Step::Factor(factor, next) => {
+                if factor.inverted {
+                    denominator += factor.len();
+                } else {
+                    numerator += factor.len();
+                }
+                position = next;
+            }
+            Step::Error(kind, start, end) => fail(kind.description(), source, start, end),
+        }
+    }
+}
+
+/// Returns the units in the numerator and denominator of a unit expression.
+///
+/// ```N``` and ```D``` must be the sizes returned by ```__unit_sizes```.
+#[doc(hidden)]
+pub const fn __unit_components<const N: usize, const D: usize>(source: &str) -> ([Unit; N], [Unit; D]) {
+    let mut numerator = [UNITLESS; N];
+    let mut denominator = [UNITLESS; D];
+    let (mut n, mut d) = (0, 0);
+
+    let mut position = 0;
+    while let Step::Factor(factor, next) = next_factor(source, position) {
+        let mut i = 0;
+        while i < factor.count {
+            if factor.inverted {
+                d = push(&mut denominator, d, factor);
+            } else {
+                n = push(&mut numerator, n, factor);
+            }
+            i += 1;
+        }
+        position = next;
+    }
+
+    (numerator, denominator)
+}
+
+/// Builds the unit of a parsed expression, using the named unit itself for a single symbol.
+#[doc(hidden)]
+pub fn __unit(numerator: &'static [Unit], denominator: &'static [Unit]) -> Unit {
+    match (numerator, denominator) {
+        ([unit], []) => unit.clone(),
+        _ => Unit::Compound(UnitList::from_static(numerator), UnitList::from_static(denominator)),
+    }
+}
+
+/// Parses a decimal number literal like ```12```, ```3.5``` or ```1e-3``` into ```(mantissa, exponent)```,
+/// such that the number is ```mantissa * 10^exponent```.
+///
+/// Panics with a descriptive message if the literal is invalid or doesn't fit into an ```i128```.
+#[doc(hidden)]
+pub const fn __decimal_literal(source: &str) -> (i128, i32) {
+    let bytes = source.as_bytes();
+    let mut mantissa: i128 = 0;
+    let mut exponent: i32 = 0;
+    let mut position = 0;
+    let mut seen_point = false;
+    let mut seen_digit = false;
+
+    while position < bytes.len() {
+        let byte = bytes[position];
+        if byte.is_ascii_digit() {
+            mantissa = match mantissa.checked_mul(10) {
+                Some(mantissa) => match mantissa.checked_add((byte - b'0') as i128) {
+                    Some(mantissa) => mantissa,
+                    None => fail("number literal is too large:", source, 0, bytes.len()),
+                },
+                None => fail("number literal is too large:", source, 0, bytes.len()),
+            };
+            if seen_point {
+                exponent -= 1;
+            }
+            seen_digit = true;
+        } else if byte == b'.' && !seen_point {
+            seen_point = true;
+        } else if byte != b'_' {
+            break;
+        }
+        position += 1;
+    }
+
+    if position < bytes.len() && (bytes[position] == b'e' || bytes[position] == b'E') {
+        position += 1;
+        let negative = position < bytes.len() && bytes[position] == b'-';
+        if position < bytes.len() && (bytes[position] == b'-' || bytes[position] == b'+') {
+            position += 1;
+        }
+
+        let digits = position;
+        let mut value: i32 = 0;
+        while position < bytes.len() && (bytes[position].is_ascii_digit() || bytes[position] == b'_') {
+            if bytes[position] != b'_' {
+                value = match value.checked_mul(10) {
+                    Some(value) => value + (bytes[position] - b'0') as i32,
+                    None => fail("exponent is too large:", source, 0, bytes.len()),
+                };
+            }
+            position += 1;
+        }
+        if digits == position {
+            fail("unsupported number literal", source, 0, bytes.len());
+        }
+        exponent += if negative { -value } else { value };
+    }
+
+    if !seen_digit || position != bytes.len() {
+        fail("unsupported number literal", source, 0, bytes.len());
+    }
+    (mantissa, exponent)
+}
+
+/// Returns ```mantissa * 10^exponent```.
+#[doc(hidden)]
+pub fn __decimal(mantissa: i128, exponent: i32) -> BigRational {
+    let power = num::pow(ScalableInteger::from(10), exponent.unsigned_abs() as usize);
+    if exponent >= 0 {
+        BigRational::from_integer(ScalableInteger::from(mantissa) * power)
+    } else {
+        BigRational::new(mantissa.into(), power)
+    }
+}
+
+/// Appends the units of one repetition of a factor to ```units```, returning the new length.
+const fn push<const N: usize>(units: &mut [Unit; N], mut length: usize, factor: Factor) -> usize {
+    if factor.prefix != NO_PREFIX {
+        // The replaced values are UNITLESS, which owns nothing, so forgetting them leaks nothing
+        std::mem::forget(std::mem::replace(&mut units[length], Unit::from_index(factor.prefix)));
+        length += 1;
+    }
+    std::mem::forget(std::mem::replace(&mut units[length], Unit::from_index(factor.unit)));
+    length + 1
+}
+
+/// Finds a symbol, either as a named unit or as a modifier followed by a named unit.
+///
+/// Returns the indices of the modifier (or ```NO_PREFIX```) and the unit.
+const fn lookup(symbol: &[u8]) -> Option<(usize, usize)> {
+    if let Some(unit) = find(symbol, false) {
+        return Some((NO_PREFIX, unit));
+    }
+
+    let mut split = 1;
+    while split < symbol.len() {
+        let (prefix, rest) = symbol.split_at(split);
+        if let (Some(prefix), Some(unit)) = (find(prefix, true), find(rest, false)) {
+            return Some((prefix, unit));
+        }
+        split += 1;
+    }
+
+    // A modifier on its own, e.g. k
+    match find(symbol, true) {
+        Some(modifier) => Some((NO_PREFIX, modifier)),
+        None => None,
+    }
+}
+
+/// Finds the index of the named unit with the given symbol that is or isn't a modifier.
+const fn find(symbol: &[u8], modifier: bool) -> Option<usize> {
+    let mut i = 0;
+    while i < Unit::NAMED_UNITS.len() {
+        if Unit::NAMED_UNITS[i].is_modifier() == modifier && bytes_equal(Unit::SYMBOLS[i].as_bytes(), symbol) {
+            return Some(i);
+        }
+        i += 1;
+    }
+    None
+}
+
+const fn bytes_equal(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+const fn is_delimiter(byte: u8) -> bool {
+    byte.is_ascii_whitespace() || byte.is_ascii_digit() || matches!(byte, b'*' | b'/' | b'^' | b'(' | b')' | b'-')
+}
+
+/// Whether a ```/``` followed by a digit, which starts the denominator of a fraction, is at ```position```.
+fn is_fraction_bar(bytes: &[u8], position: usize) -> bool {
+    position + 1 < bytes.len() && bytes[position] == b'/' && bytes[position + 1].is_ascii_digit()
+}
+
+/// Returns the position after the ASCII digits starting at ```position```.
+fn digits_end(bytes: &[u8], mut position: usize) -> usize {
+    while position < bytes.len() && bytes[position].is_ascii_digit() {
+        position += 1;
+    }
+    position
+}
+
+/// Scans the proper fraction of a mixed number like ```1 1/2``` or ```2-3/8```, whose whole part
+/// spans ```start..whole_end```. Returns ```None``` if there is no such fraction, so the caller
+/// can parse the rest as a unit.
+///
+/// The whole part and both parts of the fraction must be plain integers.
+fn scan_mixed_fraction(source: &str, start: usize, whole_end: usize) -> Result<Option<(BigRational, usize)>, ParseQuantityError> {
+    let bytes = source.as_bytes();
+    let whole = source[start..whole_end].trim_start_matches(['-', '+']);
+    if whole.is_empty() || !whole.bytes().all(|b| b.is_ascii_digit()) {
+        return Ok(None);
+    }
+
+    let numerator_start = match bytes.get(whole_end) {
+        Some(b'-') => whole_end + 1,
+        Some(b) if b.is_ascii_whitespace() => skip_whitespace(bytes, whole_end),
+        _ => return Ok(None),
+    };
+    let numerator_end = digits_end(bytes, numerator_start);
+    if numerator_end == numerator_start || !is_fraction_bar(bytes, numerator_end) {
+        return Ok(None);
+    }
+    let end = digits_end(bytes, numerator_end + 1);
+
+    let parse = |range: Range<usize>| ScalableInteger::from_str_radix(&source[range], 10).expect("digits are a valid integer");
+    let (numerator, denominator) = (parse(numerator_start..numerator_end), parse(numerator_end + 1..end));
+    if denominator.is_zero() {
+        return Err(ParseQuantityError::new(ErrorKind::ZeroDenominator, numerator_end + 1..end, source));
+    }
+    // Something like 1 3/2 is more likely a typo than one and a half and three halves
+    if numerator >= denominator {
+        return Err(ParseQuantityError::new(ErrorKind::InvalidNumber, start..end, source));
+    }
+    Ok(Some((BigRational::new(numerator, denominator), end)))
+}
+
+const fn skip_whitespace(bytes: &[u8], mut position: usize) -> usize {
+    while position < bytes.len() && bytes[position].is_ascii_whitespace() {
+        position += 1;
+    }
+    position
+}
+
+/// Returns the position after the character at ```position```, so errors can point at it.
+const fn next_char_boundary(bytes: &[u8], mut position: usize) -> usize {
+    if position < bytes.len() {
+        position += 1;
+        // Skip UTF-8 continuation bytes
+        while position < bytes.len() && bytes[position] & 0xc0 == 0x80 {
+            position += 1;
+        }
+    }
+    position
+}
+
+const fn slice(bytes: &[u8], start: usize, end: usize) -> &[u8] {
+    bytes.split_at(end).0.split_at(start).1
+}
+
+/// Panics with ```{description} `{source[start..end]}` in `{source}` ```.
+///
+/// Const panics can only print a single string, so the message is assembled in a buffer first.
+const fn fail(description: &str, source: &str, start: usize, end: usize) -> ! {
+    let mut buffer = [0u8; MESSAGE_CAPACITY];
+    let mut length = 0;
+
+    let bytes = source.as_bytes();
+    let token: &[u8] = if start == end { b"end of input" } else { slice(bytes, start, end) };
+    let parts: [&[u8]; 6] = [description.as_bytes(), b" `", token, b"` in `", bytes, b"`"];
+
+    let mut part = 0;
+    while part < parts.len() {
+        let mut i = 0;
+        while i < parts[part].len() && length < MESSAGE_CAPACITY {
+            buffer[length] = parts[part][i];
+            length += 1;
+            i += 1;
+        }
+        part += 1;
+    }
+
+    match std::str::from_utf8(buffer.split_at(length).0) {
+        Ok(message) => panic!("{}", message),
+        // The buffer was truncated in the middle of a character
+        Err(_) => panic!("{}", description),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::unit::Unit::*;
+    use super::*;
+
+    fn factors(source: &str) -> Vec<Step> {
+        let mut steps = vec![];
+        let mut position = 0;
+        loop {
+            let step = next_factor(source, position);
+            steps.push(step);
+            match step {
+                Step::Factor(_, next) => position = next,
+                _ => return steps,
+            }
+        }
+    }
+
+    fn index(unit: Unit) -> usize {
+        unit.index().unwrap()
+    }
+
+    #[test]
+    fn lookup_symbols() {
+        assert_eq!(lookup(b"m"), Some((NO_PREFIX, index(Meter))));
+        assert_eq!(lookup(b"km"), Some((index(Kilo), index(Meter))));
+        assert_eq!(lookup(b"mm"), Some((index(Milli), index(Meter))));
+        assert_eq!(lookup("µs".as_bytes()), Some((index(Micro), index(Second))));
+        assert_eq!(lookup(b"cd"), Some((NO_PREFIX, index(Candela))));
+        assert_eq!(lookup(b"min"), Some((NO_PREFIX, index(Minute))));
+        assert_eq!(lookup(b"k"), Some((NO_PREFIX, index(Kilo))));
+        assert_eq!(lookup(b"kfoo"), None);
+        assert_eq!(lookup(b"xyz"), None);
+    }
+
+    #[test]
+    fn scan_factors() {
+        let steps = factors("kg*m / s^2");
+        assert_eq!(steps, [
+            Step::Factor(Factor { prefix: index(Kilo), unit: index(Gram), count: 1, inverted: false }, 2),
+            Step::Factor(Factor { prefix: NO_PREFIX, unit: index(Meter), count: 1, inverted: false }, 4),
+            Step::Factor(Factor { prefix: NO_PREFIX, unit: index(Second), count: 2, inverted: true }, 10),
+            Step::End,
+        ]);
+
+        let steps = factors("1/s");
+        assert_eq!(steps[0], Step::Factor(Factor { prefix: NO_PREFIX, unit: index(Second), count: 1, inverted: true }, 3));
+
+        let steps = factors("Hz ^ - 1");
+        assert_eq!(steps[0], Step::Factor(Factor { prefix: NO_PREFIX, unit: index(Hertz), count: 1, inverted: true }, 8));
+    }
+
+    #[test]
+    fn scan_errors() {
+        assert_eq!(factors("m/")[1], Step::Error(ErrorKind::ExpectedSymbol, 2, 2));
+        assert_eq!(factors("2 m")[0], Step::Error(ErrorKind::ExpectedSymbol, 0, 1));
+        assert_eq!(factors("m * foo")[1], Step::Error(ErrorKind::UnknownSymbol, 4, 7));
+        assert_eq!(factors("s^0")[0], Step::Error(ErrorKind::InvalidExponent, 2, 3));
+        assert_eq!(factors("s^")[0], Step::Error(ErrorKind::InvalidExponent, 2, 2));
+        assert_eq!(factors("(m)")[0], Step::Error(ErrorKind::ExpectedSymbol, 0, 1));
+    }
+
+    #[test]
+    fn components() {
+        const SOURCE: &str = "km^2 / h";
+        const SIZES: (usize, usize) = __unit_sizes(SOURCE);
+        static UNITS: ([Unit; SIZES.0], [Unit; SIZES.1]) = __unit_components(SOURCE);
+
+        assert_eq!(SIZES, (4, 1));
+        assert_eq!(__unit(&UNITS.0, &UNITS.1), (Kilo * Meter * Kilo * Meter) / Hour);
+    }
+
+    #[test]
+    fn decimal_literals() {
+        assert_eq!(__decimal_literal("12"), (12, 0));
+        assert_eq!(__decimal_literal("3.25"), (325, -2));
+        assert_eq!(__decimal_literal("1_000.5"), (10005, -1));
+        assert_eq!(__decimal_literal("1e3"), (1, 3));
+        assert_eq!(__decimal_literal("2.5E-4"), (25, -5));
+        assert_eq!(__decimal(25, -5), BigRational::new(1.into(), 4000.into()));
+        assert_eq!(__decimal(7, 2), BigRational::from_integer(700.into()));
+    }
+
+    #[test]
+    fn quantity_macro() {
+        use crate::quantity::Quantity;
+        use crate::unit::{KILOMETER_PER_HOUR, UNITLESS};
+
+        assert_eq!(crate::q!(12.5 km/h), Quantity::new(BigRational::new(25.into(), 2.into()), KILOMETER_PER_HOUR));
+        assert_eq!(crate::q!(3 kg m / s^2), Quantity::new(BigRational::from_integer(3.into()), Kilo * Gram * Meter / (Second * Second)));
+        assert_eq!(crate::q!(2 1/s), Quantity::new(BigRational::from_integer(2.into()), UNITLESS / Second));
+        assert_eq!(crate::q!(2 Hz^-1), Quantity::new(BigRational::from_integer(2.into()), UNITLESS / Hertz));
+        assert_eq!(crate::q!(-1/4 mm), Quantity::new(BigRational::new((-1).into(), 4.into()), Milli * Meter));
+        assert_eq!(crate::q!(1e3 Ω), Quantity::new(BigRational::from_integer(1000.into()), Ohm));
+        assert_eq!(crate::q!(0.25), Quantity::new(BigRational::new(1.into(), 4.into()), UNITLESS));
+    }
+
+    #[test]
+    fn macro_matches_from_str() {
+        macro_rules! check {
+            ($($source:literal),* $(,)?) => {
+                $(assert_eq!(crate::unit!($source), $source.parse::<Unit>().unwrap(), "{}", $source);)*
+            };
+        }
+
+        check!(
+            "m", "km", "km/h", "kg*m/s^2", "kg m / s^2", "J/kg/K", "1/s", "Hz^-1", "°C", "µs",
+            "kW h", "m^3", "N * m", "mol/L", "cd", "min", "k", "gal/min", "Ω*m", "", "  s  ",
+        );
+        assert_eq!(crate::unit!(kg m / s^2), "kg m / s^2".parse().unwrap());
+        assert_eq!(crate::unit!(1/s), "1/s".parse().unwrap());
+        assert_eq!(crate::unit!(Hz^-1), "Hz^-1".parse().unwrap());
+        assert_eq!(crate::unit!(), UNITLESS);
+    }
+
+    #[test]
+    fn from_str_errors() {
+        let error = "m * foo".parse::<Unit>().unwrap_err();
+        assert_eq!((error.kind(), error.span()), (ErrorKind::UnknownSymbol, 4..7));
+        assert_eq!(error.to_string(), "unknown unit symbol `foo` in `m * foo`");
+
+        let error = "m/".parse::<Unit>().unwrap_err();
+        assert_eq!((error.kind(), error.span()), (ErrorKind::ExpectedSymbol, 2..2));
+        assert_eq!(error.to_string(), "expected a unit symbol at `end of input` in `m/`");
+
+        let error = "s^1025".parse::<Unit>().unwrap_err();
+        assert_eq!((error.kind(), error.span()), (ErrorKind::InvalidExponent, 2..6));
+        assert_eq!("s^1024".parse::<Unit>().map(|u| u.to_fraction().0.len()), Ok(1024));
+
+        let error = "°F*°X".parse::<Unit>().unwrap_err();
+        assert_eq!(error.to_string(), "unknown unit symbol `°X` in `°F*°X`");
+        assert_eq!("°F*°".parse::<Unit>(), Ok(Fahrenheit * Degree));
+    }
+
+    #[test]
+    fn parse_named_units() {
+        use crate::assert_quantity_eq;
+        use crate::quantity::Quantity;
+
+        for unit in Unit::NAMED_UNITS {
+            if unit.is_modifier() {
+                // Bare modifier symbols like m are taken by units, so parse them as prefixes of grams
+                let parsed: Quantity = format!("1 {}g", unit.symbol()).parse().unwrap();
+                assert_eq!(parsed, Quantity::from_unit(unit.clone() * Gram), "{}", unit.symbol());
+            } else {
+                // Symbols like in^3 parse to an equivalent compound unit
+                let parsed: Quantity = format!("1 {}", unit.symbol()).parse().unwrap();
+                assert_quantity_eq!(Quantity::from_unit(unit.clone()), parsed, "{}", unit.symbol());
+            }
+        }
+    }
+
+    #[test]
+    fn parse_quantities() {
+        use crate::quantity::Quantity;
+        use crate::unit::KILOMETER_PER_HOUR;
+
+        let table = [
+            ("12.5 km/h", Quantity::new(BigRational::new(25.into(), 2.into()), KILOMETER_PER_HOUR)),
+            ("12.5km/h", Quantity::new(BigRational::new(25.into(), 2.into()), KILOMETER_PER_HOUR)),
+            ("3e8 m/s", Quantity::new(BigRational::from_integer(300_000_000.into()), Meter / Second)),
+            ("2.5E-3 s", Quantity::new(BigRational::new(1.into(), 400.into()), Second)),
+            ("7/8 in", Quantity::new(BigRational::new(7.into(), 8.into()), Inch)),
+            ("-1.5/3 m", Quantity::new(BigRational::new((-1).into(), 2.into()), Meter)),
+            ("42", Quantity::from_i64(42)),
+            ("  +42  ", Quantity::from_i64(42)),
+            ("-40 °C", Quantity::from_i64_with_unit(-40, Celsius)),
+            ("2/s", Quantity::from_i64_with_unit(2, UNITLESS / Second)),
+            ("2 1/s", Quantity::from_i64_with_unit(2, UNITLESS / Second)),
+            ("2Em", Quantity::from_i64_with_unit(2, Exa * Meter)),
+            (".5 kg m/s^2", Quantity::new(BigRational::new(1.into(), 2.into()), Kilo * Gram * Meter / (Second * Second))),
+            ("123456789012345678901234567890 B", Quantity::new(BigRational::from_integer("123456789012345678901234567890".parse::<num::BigInt>().unwrap().into()), Byte)),
+        ];
+        for (source, expected) in table {
+            assert_eq!(source.parse::<Quantity>(), Ok(expected), "{source}");
+        }
+    }
+
+    #[test]
+    fn parse_quantity_errors() {
+        use crate::quantity::Quantity;
+
+        let error = |source: &str| {
+            let error = source.parse::<Quantity>().unwrap_err();
+            (error.kind(), error.span())
+        };
+        assert_eq!(error(""), (ErrorKind::InvalidNumber, 0..0));
+        assert_eq!(error("km"), (ErrorKind::InvalidNumber, 0..1));
+        assert_eq!(error("- 5 m"), (ErrorKind::InvalidNumber, 0..2));
+        assert_eq!(error("1/0 m"), (ErrorKind::ZeroDenominator, 2..3));
+        assert_eq!(error("1e10001 m"), (ErrorKind::InvalidNumber, 0..7));
+        assert_eq!(error("12 km/h xyz"), (ErrorKind::UnknownSymbol, 8..11));
+        assert_eq!(error("12 km)"), (ErrorKind::ExpectedSymbol, 5..6));
+        assert_eq!(error("1.2.3 m"), (ErrorKind::UnknownSymbol, 3..4));
+
+        let message = "5 m/".parse::<Quantity>().unwrap_err().to_string();
+        assert_eq!(message, "expected a unit symbol at `end of input` in `5 m/`");
+    }
+
+    #[test]
+    fn parse_mixed_numbers() {
+        use crate::format::QuantityFormatter;
+        use crate::quantity::Quantity;
+
+        let value = |numer: i64, denom: i64| BigRational::new(numer.into(), denom.into());
+        let table = [
+            ("3/4 in", Quantity::new(value(3, 4), Inch)),
+            ("1 1/2 gal", Quantity::new(value(3, 2), Gallon)),
+            ("2-3/8 in", Quantity::new(value(19, 8), Inch)),
+            ("-1 1/4 in", Quantity::new(value(-5, 4), Inch)),
+            ("1 1/2 m/s", Quantity::new(value(3, 2), Meter / Second)),
+            ("1 1/2", Quantity::new(value(3, 2), UNITLESS)),
+        ];
+        for (source, expected) in table {
+            assert_eq!(source.parse::<Quantity>(), Ok(expected), "{source}");
+        }
+
+        let error = |source: &str| {
+            let error = source.parse::<Quantity>().unwrap_err();
+            (error.kind(), error.span())
+        };
+        assert_eq!(error("1/2/3 m"), (ErrorKind::InvalidNumber, 0..5));
+        assert_eq!(error("1 1/2/3 m"), (ErrorKind::InvalidNumber, 0..7));
+        assert_eq!(error("1 3/2 m"), (ErrorKind::InvalidNumber, 0..5));
+        assert_eq!(error("1 1/0 m"), (ErrorKind::ZeroDenominator, 4..5));
+
+        // The mixed number display mode round trips
+        let formatter = QuantityFormatter::new().mixed_fractions(true).space(true);
+        for magnitude in [value(29, 8), value(-5, 4), value(5, 16), value(7, 1)] {
+            let quantity = Quantity::new(magnitude, Inch);
+            let formatted = formatter.format(&quantity);
+            assert_eq!(formatted.parse::<Quantity>(), Ok(quantity), "{formatted}");
+        }
+    }
+
+    #[test]
+    fn parse_with_locale() {
+        use crate::format::NumberLocale;
+        use crate::quantity::Quantity;
+
+        let parse = |source: &str, locale: &NumberLocale| Quantity::from_str_with_locale(source, locale).map(|q| (q.magnitude, q.unit));
+        let value = |numer: i64, denom: i64| BigRational::new(numer.into(), denom.into());
+
+        assert_eq!(parse("1.234,5 km", &NumberLocale::DE_DE), Ok((value(12345, 10), Kilo * Meter)));
+        assert_eq!(parse("1,234.5 km", &NumberLocale::EN_US), Ok((value(12345, 10), Kilo * Meter)));
+        assert_eq!(parse("1\u{202F}234,5 km", &NumberLocale::FR_FR), Ok((value(12345, 10), Kilo * Meter)));
+        assert_eq!(parse("1234,5 km", &NumberLocale::DE_DE), Ok((value(12345, 10), Kilo * Meter)));
+        assert_eq!(parse("-12.345.678 m", &NumberLocale::DE_DE), Ok((value(-12_345_678, 1), Meter)));
+        assert_eq!(parse("0,5e3 m", &NumberLocale::DE_DE), Ok((value(500, 1), Meter)));
+        assert_eq!(parse("1,5/2 m", &NumberLocale::DE_DE), Ok((value(3, 4), Meter)));
+        assert_eq!(parse("1234.5 m", &NumberLocale::PLAIN), Ok((value(12345, 10), Meter)));
+
+        // The same digits mean different numbers in different locales
+        assert_eq!(parse("1.234 m", &NumberLocale::EN_US), Ok((value(1234, 1000), Meter)));
+        assert_eq!(parse("1.234 m", &NumberLocale::DE_DE), Ok((value(1234, 1), Meter)));
+        assert_eq!(parse("1,234 m", &NumberLocale::EN_US), Ok((value(1234, 1), Meter)));
+        assert_eq!(parse("1,234 m", &NumberLocale::DE_DE), Ok((value(1234, 1000), Meter)));
+
+        // A separator without a digit after it isn't part of the number
+        assert_eq!(parse("5, m", &NumberLocale::EN_US).unwrap_err().kind(), ErrorKind::UnknownSymbol);
+        assert_eq!("1,234.5 km".parse::<Quantity>(), Quantity::from_str_with_locale("1,234.5 km", &NumberLocale::EN_US));
+
+        let error = |source: &str, locale: &NumberLocale| {
+            let error = Quantity::from_str_with_locale(source, locale).unwrap_err();
+            (error.kind(), error.span())
+        };
+        assert_eq!(error("1.23 m", &NumberLocale::DE_DE), (ErrorKind::InvalidNumber, 0..4));
+        assert_eq!(error("12.34,5 m", &NumberLocale::DE_DE), (ErrorKind::InvalidNumber, 0..5));
+        assert_eq!(error("1.2345 m", &NumberLocale::DE_DE), (ErrorKind::InvalidNumber, 0..6));
+        assert_eq!(error("1234.567 m", &NumberLocale::DE_DE), (ErrorKind::InvalidNumber, 0..8));
+        assert_eq!(error("1.234.56 m", &NumberLocale::DE_DE), (ErrorKind::InvalidNumber, 0..8));
+        assert_eq!(error("-1,23,456 m", &NumberLocale::EN_US), (ErrorKind::InvalidNumber, 0..9));
+        assert_eq!(error("2,5 m", &NumberLocale::EN_US), (ErrorKind::InvalidNumber, 0..3));
+        assert_eq!(error("1,5 m", &NumberLocale::PLAIN), (ErrorKind::UnknownSymbol, 1..2));
+    }
+
+    #[test]
+    fn iso8601_durations() {
+        use crate::quantity::Quantity;
+
+        let seconds = |value: i64| Ok(Quantity::from_i64_with_unit(value, Second));
+        assert_eq!(Quantity::from_iso8601_duration("P1Y"), seconds(31_557_600));
+        assert_eq!(Quantity::from_iso8601_duration("P1M"), seconds(2_629_746));
+        assert_eq!(Quantity::from_iso8601_duration("P1W"), seconds(604_800));
+        assert_eq!(Quantity::from_iso8601_duration("P1D"), seconds(86_400));
+        assert_eq!(Quantity::from_iso8601_duration("PT1H"), seconds(3600));
+        assert_eq!(Quantity::from_iso8601_duration("PT1M"), seconds(60));
+        assert_eq!(Quantity::from_iso8601_duration("PT1S"), seconds(1));
+        assert_eq!(Quantity::from_iso8601_duration("P0D"), seconds(0));
+
+        assert_eq!(Quantity::from_iso8601_duration("PT1H30M"), seconds(5400));
+        assert_eq!(Quantity::from_iso8601_duration("P2DT3H"), seconds(183_600));
+        assert_eq!(Quantity::from_iso8601_duration("P1M1DT1M"), seconds(2_629_746 + 86_400 + 60));
+        assert_eq!(Quantity::from_iso8601_duration("P1Y2M3W4DT5H6M7S"), seconds(31_557_600 + 5_259_492 + 1_814_400 + 345_600 + 18_000 + 360 + 7));
+        assert_eq!(Quantity::from_iso8601_duration("PT36H"), seconds(129_600));
+
+        assert_eq!(Quantity::from_iso8601_duration("PT0.5S"), Ok(Quantity::new(BigRational::new(1.into(), 2.into()), Second)));
+        assert_eq!(Quantity::from_iso8601_duration("PT0,25S"), Ok(Quantity::new(BigRational::new(1.into(), 4.into()), Second)));
+        assert_eq!(Quantity::from_iso8601_duration("P1DT1.5H"), seconds(86_400 + 5400));
+        assert_eq!(Quantity::from_iso8601_duration("P1.5Y"), seconds(47_336_400));
+        assert_eq!(Quantity::from_iso8601_duration("P0.5D"), seconds(43_200));
+        assert_eq!(Quantity::from_iso8601_duration("-PT5M"), seconds(-300));
+    }
+
+    #[test]
+    fn iso8601_duration_errors() {
+        use crate::quantity::Quantity;
+
+        let error = |source: &str| {
+            let error = Quantity::from_iso8601_duration(source).unwrap_err();
+            (error.kind(), error.span())
+        };
+        assert_eq!(error("P1.5Y2D"), (ErrorKind::InvalidNumber, 1..4));
+        assert_eq!(error("P1.5DT2H"), (ErrorKind::InvalidNumber, 1..4));
+        assert_eq!(error("PT0.5M1S"), (ErrorKind::InvalidNumber, 2..5));
+        assert_eq!(error(""), (ErrorKind::InvalidDesignator, 0..0));
+        assert_eq!(error("1D"), (ErrorKind::InvalidDesignator, 0..1));
+        assert_eq!(error("P"), (ErrorKind::InvalidDesignator, 1..1));
+        assert_eq!(error("PT"), (ErrorKind::InvalidDesignator, 2..2));
+        assert_eq!(error("P1DT"), (ErrorKind::InvalidDesignator, 4..4));
+        assert_eq!(error("P1H"), (ErrorKind::InvalidDesignator, 2..3));
+        assert_eq!(error("PT1D"), (ErrorKind::InvalidDesignator, 3..4));
+        assert_eq!(error("P1D2Y"), (ErrorKind::InvalidDesignator, 4..5));
+        assert_eq!(error("PT1M1M"), (ErrorKind::InvalidDesignator, 5..6));
+        assert_eq!(error("P1"), (ErrorKind::InvalidDesignator, 2..2));
+        assert_eq!(error("pt1h"), (ErrorKind::InvalidDesignator, 0..1));
+        assert_eq!(error("PD"), (ErrorKind::InvalidNumber, 1..2));
+        assert_eq!(error("PT.5S"), (ErrorKind::InvalidDesignator, 2..3));
+        assert_eq!(error("PT1.S"), (ErrorKind::InvalidNumber, 2..5));
+        assert_eq!(error("P-1D"), (ErrorKind::InvalidNumber, 1..2));
+        assert_eq!(error("--P1D"), (ErrorKind::InvalidDesignator, 1..2));
+        assert_eq!(error("-"), (ErrorKind::InvalidDesignator, 1..1));
+
+        let message = Quantity::from_iso8601_duration("P1.5Y2D").unwrap_err().to_string();
+        assert_eq!(message, "invalid number at `1.5` in `P1.5Y2D`");
+    }
+
+    #[test]
+    fn suffixed_numbers() {
+        use crate::quantity::Quantity;
+
+        for modifier in Unit::NAMED_UNITS.iter().filter(|unit| unit.is_modifier()) {
+            let (_, factor, _) = modifier.clone().to_si_units();
+            let source = format!("3{}", modifier.symbol());
+            let expected = Quantity::new(BigRational::from_integer(3.into()) * &factor, Second);
+            assert_eq!(Quantity::from_suffixed_str_with_unit(&source, Second), Ok(expected), "{source}");
+            if !matches!(modifier, Milli | Deci | Hecto | Tera) {
+                assert_eq!(Quantity::from_suffixed_str(&source), Ok(Quantity::from_rational(factor * BigRational::from_integer(3.into()))), "{source}");
+            }
+        }
+
+        assert_eq!(Quantity::from_suffixed_str("5k"), Ok(Quantity::from_i64(5000)));
+        assert_eq!(Quantity::from_suffixed_str("1.2M"), Ok(Quantity::from_i64(1_200_000)));
+        assert_eq!(Quantity::from_suffixed_str(" -2.5Ki "), Ok(Quantity::from_i64(-2560)));
+        assert_eq!(Quantity::from_suffixed_str("3Gi"), Ok(Quantity::from_i64(3 * 1024 * 1024 * 1024)));
+        assert_eq!(Quantity::from_suffixed_str("2E"), Ok(Quantity::new(BigRational::from_integer(2_000_000_000_000_000_000i128.into()), UNITLESS)));
+        assert_eq!(Quantity::from_suffixed_str("1e3k"), Ok(Quantity::from_i64(1_000_000)));
+        assert_eq!(Quantity::from_suffixed_str("42"), Ok(Quantity::from_i64(42)));
+    }
+
+    #[test]
+    fn suffixed_number_errors() {
+        use crate::quantity::Quantity;
+
+        let error = |source: &str| {
+            let error = Quantity::from_suffixed_str(source).unwrap_err();
+            (error.kind(), error.span())
+        };
+        assert_eq!(error("5m"), (ErrorKind::AmbiguousSuffix, 1..2));
+        assert_eq!(error("5h"), (ErrorKind::AmbiguousSuffix, 1..2));
+        assert_eq!(error("5d"), (ErrorKind::AmbiguousSuffix, 1..2));
+        assert_eq!(error("5T"), (ErrorKind::AmbiguousSuffix, 1..2));
+        assert_eq!(error("5 k"), (ErrorKind::UnknownSymbol, 1..3));
+        assert_eq!(error("5K"), (ErrorKind::UnknownSymbol, 1..2));
+        assert_eq!(error("5km"), (ErrorKind::UnknownSymbol, 1..3));
+        assert_eq!(error("k"), (ErrorKind::InvalidNumber, 0..1));
+
+        let message = Quantity::from_suffixed_str("5m").unwrap_err().to_string();
+        assert_eq!(message, "ambiguous suffix `m` in `5m`");
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown unit symbol `foo` in `m * foo`")]
+    fn error_message() {
+        __unit_sizes("m * foo");
+    }
+
+    #[test]
+    #[should_panic(expected = "unsupported number literal `5i64` in `5i64`")]
+    fn literal_suffix() {
+        __decimal_literal("5i64");
+    }
+}
@@ -0,0 +1,204 @@
+//! Which unit the sum or difference of two quantities is in.
+//!
+//! By default ```a + b``` is in the unit of ```a```. The default can be changed for the current
+//! thread with ```AddPolicy::set_default```, or a policy can be given for a single operation with
+//! ```Quantity::add_with_policy```.
+//!
+//! # Example:
+//! ```
+//! use tantalum_unit::prelude::*;
+//! use tantalum_unit::policy::AddPolicy;
+//!
+//! let gallon = Quantity::from_i64_with_unit(1, Gallon);
+//! let liter = Quantity::from_i64_with_unit(1, Liter);
+//!
+//! let sum = Quantity::add_with_policy(&gallon, &liter, AddPolicy::Smaller).unwrap();
+//! assert_eq!(sum, Quantity::new(ratio!(4785411784i64, 1000000000), Liter));
+//!
+//! AddPolicy::set_default(AddPolicy::RightOperand);
+//! assert_eq!((gallon + liter).unit, Liter);
+//! AddPolicy::set_default(AddPolicy::LeftOperand);
+//! ```
+
+use std::cell::Cell;
+use crate::error::ConversionError;
+use crate::quantity::Quantity;
+use crate::scalable_integer::BigRational;
+use crate::unit::Unit;
+
+thread_local! {
+    static DEFAULT: Cell<AddPolicy> = const { Cell::new(AddPolicy::LeftOperand) };
+}
+
+/// The unit the result of adding or subtracting two quantities is in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum AddPolicy {
+    /// The unit of the left operand.
+    #[default]
+    LeftOperand,
+    /// The unit of the right operand.
+    RightOperand,
+    /// The SI unit of the dimension, as returned by ```Unit::to_si_units``` for the left operand.
+    SiBase,
+    /// The unit of the operand whose unit is larger, like ```Gallon``` for gallons and liters.
+    /// The left operand wins a tie.
+    Larger,
+    /// The unit of the operand whose unit is smaller, like ```Liter``` for gallons and liters.
+    /// The left operand wins a tie.
+    Smaller,
+}
+
+impl AddPolicy {
+    /// The policy ```+``` and ```-``` use on the current thread, ```LeftOperand``` unless changed.
+    pub fn current_default() -> AddPolicy {
+        DEFAULT.with(Cell::get)
+    }
+
+    /// Sets the policy ```+``` and ```-``` use on the current thread.
+    pub fn set_default(policy: AddPolicy) {
+        DEFAULT.with(|default| default.set(policy));
+    }
+
+    /// The unit of the result of adding quantities in ```left``` and ```right```.
+    pub fn result_unit(self, left: &Unit, right: &Unit) -> Unit {
+        let slopes = || (left.si_affine().1, right.si_affine().1);
+        let pick = |left_wins: bool| if left_wins { left.clone() } else { right.clone() };
+        match self {
+            AddPolicy::LeftOperand => left.clone(),
+            AddPolicy::RightOperand => right.clone(),
+            AddPolicy::SiBase => left.clone().to_si_units().2,
+            AddPolicy::Larger | AddPolicy::Smaller if left == right => left.clone(),
+            AddPolicy::Larger => {
+                let (left_slope, right_slope) = slopes();
+                pick(left_slope >= right_slope)
+            }
+            AddPolicy::Smaller => {
+                let (left_slope, right_slope) = slopes();
+                pick(left_slope <= right_slope)
+            }
+        }
+    }
+}
+
+impl Quantity {
+    /// Adds two quantities, with the result in the unit chosen by ```policy```.
+    ///
+    /// Like ```+```, a unitless zero is the identity for every unit. Returns an error if an
+    /// operand can't be converted to the unit of the result.
+    pub fn add_with_policy(a: &Quantity, b: &Quantity, policy: AddPolicy) -> Result<Quantity, ConversionError> {
+        if b.is_unitless_zero() {
+            return Ok(a.clone());
+        }
+        if a.is_unitless_zero() {
+            return Ok(b.clone());
+        }
+        let (left, right, unit) = a.clone().operands_in(b.clone(), policy)?;
+        Ok(Quantity::new(left + right, unit))
+    }
+
+    /// Subtracts ```b``` from ```a```, with the result in the unit chosen by ```policy```.
+    ///
+    /// Like ```-```, a unitless zero is the identity for every unit. Returns an error if an
+    /// operand can't be converted to the unit of the result.
+    pub fn sub_with_policy(a: &Quantity, b: &Quantity, policy: AddPolicy) -> Result<Quantity, ConversionError> {
+        if b.is_unitless_zero() {
+            return Ok(a.clone());
+        }
+        if a.is_unitless_zero() {
+            return Ok(-b.clone());
+        }
+        let (left, right, unit) = a.clone().operands_in(b.clone(), policy)?;
+        Ok(Quantity::new(left - right, unit))
+    }
+
+    /// The magnitudes of both operands in the unit chosen by ```policy```, and that unit.
+    pub(crate) fn operands_in(self, rhs: Quantity, policy: AddPolicy) -> Result<(BigRational, BigRational, Unit), ConversionError> {
+        let unit = policy.result_unit(&self.unit, &rhs.unit);
+        let convert = |quantity: Quantity| {
+            if quantity.unit == unit {
+                return Ok(quantity.magnitude);
+            }
+            quantity.convert_to(unit.clone())
+                .map(|converted| converted.magnitude)
+        };
+        // Convert the right operand first, so incompatible units are reported as by +
+        let right = convert(rhs)?;
+        let left = convert(self)?;
+        Ok((left, right, unit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::Zero;
+    use crate::ratio;
+    use crate::error::ConversionError;
+    use crate::policy::AddPolicy;
+    use crate::quantity::Quantity;
+    use crate::scalable_integer::BigRational;
+    use crate::unit::Unit::*;
+
+    fn gallon_and_liter() -> (Quantity, Quantity) {
+        (Quantity::from_i64_with_unit(1, Gallon), Quantity::from_i64_with_unit(1, Liter))
+    }
+
+    #[test]
+    fn add_with_policy() {
+        let (gallon, liter) = gallon_and_liter();
+        let add = |policy| Quantity::add_with_policy(&gallon, &liter, policy).unwrap();
+
+        assert_eq!(add(AddPolicy::LeftOperand), Quantity::new(ratio!(598176473, 473176473), Gallon));
+        assert_eq!(add(AddPolicy::RightOperand), Quantity::new(ratio!(4785411784i64, 1000000000), Liter));
+        assert_eq!(add(AddPolicy::SiBase), Quantity::new(ratio!(4785411784i64, 1000000000000i64), Meter * Meter * Meter));
+        assert_eq!(add(AddPolicy::Larger), Quantity::new(ratio!(598176473, 473176473), Gallon));
+        assert_eq!(add(AddPolicy::Smaller), Quantity::new(ratio!(4785411784i64, 1000000000), Liter));
+
+        // The unit doesn't depend on the order of the operands
+        assert_eq!(Quantity::add_with_policy(&liter, &gallon, AddPolicy::Larger).unwrap().unit, Gallon);
+        assert_eq!(Quantity::add_with_policy(&liter, &gallon, AddPolicy::Smaller).unwrap().unit, Liter);
+    }
+
+    #[test]
+    fn sub_with_policy() {
+        let (gallon, liter) = gallon_and_liter();
+        let sub = |policy| Quantity::sub_with_policy(&gallon, &liter, policy).unwrap();
+
+        assert_eq!(sub(AddPolicy::LeftOperand), Quantity::new(ratio!(348176473, 473176473), Gallon));
+        assert_eq!(sub(AddPolicy::RightOperand), Quantity::new(ratio!(2785411784i64, 1000000000), Liter));
+        assert_eq!(sub(AddPolicy::SiBase), Quantity::new(ratio!(2785411784i64, 1000000000000i64), Meter * Meter * Meter));
+        assert_eq!(sub(AddPolicy::Smaller), Quantity::new(ratio!(2785411784i64, 1000000000), Liter));
+        assert_eq!(Quantity::sub_with_policy(&liter, &gallon, AddPolicy::Smaller).unwrap().magnitude, ratio!(-2785411784i64, 1000000000));
+    }
+
+    #[test]
+    fn policy_errors_and_identity() {
+        let (gallon, _) = gallon_and_liter();
+        let meter = Quantity::from_i64_with_unit(1, Meter);
+        assert_eq!(Quantity::add_with_policy(&gallon, &meter, AddPolicy::LeftOperand),
+                   Err(ConversionError::IncompatibleUnits { from: Meter, to: Gallon }));
+        assert!(Quantity::add_with_policy(&gallon, &meter, AddPolicy::Smaller).is_err());
+        assert_eq!(Quantity::add_with_policy(&gallon, &Quantity::zero(), AddPolicy::SiBase), Ok(gallon.clone()));
+        assert_eq!(Quantity::sub_with_policy(&Quantity::zero(), &gallon, AddPolicy::SiBase), Ok(-gallon));
+    }
+
+    #[test]
+    fn default_policy() {
+        let (gallon, liter) = gallon_and_liter();
+        assert_eq!(AddPolicy::current_default(), AddPolicy::LeftOperand);
+        assert_eq!((gallon.clone() + liter.clone()).unit, Gallon);
+
+        AddPolicy::set_default(AddPolicy::Smaller);
+        let sum = gallon.clone() + liter.clone();
+        let difference = gallon.clone() - liter.clone();
+        AddPolicy::set_default(AddPolicy::LeftOperand);
+        assert_eq!(sum, Quantity::new(ratio!(4785411784i64, 1000000000), Liter));
+        assert_eq!(difference, Quantity::new(ratio!(2785411784i64, 1000000000), Liter));
+
+        // The default only applies to the current thread
+        AddPolicy::set_default(AddPolicy::SiBase);
+        let other = std::thread::spawn(AddPolicy::current_default).join().unwrap();
+        AddPolicy::set_default(AddPolicy::LeftOperand);
+        assert_eq!(other, AddPolicy::LeftOperand);
+        assert_eq!((gallon + liter).unit, Gallon);
+    }
+}
@@ -0,0 +1,279 @@
+//! Powers of quantities with integer and rational exponents.
+//!
+//! The unit is raised to the power as well, so a rational exponent only works if it yields whole
+//! powers of every unit, like the square root of ```m^2```. Powers are exact, except that
+//! ```Quantity::try_pow_with_precision``` approximates irrational powers of unitless quantities.
+//!
+//! # Example:
+//! ```
+//! use tantalum_unit::prelude::*;
+//! use tantalum_unit::error::PowError;
+//!
+//! assert_eq!(q!(2 m).powi(3), Quantity::from_i64_with_unit(8, c!(Meter^3;)));
+//! assert_eq!(q!(4 m^2).try_pow(&q!(0.5)), Ok(q!(2 m)));
+//! assert_eq!(q!(2 m).try_pow(&q!(3 s)), Err(PowError::UnitExponent(Second)));
+//! ```
+
+use num::{BigInt, Signed, Zero};
+use crate::error::PowError;
+use crate::quantity::Quantity;
+use crate::scalable_integer::{BigRational, ScalableInteger};
+use crate::unit::Unit;
+use crate::unit_list::UnitList;
+
+/// The largest number of digits ```Quantity::try_pow_with_precision``` scales a value by before
+/// taking a root, which is the degree of the root times the requested decimal places.
+pub const MAX_ROOT_DIGITS: u32 = 100_000;
+
+impl Quantity {
+    /// Raises the Quantity and its unit to an integer power.
+    ///
+    /// Panics if zero is raised to a negative power.
+    pub fn powi(self, exponent: i32) -> Self {
+        Self::new(magnitude_pow(self.magnitude, exponent), self.unit.pow(exponent))
+    }
+
+    /// Raises the Quantity and its unit to a rational power, if the result is exact.
+    ///
+    /// Returns an error unless the root of the magnitude is rational and the powers of all units
+    /// are integers, so ```4 m^2``` has a square root but ```2 m``` hasn't.
+    pub fn pow_ratio(self, exponent: &BigRational) -> Result<Self, PowError> {
+        let (p, q) = split_exponent(exponent)?;
+        if self.magnitude.is_zero() && p < 0 {
+            return Err(PowError::DivisionByZero);
+        }
+
+        let root = exact_root(&self.magnitude, q)?;
+        let unit = unit_pow(self.unit, p, q).ok_or(PowError::InexactResult)?;
+        Ok(Self::new(magnitude_pow(root, p), unit))
+    }
+
+    /// Raises the Quantity to the power of a unitless Quantity, like ```pow_ratio```.
+    ///
+    /// Returns an error if the exponent has a unit. Modifiers of the exponent are applied, so an
+    /// exponent of ```m/mm``` is ```1000```.
+    pub fn try_pow(self, exponent: &Quantity) -> Result<Self, PowError> {
+        self.pow_ratio(&unitless_exponent(exponent)?)
+    }
+
+    /// Like ```try_pow```, but approximates irrational powers of unitless quantities instead of
+    /// returning an error.
+    ///
+    /// The approximation is rounded toward zero to ```digits``` decimal places. Returns
+    /// ```PowError::PrecisionTooLarge``` if ```digits``` times the denominator of the exponent
+    /// exceeds ```MAX_ROOT_DIGITS```.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let root = Quantity::from_i64(2).try_pow_with_precision(&q!(0.5), 6);
+    /// assert_eq!(root, Ok(Quantity::new(ratio!(1414213, 1000000), UNITLESS)));
+    /// ```
+    pub fn try_pow_with_precision(self, exponent: &Quantity, digits: u32) -> Result<Self, PowError> {
+        let exponent = unitless_exponent(exponent)?;
+        match self.clone().pow_ratio(&exponent) {
+            Err(PowError::InexactResult) if self.is_unitless() => {
+                let (p, q) = split_exponent(&exponent)?;
+                let scale = digits.checked_mul(q)
+                    .filter(|&scale| scale <= MAX_ROOT_DIGITS)
+                    .ok_or(PowError::PrecisionTooLarge)?;
+                Ok(Self::from_rational(approximate_root(&magnitude_pow(self.magnitude, p), q, digits, scale)))
+            }
+            result => result,
+        }
+    }
+}
+
+impl Unit {
+    /// Raises the unit to an integer power, keeping the exponent as a count so equal units cancel.
+    ///
+    /// The result is the simplified ```Power``` of the unit, like the product of the unit with
+    /// itself, ```UNITLESS``` for the zeroth power and the unit itself for the first power.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// assert_eq!(Meter.pow(3), Meter * Meter * Meter);
+    /// assert_eq!(Second.pow(-2), UNITLESS / (Second * Second));
+    /// assert_eq!((Meter / Second).pow(2).to_string(), "m^2/s^2");
+    /// assert_eq!(Meter.pow(i32::MAX).to_string(), "m^2147483647");
+    /// ```
+    pub fn pow(self, exponent: i32) -> Unit {
+        Unit::Power(Box::new(self), exponent).simplify()
+    }
+}
+
+/// The magnitude of a unitless exponent, or an error if it has a unit.
+fn unitless_exponent(exponent: &Quantity) -> Result<BigRational, PowError> {
+    let stripped = exponent.clone().apply_modifiers();
+    if stripped.is_unitless() {
+        Ok(stripped.magnitude)
+    } else {
+        Err(PowError::UnitExponent(exponent.unit.clone()))
+    }
+}
+
+/// Splits an exponent into its numerator and denominator.
+fn split_exponent(exponent: &BigRational) -> Result<(i32, u32), PowError> {
+    let p = i32::try_from(exponent.numer().clone().to_big_int()).map_err(|_| PowError::ExponentTooLarge)?;
+    let q = u32::try_from(exponent.denom().clone().to_big_int()).map_err(|_| PowError::ExponentTooLarge)?;
+    Ok((p, q))
+}
+
+pub(crate) fn magnitude_pow(value: BigRational, exponent: i32) -> BigRational {
+    let n = exponent.unsigned_abs() as usize;
+    let power = BigRational::new(num::pow(value.numer().clone(), n), num::pow(value.denom().clone(), n));
+    if exponent < 0 { power.recip() } else { power }
+}
+
+/// The ```q```th root of a rational, if it is rational.
+fn exact_root(value: &BigRational, q: u32) -> Result<BigRational, PowError> {
+    if q == 1 {
+        return Ok(value.clone());
+    }
+    let negative = value.numer().clone().to_big_int().is_negative();
+    if negative && q.is_multiple_of(2) {
+        return Err(PowError::NegativeBase);
+    }
+
+    let root = |n: &ScalableInteger| {
+        let n = n.clone().to_big_int().abs();
+        let root = n.nth_root(q);
+        (num::pow(root.clone(), q as usize) == n).then(|| ScalableInteger::from(root))
+    };
+    let (Some(numer), Some(denom)) = (root(value.numer()), root(value.denom())) else {
+        return Err(PowError::InexactResult);
+    };
+    let root = BigRational::new(numer, denom);
+    Ok(if negative { -root } else { root })
+}
+
+/// The ```q```th root of a rational, rounded toward zero to ```digits``` decimal places.
+///
+/// ```scale``` is ```digits * q```, checked by the caller.
+fn approximate_root(value: &BigRational, q: u32, digits: u32, scale: u32) -> BigRational {
+    let numer = value.numer().clone().to_big_int();
+    let denom = value.denom().clone().to_big_int();
+
+    // floor(root(x * 10^(digits * q))) = floor(root(x) * 10^digits)
+    let scale = num::pow(BigInt::from(10), scale as usize);
+    let root = ((numer.abs() * scale) / denom).nth_root(q);
+    let root = BigRational::new(root.into(), num::pow(ScalableInteger::from(10), digits as usize));
+    if numer.is_negative() { -root } else { root }
+}
+
+/// Raises a unit to the power ```p/q```, or returns ```None``` if a unit would get a fractional power.
+fn unit_pow(unit: Unit, p: i32, q: u32) -> Option<Unit> {
+    if i64::from(p) == i64::from(q) {
+        return Some(unit);
+    }
+
+    // Repeated units are merged, so every component is a distinct power
+    let (numerator, denominator) = unit.simplify().into_components();
+    let components = numerator.iter().map(|u| (u, 1)).chain(denominator.iter().map(|u| (u, -1)));
+
+    let mut new_num = vec![];
+    for (u, sign) in components {
+        let (base, exponent) = u.as_power();
+        let scaled = sign * i64::from(exponent) * i64::from(p);
+        if scaled % i64::from(q) != 0 {
+            return None;
+        }
+        let exponent = i32::try_from(scaled / i64::from(q)).unwrap_or(if scaled < 0 { i32::MIN } else { i32::MAX });
+        new_num.push(Unit::Power(Box::new(base.clone()), exponent));
+    }
+
+    Some(Unit::Compound(new_num.into(), UnitList::new()).simplify())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{c, int, ratio};
+    use crate::unit::Unit::*;
+    use crate::unit::UNITLESS;
+    use super::*;
+
+    #[test]
+    fn integer_powers() {
+        assert_eq!(Quantity::from_i64_with_unit(2, Meter).powi(3), Quantity::from_i64_with_unit(8, c!(Meter^3;)));
+        assert_eq!(Quantity::from_i64_with_unit(2, Kilo * Meter / Second).powi(-2), Quantity::new(ratio!(1, 4), c!(Second^2; Kilo^2, Meter^2)));
+        assert_eq!(Quantity::new(ratio!(-2, 3), Meter).powi(3), Quantity::new(ratio!(-8, 27), c!(Meter^3;)));
+        assert_eq!(Quantity::from_i64_with_unit(5, Meter).powi(0), Quantity::from_i64(1));
+        assert_eq!(Quantity::from_i64_with_unit(5, Meter * Second / Meter).powi(1), Quantity::from_i64_with_unit(5, Meter * Second / Meter));
+        assert_eq!(Quantity::from_i64_with_unit(3, Meter * Second / Meter).powi(2), Quantity::from_i64_with_unit(9, c!(Second^2;)));
+    }
+
+    #[test]
+    fn unit_powers() {
+        assert_eq!(Meter.pow(3), Meter * Meter * Meter);
+        assert_eq!(Second.pow(-2), UNITLESS / (Second * Second));
+        assert_eq!(Joule.pow(0), UNITLESS);
+        assert_eq!(Joule.pow(1), Joule);
+        assert_eq!((Kilo * Meter / Second).pow(-2), c!(Second^2; Kilo^2, Meter^2));
+        assert_eq!((Meter * Second / Meter).pow(2), c!(Second^2;));
+        assert_eq!(Unit::Power(Box::new(Meter), 2).pow(-1), UNITLESS / (Meter * Meter));
+
+        // The exponent is kept as a count instead of repeating the unit
+        assert_eq!(Meter.pow(i32::MAX), Unit::Power(Box::new(Meter), i32::MAX));
+        assert!(matches!(Meter.pow(i32::MAX), Unit::Power(_, i32::MAX)));
+        assert_eq!(Second.pow(i32::MIN).symbol(), "1/s^2147483647");
+        assert_eq!(Meter.pow(1 << 20).pow(1 << 20), Unit::Power(Box::new(Meter), i32::MAX));
+        assert_eq!(Quantity::from_i64_with_unit(1, Meter).powi(i32::MAX), Quantity::from_i64_with_unit(1, Unit::Power(Box::new(Meter), i32::MAX)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_to_negative_power() {
+        let _ = Quantity::from_i64_with_unit(0, Meter).powi(-1);
+    }
+
+    #[test]
+    fn rational_powers() {
+        let pow = |base: Quantity, numer: i64, denom: i64| base.pow_ratio(&ratio!(numer, denom));
+
+        assert_eq!(pow(Quantity::from_i64_with_unit(4, Meter * Meter), 1, 2), Ok(Quantity::from_i64_with_unit(2, Meter)));
+        assert_eq!(pow(Quantity::from_i64_with_unit(8, c!(Meter^3;)), 2, 3), Ok(Quantity::from_i64_with_unit(4, c!(Meter^2;))));
+        assert_eq!(pow(Quantity::new(ratio!(9, 16), c!(Meter^2; Second^4)), -1, 2), Ok(Quantity::new(ratio!(4, 3), c!(Second^2; Meter))));
+        assert_eq!(pow(Quantity::from_i64(-8), 1, 3), Ok(Quantity::from_i64(-2)));
+        assert_eq!(pow(Quantity::from_i64(0), 1, 2), Ok(Quantity::from_i64(0)));
+
+        assert_eq!(pow(Quantity::from_i64_with_unit(2, Meter), 1, 2), Err(PowError::InexactResult));
+        assert_eq!(pow(Quantity::from_i64_with_unit(2, Meter * Meter), 1, 2), Err(PowError::InexactResult));
+        assert_eq!(pow(Quantity::from_i64(-4), 1, 2), Err(PowError::NegativeBase));
+        assert_eq!(pow(Quantity::from_i64(0), -1, 1), Err(PowError::DivisionByZero));
+        assert_eq!(Quantity::from_i64(2).pow_ratio(&int!(1i64 << 40)), Err(PowError::ExponentTooLarge));
+    }
+
+    #[test]
+    fn quantity_exponents() {
+        let base = Quantity::from_i64_with_unit(2, Meter);
+        assert_eq!(base.clone().try_pow(&Quantity::from_i64(3)), Ok(Quantity::from_i64_with_unit(8, c!(Meter^3;))));
+        assert_eq!(base.clone().try_pow(&Quantity::from_i64_with_unit(2000, Meter / (Kilo * Meter))), Ok(Quantity::from_i64_with_unit(4, c!(Meter^2;))));
+        assert_eq!(base.clone().try_pow(&Quantity::from_i64_with_unit(3, Meter / (Kilo * Meter))), Err(PowError::InexactResult));
+        assert_eq!(base.clone().try_pow(&Quantity::from_i64_with_unit(3, Second)), Err(PowError::UnitExponent(Second)));
+        assert_eq!(base.try_pow(&Quantity::from_i64_with_unit(3, Kilo)), Ok(Quantity::new(num::pow(int!(2), 3000), c!(Meter^3000;))));
+        assert_eq!(PowError::UnitExponent(Second).to_string(), "the exponent has the unit s");
+    }
+
+    #[test]
+    fn approximate_powers() {
+        let two = Quantity::from_i64(2);
+        let half = Quantity::new(ratio!(1, 2), UNITLESS);
+
+        assert_eq!(two.clone().try_pow_with_precision(&half, 10), Ok(Quantity::new(ratio!(14142135623i64, 10_000_000_000i64), UNITLESS)));
+        assert_eq!(two.clone().try_pow_with_precision(&Quantity::new(ratio!(-1, 3), UNITLESS), 4), Ok(Quantity::new(ratio!(7937, 10_000), UNITLESS)));
+        assert_eq!(Quantity::from_i64(-2).try_pow_with_precision(&Quantity::new(ratio!(1, 3), UNITLESS), 3), Ok(Quantity::new(ratio!(-1259, 1000), UNITLESS)));
+        assert_eq!(Quantity::from_i64(4).try_pow_with_precision(&half, 10), Ok(Quantity::from_i64(2)));
+
+        assert_eq!(two.clone().try_pow(&half), Err(PowError::InexactResult));
+        assert_eq!(Quantity::from_i64_with_unit(2, Meter).try_pow_with_precision(&half, 10), Err(PowError::InexactResult));
+        assert_eq!(Quantity::from_i64(-2).try_pow_with_precision(&half, 10), Err(PowError::NegativeBase));
+
+        let huge_root = Quantity::new(ratio!(1, i64::from(u32::MAX)), UNITLESS);
+        assert_eq!(two.clone().try_pow_with_precision(&huge_root, 2), Err(PowError::PrecisionTooLarge));
+        assert_eq!(two.clone().try_pow_with_precision(&half, u32::MAX), Err(PowError::PrecisionTooLarge));
+        assert_eq!(two.try_pow_with_precision(&half, MAX_ROOT_DIGITS / 2 + 1), Err(PowError::PrecisionTooLarge));
+    }
+}
@@ -0,0 +1,40 @@
+//! The types and macros needed by most code using this crate.
+//!
+//! The variants of ```Unit``` are exported as well, so units can be written as ```Meter```
+//! instead of ```Unit::Meter```. None of them share a name with the other items exported here
+//! or with the standard prelude.
+//!
+//! # Example:
+//! ```
+//! use tantalum_unit::prelude::*;
+//!
+//! let speed = Quantity::new(ratio!(36, 1), Kilo * Meter / Hour);
+//! let converted = speed.convert_to(Meter / Second).unwrap();
+//!
+//! assert_eq!(converted, Quantity::from_i64_with_unit(10, c!(Meter; Second)));
+//! assert_eq!(q!(10 m/s), converted);
+//! assert!(Quantity::from_unit(UNITLESS).is_unitless());
+//! ```
+
+pub use crate::quantity::Quantity;
+pub use crate::scalable_integer::BigRational;
+pub use crate::unit::Unit;
+pub use crate::unit::Unit::*;
+pub use crate::unit::UNITLESS;
+pub use crate::{c, int, q, ratio};
+
+#[cfg(test)]
+mod tests {
+    // Fails to compile if anything the prelude exports is ambiguous
+    use crate::prelude::*;
+
+    #[test]
+    fn glob_import() {
+        let quantity: Quantity = Quantity::new(int!(2), c!(Kilo, Meter; Hour));
+        let ratio: BigRational = ratio!(1, 2);
+
+        assert_eq!(quantity * ratio, q!(1 km/h));
+        assert_eq!(Unit::Compound(Default::default(), Default::default()), UNITLESS);
+        assert_eq!(Some(Meter).map(|u| u.symbol()), Some("m".into()));
+    }
+}
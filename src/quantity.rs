@@ -1,15 +1,22 @@
 //! An arbitrary precision value with a ```Unit```.
 
-use std::fmt::{Display, Formatter};
-use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
-use num::{FromPrimitive, One, ToPrimitive};
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt::{Debug, Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
+use num::{Num, One, ToPrimitive, Zero};
+use indexmap::IndexMap;
 use num::traits::Inv;
-use crate::{one};
-use crate::scalable_integer::BigRational;
+use crate::{one, zero};
+use crate::error::{ArithmeticError, ConversionError, PrefixError};
+use crate::policy::AddPolicy;
+use crate::rounding::to_significant_string;
+use crate::scalable_integer::{BigRational, ScalableInteger};
 use crate::unit::{Unit, UNITLESS};
 
 /// An arbitrary precision value with a ```Unit```.
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct Quantity {
     pub magnitude: BigRational,
     pub unit: Unit,
@@ -37,20 +44,6 @@ impl Quantity {
         }
     }
 
-    pub fn from_f64(value: f64) -> Self {
-        let ratio = num::BigRational::from_f64(value).unwrap();
-        let ratio = BigRational::new_raw(ratio.numer().clone().into(),
-                                         ratio.denom().clone().into());
-        Self::from_rational(ratio)
-    }
-
-    pub fn from_f64_with_unit(value: f64, unit: Unit) -> Self {
-        let ratio = num::BigRational::from_f64(value).unwrap();
-        let ratio = BigRational::new_raw(ratio.numer().clone().into(),
-                                         ratio.denom().clone().into());
-        Self::from_rational_with_unit(ratio, unit)
-    }
-
     pub fn from_i64(value: i64) -> Self {
         let ratio = BigRational::from_integer(value.into());
         Self::from_rational(ratio)
@@ -61,6 +54,25 @@ impl Quantity {
         Self::from_rational_with_unit(ratio, unit)
     }
 
+    /// Constructs a Quantity with an integer magnitude, usable in ```const``` and ```static``` items.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    /// use tantalum_unit::unit::KILOMETER_PER_HOUR;
+    ///
+    /// const MAX_SPEED: Quantity = Quantity::const_from_i64(130, KILOMETER_PER_HOUR);
+    /// static TANK_SIZE: Quantity = Quantity::const_from_i64(50, Unit::Liter);
+    ///
+    /// assert_eq!(MAX_SPEED, Quantity::from_i64_with_unit(130, KILOMETER_PER_HOUR));
+    /// ```
+    pub const fn const_from_i64(value: i64, unit: Unit) -> Self {
+        Self {
+            magnitude: BigRational::new_raw(ScalableInteger::const_from_i64(value), ScalableInteger::const_from_i64(1)),
+            unit,
+        }
+    }
+
     /// Constructs a Quantity with a magnitude of 1 and the given unit.
     pub fn from_unit(unit: Unit) -> Self {
         Self {
@@ -73,11 +85,7 @@ impl Quantity {
     ///
     /// # Example:
     /// ```
-    /// # use tantalum_unit::quantity::Quantity;
-    /// # use tantalum_unit::ratio;
-    /// # use tantalum_unit::unit::Unit;
-    /// use tantalum_unit::scalable_integer::BigRational;
-    /// use tantalum_unit::unit::Unit::*;
+    /// use tantalum_unit::prelude::*;
     ///
     /// let temperature = Quantity::from_i64_with_unit(0, Celsius); // 0°C
     /// let si_temperature = temperature.to_si_units(); // 273.15K
@@ -93,14 +101,116 @@ impl Quantity {
         }
     }
 
+    /// The magnitude of the Quantity in SI base units, like ```to_si_units().magnitude``` but without
+    /// consuming the Quantity or building a new one.
+    ///
+    /// The base unit of mass is the gram, so ```1 kg``` has an SI magnitude of ```1000```. Derived
+    /// units like ```Joule``` are defined in kilograms though, so ```1 J``` has an SI magnitude of
+    /// ```1``` in ```kg*m^2/s^2```.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// assert_eq!(Quantity::from_i64_with_unit(0, Celsius).si_magnitude(), ratio!(5463, 20));
+    /// assert_eq!(Quantity::from_i64_with_unit(2, Kilo * Gram).si_magnitude(), int!(2000));
+    /// assert_eq!(Quantity::from_i64_with_unit(2, Joule).si_magnitude(), int!(2));
+    /// ```
+    pub fn si_magnitude(&self) -> BigRational {
+        let (offset, slope, _) = self.unit.clone().to_si_units();
+        (&self.magnitude + offset) * slope
+    }
+
+    /// Whether both quantities are the same amount, regardless of the units they are written in,
+    /// like ```1 km``` and ```1000 m```.
+    ///
+    /// Both magnitudes are converted into SI base units, including offsets, so ```0 °C``` is
+    /// equivalent to ```273.15 K```. Quantities of different dimensions are never equivalent.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// assert!(q!(1 km).equivalent_to(&q!(1000 m)));
+    /// assert!(Quantity::from_i64_with_unit(0, Celsius).equivalent_to(&q!(273.15 K)));
+    /// assert!(!q!(1 km).equivalent_to(&q!(1000 s)));
+    /// ```
+    pub fn equivalent_to(&self, other: &Quantity) -> bool {
+        self.unit.has_same_dimension(&other.unit) && self.base_magnitude() == other.base_magnitude()
+    }
+
+    /// Checks if the units of both quantities are compatible, see ```Unit::is_compatible_with```.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let energy = Quantity::from_i64_with_unit(2, Kilo * Watt * Hour);
+    /// assert!(energy.is_compatible_with(&Quantity::from_i64_with_unit(5, Joule)));
+    /// assert!(!energy.is_compatible_with(&Quantity::from_i64_with_unit(5, Watt)));
+    /// ```
+    pub fn is_compatible_with(&self, other: &Quantity) -> bool {
+        self.unit.is_compatible_with(&other.unit)
+    }
+
+    /// Compares two quantities of the same dimension by amount, regardless of their units.
+    ///
+    /// This is what ```<``` and ```>``` compare, but returns an error if the dimensions differ.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    /// use std::cmp::Ordering;
+    ///
+    /// assert_eq!(q!(1 gal).cmp_converted(&q!(3 L)), Ok(Ordering::Greater));
+    /// assert!(q!(1 gal).cmp_converted(&q!(3 m)).is_err());
+    /// ```
+    pub fn cmp_converted(&self, other: &Quantity) -> Result<Ordering, ConversionError> {
+        if !self.unit.has_same_dimension(&other.unit) {
+            return Err(ConversionError::IncompatibleUnits { from: other.unit.clone(), to: self.unit.clone() });
+        }
+        Ok(self.base_magnitude().cmp(&other.base_magnitude()))
+    }
+
+    /// The magnitude in SI base units without prefixes, which orders quantities of a dimension.
+    fn base_magnitude(&self) -> BigRational {
+        let (offset, slope) = self.unit.si_affine();
+        (&self.magnitude + offset) * slope
+    }
+
+    /// A string identifying the quantity independently of the unit it is written in, for grouping
+    /// and deduplicating quantities.
+    ///
+    /// Quantities that are ```equivalent_to``` each other have the same key. The key is the
+    /// magnitude in SI base units without prefixes, written as a reduced fraction
+    /// ```numerator/denominator``` or as an integer if the denominator is one, followed by a
+    /// space and ```Unit::canonical_key```. Unitless quantities are just the number. The format
+    /// is stable across versions.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let length = Quantity::from_i64_with_unit(1, Kilo * Meter);
+    /// assert_eq!(length.canonical_key(), "1000 m^1");
+    /// assert_eq!(Quantity::from_i64_with_unit(1000, Meter).canonical_key(), length.canonical_key());
+    /// assert_eq!(Quantity::new(ratio!(1, 2), Joule).canonical_key(), "500 g^1·m^2·s^-2");
+    /// ```
+    pub fn canonical_key(&self) -> String {
+        let magnitude = self.base_magnitude();
+        let unit = self.unit.canonical_key();
+        if unit.is_empty() {
+            magnitude.to_string()
+        } else {
+            format!("{magnitude} {unit}")
+        }
+    }
+
     /// Removes all SI and binary modifiers from the unit and applies them to the magnitude.
     ///
     /// # Example:
     /// ```
-    /// # use tantalum_unit::c;
-    /// # use tantalum_unit::quantity::Quantity;
-    /// # use tantalum_unit::unit::Unit;
-    /// use tantalum_unit::unit::Unit::*;
+    /// use tantalum_unit::prelude::*;
     ///
     /// let length = Quantity::from_i64_with_unit(5, Kilo * Meter); // 5km
     /// let new_length = length.apply_modifiers();
@@ -108,36 +218,39 @@ impl Quantity {
     /// assert_eq!(new_length, Quantity::from_i64_with_unit(5_000, Meter)); // 5000m
     /// ```
     pub fn apply_modifiers(self) -> Self {
-        let (numerator, denominator) = self.unit.flatten().to_fraction();
-
-        let mut new_magnitude = self.magnitude;
-        let mut new_num = vec![];
-        let mut new_denom = vec![];
-
-        for unit in numerator {
-            if unit.is_modifier() {
-                let (_, slope, _) = unit.to_si_units();
-                new_magnitude *= slope;
-            } else {
-                new_num.push(unit);
-            }
-        }
-
-        for unit in denominator {
-            if unit.is_modifier() {
-                let (_, slope, _) = unit.to_si_units();
-                new_magnitude /= slope;
-            } else {
-                new_denom.push(unit);
-            }
-        }
-
+        let (factor, unit) = self.unit.strip_prefixes();
         Self {
-            magnitude: new_magnitude,
-            unit: Unit::Compound(new_num, new_denom).simplify(),
+            magnitude: self.magnitude * factor,
+            unit,
         }
     }
 
+    /// Cancels the units of the Quantity against each other, expanding derived units like
+    /// ```Hertz```, units with a scale like ```Hour``` and modifiers into SI units if that lets
+    /// components cancel, and rescales the magnitude accordingly.
+    ///
+    /// Unlike ```Unit::normalize``` the scale of the unit may change, e.g. ```Hertz * Hour```
+    /// becomes a unitless 3600. A result with the dimension of a named derived unit is rewritten to
+    /// it like in ```with_named_unit```. If nothing cancels, the Quantity is returned unchanged.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let cycles = Quantity::from_i64_with_unit(5, Hertz * Hour);
+    /// assert_eq!(cycles.simplify_units(), Quantity::from_i64_with_unit(18_000, UNITLESS));
+    ///
+    /// let area = Quantity::from_i64_with_unit(3, Newton / Pascal);
+    /// assert_eq!(area.simplify_units(), Quantity::from_i64_with_unit(3, Meter * Meter));
+    /// ```
+    pub fn simplify_units(self) -> Self {
+        let Some((slope, unit)) = self.unit.expand_and_cancel(|_| true) else {
+            return self;
+        };
+        let (factor, unit) = unit.strip_prefixes();
+        Quantity::new(self.magnitude * slope * factor, unit).bounded().with_named_unit()
+    }
+
     pub fn normalize_modifier(mut self) -> Self {
         use Unit::*;
 
@@ -160,59 +273,567 @@ impl Quantity {
         }
     }
 
+    /// Re-expresses the Quantity with an explicit SI or binary prefix on its unit.
+    ///
+    /// Existing prefixes are applied to the magnitude first, so ```1500 m``` and ```3/2 km``` both
+    /// become ```3/2 km``` with ```Kilo```. Returns an error if ```prefix``` isn't a modifier or if
+    /// the unit consists of more than one unit apart from its prefixes, like ```m/s```.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let length = Quantity::from_i64_with_unit(1500, Meter);
+    /// assert_eq!(length.with_prefix(Kilo), Ok(Quantity::new(ratio!(3, 2), Kilo * Meter)));
+    ///
+    /// let time = Quantity::new(ratio!(1, 500), Second);
+    /// assert_eq!(time.with_prefix(Milli), Ok(Quantity::from_i64_with_unit(2, Milli * Second)));
+    /// ```
+    pub fn with_prefix(self, prefix: Unit) -> Result<Self, PrefixError> {
+        if !prefix.is_modifier() {
+            return Err(PrefixError::NotAPrefix(prefix));
+        }
+
+        let unit = self.unit.clone();
+        let stripped = self.apply_modifiers();
+        if matches!(stripped.unit, Unit::Compound(..) | Unit::Power(..)) {
+            return Err(PrefixError::CompoundUnit(unit));
+        }
+
+        let (_, slope, _) = prefix.clone().to_si_units();
+        Ok(Self {
+            magnitude: stripped.magnitude / slope,
+            unit: prefix * stripped.unit,
+        })
+    }
+
+    /// Re-expresses the Quantity in the named derived unit of its dimension, like ```Watt``` for
+    /// ```kg*m^2/s^3```, see ```Unit::to_named```.
+    ///
+    /// Unlike ```Unit::to_named``` this also rewrites units that differ from the named unit by a
+    /// factor, which is folded into the magnitude. Quantities without a named unit are returned
+    /// unchanged.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let force = Quantity::from_i64_with_unit(3, Gram * Meter / (Second * Second));
+    /// assert_eq!(force.with_named_unit(), Quantity::new(ratio!(3, 1000), Newton));
+    /// ```
+    pub fn with_named_unit(self) -> Self {
+        let Some(named) = self.unit.named_equivalent() else {
+            return self;
+        };
+        // Like conversion_table, as the SI units of kg*m/s^2 and N differ in where the kilo is
+        let (offset, slope) = self.unit.si_affine();
+        let (offset_to, slope_to) = named.si_affine();
+        let magnitude = (self.magnitude + offset) * slope / slope_to - offset_to;
+        Quantity::new(magnitude, named).bounded()
+    }
+
     fn is_in_normalized_range(value: BigRational) -> bool {
         value >= one!() && value < BigRational::from_integer(1_000.into())
     }
 
     /// Converts the Quantity to an arbitrary Unit. Returns an Error if that is not possible.
     ///
+    /// Offsets only apply to a lone affine unit like ```Celsius```, while compound units like
+    /// ```Joule / Celsius``` convert by their slope. Temperatures in compound units like
+    /// ```Kilo * Celsius``` are rejected with ```ConversionError::AmbiguousOffset```.
+    ///
+    /// Custom units of a ```UnitRegistry``` aren't supported yet, see the ```registry``` module.
+    ///
     /// # Example:
     /// ```
-    /// # use tantalum_unit::c;
-    /// # use tantalum_unit::quantity::Quantity;
-    /// # use tantalum_unit::unit::Unit;
-    /// use tantalum_unit::unit::Unit::*;
+    /// use tantalum_unit::prelude::*;
     ///
     /// let joule_per_second = Quantity::from_i64_with_unit(3000, Joule / Second);
     /// let kilo_watt = joule_per_second.convert_to(Kilo * Watt).unwrap();
     ///
     /// assert_eq!(kilo_watt, Quantity::from_i64_with_unit(3, Kilo * Watt));
     /// ```
-    pub fn convert_to(self, to: Unit) -> Result<Self, ()> {
-        let (offset, slope, unit) = self.unit.to_si_units();
-        let (offset_to, slope_to, mut unit_to) = to.clone().to_si_units();
-
-        let mut take_reciprocal = false;
-        if unit != unit_to {
-            // Try the reciprocal
-            unit_to = UNITLESS / unit_to;
-            if unit != unit_to {
-                return Err(());
-            } else {
-                take_reciprocal = true;
+    pub fn convert_to(self, to: Unit) -> Result<Self, ConversionError> {
+        // The dimensions are cached, so incompatible units are rejected before any slope is computed
+        let take_reciprocal = Self::check_convertible(&self.unit, &to)?;
+        let source = self.unit.si_affine();
+        Ok(Self::convert_from_si(self.magnitude, &source, to, take_reciprocal))
+    }
+
+    /// Converts the Quantity to each of the targets, decomposing its own unit into SI units only once.
+    ///
+    /// The results are exactly those of calling ```convert_to``` for every target.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let power = Quantity::from_i64_with_unit(3000, Joule / Second);
+    /// let converted = power.convert_to_many(&[Kilo * Watt, Milli * Watt, Meter]);
+    ///
+    /// assert_eq!(converted[0], Ok(Quantity::from_i64_with_unit(3, Kilo * Watt)));
+    /// assert_eq!(converted[1], Ok(Quantity::from_i64_with_unit(3_000_000, Milli * Watt)));
+    /// assert!(converted[2].is_err());
+    /// ```
+    pub fn convert_to_many(&self, targets: &[Unit]) -> Vec<Result<Self, ConversionError>> {
+        let source = self.unit.si_affine();
+        targets.iter()
+            .map(|to| {
+                let take_reciprocal = Self::check_convertible(&self.unit, to)?;
+                Ok(Self::convert_from_si(self.magnitude.clone(), &source, to.clone(), take_reciprocal))
+            })
+            .collect()
+    }
+
+    /// Sums quantities of the same dimension into a single Quantity in ```unit```.
+    ///
+    /// The result equals converting every quantity to ```unit``` and adding the magnitudes, but the
+    /// magnitudes are summed per source unit first, so every distinct unit is decomposed and
+    /// converted only once. An empty iterator sums to zero in ```unit```. Returns an error for the
+    /// first unit that doesn't measure the dimension of ```unit```.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let volumes = [q!(1 L), q!(1 gal), q!(500 mL)];
+    /// let total = Quantity::sum_in(&(Milli * Liter), &volumes).unwrap();
+    ///
+    /// assert_eq!(total, Quantity::new(ratio!(660676473, 125000), Milli * Liter));
+    /// ```
+    pub fn sum_in<I>(unit: &Unit, iter: I) -> Result<Quantity, ConversionError>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Quantity>,
+    {
+        let mut sums: IndexMap<Unit, (BigRational, u64)> = IndexMap::new();
+        for quantity in iter {
+            let quantity = quantity.borrow();
+            match sums.get_mut(&quantity.unit) {
+                Some((sum, count)) => {
+                    *sum += &quantity.magnitude;
+                    *count += 1;
+                }
+                None => {
+                    if !quantity.unit.has_same_dimension(unit) {
+                        return Err(ConversionError::IncompatibleUnits { from: quantity.unit.clone(), to: unit.clone() });
+                    }
+                    sums.insert(quantity.unit.clone(), (quantity.magnitude.clone(), 1));
+                }
             }
         }
 
-        let mut new_magnitude = self.magnitude;
-        new_magnitude += offset;
-        new_magnitude *= slope;
-        new_magnitude /= slope_to;
-        new_magnitude -= offset_to;
-        if take_reciprocal { new_magnitude = new_magnitude.inv(); }
+        let (offset_to, slope_to) = unit.si_affine();
+        let mut si_sum = zero!();
+        let mut count = 0;
+        for (source, (sum, n)) in sums {
+            let (offset, slope) = source.si_affine();
+            si_sum += (sum + offset * BigRational::from_integer(n.into())) * slope;
+            count += n;
+        }
+        let magnitude = si_sum / slope_to - offset_to * BigRational::from_integer(count.into());
+        Ok(Quantity::new(magnitude, unit.clone()))
+    }
 
-        Ok(Self {
+    /// The Quantity expressed in every named unit of its dimension, in declaration order.
+    ///
+    /// Modifiers are left out. The conversions go through SI base units, so like ```convert_to```
+    /// they succeed however the unit of the Quantity is composed, e.g. from ```kg*m^2/s^2``` to
+    /// ```Joule```. A Quantity whose dimension has no named units yields an empty table.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let table = Quantity::from_i64_with_unit(90, Minute).conversion_table();
+    ///
+    /// assert_eq!(table[0], (Second, Quantity::from_i64_with_unit(5400, Second)));
+    /// assert_eq!(table[2], (Hour, Quantity::new(ratio!(3, 2), Hour)));
+    /// ```
+    pub fn conversion_table(&self) -> Vec<(Unit, Quantity)> {
+        let (offset, slope) = self.unit.si_affine();
+        let si_magnitude = (&self.magnitude + offset) * slope;
+        self.unit.units_for_dimension().into_iter()
+            .map(|unit| {
+                let (offset_to, slope_to) = unit.si_affine();
+                let magnitude = &si_magnitude / slope_to - offset_to;
+                (unit.clone(), Quantity::new(magnitude, unit))
+            })
+            .collect()
+    }
+
+    /// Checks that a magnitude in ```from``` can be converted to ```to```, returning whether the
+    /// conversion has to take the reciprocal, like from ```Ohm``` to ```Siemens```.
+    fn check_convertible(from: &Unit, to: &Unit) -> Result<bool, ConversionError> {
+        let take_reciprocal = if from.has_same_dimension(to) {
+            false
+        } else if from.has_reciprocal_dimension(to) {
+            true
+        } else {
+            return Err(ConversionError::IncompatibleUnits { from: from.clone(), to: to.clone() });
+        };
+        if from.has_ambiguous_offset() || to.has_ambiguous_offset() {
+            return Err(ConversionError::AmbiguousOffset { from: from.clone(), to: to.clone() });
+        }
+        Ok(take_reciprocal)
+    }
+
+    /// Converts a magnitude whose unit has the offset and slope ```source```, as returned by
+    /// ```Unit::si_affine```, to the unit ```to```.
+    ///
+    /// Comparing dimensions instead of the SI units themselves means the conversion doesn't depend
+    /// on how the units are composed, so ```kg*m^2/s^2``` converts to ```Joule```.
+    fn convert_from_si(magnitude: BigRational, source: &(BigRational, BigRational), to: Unit, take_reciprocal: bool) -> Self {
+        let (offset, slope) = source;
+        let (offset_to, slope_to) = to.si_affine();
+
+        let mut si_magnitude = magnitude;
+        si_magnitude += offset;
+        si_magnitude *= slope;
+        if take_reciprocal { si_magnitude = si_magnitude.inv(); }
+
+        let mut new_magnitude = si_magnitude / slope_to;
+        new_magnitude -= offset_to;
+        Self {
             unit: to,
             magnitude: new_magnitude,
-        })
+        }.bounded()
     }
 
     pub fn is_unitless(&self) -> bool {
         self.unit.is_unitless()
     }
 
+    /// Whether the Quantity is zero without a unit, which is the identity of ```Add``` for every unit.
+    pub(crate) fn is_unitless_zero(&self) -> bool {
+        self.magnitude.is_zero() && self.is_unitless()
+    }
+
     pub fn display_exact(&self) -> String {
         format!("{}{}", self.magnitude, self.unit)
     }
+
+    /// Adds two quantities like ```Add```, but returns an error instead of panicking if an
+    /// operand can't be converted to the unit of the result.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    /// use tantalum_unit::error::ConversionError;
+    ///
+    /// assert_eq!(q!(1 km).try_add(q!(500 m)), Ok(q!(1.5 km)));
+    /// assert_eq!(q!(1 km).try_add(q!(1 s)), Err(ConversionError::IncompatibleUnits { from: Second, to: Kilo * Meter }));
+    /// ```
+    pub fn try_add(self, rhs: Self) -> Result<Self, ConversionError> {
+        if rhs.is_unitless_zero() {
+            return Ok(self);
+        }
+        if self.is_unitless_zero() {
+            return Ok(rhs);
+        }
+
+        let (left, right, unit) = self.operands_in(rhs, AddPolicy::current_default())?;
+        Ok(Self {
+            magnitude: left + right,
+            unit,
+        }.bounded())
+    }
+
+    /// Subtracts two quantities like ```Sub```, but returns an error instead of panicking if an
+    /// operand can't be converted to the unit of the result.
+    pub fn try_sub(self, rhs: Self) -> Result<Self, ConversionError> {
+        if rhs.is_unitless_zero() {
+            return Ok(self);
+        }
+        if self.is_unitless_zero() {
+            return Ok(-rhs);
+        }
+
+        let (left, right, unit) = self.operands_in(rhs, AddPolicy::current_default())?;
+        Ok(Self {
+            magnitude: left - right,
+            unit,
+        }.bounded())
+    }
+
+    /// Adds two quantities like ```Add```, but without converting ```rhs``` implicitly.
+    ///
+    /// The units must be equal up to their order and cancelling, so ```N*m``` and ```m*N``` may
+    /// be added, while ```km``` and ```m``` fail with ```ArithmeticError::ImplicitConversion```.
+    /// A unitless zero is still the identity for every unit.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    /// use tantalum_unit::error::ArithmeticError;
+    ///
+    /// assert_eq!(q!(1 L).try_add_strict(q!(2 L)), Ok(q!(3 L)));
+    /// assert_eq!(q!(1 L).try_add_strict(q!(2 mL)), Err(ArithmeticError::ImplicitConversion {
+    ///     left: Liter,
+    ///     right: Milli * Liter,
+    /// }));
+    /// ```
+    pub fn try_add_strict(self, rhs: Self) -> Result<Self, ArithmeticError> {
+        if self.is_unitless_zero() || rhs.is_unitless_zero() {
+            return Ok(self + rhs);
+        }
+        self.check_strict(&rhs)?;
+        Ok(Self {
+            magnitude: self.magnitude + rhs.magnitude,
+            unit: self.unit,
+        })
+    }
+
+    /// Subtracts two quantities like ```Sub```, but without converting ```rhs``` implicitly.
+    ///
+    /// The units must be equal like in ```try_add_strict```.
+    pub fn try_sub_strict(self, rhs: Self) -> Result<Self, ArithmeticError> {
+        if self.is_unitless_zero() || rhs.is_unitless_zero() {
+            return Ok(self - rhs);
+        }
+        self.check_strict(&rhs)?;
+        Ok(Self {
+            magnitude: self.magnitude - rhs.magnitude,
+            unit: self.unit,
+        })
+    }
+
+    /// Adds two quantities like ```try_add```, returning ```None``` if an operand can't be
+    /// converted to the unit of the result.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.try_add(rhs).ok()
+    }
+
+    /// Subtracts two quantities like ```try_sub```, returning ```None``` if an operand can't be
+    /// converted to the unit of the result.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.try_sub(rhs).ok()
+    }
+
+    /// Multiplies two quantities like ```Mul```. Multiplication can't fail, so this always returns
+    /// ```Some```, it only exists to complete the checked operations.
+    pub fn checked_mul(self, rhs: Self) -> Option<Self> {
+        Some(self * rhs)
+    }
+
+    /// Divides two quantities like ```Div```, but returns ```None``` instead of panicking if the
+    /// magnitude of ```rhs``` is zero, whatever its unit.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// assert_eq!(q!(6 m).checked_div(q!(2 s)), Some(q!(3 m/s)));
+    /// assert_eq!(q!(6 m).checked_div(q!(0 s)), None);
+    /// ```
+    pub fn checked_div(self, rhs: Self) -> Option<Self> {
+        if rhs.magnitude.is_zero() {
+            return None;
+        }
+        Some(self / rhs)
+    }
+
+    /// The Quantity with the absolute value of its magnitude.
+    pub fn abs(&self) -> Self {
+        if self.magnitude < BigRational::zero() {
+            -self.clone()
+        } else {
+            self.clone()
+        }
+    }
+
+    /// The absolute difference ```|self - other|``` in the unit of ```self```.
+    ///
+    /// Returns an error if ```other``` can't be converted to the unit of ```self```.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let measured = Quantity::from_i64_with_unit(1, Meter);
+    /// let expected = Quantity::from_i64_with_unit(3, Feet);
+    /// assert_eq!(measured.abs_diff(&expected), Ok(Quantity::new(ratio!(107, 1250), Meter)));
+    /// ```
+    pub fn abs_diff(&self, other: &Quantity) -> Result<Quantity, ConversionError> {
+        let other = other.clone()
+            .convert_to(self.unit.clone())?;
+        Ok(Quantity::new(&self.magnitude - other.magnitude, self.unit.clone()).abs())
+    }
+
+    /// The relative difference ```|self - other| / |self|``` as an exact unitless number.
+    ///
+    /// Returns an error if ```other``` can't be converted to the unit of ```self``` or if ```self```
+    /// is zero.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let expected = Quantity::from_i64_with_unit(200, Meter);
+    /// let measured = Quantity::from_i64_with_unit(190, Meter);
+    /// assert_eq!(expected.relative_diff(&measured), Ok(ratio!(1, 20)));
+    /// ```
+    pub fn relative_diff(&self, other: &Quantity) -> Result<BigRational, ConversionError> {
+        let difference = self.abs_diff(other)?;
+        if self.magnitude.is_zero() {
+            return Err(ConversionError::ZeroReference);
+        }
+        Ok(difference.magnitude / self.abs().magnitude)
+    }
+
+    /// The share of ```whole``` the Quantity makes up, in ```Percent```.
+    ///
+    /// Returns an error if ```whole``` can't be converted to the unit of the Quantity or is zero.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let part = Quantity::from_i64_with_unit(500, Milli * Liter);
+    /// let whole = Quantity::from_i64_with_unit(2, Liter);
+    /// assert_eq!(part.percent_of(&whole), Ok(Quantity::from_i64_with_unit(25, Percent)));
+    /// ```
+    pub fn percent_of(&self, whole: &Quantity) -> Result<Quantity, ConversionError> {
+        let whole = whole.clone()
+            .convert_to(self.unit.clone())?;
+        if whole.magnitude.is_zero() {
+            return Err(ConversionError::ZeroReference);
+        }
+        Ok(Quantity::new(&self.magnitude / whole.magnitude * BigRational::from_integer(100.into()), Unit::Percent))
+    }
+
+    /// The signed change from ```from``` to the Quantity relative to ```|from|```, in ```Percent```.
+    ///
+    /// Returns an error if the Quantity can't be converted to the unit of ```from``` or if
+    /// ```from``` is zero.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let before = Quantity::from_i64_with_unit(80, Kilo * Gram);
+    /// let after = Quantity::from_i64_with_unit(76, Kilo * Gram);
+    /// assert_eq!(after.percent_change(&before), Ok(Quantity::from_i64_with_unit(-5, Percent)));
+    /// ```
+    pub fn percent_change(&self, from: &Quantity) -> Result<Quantity, ConversionError> {
+        let to = self.clone()
+            .convert_to(from.unit.clone())?;
+        if from.magnitude.is_zero() {
+            return Err(ConversionError::ZeroReference);
+        }
+        let change = (to.magnitude - &from.magnitude) / from.abs().magnitude;
+        Ok(Quantity::new(change * BigRational::from_integer(100.into()), Unit::Percent))
+    }
+
+    /// The bit rate of a symbol rate in ```Baud``` when every symbol carries ```bits_per_symbol```
+    /// bits, in ```Bit / Second```.
+    ///
+    /// Returns an error if the Quantity can't be converted to ```Baud```.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// // 16-QAM carries 4 bits per symbol
+    /// let rate = Quantity::from_i64_with_unit(2400, Baud);
+    /// assert_eq!(rate.baud_to_bitrate(4), Ok(Quantity::from_i64_with_unit(9600, Bit / Second)));
+    /// ```
+    pub fn baud_to_bitrate(&self, bits_per_symbol: u32) -> Result<Quantity, ConversionError> {
+        let symbols = self.clone()
+            .convert_to(Unit::Baud)?;
+        Ok(Quantity::new(symbols.magnitude * BigRational::from_integer(bits_per_symbol.into()), Unit::Bit / Unit::Second))
+    }
+
+    /// Adds two quantities in the common unit of their units, see ```Unit::common_unit```.
+    ///
+    /// Unlike ```+```, which keeps the unit of the left operand, the result doesn't depend on the
+    /// order of the operands. Returns an error if the units measure different dimensions.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let a = Quantity::from_i64_with_unit(3, Mile);
+    /// let b = Quantity::from_i64_with_unit(1, Milli * Meter);
+    /// let sum = Quantity::add_in_common(&a, &b).unwrap();
+    /// assert_eq!(sum, Quantity::from_i64_with_unit(4828033, Milli * Meter));
+    /// ```
+    pub fn add_in_common(a: &Quantity, b: &Quantity) -> Result<Quantity, ConversionError> {
+        let unit = a.unit.common_unit(&b.unit)?;
+        let convert = |quantity: &Quantity| quantity.clone()
+            .convert_to(unit.clone());
+        Ok(Quantity::new(convert(a)?.magnitude + convert(b)?.magnitude, unit.clone()))
+    }
+
+    /// The physical length of a Quantity of ```Pixel``` at the density ```dpi```, in ```Inch```.
+    ///
+    /// The density may be in any unit convertible to ```Pixel / Inch```, like ```DotsPerInch```.
+    /// Returns an error if the Quantity isn't a number of pixels, if the density isn't a density
+    /// or if it's zero.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let width = Quantity::from_i64_with_unit(1920, Pixel);
+    /// let dpi = Quantity::from_i64_with_unit(96, DotsPerInch);
+    /// assert_eq!(width.px_to_length(&dpi), Ok(Quantity::from_i64_with_unit(20, Inch)));
+    /// ```
+    pub fn px_to_length(&self, dpi: &Quantity) -> Result<Quantity, ConversionError> {
+        let pixels = self.clone()
+            .convert_to(Unit::Pixel)?;
+        let density = dpi.pixel_density()?;
+        Ok(Quantity::new(pixels.magnitude / density, Unit::Inch))
+    }
+
+    /// The number of pixels a length spans at the density ```dpi```, the inverse of
+    /// ```px_to_length```.
+    ///
+    /// Returns an error if the Quantity isn't a length, if the density isn't a density or if
+    /// it's zero.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let width = Quantity::from_i64_with_unit(2, Inch);
+    /// let dpi = Quantity::from_i64_with_unit(300, DotsPerInch);
+    /// assert_eq!(width.length_to_px(&dpi), Ok(Quantity::from_i64_with_unit(600, Pixel)));
+    /// ```
+    pub fn length_to_px(&self, dpi: &Quantity) -> Result<Quantity, ConversionError> {
+        let length = self.clone()
+            .convert_to(Unit::Inch)?;
+        let density = dpi.pixel_density()?;
+        Ok(Quantity::new(length.magnitude * density, Unit::Pixel))
+    }
+
+    /// The magnitude of a density in ```Pixel / Inch```, which must not be zero.
+    fn pixel_density(&self) -> Result<BigRational, ConversionError> {
+        let per_inch = Unit::Pixel / Unit::Inch;
+        let density = self.clone()
+            .convert_to(per_inch.clone())?;
+        if density.magnitude.is_zero() {
+            return Err(ConversionError::ZeroReference);
+        }
+        Ok(density.magnitude)
+    }
+
+    /// Checks that ```rhs``` can be added to ```self``` without a conversion.
+    ///
+    /// Units that are equal once flattened and simplified differ only in their order, so their
+    /// magnitudes can be combined directly. Symbols aren't compared, since a modifier can share
+    /// its symbol with a unit, like ```T``` for ```Tera``` and ```Tesla```.
+    fn check_strict(&self, rhs: &Self) -> Result<(), ArithmeticError> {
+        if self.unit.clone().flatten().simplify() == rhs.unit.clone().flatten().simplify() {
+            return Ok(());
+        }
+        let (left, right) = (self.unit.clone(), rhs.unit.clone());
+        if self.unit.has_same_dimension(&rhs.unit) || self.unit.has_reciprocal_dimension(&rhs.unit) {
+            Err(ArithmeticError::ImplicitConversion { left, right })
+        } else {
+            Err(ArithmeticError::IncompatibleUnits { left, right })
+        }
+    }
 }
 
 impl Display for Quantity {
@@ -224,6 +845,21 @@ impl Display for Quantity {
     }
 }
 
+impl Debug for Quantity {
+    /// Shows the exact magnitude, an approximate decimal and the unit symbol.
+    ///
+    /// E.g. ```Quantity(4040113137766/473176473 ≈ 8538.28 gal)```
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let unit = if self.is_unitless() { "".to_owned() } else { format!(" {}", self.unit) };
+        if self.magnitude.is_integer() {
+            write!(f, "Quantity({}{})", self.magnitude, unit)
+        } else {
+            let approximation = to_significant_string(&self.magnitude, 6);
+            write!(f, "Quantity({} ≈ {}{})", self.magnitude, approximation, unit)
+        }
+    }
+}
+
 impl Mul for Quantity {
     type Output = Quantity;
 
@@ -231,135 +867,333 @@ impl Mul for Quantity {
         Quantity {
             magnitude: self.magnitude * rhs.magnitude,
             unit: self.unit * rhs.unit,
+        }.bounded()
+    }
+}
+
+impl MulAssign for Quantity {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = self.clone() * rhs;
+    }
+}
+
+impl Mul<BigRational> for Quantity {
+    type Output = Quantity;
+
+    fn mul(self, rhs: BigRational) -> Self::Output {
+        Self {
+            unit: self.unit,
+            magnitude: self.magnitude * rhs,
         }
     }
 }
 
-impl MulAssign for Quantity {
-    fn mul_assign(&mut self, rhs: Self) {
-        *self = self.clone() * rhs;
+impl MulAssign<BigRational> for Quantity {
+    fn mul_assign(&mut self, rhs: BigRational) {
+        *self = self.clone() * rhs;
+    }
+}
+
+impl Div for Quantity {
+    type Output = Quantity;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        Quantity {
+            magnitude: self.magnitude / rhs.magnitude,
+            unit: self.unit / rhs.unit,
+        }.bounded()
+    }
+}
+
+impl DivAssign for Quantity {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = self.clone() / rhs;
+    }
+}
+
+impl Div<BigRational> for Quantity {
+    type Output = Quantity;
+
+    fn div(self, rhs: BigRational) -> Self::Output {
+        Self {
+            unit: self.unit,
+            magnitude: self.magnitude / rhs,
+        }
+    }
+}
+
+impl DivAssign<BigRational> for Quantity {
+    fn div_assign(&mut self, rhs: BigRational) { *self = self.clone() / rhs; }
+}
+
+impl Neg for Quantity {
+    type Output = Quantity;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            magnitude: -self.magnitude,
+            unit: self.unit,
+        }
+    }
+}
+
+impl Add for Quantity {
+    type Output = Quantity;
+
+    /// Adds two quantities, converting them to the unit chosen by ```AddPolicy::current_default```,
+    /// which is the unit of ```self``` unless changed.
+    ///
+    /// A unitless zero, like ```Quantity::zero()```, is the identity for every unit, so summing
+    /// quantities starting from zero works. Panics if an operand can't be converted.
+    fn add(self, rhs: Self) -> Self::Output {
+        self.try_add(rhs).unwrap_or_else(|error| panic_on(error))
+    }
+}
+
+impl AddAssign for Quantity {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.clone() + rhs;
+    }
+}
+
+impl Sub for Quantity {
+    type Output = Quantity;
+
+    /// Subtracts two quantities, converting them to the unit chosen by ```AddPolicy::current_default```.
+    ///
+    /// Like in ```add```, a unitless zero is the identity for every unit. Panics if an operand can't be converted.
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.try_sub(rhs).unwrap_or_else(|error| panic_on(error))
+    }
+}
+
+/// Panics with the error of ```+``` or ```-```.
+fn panic_on(error: ConversionError) -> ! {
+    match error {
+        ConversionError::IncompatibleUnits { from, to } => panic!("Cannot convert {} to {}.", from.symbol(), to.symbol()),
+        error => panic!("{error}"),
+    }
+}
+
+impl SubAssign for Quantity {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = self.clone() - rhs;
+    }
+}
+
+/// The magnitude of a unitless Quantity, which a number can be added to or subtracted from.
+///
+/// Panics if the Quantity has a unit, like adding quantities of incompatible units does.
+fn unitless_magnitude(quantity: Quantity, operation: &str) -> BigRational {
+    if !quantity.is_unitless() {
+        panic!("Cannot {operation} a number and a quantity in {}.", quantity.unit.symbol());
     }
+    quantity.magnitude
 }
 
-impl Mul<BigRational> for Quantity {
+impl Add<BigRational> for Quantity {
     type Output = Quantity;
 
-    fn mul(self, rhs: BigRational) -> Self::Output {
-        Self {
-            unit: self.unit,
-            magnitude: self.magnitude * rhs,
-        }
-    }
-}
-
-impl MulAssign<BigRational> for Quantity {
-    fn mul_assign(&mut self, rhs: BigRational) {
-        *self = self.clone() * rhs;
+    /// Adds a number to a unitless Quantity, including units that cancel like ```m/m```.
+    ///
+    /// Panics if the Quantity has a unit.
+    fn add(self, rhs: BigRational) -> Self::Output {
+        Self::from_rational(unitless_magnitude(self, "add") + rhs)
     }
 }
 
-impl Div for Quantity {
+impl Add<Quantity> for BigRational {
     type Output = Quantity;
 
-    fn div(self, rhs: Self) -> Self::Output {
-        Quantity {
-            magnitude: self.magnitude / rhs.magnitude,
-            unit: self.unit / rhs.unit,
-        }
+    fn add(self, rhs: Quantity) -> Self::Output {
+        rhs + self
     }
 }
 
-impl DivAssign for Quantity {
-    fn div_assign(&mut self, rhs: Self) {
-        *self = self.clone() / rhs;
+impl AddAssign<BigRational> for Quantity {
+    fn add_assign(&mut self, rhs: BigRational) {
+        *self = self.clone() + rhs;
     }
 }
 
-impl Div<BigRational> for Quantity {
+impl Sub<BigRational> for Quantity {
     type Output = Quantity;
 
-    fn div(self, rhs: BigRational) -> Self::Output {
-        Self {
-            unit: self.unit,
-            magnitude: self.magnitude / rhs,
-        }
+    /// Subtracts a number from a unitless Quantity, see ```add```.
+    fn sub(self, rhs: BigRational) -> Self::Output {
+        Self::from_rational(unitless_magnitude(self, "subtract") - rhs)
     }
 }
 
-impl DivAssign<BigRational> for Quantity {
-    fn div_assign(&mut self, rhs: BigRational) { *self = self.clone() / rhs; }
+impl Sub<Quantity> for BigRational {
+    type Output = Quantity;
+
+    fn sub(self, rhs: Quantity) -> Self::Output {
+        Quantity::from_rational(self - unitless_magnitude(rhs, "subtract"))
+    }
 }
 
-impl Neg for Quantity {
-    type Output = Quantity;
+impl SubAssign<BigRational> for Quantity {
+    fn sub_assign(&mut self, rhs: BigRational) {
+        *self = self.clone() - rhs;
+    }
+}
 
-    fn neg(self) -> Self::Output {
-        Self {
-            magnitude: -self.magnitude,
-            unit: self.unit,
-        }
+impl From<BigRational> for Quantity {
+    fn from(ratio: BigRational) -> Self {
+        Self::from_rational(ratio)
     }
 }
 
-impl Add for Quantity {
+impl Rem for Quantity {
     type Output = Quantity;
 
-    fn add(self, mut rhs: Self) -> Self::Output {
+    /// The remainder of dividing two quantities, converting ```rhs``` to the unit of ```self```.
+    ///
+    /// Panics if ```rhs``` can't be converted or is zero.
+    fn rem(self, mut rhs: Self) -> Self::Output {
+        if self.is_unitless_zero() {
+            return Self::new(zero!(), rhs.unit);
+        }
+
         let self_unit_symbol = self.unit.symbol();
         let rhs_unit_symbol = rhs.unit.symbol();
 
         rhs = rhs.convert_to(self.unit.clone())
-            .expect(format!("Cannot convert {rhs_unit_symbol} to {self_unit_symbol}.").as_str());
+            .unwrap_or_else(|_| panic!("Cannot convert {rhs_unit_symbol} to {self_unit_symbol}."));
 
         Self {
-            magnitude: self.magnitude + rhs.magnitude,
+            magnitude: self.magnitude % rhs.magnitude,
             unit: self.unit,
         }
     }
 }
 
-impl AddAssign for Quantity {
-    fn add_assign(&mut self, rhs: Self) {
-        *self = self.clone() + rhs;
+impl RemAssign for Quantity {
+    fn rem_assign(&mut self, rhs: Self) {
+        *self = self.clone() % rhs;
     }
 }
 
-impl Sub for Quantity {
-    type Output = Quantity;
-
-    fn sub(self, mut rhs: Self) -> Self::Output {
-        let self_unit_symbol = self.unit.symbol();
-        let rhs_unit_symbol = rhs.unit.symbol();
+impl Zero for Quantity {
+    /// A unitless zero, which ```Add``` and ```Sub``` treat as the identity for every unit.
+    fn zero() -> Self {
+        Self::from_rational(zero!())
+    }
 
-        rhs = rhs.convert_to(self.unit.clone())
-            .expect(format!("Cannot convert {rhs_unit_symbol} to {self_unit_symbol}.").as_str());
+    /// Whether the magnitude is zero, regardless of the unit.
+    fn is_zero(&self) -> bool {
+        self.magnitude.is_zero()
+    }
+}
 
-        Self {
-            magnitude: self.magnitude - rhs.magnitude,
-            unit: self.unit,
-        }
+impl One for Quantity {
+    /// A unitless one.
+    fn one() -> Self {
+        Self::from_rational(one!())
     }
 }
 
-impl SubAssign for Quantity {
-    fn sub_assign(&mut self, rhs: Self) {
-        *self = self.clone() - rhs;
+/// Lets ```Quantity``` be used in generic code bounded on ```num::Num```.
+///
+/// Quantities with incompatible units can't be added, subtracted or divided with a remainder,
+/// so those operators panic in generic code just like they do elsewhere. A unitless zero can
+/// be added to and subtracted from any quantity.
+///
+/// # Example:
+/// ```
+/// use tantalum_unit::prelude::*;
+/// use num::Num;
+///
+/// fn sum<T: Num + Clone>(values: &[T]) -> T {
+///     values.iter().cloned().fold(T::zero(), |sum, value| sum + value)
+/// }
+///
+/// let lengths = [Quantity::from_i64_with_unit(2, Meter), Quantity::from_i64_with_unit(3, Meter)];
+/// assert_eq!(sum(&lengths), Quantity::from_i64_with_unit(5, Meter));
+/// ```
+impl Num for Quantity {
+    type FromStrRadixErr = <BigRational as Num>::FromStrRadixErr;
+
+    /// Parses a unitless quantity from an integer or a fraction like "3/4".
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        // Ratio only parses fractions, so integers get an explicit denominator
+        let magnitude = if str.contains('/') {
+            BigRational::from_str_radix(str, radix)
+        } else {
+            BigRational::from_str_radix(&format!("{str}/1"), radix)
+        };
+        magnitude.map(Self::from_rational)
     }
 }
 
+/// Compares the units structurally and the magnitudes by value, so unreduced magnitudes like
+/// ```2/4``` are equal to their reduced form.
+/// Compares quantities by amount, like ```Quantity::equivalent_to```, so ```1 km == 1000 m``` and
+/// ```0 °C == 273.15 K```. Quantities of different dimensions are never equal.
+///
+/// Compare the ```unit``` fields as well to tell equal amounts in different units apart.
 impl PartialEq for Quantity {
     fn eq(&self, other: &Self) -> bool {
-        self.unit == other.unit
-            && self.magnitude.denom() == other.magnitude.denom()
-            && self.magnitude.numer() == other.magnitude.numer()
+        if self.unit == other.unit {
+            self.magnitude == other.magnitude
+        } else {
+            self.equivalent_to(other)
+        }
     }
 }
 
 impl Eq for Quantity {}
 
+/// Hashes a canonical form, consistent with ```==```: the reduced magnitude in SI base units
+/// without prefixes and the dimension of the unit.
+impl Hash for Quantity {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let magnitude = self.base_magnitude().reduced();
+        magnitude.numer().clone().to_big_int().hash(state);
+        magnitude.denom().clone().to_big_int().hash(state);
+        self.unit.si_exponents().hash(state);
+    }
+}
+
+/// Compares quantities of the same dimension by amount, like ```Quantity::cmp_converted```, so
+/// ```1 gal > 3 L``` and ```1 km``` equals ```1000 m``` just like ```==```. Quantities of
+/// different dimensions are unordered.
+impl PartialOrd for Quantity {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.cmp_converted(other).ok()
+    }
+}
+
+/// Compares a unitless Quantity with a number by value, so unreduced magnitudes are equal to
+/// their reduced form. A Quantity with a unit is never equal to a number.
+impl PartialEq<BigRational> for Quantity {
+    fn eq(&self, other: &BigRational) -> bool {
+        self.magnitude == *other && self.is_unitless()
+    }
+}
+
+impl PartialEq<Quantity> for BigRational {
+    fn eq(&self, other: &Quantity) -> bool {
+        other == self
+    }
+}
+
+impl PartialEq<i64> for Quantity {
+    fn eq(&self, other: &i64) -> bool {
+        *self == BigRational::from_integer((*other).into())
+    }
+}
+
+impl PartialEq<Quantity> for i64 {
+    fn eq(&self, other: &Quantity) -> bool {
+        other == self
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{int, ratio};
+    use crate::{c, int, ratio};
     use crate::unit::Unit::*;
     use crate::scalable_integer::BigRational;
     use super::*;
@@ -422,6 +1256,121 @@ mod tests {
         eq!(result, ratio!(4040113137766i64, 473176473i64), Gallon);
     }
 
+    #[test]
+    fn si_magnitude() {
+        let quantities = [
+            q!(int!(25), Celsius),
+            q!(ratio!(-40, 3), Fahrenheit),
+            q!(int!(3), Gallon),
+            q!(int!(2), Kilo * Gram),
+            q!(int!(5), Joule),
+            q!(int!(7), Kilo * Watt * Hour),
+            q!(ratio!(3, 2), Mile / Hour),
+            q!(int!(12), Mebi * Byte),
+            q!(int!(4), c!(c!(Meter; Second); Second)),
+            q!(int!(9), UNITLESS),
+        ];
+        for quantity in quantities {
+            assert_eq!(quantity.si_magnitude(), quantity.clone().to_si_units().magnitude, "{quantity:?}");
+        }
+        assert_eq!(q!(int!(25), Celsius).si_magnitude(), ratio!(5963, 20));
+    }
+
+    #[test]
+    fn with_named_unit() {
+        let power = (q!(int!(6), Newton * Kilo * Meter) / q!(int!(2), Second)).with_named_unit();
+        eq!(power, int!(3000), Watt);
+        let force = (q!(int!(2), Kilo * Gram) * q!(int!(3), Meter / (Second * Second))).with_named_unit();
+        eq!(force, int!(6), Newton);
+        let force = q!(int!(5), Gram * Meter / (Second * Second)).with_named_unit();
+        eq!(force, ratio!(1, 200), Newton);
+        let rate = q!(int!(120), UNITLESS / Minute).with_named_unit();
+        eq!(rate, int!(2), Hertz);
+
+        // Without a named unit the quantity is unchanged
+        let speed = q!(int!(5), Kilo * Meter / Hour).with_named_unit();
+        eq!(speed, int!(5), Kilo * Meter / Hour);
+        let volume = q!(int!(2), Gallon).with_named_unit();
+        eq!(volume, int!(2), Gallon);
+    }
+
+    #[test]
+    fn canonical_key() {
+        // Snapshots of the documented format, which must not change
+        let snapshots = [
+            (q!(int!(2), Kilo * Meter), "2000 m^1"),
+            (q!(int!(25), Celsius), "5963/20 K^1"),
+            (q!(int!(1), Kilo * Watt * Hour), "3600000000 g^1·m^2·s^-2"),
+            (q!(ratio!(-3, 4), Meter / Second), "-3/4 m^1·s^-1"),
+            (q!(int!(50), Percent), "1/2"),
+            (q!(int!(0), Meter), "0 m^1"),
+        ];
+        for (quantity, key) in snapshots {
+            assert_eq!(quantity.canonical_key(), key, "{quantity:?}");
+        }
+
+        // Equivalent quantities share a key however they are constructed
+        let equivalent = [
+            vec![q!(int!(1), Kilo * Meter), q!(int!(1000), Meter), q!(int!(100000), Centi * Meter), q!(ratio!(1, 1000), Mega * Meter)],
+            vec![q!(int!(36), Kilo * Meter / Hour), q!(int!(10), Meter / Second), q!(int!(10), c!(Meter; Second))],
+            vec![q!(int!(2), Kilo * Watt), q!(int!(2000), Joule / Second), q!(int!(2000), Volt * Ampere), q!(int!(2000), Ampere * Volt)],
+            vec![q!(int!(0), Celsius), q!(ratio!(5463, 20), Kelvin), q!(int!(273150), Milli * Kelvin)],
+            vec![q!(int!(1), Liter), q!(int!(1000), Milli * Liter), q!(ratio!(1, 1000), Meter * Meter * Meter)],
+        ];
+        for quantities in equivalent {
+            for quantity in &quantities {
+                assert_eq!(quantity.canonical_key(), quantities[0].canonical_key(), "{quantity:?}");
+            }
+        }
+        assert_ne!(q!(int!(1), Meter).canonical_key(), q!(int!(1), Second).canonical_key());
+        assert_ne!(q!(int!(1), Meter).canonical_key(), q!(int!(2), Meter).canonical_key());
+    }
+
+    #[test]
+    fn add_ratio() {
+        let quotient = q!(int!(6), Meter) / q!(int!(2), Meter);
+        eq!(quotient.clone() + ratio!(1, 2), ratio!(7, 2), UNITLESS);
+        eq!(ratio!(1, 2) + quotient.clone(), ratio!(7, 2), UNITLESS);
+        eq!(quotient.clone() - ratio!(1, 2), ratio!(5, 2), UNITLESS);
+        eq!(ratio!(1, 2) - quotient.clone(), ratio!(-5, 2), UNITLESS);
+
+        let mut sum = q!(int!(2), Compound(vec![Kilo, Meter].into(), vec![Kilo, Meter].into()));
+        sum += ratio!(1, 3);
+        sum -= int!(1);
+        eq!(sum, ratio!(4, 3), UNITLESS);
+        assert_eq!(Quantity::from(ratio!(3, 4)), Quantity::from_rational(ratio!(3, 4)));
+    }
+
+    #[test]
+    fn eq_number() {
+        let quotient = q!(int!(6), Meter) / q!(int!(2), Meter);
+        assert_eq!(quotient, int!(3));
+        assert_eq!(quotient, 3);
+        assert_eq!(int!(3), quotient);
+        assert_eq!(3, quotient);
+        assert_ne!(quotient, 4);
+
+        let unreduced = q!(BigRational::new_raw(6.into(), 4.into()), Compound(vec![Meter].into(), vec![Meter].into()));
+        assert_eq!(unreduced, ratio!(3, 2));
+        assert_eq!(q!(BigRational::new_raw(8.into(), 4.into()), UNITLESS), 2);
+
+        assert_ne!(q!(int!(3), Meter), int!(3));
+        assert_ne!(q!(int!(3), Meter), 3);
+        assert_ne!(3, q!(int!(3), Kilo));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot add a number and a quantity in m.")]
+    fn invalid_add_ratio() {
+        let _result = q!(int!(3), Meter) + ratio!(1, 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_sub_ratio() {
+        let _result = ratio!(1, 2) - q!(int!(3), Kilo);
+    }
+
     #[test]
     #[should_panic]
     fn invalid_add() {
@@ -431,6 +1380,400 @@ mod tests {
         let _result = a + b;
     }
 
+    #[test]
+    fn equivalent_to() {
+        let pairs = [
+            (q!(int!(1), Kilo * Meter), q!(int!(1000), Meter)),
+            (q!(int!(0), Celsius), q!(ratio!(5463, 20), Kelvin)),
+            (q!(int!(-40), Celsius), q!(ratio!(4663, 20), Kelvin)),
+            (q!(int!(3), Joule / Second), q!(int!(3), Watt)),
+            (q!(int!(3000), Joule / Second), q!(int!(3), Kilo * Watt)),
+            (q!(int!(5), Kilo * Gram * Meter / (Second * Second)), q!(int!(5), Newton)),
+            (q!(int!(2), Newton * Meter), q!(int!(2), Meter * Newton)),
+            (q!(int!(1), Gallon), q!(ratio!(473176473, 125000000), Liter)),
+            (q!(int!(2), UNITLESS), q!(int!(200), Percent)),
+        ];
+        for (a, b) in pairs {
+            assert!(a.equivalent_to(&b), "{a:?} {b:?}");
+            assert!(b.equivalent_to(&a), "{b:?} {a:?}");
+            assert_eq!(a.canonical_key(), b.canonical_key());
+        }
+
+        assert!(!q!(int!(1), Kilo * Meter).equivalent_to(&q!(int!(999), Meter)));
+        assert!(!q!(int!(0), Celsius).equivalent_to(&q!(int!(0), Kelvin)));
+        assert!(!q!(int!(1), Meter).equivalent_to(&q!(int!(1), Second)));
+        assert!(!q!(int!(1), Ohm).equivalent_to(&q!(int!(1), Siemens)));
+    }
+
+    #[test]
+    fn ordering() {
+        let gallon = q!(int!(1), Gallon);
+        assert!(gallon > q!(int!(3), Liter));
+        assert!(gallon < q!(int!(4), Liter));
+        assert!(q!(int!(3), Liter) < gallon);
+        assert_eq!(gallon.cmp_converted(&q!(ratio!(473176473, 125000000), Liter)), Ok(Ordering::Equal));
+        assert_eq!(gallon.partial_cmp(&q!(ratio!(473176473, 125000000), Liter)), Some(Ordering::Equal));
+        assert_eq!(gallon.partial_cmp(&q!(int!(1), Gallon)), Some(Ordering::Equal));
+
+        // Equal amounts in different units are equal, so both bounds of a threshold hold
+        let (kilometer, meters) = (q!(int!(1), Kilo * Meter), q!(int!(1000), Meter));
+        assert!(kilometer <= meters);
+        assert!(kilometer >= meters);
+        assert_eq!(kilometer, meters);
+
+        // Offsets are applied, so 0 °C is above 0 K but equal to 273.15 K
+        let freezing = q!(int!(0), Celsius);
+        assert!(freezing > q!(int!(0), Kelvin));
+        assert!(freezing < q!(int!(274), Kelvin));
+        assert_eq!(freezing.cmp_converted(&q!(ratio!(5463, 20), Kelvin)), Ok(Ordering::Equal));
+
+        assert_eq!(gallon.partial_cmp(&q!(int!(1), Meter)), None);
+        let meter = q!(int!(1), Meter);
+        assert!(!gallon.lt(&meter) && !gallon.gt(&meter) && !gallon.le(&meter));
+        assert_eq!(gallon.cmp_converted(&q!(int!(1), Meter)), Err(ConversionError::IncompatibleUnits { from: Meter, to: Gallon }));
+
+        let mut lengths = vec![q!(int!(1), Kilo * Meter), q!(int!(3), Feet), q!(int!(2), Meter), q!(int!(1), Mile)];
+        lengths.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(lengths, [q!(int!(3), Feet), q!(int!(2), Meter), q!(int!(1), Kilo * Meter), q!(int!(1), Mile)]);
+
+        // Sorting is stable for equal amounts in different units
+        let mut lengths = vec![q!(int!(1), Kilo * Meter), q!(int!(2), Meter), q!(int!(1000), Meter)];
+        lengths.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let units: Vec<Unit> = lengths.into_iter().map(|q| q.unit).collect();
+        assert_eq!(units, [Meter, Kilo * Meter, Meter]);
+    }
+
+    #[test]
+    fn ordering_is_consistent_with_eq() {
+        let quantities = [
+            q!(int!(1), Kilo * Meter),
+            q!(int!(1000), Meter),
+            q!(ratio!(2000, 2), Meter),
+            q!(int!(1), Meter * Second / Second),
+            q!(int!(0), Celsius),
+            q!(ratio!(5463, 20), Kelvin),
+            q!(int!(3), Newton * Meter),
+            q!(int!(3), Meter * Newton),
+            q!(int!(3), Joule),
+            q!(int!(1), Second),
+        ];
+        for a in &quantities {
+            for b in &quantities {
+                assert_eq!(a == b, a.partial_cmp(b) == Some(Ordering::Equal), "{a:?} {b:?}");
+                assert_eq!(a.partial_cmp(b), b.partial_cmp(a).map(Ordering::reverse), "{a:?} {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn hash() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashMap;
+
+        let hash = |quantity: &Quantity| {
+            let mut hasher = DefaultHasher::new();
+            quantity.hash(&mut hasher);
+            hasher.finish()
+        };
+        let raw = |numer: i64, denom: i64| BigRational::new_raw(numer.into(), denom.into());
+        let pairs = [
+            (q!(raw(2, 4), Meter), q!(ratio!(1, 2), Meter)),
+            (q!(raw(-6, -3), Kilo * Meter), q!(int!(2), Kilo * Meter)),
+            (q!(raw(3, -9), Joule / Second), q!(ratio!(-1, 3), Joule / Second)),
+            (q!(int!(1), c!(c!(Kilo, Meter; Second); Second)), q!(int!(1), c!(c!(Kilo, Meter; Second); Second))),
+            (q!(raw(10, 5), Newton * Meter / (Milli * Second)), q!(int!(2), Newton * Meter / (Milli * Second))),
+            (q!(BigRational::new_raw(ScalableInteger::Big(4.into()), ScalableInteger::Double(2)), Mebi * Byte), q!(int!(2), Mebi * Byte)),
+        ];
+        for (a, b) in pairs {
+            assert_eq!(a, b);
+            assert_eq!(hash(&a), hash(&b), "{a:?}");
+        }
+
+        // Units differing only in order hash alike, which is consistent with equality either way
+        assert_eq!(hash(&q!(int!(1), Newton * Meter)), hash(&q!(int!(1), Meter * Newton)));
+        assert_ne!(hash(&q!(int!(1), Meter)), hash(&q!(int!(1), UNITLESS / Meter)));
+
+        // Equal amounts in different units are equal, so they hash alike
+        assert_eq!(hash(&q!(int!(1), Kilo * Meter)), hash(&q!(int!(1000), Meter)));
+        assert_eq!(hash(&q!(int!(0), Celsius)), hash(&q!(ratio!(5463, 20), Kelvin)));
+        assert_eq!(hash(&q!(int!(3), Joule / Second)), hash(&q!(int!(3), Watt)));
+        assert_ne!(hash(&q!(int!(1), Meter)), hash(&q!(int!(1), Second)));
+
+        // Powers and nested compounds hash like their flattened form
+        let cubic_meter = q!(int!(1), Unit::Power(Box::new(Meter), 3));
+        assert_eq!(cubic_meter, q!(int!(1), Meter * Meter * Meter));
+        assert_eq!(hash(&cubic_meter), hash(&q!(int!(1), Meter * Meter * Meter)));
+        assert_eq!(cubic_meter.partial_cmp(&q!(int!(1), Meter * Meter * Meter)), Some(Ordering::Equal));
+        assert_eq!(hash(&q!(int!(1), Meter)), hash(&q!(int!(1), c!(Meter;))));
+
+        let mut memo = HashMap::new();
+        memo.insert(q!(raw(4, 8), Kilo * Watt), "half");
+        assert_eq!(memo.get(&q!(ratio!(1, 2), Kilo * Watt)), Some(&"half"));
+        assert_eq!(memo.get(&q!(ratio!(1, 2), Watt)), None);
+    }
+
+    #[test]
+    fn subtract_min_magnitudes() {
+        let min = q!(int!(i64::MIN), Meter);
+        let difference = q!(int!(0), Meter) - min.clone();
+        eq!(difference, BigRational::from_integer(ScalableInteger::Double(-(i64::MIN as i128))), Meter);
+        let difference = q!(int!(-1), Meter) - min.clone();
+        eq!(difference, int!(i64::MAX), Meter);
+        assert!(matches!(difference.magnitude.numer(), ScalableInteger::Single(i64::MAX)));
+
+        let sum = -min.clone() + min.clone();
+        eq!(sum, int!(0), Meter);
+        let negated = Quantity::zero() - min;
+        eq!(negated, BigRational::from_integer(ScalableInteger::Double(1 << 63)), Meter);
+    }
+
+    #[test]
+    fn unreduced_equality() {
+        let raw = |numer: i64, denom: i64| BigRational::new_raw(numer.into(), denom.into());
+        let half = q!(raw(2, 4), Meter);
+        assert_eq!(half, q!(ratio!(1, 2), Meter));
+        assert_eq!(q!(ratio!(1, 2), Meter), half);
+        assert_eq!(q!(raw(-3, -6), Meter), half);
+        assert_eq!(q!(raw(0, 5), Meter), q!(int!(0), Meter));
+        assert_ne!(half, q!(raw(2, 5), Meter));
+        assert_ne!(half, q!(ratio!(1, 2), Second));
+
+        // Converted magnitudes compare equal regardless of how far they were reduced
+        let converted = q!(int!(1), Kilo * Meter).convert_to(Meter).unwrap();
+        assert_eq!(converted, q!(raw(3000, 3), Meter));
+    }
+
+    #[test]
+    fn try_add_and_sub() {
+        let gallon = q!(int!(8342), Gallon);
+        let liter = q!(int!(743), Liter);
+        assert_eq!(gallon.clone().try_add(liter.clone()), Ok(gallon.clone() + liter.clone()));
+        assert_eq!(gallon.clone().try_sub(liter.clone()), Ok(gallon.clone() - liter));
+
+        let error = ConversionError::IncompatibleUnits { from: Joule / Candela, to: Gallon };
+        assert_eq!(gallon.clone().try_add(q!(int!(743), Joule / Candela)), Err(error.clone()));
+        assert_eq!(gallon.clone().try_sub(q!(int!(743), Joule / Candela)), Err(error));
+
+        // A unitless zero is the identity, like for the operators
+        assert_eq!(gallon.clone().try_add(Quantity::zero()), Ok(gallon.clone()));
+        assert_eq!(Quantity::zero().try_sub(gallon.clone()), Ok(-gallon));
+    }
+
+    const CONST_QUANTITY: Quantity = Quantity::const_from_i64(-42, crate::unit::KILOMETER);
+    static STATIC_QUANTITY: Quantity = Quantity::const_from_i64(7, Meter);
+
+    #[test]
+    fn const_quantities() {
+        eq!(CONST_QUANTITY, int!(-42), Kilo * Meter);
+        eq!(STATIC_QUANTITY, int!(7), Meter);
+        assert_eq!(CONST_QUANTITY + STATIC_QUANTITY.clone(), q!(ratio!(-41993, 1000), Kilo * Meter));
+    }
+
+    #[test]
+    fn unitless_zero_is_additive_identity() {
+        let a = q!(int!(5), Meter);
+        let result = Quantity::zero() + a.clone();
+        eq!(result, int!(5), Meter);
+        let result = a.clone() + Quantity::zero();
+        eq!(result, int!(5), Meter);
+        let result = Quantity::zero() - a.clone();
+        eq!(result, int!(-5), Meter);
+        let result = a - Quantity::zero();
+        eq!(result, int!(5), Meter);
+
+        assert!(q!(int!(0), Kelvin).is_zero());
+        assert!(!q!(int!(1), UNITLESS).is_zero());
+    }
+
+    #[test]
+    fn rem() {
+        let a = q!(int!(2500), Meter);
+        let b = q!(int!(1), Kilo * Meter);
+        let result = a % b;
+        eq!(result, int!(500), Meter);
+
+        let a = q!(ratio!(7, 2), Second);
+        let result = a % q!(int!(1), Second);
+        eq!(result, ratio!(1, 2), Second);
+    }
+
+    #[test]
+    #[should_panic]
+    fn invalid_rem() {
+        let _result = q!(int!(1), Meter) % q!(int!(1), Second);
+    }
+
+    fn sum<T: Num + Clone>(values: &[T]) -> T {
+        values.iter().cloned().fold(T::zero(), |sum, value| sum + value)
+    }
+
+    fn evaluate_polynomial<T: Num + Clone>(coefficients: &[T], x: T) -> T {
+        coefficients.iter().rev().cloned().fold(T::zero(), |result, coefficient| result * x.clone() + coefficient)
+    }
+
+    #[test]
+    fn generic_num() {
+        let values = [q!(int!(1), Kilo * Meter), q!(int!(250), Meter), q!(ratio!(1, 2), Kilo * Meter)];
+        let result = sum(&values);
+        eq!(result, ratio!(7, 4), Kilo * Meter);
+
+        let result = num::pow(q!(int!(3), Meter), 3);
+        eq!(result, int!(27), Meter * Meter * Meter);
+        let result = num::pow(q!(int!(3), Meter), 0);
+        eq!(result, int!(1), UNITLESS);
+
+        let coefficients = [Quantity::from_i64(1), Quantity::from_i64(2), Quantity::from_i64(3)];
+        assert_eq!(evaluate_polynomial(&coefficients, Quantity::from_i64(2)), Quantity::from_i64(17));
+
+        assert_eq!(Quantity::from_str_radix("-3/4", 10), Ok(Quantity::from_rational(ratio!(-3, 4))));
+        assert_eq!(Quantity::from_str_radix("ff", 16), Ok(Quantity::from_i64(255)));
+        assert!(Quantity::from_str_radix("1/0", 10).is_err());
+    }
+
+    #[test]
+    fn strict_arithmetic() {
+        let gallon = q!(int!(1), Gallon);
+        let liter = q!(int!(2), Liter);
+        assert!((gallon.clone() + liter.clone()).unit == Gallon);
+        assert_eq!(gallon.clone().try_add_strict(liter.clone()), Err(ArithmeticError::ImplicitConversion { left: Gallon, right: Liter }));
+        assert_eq!(gallon.clone().try_sub_strict(liter), Err(ArithmeticError::ImplicitConversion { left: Gallon, right: Liter }));
+
+        let kilometer = q!(int!(1), Kilo * Meter);
+        let meter = q!(int!(500), Meter);
+        eq!(kilometer.clone() + meter.clone(), ratio!(3, 2), Kilo * Meter);
+        assert_eq!(kilometer.try_add_strict(meter.clone()), Err(ArithmeticError::ImplicitConversion { left: Kilo * Meter, right: Meter }));
+
+        eq!(meter.clone().try_add_strict(q!(int!(2), Meter)).unwrap(), int!(502), Meter);
+        eq!(meter.clone().try_sub_strict(q!(int!(2), Meter)).unwrap(), int!(498), Meter);
+        eq!(q!(int!(2), Newton * Meter).try_add_strict(q!(int!(3), Meter * Newton)).unwrap(), int!(5), Newton * Meter);
+        eq!(meter.clone().try_add_strict(Quantity::zero()).unwrap(), int!(500), Meter);
+        assert_eq!(meter.try_add_strict(q!(int!(1), Second)), Err(ArithmeticError::IncompatibleUnits { left: Meter, right: Second }));
+
+        // Modifiers that share their symbol with a unit aren't that unit
+        let tera = Compound(vec![Tera].into(), vec![].into());
+        let milli = Compound(vec![Milli].into(), vec![].into());
+        assert_eq!(q!(int!(1), tera.clone()).try_add_strict(q!(int!(1), Tesla)), Err(ArithmeticError::IncompatibleUnits { left: tera, right: Tesla }));
+        assert_eq!(q!(int!(1), milli.clone()).try_add_strict(q!(int!(1), Meter)), Err(ArithmeticError::IncompatibleUnits { left: milli, right: Meter }));
+    }
+
+    #[test]
+    fn abs_diff() {
+        let meter = q!(int!(1), Meter);
+        let feet = q!(int!(3), Feet);
+        eq!(meter.abs_diff(&feet).unwrap(), ratio!(107, 1250), Meter);
+        eq!(feet.abs_diff(&meter).unwrap(), ratio!(107, 381), Feet);
+        eq!(q!(int!(-2), Meter).abs_diff(&q!(int!(3), Meter)).unwrap(), int!(5), Meter);
+        eq!(q!(int!(-2), Meter).abs(), int!(2), Meter);
+        assert_eq!(meter.abs_diff(&q!(int!(1), Second)), Err(ConversionError::IncompatibleUnits { from: Second, to: Meter }));
+    }
+
+    #[test]
+    fn relative_diff() {
+        let meter = q!(int!(1), Meter);
+        let feet = q!(int!(3), Feet);
+        assert_eq!(meter.relative_diff(&feet), Ok(ratio!(107, 1250)));
+        assert_eq!(feet.relative_diff(&meter), Ok(ratio!(107, 1143)));
+        assert_eq!(q!(int!(-4), Meter).relative_diff(&q!(int!(-3), Meter)), Ok(ratio!(1, 4)));
+        assert_eq!(q!(int!(0), Meter).relative_diff(&feet), Err(ConversionError::ZeroReference));
+        assert_eq!(meter.relative_diff(&q!(int!(1), Second)), Err(ConversionError::IncompatibleUnits { from: Second, to: Meter }));
+    }
+
+    #[test]
+    fn percent_of() {
+        let part = q!(int!(500), Milli * Liter);
+        eq!(part.percent_of(&q!(int!(2), Liter)).unwrap(), int!(25), Percent);
+        eq!(q!(int!(1), Meter).percent_of(&q!(int!(4), Meter)).unwrap(), int!(25), Percent);
+        eq!(q!(int!(3), Feet).percent_of(&q!(int!(1), Yard)).unwrap(), int!(100), Percent);
+        eq!(q!(int!(-1), Meter).percent_of(&q!(int!(3), Meter)).unwrap(), ratio!(-100, 3), Percent);
+        assert_eq!(part.percent_of(&q!(int!(0), Liter)), Err(ConversionError::ZeroReference));
+        assert_eq!(part.percent_of(&q!(int!(1), Meter)), Err(ConversionError::IncompatibleUnits { from: Meter, to: Milli * Liter }));
+
+        let share = part.percent_of(&q!(int!(2), Liter)).unwrap().convert_to(UNITLESS).unwrap();
+        eq!(share, ratio!(1, 4), UNITLESS);
+        assert_eq!("25 %".parse(), Ok(q!(int!(25), Percent)));
+    }
+
+    #[test]
+    fn percent_change() {
+        let before = q!(int!(2), Liter);
+        eq!(q!(int!(2500), Milli * Liter).percent_change(&before).unwrap(), int!(25), Percent);
+        eq!(q!(int!(1500), Milli * Liter).percent_change(&before).unwrap(), int!(-25), Percent);
+        eq!(q!(int!(-3), Meter).percent_change(&q!(int!(-2), Meter)).unwrap(), int!(-50), Percent);
+        assert_eq!(q!(int!(1), Liter).percent_change(&q!(int!(0), Liter)), Err(ConversionError::ZeroReference));
+        assert_eq!(q!(int!(1), Second).percent_change(&before), Err(ConversionError::IncompatibleUnits { from: Second, to: Liter }));
+    }
+
+    #[test]
+    fn baud_to_bitrate() {
+        eq!(q!(int!(2400), Baud).baud_to_bitrate(4).unwrap(), int!(9600), Bit / Second);
+        eq!(q!(int!(3), Kilo * Baud).baud_to_bitrate(2).unwrap(), int!(6000), Bit / Second);
+        eq!(q!(int!(50), Hertz).baud_to_bitrate(1).unwrap(), int!(50), Bit / Second);
+        eq!(q!(int!(50), Baud).baud_to_bitrate(0).unwrap(), int!(0), Bit / Second);
+        assert_eq!(q!(int!(1), Meter).baud_to_bitrate(8), Err(ConversionError::IncompatibleUnits { from: Meter, to: Baud }));
+    }
+
+    #[test]
+    fn add_in_common() {
+        let miles = q!(int!(3), Mile);
+        let millimeter = q!(int!(1), Milli * Meter);
+        let sum = Quantity::add_in_common(&miles, &millimeter).unwrap();
+        eq!(sum.clone(), int!(4828033), Milli * Meter);
+        assert_eq!(Quantity::add_in_common(&millimeter, &miles), Ok(sum));
+
+        // Adding with + keeps miles, with a large denominator
+        let naive = miles + millimeter;
+        assert_eq!(naive.magnitude, ratio!(4828033, 1609344));
+
+        eq!(Quantity::add_in_common(&q!(int!(2), Byte), &q!(int!(3), Bit)).unwrap(), int!(19), Bit);
+        eq!(Quantity::add_in_common(&q!(int!(1), Inch), &q!(int!(1), Centi * Meter)).unwrap(), ratio!(354, 10000), Meter);
+        assert_eq!(Quantity::add_in_common(&q!(int!(1), Meter), &q!(int!(1), Second)),
+                   Err(ConversionError::IncompatibleUnits { from: Meter, to: Second }));
+    }
+
+    #[test]
+    fn px_to_length() {
+        let width = q!(int!(1920), Pixel);
+        eq!(width.px_to_length(&q!(int!(96), DotsPerInch)).unwrap(), int!(20), Inch);
+        eq!(width.px_to_length(&q!(int!(300), PixelsPerInch)).unwrap(), ratio!(32, 5), Inch);
+        let length = width.px_to_length(&q!(int!(96), Pixel / Inch)).unwrap().convert_to(Meter).unwrap();
+        eq!(length, ratio!(508, 1000), Meter);
+        eq!(q!(int!(1920), Dot).px_to_length(&q!(int!(96), Dot / Inch)).unwrap(), int!(20), Inch);
+
+        assert_eq!(width.px_to_length(&q!(int!(0), DotsPerInch)), Err(ConversionError::ZeroReference));
+        assert_eq!(q!(int!(1), Meter).px_to_length(&q!(int!(96), DotsPerInch)),
+                   Err(ConversionError::IncompatibleUnits { from: Meter, to: Pixel }));
+        assert_eq!(width.px_to_length(&q!(int!(96), Meter)),
+                   Err(ConversionError::IncompatibleUnits { from: Meter, to: Pixel / Inch }));
+    }
+
+    #[test]
+    fn length_to_px() {
+        let dpi = q!(int!(300), DotsPerInch);
+        eq!(q!(ratio!(32, 5), Inch).length_to_px(&dpi).unwrap(), int!(1920), Pixel);
+        eq!(q!(ratio!(254, 100), Centi * Meter).length_to_px(&dpi).unwrap(), int!(300), Pixel);
+        let width = q!(int!(1920), Pixel);
+        assert_eq!(width.px_to_length(&dpi).unwrap().length_to_px(&dpi), Ok(width));
+        assert_eq!(q!(int!(1), Second).length_to_px(&dpi), Err(ConversionError::IncompatibleUnits { from: Second, to: Inch }));
+    }
+
+    #[test]
+    fn sum_in() {
+        let volumes: Vec<Quantity> = (1..=20)
+            .map(|i| if i % 3 == 0 { q!(int!(i), Gallon) } else { q!(ratio!(i, 7), Milli * Liter) })
+            .collect();
+        let naive = volumes.iter()
+            .map(|volume| volume.clone().convert_to(Liter).unwrap())
+            .fold(q!(int!(0), Liter), |sum, volume| sum + volume);
+        assert_eq!(Quantity::sum_in(&Liter, &volumes), Ok(naive));
+
+        let temperatures = [q!(int!(10), Celsius), q!(ratio!(28315, 100), Kelvin), q!(int!(20), Celsius)];
+        let total = Quantity::sum_in(&Celsius, temperatures).unwrap();
+        eq!(total, int!(40), Celsius);
+        eq!(Quantity::sum_in(&Meter, Vec::<Quantity>::new()).unwrap(), int!(0), Meter);
+        assert_eq!(Quantity::sum_in(&Liter, [q!(int!(1), Liter), q!(int!(1), Meter)]),
+                   Err(ConversionError::IncompatibleUnits { from: Meter, to: Liter }));
+    }
+
     #[test]
     fn sub_int() {
         let a = q!(int!(8342), Gallon);
@@ -452,6 +1795,24 @@ mod tests {
         let _result = a - b;
     }
 
+    #[test]
+    fn apply_newest_modifiers() {
+        let a = q!(int!(2), Ronna * Gram);
+        let result = a.apply_modifiers();
+        eq!(result, int!(2_000_000_000_000_000_000_000_000_000_i128), Gram);
+
+        let a = q!(int!(3), Quecto * Second);
+        let result = a.apply_modifiers();
+        eq!(result, ratio!(3, 1_000_000_000_000_000_000_000_000_000_000_i128), Second);
+
+        let a = q!(int!(5), Quetta * Meter / (Ronto * Second));
+        let result = a.apply_modifiers();
+        let expected = ScalableInteger::from(5) * num::pow(ScalableInteger::from(10), 57);
+        eq!(result, BigRational::from(expected), Meter / Second);
+
+        assert!([Quecto, Ronto, Ronna, Quetta].iter().all(Unit::is_modifier));
+    }
+
     #[test]
     fn apply_modifiers_single_big() {
         let a = q!(int!(13), Yotta * Meter);
@@ -545,6 +1906,21 @@ mod tests {
         eq!(result, int!(25_000), Meter);
     }
 
+    #[test]
+    fn debug() {
+        let a = q!(ratio!(4040113137766i64, 473176473i64), Gallon);
+        assert_eq!(format!("{a:?}"), "Quantity(4040113137766/473176473 ≈ 8538.28 gal)");
+
+        let a = q!(int!(-12), Meter / Second);
+        assert_eq!(format!("{a:?}"), "Quantity(-12 m/s)");
+
+        let a = q!(ratio!(1, 3), UNITLESS);
+        assert_eq!(format!("{a:?}"), "Quantity(1/3 ≈ 0.333333)");
+
+        let a = q!(BigRational::new(num::pow(int!(10), 40).to_integer() + 1.into(), 3.into()), Meter);
+        assert_eq!(format!("{a:?}"), "Quantity(10000000000000000000000000000000000000001/3 ≈ 3.33333e39 m)");
+    }
+
     #[test]
     fn conversion() {
         let a = q!(int!(152), Meter);
@@ -553,7 +1929,7 @@ mod tests {
 
         let a = q!(int!(152), Meter);
         let result = a.convert_to(Joule);
-        assert!(result.is_err());
+        assert_eq!(result, Err(ConversionError::IncompatibleUnits { from: Meter, to: Joule }));
 
         let a = q!(int!(38), Joule / Second);
         let result = a.convert_to(Watt).unwrap();
@@ -574,5 +1950,168 @@ mod tests {
         let a = q!(int!(20), Ohm);
         let result = a.convert_to(Siemens).unwrap();
         eq!(result, ratio!(1, 20), Siemens);
+
+        let a = q!(int!(20), Ohm);
+        let result = a.convert_to(Milli * Siemens).unwrap();
+        eq!(result, int!(50), Milli * Siemens);
+
+        // The SI units of derived units keep their prefix, which doesn't matter
+        let a = q!(int!(3), Kilo * Gram * Meter * Meter / (Second * Second));
+        let result = a.convert_to(Joule).unwrap();
+        eq!(result, int!(3), Joule);
+    }
+
+    #[test]
+    fn conversion_errors() {
+        let error = q!(int!(2), Gallon).convert_to(Joule / Candela).unwrap_err();
+        assert_eq!(error, ConversionError::IncompatibleUnits { from: Gallon, to: Joule / Candela });
+        assert!(error.to_string().starts_with("cannot convert gal to J/cd"));
+        let (from, to) = error.si_units().unwrap();
+        assert_eq!(from, Meter * Meter * Meter);
+        assert_eq!(to, Kilo * Gram * Meter * Meter / (Second * Second * Candela));
+
+        // Errors are reported with the units as given, not as reduced for the conversion
+        let error = q!(int!(1), Kilo * Meter / Hour).convert_to(Second).unwrap_err();
+        assert_eq!(error, ConversionError::IncompatibleUnits { from: Kilo * Meter / Hour, to: Second });
+        assert_eq!(ConversionError::Overflow.si_units(), None);
+    }
+
+    #[test]
+    fn simplify_units() {
+        eq!(q!(int!(2), Hertz * Second).simplify_units(), int!(2), UNITLESS);
+        eq!(q!(int!(2), Ohm * Siemens).simplify_units(), int!(2), UNITLESS);
+        eq!(q!(int!(3), Newton / Pascal).simplify_units(), int!(3), Meter * Meter);
+        eq!(q!(int!(5), Hertz * Hour).simplify_units(), int!(18_000), UNITLESS);
+        eq!(q!(int!(1), Kilo * Watt * Hour / Joule).simplify_units(), int!(3_600_000), UNITLESS);
+        eq!(q!(int!(2), Kilo * Joule / Meter).simplify_units(), int!(2000), Newton);
+        eq!(q!(int!(4), Liter / (Meter * Meter)).simplify_units(), ratio!(4, 1000), Meter);
+
+        // Nothing cancels, so the units are kept
+        eq!(q!(int!(7), Kilo * Meter / Hour).simplify_units(), int!(7), Kilo * Meter / Hour);
+        eq!(q!(int!(7), Celsius).simplify_units(), int!(7), Celsius);
+    }
+
+    #[test]
+    fn checked_operations() {
+        assert_eq!(q!(int!(6), Meter).checked_div(q!(int!(0), Second)), None);
+        assert_eq!(q!(int!(6), Meter).checked_div(Quantity::zero()), None);
+        assert_eq!(Quantity::zero().checked_div(Quantity::zero()), None);
+        eq!(q!(int!(6), Meter).checked_div(q!(int!(4), Second)).unwrap(), ratio!(3, 2), Meter / Second);
+
+        eq!(q!(int!(6), Meter).checked_mul(q!(int!(0), Second)).unwrap(), zero!(), Meter * Second);
+        eq!(q!(int!(1), Kilo * Meter).checked_add(q!(int!(500), Meter)).unwrap(), ratio!(3, 2), Kilo * Meter);
+        eq!(q!(int!(1), Kilo * Meter).checked_sub(q!(int!(500), Meter)).unwrap(), ratio!(1, 2), Kilo * Meter);
+        assert_eq!(q!(int!(1), Meter).checked_add(q!(int!(1), Second)), None);
+        assert_eq!(q!(int!(1), Meter).checked_sub(q!(int!(1), Second)), None);
+        eq!(Quantity::zero().checked_sub(q!(int!(2), Second)).unwrap(), int!(-2), Second);
+    }
+
+    #[test]
+    fn conversion_with_offsets() {
+        // A heat capacity converts temperature differences, so only the slope applies
+        let result = q!(int!(4), Joule / Celsius).convert_to(Joule / Kelvin).unwrap();
+        eq!(result, int!(4), Joule / Kelvin);
+
+        let result = q!(int!(100), Celsius).convert_to(Fahrenheit).unwrap();
+        assert_eq!(result.convert_to(Celsius).unwrap(), q!(int!(100), Celsius));
+        let result = q!(int!(-40), Celsius).convert_to(Kelvin).unwrap();
+        eq!(result, ratio!(4663, 20), Kelvin);
+
+        let result = q!(int!(3), Celsius * Second).convert_to(Kelvin * Second).unwrap();
+        eq!(result, int!(3), Kelvin * Second);
+
+        // Kilo * Celsius could be an absolute temperature or a difference
+        let result = q!(int!(1), Kilo * Celsius).convert_to(Kelvin);
+        assert_eq!(result, Err(ConversionError::AmbiguousOffset { from: Kilo * Celsius, to: Kelvin }));
+        let result = q!(int!(1), Celsius).convert_to_many(&[Milli * Celsius]);
+        assert_eq!(result[0], Err(ConversionError::AmbiguousOffset { from: Celsius, to: Milli * Celsius }));
+        assert!(q!(int!(1), Kilo * Kelvin).convert_to(Celsius).is_ok());
+    }
+
+    #[test]
+    fn conversion_to_many() {
+        let targets = [Watt, Kilo * Watt, Milli * Watt, (Kilo * Coulomb * Volt) / Second, (Newton * Meter) / Second, Meter, UNITLESS / Watt];
+        let quantities = [
+            q!(int!(3800), Joule / Second),
+            q!(ratio!(-7, 3), Mega * Watt),
+            q!(int!(20), Kelvin),
+            q!(int!(1), Milli * Second / Joule),
+        ];
+
+        for a in quantities {
+            let results = a.convert_to_many(&targets);
+            assert_eq!(results.len(), targets.len());
+            for (result, to) in results.into_iter().zip(&targets) {
+                let expected = a.clone().convert_to(to.clone());
+                assert_eq!(result.clone().ok(), expected.ok(), "{a:?} to {to}");
+                if let Err(error) = result {
+                    assert_eq!(error, ConversionError::IncompatibleUnits { from: a.unit.clone(), to: to.clone() });
+                }
+            }
+        }
+
+        assert!(q!(int!(1), Meter).convert_to_many(&[]).is_empty());
+        let temperatures = Quantity::from_i64_with_unit(0, Celsius).convert_to_many(&[Kelvin, Fahrenheit]);
+        assert_eq!(temperatures[0], Ok(q!(ratio!(5463, 20), Kelvin)));
+    }
+
+    #[test]
+    fn conversion_table() {
+        let table = Quantity::from_unit(Meter).conversion_table();
+        let units: Vec<Unit> = table.iter().map(|(unit, _)| unit.clone()).collect();
+        assert_eq!(units, [Meter, AU, Inch, Feet, Yard, Mile, NauticalMile, LightYear, Parsec]);
+        assert_eq!(table[0].1, q!(int!(1), Meter));
+        assert_eq!(table[2].1, q!(ratio!(5000, 127), Inch));
+        assert_eq!(table[5].1, q!(ratio!(125, 201168), Mile));
+        assert_eq!(table[8].1, q!(ratio!(1, 30856775814913673i64), Parsec));
+        for (unit, quantity) in &table {
+            assert_eq!(&quantity.unit, unit);
+        }
+
+        let table = Quantity::from_i64_with_unit(0, Celsius).conversion_table();
+        assert!(table.contains(&(Kelvin, q!(ratio!(5463, 20), Kelvin))));
+        assert!(table.contains(&(Celsius, q!(int!(0), Celsius))));
+
+        let table = q!(int!(2), Kilo * Gram * Meter * Meter / (Second * Second)).conversion_table();
+        assert!(table.contains(&(Joule, q!(int!(2), Joule))));
+
+        // Siemens is only the reciprocal of Ohm
+        assert!(q!(int!(1), Ohm).conversion_table().iter().all(|(unit, _)| *unit != Siemens));
+        assert!(q!(int!(1), Kilo * Meter / Hour).conversion_table().is_empty());
+    }
+
+    #[test]
+    fn with_prefix() {
+        let result = q!(int!(1500), Meter).with_prefix(Kilo).unwrap();
+        eq!(result, ratio!(3, 2), Kilo * Meter);
+
+        let result = q!(ratio!(1, 500), Second).with_prefix(Milli).unwrap();
+        eq!(result, int!(2), Milli * Second);
+
+        let result = q!(int!(3), Giga * Meter).with_prefix(Nano).unwrap();
+        eq!(result, int!(3_000_000_000_000_000_000i64), Nano * Meter);
+
+        let result = q!(int!(5), Yocto * Gram).with_prefix(Yotta).unwrap();
+        eq!(result, BigRational::new(5.into(), num::pow(ScalableInteger::from(10), 48)), Yotta * Gram);
+
+        let result = q!(int!(3), Kilo * Gram).with_prefix(Kilo).unwrap();
+        eq!(result, int!(3), Kilo * Gram);
+
+        let result = q!(int!(2048), Byte).with_prefix(Kibi).unwrap();
+        eq!(result, int!(2), Kibi * Byte);
+
+        let result = q!(int!(3), Mebi * Byte).with_prefix(Kilo).unwrap();
+        eq!(result, ratio!(393216, 125), Kilo * Byte);
+    }
+
+    #[test]
+    fn with_prefix_errors() {
+        assert_eq!(q!(int!(1), Meter).with_prefix(Inch), Err(PrefixError::NotAPrefix(Inch)));
+        assert_eq!(q!(int!(1), Meter).with_prefix(Kilo * Meter), Err(PrefixError::NotAPrefix(Kilo * Meter)));
+        assert_eq!(q!(int!(1), Meter / Second).with_prefix(Kilo), Err(PrefixError::CompoundUnit(Meter / Second)));
+        assert_eq!(q!(int!(1), Kilo * Meter * Meter).with_prefix(Kilo), Err(PrefixError::CompoundUnit(Kilo * Meter * Meter)));
+        assert_eq!(q!(int!(1), UNITLESS).with_prefix(Kilo), Err(PrefixError::CompoundUnit(UNITLESS)));
+        assert_eq!(PrefixError::NotAPrefix(Inch).to_string(), "in is not a prefix");
+        assert_eq!(PrefixError::CompoundUnit(Meter / Second).to_string(), "cannot prefix the compound unit m/s");
     }
 }
@@ -0,0 +1,264 @@
+//! Units defined at runtime by conversions to each other and to built-in units.
+//!
+//! A ```UnitRegistry``` is a graph whose nodes are custom units, identified by their names, and
+//! built-in units. Every registered conversion like ```1 widget = 3 gadget``` is an edge, so custom
+//! units don't need to be anchored to SI units directly. The conversion factor between two units
+//! is the product of the factors along a path between them. Built-in units of the same dimension
+//! are connected implicitly by their usual conversion factor.
+//!
+//! Units with an offset like ```Celsius``` can't be converted by a factor alone, so they are
+//! rejected with ```RegistryError::AffineUnit```.
+//!
+//! A conversion that contradicts the ones registered before, like ```1 widget = 2 gadget``` after
+//! ```1 widget = 3 gadget```, is rejected, so every path between two units gives the same factor.
+//!
+//! Not implemented yet: ```Quantity::convert_to``` should consult a registry when either side is
+//! a custom unit, but ```Unit``` has no variant for custom units to carry one. Until that follow-up
+//! lands, convert magnitudes with ```UnitRegistry::convert```.
+//!
+//! # Example:
+//! ```
+//! use tantalum_unit::prelude::*;
+//! use tantalum_unit::registry::UnitRegistry;
+//!
+//! let mut registry = UnitRegistry::new();
+//! registry.register_conversion("widget", "gadget", int!(3)).unwrap();
+//! registry.register_conversion("gadget", Gram, int!(5)).unwrap();
+//!
+//! assert_eq!(registry.conversion_factor("widget", Kilo * Gram), Ok(ratio!(3, 200)));
+//! assert_eq!(registry.convert(&int!(2), Kilo * Gram, "gadget"), Ok(int!(400)));
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use indexmap::IndexMap;
+use num::Zero;
+use crate::error::RegistryError;
+use crate::scalable_integer::BigRational;
+use crate::unit::Unit;
+
+/// A node of a ```UnitRegistry```.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RegistryUnit {
+    /// A unit defined only by its conversions in the registry.
+    Custom(String),
+    /// A built-in unit.
+    Unit(Unit),
+}
+
+impl From<&str> for RegistryUnit {
+    fn from(name: &str) -> Self {
+        RegistryUnit::Custom(name.to_owned())
+    }
+}
+
+impl From<String> for RegistryUnit {
+    fn from(name: String) -> Self {
+        RegistryUnit::Custom(name)
+    }
+}
+
+impl From<Unit> for RegistryUnit {
+    fn from(unit: Unit) -> Self {
+        RegistryUnit::Unit(unit)
+    }
+}
+
+impl Display for RegistryUnit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RegistryUnit::Custom(name) => f.write_str(name),
+            RegistryUnit::Unit(unit) => f.write_str(&unit.symbol()),
+        }
+    }
+}
+
+/// A graph of conversions between custom and built-in units.
+#[derive(Clone, Debug, Default)]
+pub struct UnitRegistry {
+    edges: IndexMap<RegistryUnit, Vec<(RegistryUnit, BigRational)>>,
+}
+
+impl UnitRegistry {
+    /// Creates a registry without any conversions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers that one ```from``` equals ```factor``` times ```to```.
+    ///
+    /// At least one of the units must be custom, and the factor must be positive. Built-in units
+    /// with an offset fail with ```RegistryError::AffineUnit```. Registering a
+    /// conversion that follows from the registered ones has no effect, while one contradicting
+    /// them fails with ```RegistryError::Inconsistent```.
+    pub fn register_conversion(&mut self, from: impl Into<RegistryUnit>, to: impl Into<RegistryUnit>, factor: BigRational) -> Result<(), RegistryError> {
+        let (from, to) = (from.into(), to.into());
+        if let (RegistryUnit::Unit(_), RegistryUnit::Unit(_)) = (&from, &to) {
+            return Err(RegistryError::BuiltInUnits);
+        }
+        check_linear(&from)?;
+        check_linear(&to)?;
+        if factor <= BigRational::zero() {
+            return Err(RegistryError::InvalidFactor(factor));
+        }
+        if let Some(existing) = self.find_factor(&from, &to) {
+            return if existing == factor {
+                Ok(())
+            } else {
+                Err(RegistryError::Inconsistent { from: from.to_string(), to: to.to_string(), existing })
+            };
+        }
+        let inverse = BigRational::from_integer(1.into()) / &factor;
+        self.edges.entry(from.clone()).or_default().push((to.clone(), factor));
+        self.edges.entry(to).or_default().push((from, inverse));
+        Ok(())
+    }
+
+    /// The factor to multiply a magnitude in ```from``` with to express it in ```to```.
+    ///
+    /// Fails with ```RegistryError::UnknownUnit``` for custom units without registered
+    /// conversions, with ```RegistryError::AffineUnit``` for built-in units with an offset and with
+    /// ```RegistryError::NoPath``` if no chain of conversions connects the units.
+    pub fn conversion_factor(&self, from: impl Into<RegistryUnit>, to: impl Into<RegistryUnit>) -> Result<BigRational, RegistryError> {
+        let (from, to) = (from.into(), to.into());
+        for unit in [&from, &to] {
+            check_linear(unit)?;
+            if let RegistryUnit::Custom(name) = unit {
+                if !self.edges.contains_key(unit) {
+                    return Err(RegistryError::UnknownUnit(name.clone()));
+                }
+            }
+        }
+        self.find_factor(&from, &to).ok_or_else(|| RegistryError::NoPath { from: from.to_string(), to: to.to_string() })
+    }
+
+    /// Converts the magnitude of a value in ```from``` into ```to```.
+    pub fn convert(&self, magnitude: &BigRational, from: impl Into<RegistryUnit>, to: impl Into<RegistryUnit>) -> Result<BigRational, RegistryError> {
+        Ok(self.conversion_factor(from, to)? * magnitude)
+    }
+
+    /// Searches the graph breadth-first for a path from ```from``` to ```to```.
+    fn find_factor(&self, from: &RegistryUnit, to: &RegistryUnit) -> Option<BigRational> {
+        let mut factors = IndexMap::new();
+        let mut queue = VecDeque::new();
+        factors.insert(from.clone(), BigRational::from_integer(1.into()));
+        queue.push_back(from.clone());
+        while let Some(unit) = queue.pop_front() {
+            let factor = factors[&unit].clone();
+            if &unit == to {
+                return Some(factor);
+            }
+            for (next, step) in self.neighbours(&unit, to) {
+                if !factors.contains_key(&next) {
+                    factors.insert(next.clone(), &factor * step);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// The units one conversion away from ```unit```, including the built-in units of the same
+    /// dimension among the registered ones and ```target```.
+    fn neighbours(&self, unit: &RegistryUnit, target: &RegistryUnit) -> Vec<(RegistryUnit, BigRational)> {
+        let mut neighbours = self.edges.get(unit).cloned().unwrap_or_default();
+        if let RegistryUnit::Unit(unit) = unit {
+            let slope = unit.si_affine().1;
+            let built_in = self.edges.keys().chain([target]).filter_map(|other| match other {
+                RegistryUnit::Unit(other) if other != unit && other.has_same_dimension(unit) => Some(other),
+                _ => None,
+            });
+            for other in built_in {
+                neighbours.push((RegistryUnit::Unit(other.clone()), &slope / other.si_affine().1));
+            }
+        }
+        neighbours
+    }
+}
+
+/// Rejects built-in units whose conversion has an offset, which a factor can't express.
+fn check_linear(unit: &RegistryUnit) -> Result<(), RegistryError> {
+    match unit {
+        RegistryUnit::Unit(unit) if !unit.offset().is_zero() || unit.has_ambiguous_offset() => {
+            Err(RegistryError::AffineUnit(unit.clone()))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{int, ratio};
+    use crate::error::RegistryError;
+    use crate::unit::Unit::*;
+    use super::*;
+
+    fn registry() -> UnitRegistry {
+        let mut registry = UnitRegistry::new();
+        registry.register_conversion("widget", "gadget", int!(3)).unwrap();
+        registry.register_conversion("gadget", "gizmo", int!(4)).unwrap();
+        registry.register_conversion("gizmo", Gram, int!(5)).unwrap();
+        registry.register_conversion("doohickey", Kilo * Gram, int!(2)).unwrap();
+        registry
+    }
+
+    #[test]
+    fn chained_conversions() {
+        let registry = registry();
+        assert_eq!(registry.conversion_factor("widget", "gizmo"), Ok(int!(12)));
+        assert_eq!(registry.conversion_factor("gizmo", "widget"), Ok(ratio!(1, 12)));
+        assert_eq!(registry.conversion_factor("widget", Gram), Ok(int!(60)));
+        assert_eq!(registry.conversion_factor("widget", "widget"), Ok(int!(1)));
+        assert_eq!(registry.convert(&int!(2), "widget", Milli * Gram), Ok(int!(120000)));
+    }
+
+    #[test]
+    fn built_in_units_connect_custom_units() {
+        let registry = registry();
+        assert_eq!(registry.conversion_factor("widget", "doohickey"), Ok(ratio!(3, 100)));
+        assert_eq!(registry.conversion_factor(Kilo * Gram, Gram), Ok(int!(1000)));
+    }
+
+    #[test]
+    fn missing_path() {
+        let mut registry = registry();
+        registry.register_conversion("lap", Meter, int!(400)).unwrap();
+        assert_eq!(registry.conversion_factor("widget", "lap"), Err(RegistryError::NoPath { from: "widget".to_owned(), to: "lap".to_owned() }));
+        assert_eq!(registry.conversion_factor("widget", Second), Err(RegistryError::NoPath { from: "widget".to_owned(), to: "s".to_owned() }));
+        assert_eq!(registry.conversion_factor("widget", "thing"), Err(RegistryError::UnknownUnit("thing".to_owned())));
+    }
+
+    #[test]
+    fn inconsistent_cycle() {
+        let mut registry = registry();
+        assert_eq!(registry.register_conversion("gizmo", "widget", ratio!(1, 12)), Ok(()));
+        assert_eq!(registry.register_conversion("widget", Kilo * Gram, ratio!(3, 5)), Err(RegistryError::Inconsistent {
+            from: "widget".to_owned(),
+            to: "kg".to_owned(),
+            existing: ratio!(3, 50),
+        }));
+        assert_eq!(registry.register_conversion("widget", Kilo * Gram, ratio!(3, 50)), Ok(()));
+        assert_eq!(registry.conversion_factor("widget", Gram), Ok(int!(60)));
+    }
+
+    #[test]
+    fn invalid_conversions() {
+        let mut registry = registry();
+        assert_eq!(registry.register_conversion("widget", "gadget", int!(0)), Err(RegistryError::InvalidFactor(int!(0))));
+        assert_eq!(registry.register_conversion(Meter, Second, int!(1)), Err(RegistryError::BuiltInUnits));
+    }
+
+    #[test]
+    fn affine_units() {
+        let mut registry = registry();
+        assert_eq!(registry.register_conversion("x", Celsius, int!(2)), Err(RegistryError::AffineUnit(Celsius)));
+        assert_eq!(registry.register_conversion(Kilo * Celsius, "x", int!(2)), Err(RegistryError::AffineUnit(Kilo * Celsius)));
+        assert_eq!(registry.convert(&int!(0), Celsius, Fahrenheit), Err(RegistryError::AffineUnit(Celsius)));
+        assert_eq!(registry.conversion_factor(Kelvin, Fahrenheit), Err(RegistryError::AffineUnit(Fahrenheit)));
+
+        // Temperature differences have no offset
+        registry.register_conversion("degree", Kelvin, int!(1)).unwrap();
+        assert_eq!(registry.conversion_factor("degree", Kilo * Kelvin), Ok(ratio!(1, 1000)));
+        assert_eq!(registry.conversion_factor(Joule / Celsius, Joule / Kelvin), Ok(int!(1)));
+    }
+}
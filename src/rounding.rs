@@ -0,0 +1,376 @@
+//! Rounding of ```BigRational``` values to integers and to a fixed number of decimal places.
+
+use std::cmp::Ordering;
+use num::{Integer, One, ToPrimitive, Zero};
+use crate::error::ConversionError;
+use crate::quantity::Quantity;
+use crate::scalable_integer::{BigRational, ScalableInteger};
+use crate::unit::{Unit, UNITLESS};
+
+/// How to round a value that lies between two integers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Round towards negative infinity.
+    Floor,
+    /// Round towards positive infinity.
+    Ceiling,
+    /// Round towards zero, discarding the fractional part.
+    TowardZero,
+    /// Round away from zero.
+    AwayFromZero,
+    /// Round to the nearest integer, ties away from zero.
+    HalfUp,
+    /// Round to the nearest integer, ties towards zero.
+    HalfDown,
+    /// Round to the nearest integer, ties to the even neighbor.
+    HalfEven,
+}
+
+/// Rounds a rational to an integer.
+///
+/// # Example:
+/// ```
+/// use tantalum_unit::prelude::*;
+/// use tantalum_unit::rounding::{round, RoundingMode};
+///
+/// assert_eq!(round(&ratio!(5, 2), RoundingMode::HalfEven), 2.into());
+/// assert_eq!(round(&ratio!(-5, 2), RoundingMode::HalfUp), (-3).into());
+/// ```
+pub fn round(value: &BigRational, mode: RoundingMode) -> ScalableInteger {
+    use RoundingMode::*;
+
+    let (mut numer, mut denom) = (value.numer().clone(), value.denom().clone());
+    // Unreduced ratios built with new_raw may carry the sign in the denominator
+    if denom < ScalableInteger::zero() {
+        numer = -numer;
+        denom = -denom;
+    }
+
+    let (floor, remainder) = numer.div_mod_floor(&denom);
+    if remainder.is_zero() {
+        return floor;
+    }
+
+    let is_negative = floor < ScalableInteger::zero();
+    let ceiling = floor.clone() + ScalableInteger::one();
+    let nearest = |tie: ScalableInteger| match (remainder.clone() * 2.into()).cmp(&denom) {
+        Ordering::Less => floor.clone(),
+        Ordering::Greater => ceiling.clone(),
+        Ordering::Equal => tie,
+    };
+
+    match mode {
+        Floor => floor.clone(),
+        Ceiling => ceiling.clone(),
+        TowardZero => if is_negative { ceiling.clone() } else { floor.clone() },
+        AwayFromZero => if is_negative { floor.clone() } else { ceiling.clone() },
+        HalfUp => nearest(if is_negative { floor.clone() } else { ceiling.clone() }),
+        HalfDown => nearest(if is_negative { ceiling.clone() } else { floor.clone() }),
+        HalfEven => nearest(if floor.is_even() { floor.clone() } else { ceiling.clone() }),
+    }
+}
+
+/// Rounds a rational to ```scale``` decimal places and returns the result scaled by ```10^scale```.
+///
+/// # Example:
+/// ```
+/// use tantalum_unit::prelude::*;
+/// use tantalum_unit::rounding::{round_to_scale, RoundingMode};
+///
+/// // 2/3 rounded to 3 decimal places is 0.667
+/// assert_eq!(round_to_scale(&ratio!(2, 3), 3, RoundingMode::HalfUp), 667.into());
+/// ```
+pub fn round_to_scale(value: &BigRational, scale: u32, mode: RoundingMode) -> ScalableInteger {
+    let factor = num::pow(ScalableInteger::from(10), scale as usize);
+    let scaled = BigRational::new_raw(value.numer() * &factor, value.denom().clone());
+    round(&scaled, mode)
+}
+
+/// The number of decimal places of a rational that is a terminating decimal, or ```None``` if
+/// its decimal expansion repeats.
+///
+/// A reduced fraction terminates exactly if its denominator has no prime factors other than 2
+/// and 5, and then the number of decimal places is the larger of their exponents.
+///
+/// # Example:
+/// ```
+/// use tantalum_unit::prelude::*;
+/// use tantalum_unit::rounding::terminating_decimal_places;
+///
+/// assert_eq!(terminating_decimal_places(&ratio!(1, 4)), Some(2));
+/// assert_eq!(terminating_decimal_places(&ratio!(7, 1)), Some(0));
+/// assert_eq!(terminating_decimal_places(&ratio!(1, 3)), None);
+/// ```
+pub fn terminating_decimal_places(value: &BigRational) -> Option<u32> {
+    let (rest, twos) = value.denom().remove_factor(2);
+    let (rest, fives) = rest.remove_factor(5);
+    rest.is_one().then_some(twos.max(fives))
+}
+
+/// Returns the exponent of the leading decimal digit of a nonzero rational, i.e. ```floor(log10(|value|))```.
+pub fn decimal_exponent(value: &BigRational) -> i64 {
+    let numer = abs(value.numer().clone());
+    let denom = abs(value.denom().clone());
+
+    // Estimate from the bit lengths, which is off by at most one, then correct it
+    let log2 = numer.bits() as f64 - denom.bits() as f64;
+    let mut exponent = (log2 * std::f64::consts::LOG10_2).floor() as i64;
+    while compare_to_power_of_ten(&numer, &denom, exponent) == Ordering::Less {
+        exponent -= 1;
+    }
+    while compare_to_power_of_ten(&numer, &denom, exponent + 1) != Ordering::Less {
+        exponent += 1;
+    }
+    exponent
+}
+
+/// Rounds a rational to ```digits``` significant digits.
+///
+/// Returns ```(mantissa, exponent)``` such that the rounded value is ```mantissa * 10^exponent```.
+/// The mantissa has exactly ```digits``` digits unless the value is zero.
+pub fn round_to_significant(value: &BigRational, digits: u32, mode: RoundingMode) -> (ScalableInteger, i64) {
+    if value.numer().is_zero() {
+        return (ScalableInteger::zero(), 0);
+    }
+
+    let mut exponent = decimal_exponent(value) - i64::from(digits) + 1;
+    let mut mantissa = round(&scale_by_power_of_ten(value, -exponent), mode);
+
+    // Rounding up may add a digit, e.g. 9.99 to 10.0
+    if abs(mantissa.clone()) == ten_to_the(i64::from(digits)) {
+        mantissa /= 10.into();
+        exponent += 1;
+    }
+    (mantissa, exponent)
+}
+
+/// Renders a rational as a decimal with at most ```digits``` significant digits, switching to
+/// scientific notation for very large and very small values.
+///
+/// # Example:
+/// ```
+/// use tantalum_unit::prelude::*;
+/// use tantalum_unit::rounding::to_significant_string;
+///
+/// assert_eq!(to_significant_string(&ratio!(4040113137766i64, 473176473), 6), "8538.28");
+/// assert_eq!(to_significant_string(&ratio!(1, 4), 6), "0.25");
+/// assert_eq!(to_significant_string(&ratio!(10i128.pow(30), 3), 3), "3.33e29");
+/// ```
+pub fn to_significant_string(value: &BigRational, digits: u32) -> String {
+    significant_string(value, digits, RoundingMode::HalfEven)
+}
+
+/// Like ```to_significant_string```, rounding with ```mode```.
+pub(crate) fn significant_string(value: &BigRational, digits: u32, mode: RoundingMode) -> String {
+    let (mantissa, exponent) = round_to_significant(value, digits.max(1), mode);
+    if mantissa.is_zero() {
+        return "0".to_owned();
+    }
+
+    let sign = if mantissa < ScalableInteger::zero() { "-" } else { "" };
+    let digits = abs(mantissa).to_string();
+    let leading_exponent = exponent + digits.len() as i64 - 1;
+
+    if !(-7..15).contains(&leading_exponent) {
+        let fraction = digits[1..].trim_end_matches('0');
+        let point = if fraction.is_empty() { "" } else { "." };
+        format!("{sign}{}{point}{fraction}e{leading_exponent}", &digits[..1])
+    } else if exponent >= 0 {
+        format!("{sign}{digits}{}", "0".repeat(exponent as usize))
+    } else {
+        let padded = format!("{}{digits}", "0".repeat((-exponent as usize).saturating_sub(digits.len() - 1)));
+        let (whole, fraction) = padded.split_at(padded.len() - (-exponent) as usize);
+        let fraction = fraction.trim_end_matches('0');
+        let point = if fraction.is_empty() { "" } else { "." };
+        format!("{sign}{whole}{point}{fraction}")
+    }
+}
+
+fn abs(value: ScalableInteger) -> ScalableInteger {
+    if value < ScalableInteger::zero() { -value } else { value }
+}
+
+fn ten_to_the(exponent: i64) -> ScalableInteger {
+    num::pow(ScalableInteger::from(10), exponent as usize)
+}
+
+/// Multiplies a rational by ```10^exponent``` without reducing it.
+fn scale_by_power_of_ten(value: &BigRational, exponent: i64) -> BigRational {
+    if exponent >= 0 {
+        BigRational::new_raw(value.numer() * &ten_to_the(exponent), value.denom().clone())
+    } else {
+        BigRational::new_raw(value.numer().clone(), value.denom() * &ten_to_the(-exponent))
+    }
+}
+
+/// Compares ```numer / denom``` to ```10^exponent```.
+fn compare_to_power_of_ten(numer: &ScalableInteger, denom: &ScalableInteger, exponent: i64) -> Ordering {
+    if exponent >= 0 {
+        numer.cmp(&(denom * &ten_to_the(exponent)))
+    } else {
+        (numer * &ten_to_the(-exponent)).cmp(denom)
+    }
+}
+
+impl Quantity {
+    /// Converts the Quantity to ```unit``` and rounds its magnitude to an ```i64```.
+    ///
+    /// Returns an error if the Quantity can't be converted to the unit or if the rounded magnitude
+    /// doesn't fit into an ```i64```.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    /// use tantalum_unit::rounding::RoundingMode;
+    ///
+    /// let volume = Quantity::new(ratio!(12345, 10000), Liter);
+    /// assert_eq!(volume.to_i64_in(&(Milli * Liter), RoundingMode::HalfEven), Ok(1234));
+    /// assert_eq!(volume.to_i64_in(&(Milli * Liter), RoundingMode::Ceiling), Ok(1235));
+    /// ```
+    pub fn to_i64_in(&self, unit: &Unit, mode: RoundingMode) -> Result<i64, ConversionError> {
+        self.to_i128_in(unit, mode)?.try_into().map_err(|_| ConversionError::Overflow)
+    }
+
+    /// Converts the Quantity to ```unit``` and rounds its magnitude to an ```i128```, see ```to_i64_in```.
+    pub fn to_i128_in(&self, unit: &Unit, mode: RoundingMode) -> Result<i128, ConversionError> {
+        let converted = self.clone()
+            .convert_to(unit.clone())?;
+        round(&converted.magnitude, mode).to_big_int().to_i128().ok_or(ConversionError::Overflow)
+    }
+}
+
+/// Converts a unitless Quantity whose magnitude is an integer, without rounding.
+impl TryFrom<&Quantity> for i64 {
+    type Error = ConversionError;
+
+    fn try_from(quantity: &Quantity) -> Result<Self, Self::Error> {
+        if !quantity.is_unitless() {
+            return Err(ConversionError::IncompatibleUnits { from: quantity.unit.clone(), to: UNITLESS });
+        }
+        if !quantity.magnitude.is_integer() {
+            return Err(ConversionError::NotAnInteger);
+        }
+        quantity.magnitude.to_integer().to_big_int().to_i64().ok_or(ConversionError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::BigInt;
+    use crate::{int, ratio};
+    use crate::unit::Unit::*;
+    use super::*;
+    use super::RoundingMode::*;
+
+    fn rounded(value: BigRational) -> [i64; 7] {
+        [Floor, Ceiling, TowardZero, AwayFromZero, HalfUp, HalfDown, HalfEven]
+            .map(|mode| match round(&value, mode) {
+                ScalableInteger::Single(n) => n,
+                n => panic!("{n} doesn't fit"),
+            })
+    }
+
+    #[test]
+    fn round_ties() {
+        assert_eq!(rounded(ratio!(5, 2)), [2, 3, 2, 3, 3, 2, 2]);
+        assert_eq!(rounded(ratio!(7, 2)), [3, 4, 3, 4, 4, 3, 4]);
+        assert_eq!(rounded(ratio!(-5, 2)), [-3, -2, -2, -3, -3, -2, -2]);
+        assert_eq!(rounded(ratio!(-7, 2)), [-4, -3, -3, -4, -4, -3, -4]);
+    }
+
+    #[test]
+    fn round_non_ties() {
+        assert_eq!(rounded(ratio!(7, 3)), [2, 3, 2, 3, 2, 2, 2]);
+        assert_eq!(rounded(ratio!(-8, 3)), [-3, -2, -2, -3, -3, -3, -3]);
+        assert_eq!(rounded(ratio!(1, 3)), [0, 1, 0, 1, 0, 0, 0]);
+        assert_eq!(rounded(int!(4)), [4; 7]);
+    }
+
+    #[test]
+    fn round_unreduced() {
+        let value = BigRational::new_raw(5.into(), (-2).into());
+        assert_eq!(round(&value, HalfUp), (-3).into());
+    }
+
+    #[test]
+    fn exponent() {
+        assert_eq!(decimal_exponent(&int!(1)), 0);
+        assert_eq!(decimal_exponent(&int!(999)), 2);
+        assert_eq!(decimal_exponent(&int!(1000)), 3);
+        assert_eq!(decimal_exponent(&ratio!(-1, 1000)), -3);
+        assert_eq!(decimal_exponent(&ratio!(1, 1001)), -4);
+    }
+
+    #[test]
+    fn significant() {
+        assert_eq!(round_to_significant(&ratio!(2, 3), 3, HalfEven), (667.into(), -3));
+        assert_eq!(round_to_significant(&ratio!(9999, 10), 3, HalfEven), (100.into(), 1));
+        assert_eq!(round_to_significant(&int!(0), 3, HalfEven), (0.into(), 0));
+    }
+
+    #[test]
+    fn terminating_decimals() {
+        assert_eq!(terminating_decimal_places(&ratio!(1, 4)), Some(2));
+        assert_eq!(terminating_decimal_places(&ratio!(-3, 40)), Some(3));
+        assert_eq!(terminating_decimal_places(&ratio!(1, 3)), None);
+        assert_eq!(terminating_decimal_places(&ratio!(1, 12)), None);
+        assert_eq!(terminating_decimal_places(&int!(0)), Some(0));
+        assert_eq!(terminating_decimal_places(&ratio!(473176473, 125000000000i64)), Some(12));
+        assert_eq!(terminating_decimal_places(&ratio!(1, 2i128.pow(100))), Some(100));
+    }
+
+    #[test]
+    fn significant_string() {
+        assert_eq!(to_significant_string(&ratio!(1, 3), 6), "0.333333");
+        assert_eq!(to_significant_string(&ratio!(-1, 3), 2), "-0.33");
+        assert_eq!(to_significant_string(&ratio!(1, 3000), 2), "0.00033");
+        assert_eq!(to_significant_string(&int!(123456789), 6), "123457000");
+        assert_eq!(to_significant_string(&int!(5), 6), "5");
+        assert_eq!(to_significant_string(&ratio!(1, 100_000_000), 6), "1e-8");
+        assert_eq!(to_significant_string(&ratio!(-7, 10i128.pow(20)), 6), "-7e-20");
+        assert_eq!(to_significant_string(&int!(BigInt::from(3).pow(100)), 6), "5.15378e47");
+    }
+
+    #[test]
+    fn round_scaled() {
+        assert_eq!(round_to_scale(&ratio!(1, 8), 2, HalfEven), 12.into());
+        assert_eq!(round_to_scale(&ratio!(1, 8), 2, HalfUp), 13.into());
+        assert_eq!(round_to_scale(&ratio!(-1, 3), 4, Floor), (-3334).into());
+    }
+
+    #[test]
+    fn quantity_to_integer() {
+        let volume = Quantity::new(ratio!(5, 2), Milli * Liter);
+        let milliliters = [Floor, Ceiling, TowardZero, AwayFromZero, HalfUp, HalfDown, HalfEven]
+            .map(|mode| volume.to_i64_in(&(Milli * Liter), mode).unwrap());
+        assert_eq!(milliliters, [2, 3, 2, 3, 3, 2, 2]);
+
+        let volume = Quantity::new(ratio!(-35, 10000), Liter);
+        let milliliters = [Floor, Ceiling, TowardZero, AwayFromZero, HalfUp, HalfDown, HalfEven]
+            .map(|mode| volume.to_i128_in(&(Milli * Liter), mode).unwrap());
+        assert_eq!(milliliters, [-4, -3, -3, -4, -4, -3, -4]);
+
+        assert_eq!(Quantity::from_i64_with_unit(3, Kilo * Meter).to_i64_in(&Meter, HalfEven), Ok(3000));
+        assert_eq!(Quantity::from_i64_with_unit(3, Meter).to_i64_in(&Second, HalfEven),
+                   Err(ConversionError::IncompatibleUnits { from: Meter, to: Second }));
+    }
+
+    #[test]
+    fn quantity_to_integer_overflow() {
+        let huge = Quantity::from_i64_with_unit(10, Yotta * Meter);
+        assert_eq!(huge.to_i64_in(&Meter, HalfEven), Err(ConversionError::Overflow));
+        assert_eq!(huge.to_i128_in(&Meter, HalfEven), Ok(10i128.pow(25)));
+        assert_eq!(Quantity::from_i64_with_unit(i64::MAX, Meter).to_i64_in(&Meter, HalfEven), Ok(i64::MAX));
+        assert_eq!(Quantity::new(int!(i64::MAX) + ratio!(1, 2), Meter).to_i64_in(&Meter, HalfUp), Err(ConversionError::Overflow));
+    }
+
+    #[test]
+    fn exact_integer() {
+        assert_eq!(i64::try_from(&Quantity::from_i64(-42)), Ok(-42));
+        assert_eq!(i64::try_from(&Quantity::new(ratio!(8, 4), UNITLESS)), Ok(2));
+        assert_eq!(i64::try_from(&Quantity::new(ratio!(1, 3), UNITLESS)), Err(ConversionError::NotAnInteger));
+        assert_eq!(i64::try_from(&Quantity::from_i64_with_unit(3, Meter)),
+                   Err(ConversionError::IncompatibleUnits { from: Meter, to: UNITLESS }));
+        assert_eq!(i64::try_from(&Quantity::new(int!(i64::MAX) + int!(1), UNITLESS)), Err(ConversionError::Overflow));
+    }
+}
@@ -1,14 +1,15 @@
+use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
 use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign};
-use num::{BigInt, FromPrimitive, Integer, Num, One, Zero};
-use num::bigint::ParseBigIntError;
+use num::{BigInt, FromPrimitive, Integer, Num, One, Signed, ToPrimitive, Zero};
+use crate::error::ParseIntegerError;
 use num::integer::div_floor;
 use num::rational::Ratio;
 use crate::scalable_integer::ScalableInteger::{Big, Double, Single};
 
 pub type BigRational = Ratio<ScalableInteger>;
 
-#[derive(Debug, Clone, PartialOrd, Ord)]
+#[derive(Debug, Clone)]
 pub enum ScalableInteger {
     Single(i64),
     Double(i128),
@@ -16,13 +17,18 @@ pub enum ScalableInteger {
 }
 
 impl ScalableInteger {
+    /// Constructs a ```ScalableInteger``` from an ```i64```, usable in ```const``` contexts.
+    pub const fn const_from_i64(value: i64) -> Self {
+        ScalableInteger::Single(value)
+    }
+
     fn max_size(a: ScalableInteger, b: ScalableInteger) -> (ScalableInteger, ScalableInteger) {
         use ScalableInteger::*;
         match (a, b) {
             (Single(a), Single(b)) => { (Single(a), Single(b)) }
             (Single(a), Double(b)) => { (Double(a.into()), Double(b)) }
             (Single(a), Big(b)) => { (Big(a.into()), Big(b)) }
-            (Double(a), Single(b)) => { (Double(a.into()), Double(b.into())) }
+            (Double(a), Single(b)) => { (Double(a), Double(b.into())) }
             (Double(a), Double(b)) => { (Double(a), Double(b)) }
             (Double(a), Big(b)) => { (Big(a.into()), Big(b)) }
             (Big(a), Single(b)) => { (Big(a), Big(b.into())) }
@@ -52,9 +58,13 @@ impl ScalableInteger {
                 }
             }
             Big(n) => {
-                if let Ok(x) = n.clone().try_into() {
+                // Probe the magnitude by reference so the common case doesn't clone.
+                // i128::MIN is the only value of 128 bits that fits
+                if n.bits() > 128 {
+                    Big(n)
+                } else if let Some(x) = n.to_i64() {
                     Single(x)
-                } else if let Ok(x) = n.clone().try_into() {
+                } else if let Some(x) = n.to_i128() {
                     Double(x)
                 } else {
                     Big(n)
@@ -63,6 +73,40 @@ impl ScalableInteger {
         }
     }
 
+    /// Returns the number of bits needed to represent the magnitude of the integer.
+    pub fn bits(&self) -> u64 {
+        match self {
+            Single(n) => { u64::from(64 - n.unsigned_abs().leading_zeros()) }
+            Double(n) => { u64::from(128 - n.unsigned_abs().leading_zeros()) }
+            Big(n) => { n.bits() }
+        }
+    }
+
+    /// Divides out every power of ```factor```, returning the rest and the exponent of the power.
+    ///
+    /// Zero and factors below two are returned unchanged with an exponent of zero.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::scalable_integer::ScalableInteger;
+    ///
+    /// assert_eq!(ScalableInteger::from(200).remove_factor(2), (25.into(), 3));
+    /// assert_eq!(ScalableInteger::from(200).remove_factor(3), (200.into(), 0));
+    /// ```
+    pub fn remove_factor(&self, factor: u32) -> (ScalableInteger, u32) {
+        let mut rest = self.clone();
+        let mut exponent = 0;
+        if factor < 2 || rest.is_zero() {
+            return (rest, exponent);
+        }
+        let factor = ScalableInteger::from(factor);
+        while rest.is_multiple_of(&factor) {
+            rest /= factor.clone();
+            exponent += 1;
+        }
+        (rest, exponent)
+    }
+
     pub fn to_big_int(self) -> BigInt {
         match self {
             Single(n) => { BigInt::from_i64(n).unwrap() }
@@ -74,7 +118,7 @@ impl ScalableInteger {
 
 impl From<BigInt> for ScalableInteger {
     fn from(v: BigInt) -> Self {
-        ScalableInteger::Big(v)
+        ScalableInteger::Big(v).demote_size()
     }
 }
 
@@ -83,33 +127,113 @@ impl From<i8> for ScalableInteger { fn from(value: i8) -> Self { Single(value.in
 impl From<u32> for ScalableInteger { fn from(value: u32) -> Self { Single(value.into()) } }
 impl From<i32> for ScalableInteger { fn from(value: i32) -> Self { Single(value.into()) } }
 impl From<u64> for ScalableInteger { fn from(value: u64) -> Self { Double(value.into()).demote_size() } }
-impl From<i64> for ScalableInteger { fn from(value: i64) -> Self { Single(value.into()).demote_size() } }
+impl From<i64> for ScalableInteger { fn from(value: i64) -> Self { Single(value) } }
 impl From<u128> for ScalableInteger { fn from(value: u128) -> Self { Big(value.into()).demote_size() } }
-impl From<i128> for ScalableInteger { fn from(value: i128) -> Self { Double(value.into()).demote_size() } }
+impl From<i128> for ScalableInteger { fn from(value: i128) -> Self { Double(value).demote_size() } }
+
+impl ToPrimitive for ScalableInteger {
+    fn to_i64(&self) -> Option<i64> {
+        match self {
+            Single(n) => Some(*n),
+            Double(n) => n.to_i64(),
+            Big(n) => n.to_i64(),
+        }
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        match self {
+            Single(n) => Some(i128::from(*n)),
+            Double(n) => Some(*n),
+            Big(n) => n.to_i128(),
+        }
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        match self {
+            Single(n) => n.to_u64(),
+            Double(n) => n.to_u64(),
+            Big(n) => n.to_u64(),
+        }
+    }
+
+    fn to_u128(&self) -> Option<u128> {
+        match self {
+            Single(n) => n.to_u128(),
+            Double(n) => n.to_u128(),
+            Big(n) => n.to_u128(),
+        }
+    }
+
+    /// Rounds to the nearest ```f64```, which is infinite if the integer is larger than ```f64::MAX```.
+    fn to_f64(&self) -> Option<f64> {
+        match self {
+            Single(n) => n.to_f64(),
+            Double(n) => n.to_f64(),
+            Big(n) => n.to_f64(),
+        }
+    }
+}
+
+impl FromPrimitive for ScalableInteger {
+    fn from_i64(n: i64) -> Option<Self> { Some(Single(n)) }
+    fn from_u64(n: u64) -> Option<Self> { Some(n.into()) }
+    fn from_i128(n: i128) -> Option<Self> { Some(n.into()) }
+    fn from_u128(n: u128) -> Option<Self> { Some(n.into()) }
+
+    /// Truncates towards zero, and returns ```None``` for NaN and infinities.
+    fn from_f64(n: f64) -> Option<Self> {
+        BigInt::from_f64(n).map(ScalableInteger::from)
+    }
+}
 
 impl PartialEq for ScalableInteger {
     fn eq(&self, other: &Self) -> bool {
-        let (a, b) = ScalableInteger::max_size(self.clone(), other.clone());
-        match (a, b) {
-            (Single(a), Single(b)) => { a.eq(&b) }
-            (Double(a), Double(b)) => { a.eq(&b) }
-            (Big(a), Big(b)) => { a.eq(&b) }
-            _ => unreachable!()
+        match (self, other) {
+            (Single(a), Single(b)) => { a == b }
+            (Double(a), Double(b)) => { a == b }
+            (Big(a), Big(b)) => { a == b }
+            (Single(a), Double(b)) | (Double(b), Single(a)) => { i128::from(*a) == *b }
+            (Single(a), Big(b)) | (Big(b), Single(a)) => { b.to_i64() == Some(*a) }
+            (Double(a), Big(b)) | (Big(b), Double(a)) => { b.to_i128() == Some(*a) }
         }
     }
 }
 
 impl Eq for ScalableInteger {}
 
+impl PartialOrd for ScalableInteger {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ScalableInteger {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Compare the values, not the variants, since results aren't always demoted
+        match (self, other) {
+            (Single(a), Single(b)) => { a.cmp(b) }
+            (Double(a), Double(b)) => { a.cmp(b) }
+            (Big(a), Big(b)) => { a.cmp(b) }
+            (Single(a), Double(b)) => { i128::from(*a).cmp(b) }
+            (Double(a), Single(b)) => { a.cmp(&i128::from(*b)) }
+            (Single(a), Big(b)) => { BigInt::from(*a).cmp(b) }
+            (Big(a), Single(b)) => { a.cmp(&BigInt::from(*b)) }
+            (Double(a), Big(b)) => { BigInt::from(*a).cmp(b) }
+            (Big(a), Double(b)) => { a.cmp(&BigInt::from(*b)) }
+        }
+    }
+}
+
 impl Neg for ScalableInteger {
     type Output = ScalableInteger;
 
+    /// Negates the integer, promoting ```i64::MIN``` and ```i128::MIN``` whose negation overflows.
     fn neg(self) -> Self::Output {
         use ScalableInteger::*;
         match self {
-            Single(n) => Single(-n),
-            Double(n) => Double(-n),
-            Big(n) => Big(-n),
+            Single(n) => n.checked_neg().map_or_else(|| -Single(n).promote_size(), Single),
+            Double(n) => n.checked_neg().map_or_else(|| -Double(n).promote_size(), |n| Double(n).demote_size()),
+            Big(n) => Big(-n).demote_size(),
         }
     }
 }
@@ -129,6 +253,11 @@ impl Mul for &ScalableInteger {
     type Output = ScalableInteger;
 
     fn mul(self, rhs: Self) -> Self::Output {
+        if let (Single(a), Single(b)) = (self, rhs) {
+            if let Some(result) = a.checked_mul(*b) {
+                return Single(result);
+            }
+        }
         self.clone() * rhs.clone()
     }
 }
@@ -137,6 +266,11 @@ impl Div for &ScalableInteger {
     type Output = ScalableInteger;
 
     fn div(self, rhs: Self) -> Self::Output {
+        if let (Single(a), Single(b)) = (self, rhs) {
+            if let Some(result) = a.checked_div(*b) {
+                return Single(result);
+            }
+        }
         self.clone() / rhs.clone()
     }
 }
@@ -145,6 +279,11 @@ impl Add for &ScalableInteger {
     type Output = ScalableInteger;
 
     fn add(self, rhs: Self) -> Self::Output {
+        if let (Single(a), Single(b)) = (self, rhs) {
+            if let Some(result) = a.checked_add(*b) {
+                return Single(result);
+            }
+        }
         self.clone() + rhs.clone()
     }
 }
@@ -153,10 +292,43 @@ impl Sub for &ScalableInteger {
     type Output = ScalableInteger;
 
     fn sub(self, rhs: Self) -> Self::Output {
+        if let (Single(a), Single(b)) = (self, rhs) {
+            if let Some(result) = a.checked_sub(*b) {
+                return Single(result);
+            }
+        }
         self.clone() - rhs.clone()
     }
 }
 
+impl num::CheckedAdd for ScalableInteger {
+    /// Never returns ```None```, since the sum is promoted instead of overflowing.
+    fn checked_add(&self, v: &Self) -> Option<Self> {
+        Some(self + v)
+    }
+}
+
+impl num::CheckedSub for ScalableInteger {
+    /// Never returns ```None```, since the difference is promoted instead of overflowing.
+    fn checked_sub(&self, v: &Self) -> Option<Self> {
+        Some(self - v)
+    }
+}
+
+impl num::CheckedMul for ScalableInteger {
+    /// Never returns ```None```, since the product is promoted instead of overflowing.
+    fn checked_mul(&self, v: &Self) -> Option<Self> {
+        Some(self * v)
+    }
+}
+
+impl num::CheckedDiv for ScalableInteger {
+    /// Returns ```None``` only for a division by zero.
+    fn checked_div(&self, v: &Self) -> Option<Self> {
+        if v.is_zero() { None } else { Some(self / v) }
+    }
+}
+
 impl Zero for ScalableInteger {
     fn zero() -> Self {
         Single(0)
@@ -181,41 +353,44 @@ impl Add<Self> for ScalableInteger {
     type Output = Self;
 
     fn add(self, rhs: Self) -> Self::Output {
-        let (lhs, rhs) = ScalableInteger::max_size(self, rhs);
-        match (lhs.clone(), rhs.clone()) {
+        match ScalableInteger::max_size(self, rhs) {
             (Single(a), Single(b)) => {
-                if let Some(result) = a.checked_add(b) {
-                    Single(result)
-                } else {
-                    let (lhs, rhs) = (lhs.promote_size(), rhs.promote_size());
-                    lhs + rhs
+                match a.checked_add(b) {
+                    Some(result) => Single(result),
+                    // The sum of two i64 always fits into an i128 and can't be demoted again
+                    None => Double(i128::from(a) + i128::from(b)),
                 }
             }
             (Double(a), Double(b)) => {
-                if let Some(result) = a.checked_add(b) {
-                    Double(result)
-                } else {
-                    let (lhs, rhs) = (lhs.promote_size(), rhs.promote_size());
-                    lhs + rhs
+                match a.checked_add(b) {
+                    Some(result) => Double(result).demote_size(),
+                    None => Big(BigInt::from(a) + b),
                 }
             }
             (Big(a), Big(b)) => {
-                Big(a + b)
+                Big(a + b).demote_size()
             }
             _ => unreachable!()
-        }.demote_size()
+        }
     }
 }
 
 impl AddAssign for ScalableInteger {
     fn add_assign(&mut self, rhs: Self) {
-        *self = self.clone() + rhs;
+        if let (Single(a), Single(b)) = (&mut *self, &rhs) {
+            if let Some(result) = a.checked_add(*b) {
+                *a = result;
+                return;
+            }
+        }
+        *self = std::mem::replace(self, Single(0)) + rhs;
     }
 }
 
 impl Sub<Self> for ScalableInteger {
     type Output = Self;
 
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn sub(self, rhs: Self) -> Self::Output {
         let rhs = rhs.neg();
         self + rhs
@@ -224,7 +399,7 @@ impl Sub<Self> for ScalableInteger {
 
 impl SubAssign for ScalableInteger {
     fn sub_assign(&mut self, rhs: Self) {
-        *self = self.clone() - rhs;
+        *self = std::mem::replace(self, Single(0)) - rhs;
     }
 }
 
@@ -232,35 +407,31 @@ impl Mul<Self> for ScalableInteger {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        let (lhs, rhs) = ScalableInteger::max_size(self, rhs);
-        match (lhs.clone(), rhs.clone()) {
+        match ScalableInteger::max_size(self, rhs) {
             (Single(a), Single(b)) => {
-                if let Some(result) = a.checked_mul(b) {
-                    Single(result)
-                } else {
-                    let (lhs, rhs) = (lhs.promote_size(), rhs.promote_size());
-                    lhs * rhs
+                match a.checked_mul(b) {
+                    Some(result) => Single(result),
+                    // The product of two i64 always fits into an i128 and can't be demoted again
+                    None => Double(i128::from(a) * i128::from(b)),
                 }
             }
             (Double(a), Double(b)) => {
-                if let Some(result) = a.checked_mul(b) {
-                    Double(result)
-                } else {
-                    let (lhs, rhs) = (lhs.promote_size(), rhs.promote_size());
-                    lhs * rhs
+                match a.checked_mul(b) {
+                    Some(result) => Double(result).demote_size(),
+                    None => Big(BigInt::from(a) * b),
                 }
             }
             (Big(a), Big(b)) => {
-                Big(a * b)
+                Big(a * b).demote_size()
             }
             _ => unreachable!()
-        }.demote_size()
+        }
     }
 }
 
 impl MulAssign for ScalableInteger {
     fn mul_assign(&mut self, rhs: Self) {
-        *self = self.clone() * rhs;
+        *self = std::mem::replace(self, Single(0)) * rhs;
     }
 }
 
@@ -268,58 +439,59 @@ impl Div<Self> for ScalableInteger {
     type Output = Self;
 
     fn div(self, rhs: Self) -> Self::Output {
-        let (lhs, rhs) = ScalableInteger::max_size(self, rhs);
-        match (lhs.clone(), rhs.clone()) {
+        match ScalableInteger::max_size(self, rhs) {
             (Single(a), Single(b)) => {
-                if let Some(result) = a.checked_div(b) {
-                    Single(result)
-                } else {
-                    let (lhs, rhs) = (lhs.promote_size(), rhs.promote_size());
-                    lhs / rhs
+                match a.checked_div(b) {
+                    Some(result) => Single(result),
+                    None => Single(a).promote_size() / Single(b).promote_size(),
                 }
             }
             (Double(a), Double(b)) => {
-                if let Some(result) = a.checked_div(b) {
-                    Double(result)
-                } else {
-                    let (lhs, rhs) = (lhs.promote_size(), rhs.promote_size());
-                    lhs / rhs
+                match a.checked_div(b) {
+                    Some(result) => Double(result).demote_size(),
+                    None => Double(a).promote_size() / Double(b).promote_size(),
                 }
             }
             (Big(a), Big(b)) => {
-                Big(a / b)
+                Big(a / b).demote_size()
             }
             _ => unreachable!()
-        }.demote_size()
+        }
     }
 }
 
 impl DivAssign for ScalableInteger {
     fn div_assign(&mut self, rhs: Self) {
-        *self = self.clone() / rhs;
+        *self = std::mem::replace(self, Single(0)) / rhs;
     }
 }
 
 impl RemAssign<Self> for ScalableInteger {
     fn rem_assign(&mut self, rhs: Self) {
-        *self = self.clone() % rhs;
+        *self = std::mem::replace(self, Single(0)) % rhs;
     }
 }
 
 impl Rem<Self> for ScalableInteger {
     type Output = Self;
 
+    /// The remainder of truncating division, with the sign of ```self``` like ```i64::rem```.
     fn rem(self, rhs: Self) -> Self::Output {
-        let (lhs, rhs) = ScalableInteger::max_size(self.clone(), rhs.clone());
-        match (lhs, rhs) {
+        match ScalableInteger::max_size(self, rhs) {
             (Single(a), Single(b)) => {
-                Single(a.rem(&b))
+                match a.checked_rem(b) {
+                    Some(result) => Single(result),
+                    None => Single(a).promote_size() % Single(b).promote_size(),
+                }
             }
             (Double(a), Double(b)) => {
-                Double(a.rem(&b))
+                match a.checked_rem(b) {
+                    Some(result) => Double(result).demote_size(),
+                    None => Double(a).promote_size() % Double(b).promote_size(),
+                }
             }
             (Big(a), Big(b)) => {
-                Big(a.rem(&b))
+                Big(a % b).demote_size()
             }
             _ => unreachable!()
         }
@@ -327,93 +499,124 @@ impl Rem<Self> for ScalableInteger {
 }
 
 impl Num for ScalableInteger {
-    type FromStrRadixErr = ParseBigIntError;
+    type FromStrRadixErr = ParseIntegerError;
 
+    /// Parses an integer with an optional sign in a radix between 2 and 36, into the smallest
+    /// variant it fits into.
     fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        if !(2..=36).contains(&radix) {
+            return Err(ParseIntegerError::InvalidRadix(radix));
+        }
+
+        let digits = str.strip_prefix(['+', '-']).unwrap_or(str);
+        if digits.is_empty() {
+            return Err(ParseIntegerError::Empty);
+        }
+        // BigInt also accepts underscores between digits, unlike the primitive types
+        if !digits.chars().all(|c| c.is_digit(radix)) {
+            return Err(ParseIntegerError::InvalidDigit);
+        }
+
         if let Ok(parsed_int) = i64::from_str_radix(str, radix) {
             Ok(Single(parsed_int))
         } else if let Ok(parsed_int) = i128::from_str_radix(str, radix) {
             Ok(Double(parsed_int))
         } else {
-            let parsed_int = BigInt::from_str_radix(str, radix)?;
-            Ok(Big(parsed_int))
+            let parsed_int = BigInt::from_str_radix(str, radix).expect("the digits are valid");
+            Ok(Big(parsed_int).demote_size())
         }
     }
 }
 
 impl Integer for ScalableInteger {
+    /// Division rounding towards negative infinity, like ```i64::div_floor```.
     fn div_floor(&self, other: &Self) -> Self {
-        let (lhs, rhs) = ScalableInteger::max_size(self.clone(), other.clone());
-        match (lhs, rhs) {
+        match ScalableInteger::max_size(self.clone(), other.clone()) {
             (Single(a), Single(b)) => {
-                Single(div_floor(a, b))
+                match a.checked_div(b) {
+                    Some(_) => Single(div_floor(a, b)),
+                    None => Single(a).promote_size().div_floor(&Single(b).promote_size()),
+                }
             }
             (Double(a), Double(b)) => {
-                Double(div_floor(a, b))
+                match a.checked_div(b) {
+                    Some(_) => Double(div_floor(a, b)).demote_size(),
+                    None => Double(a).promote_size().div_floor(&Double(b).promote_size()),
+                }
             }
             (Big(a), Big(b)) => {
-                Big(a.div_floor(&b))
+                Big(a.div_floor(&b)).demote_size()
             }
             _ => unreachable!()
         }
     }
 
+    /// The remainder of ```div_floor```, which has the sign of ```other```.
     fn mod_floor(&self, other: &Self) -> Self {
-        let (lhs, rhs) = ScalableInteger::max_size(self.clone(), other.clone());
-        match (lhs, rhs) {
+        match ScalableInteger::max_size(self.clone(), other.clone()) {
             (Single(a), Single(b)) => {
-                Single(a.mod_floor(&b))
+                match a.checked_rem(b) {
+                    Some(_) => Single(a.mod_floor(&b)),
+                    None => Single(a).promote_size().mod_floor(&Single(b).promote_size()),
+                }
             }
             (Double(a), Double(b)) => {
-                Double(a.mod_floor(&b))
+                match a.checked_rem(b) {
+                    Some(_) => Double(a.mod_floor(&b)).demote_size(),
+                    None => Double(a).promote_size().mod_floor(&Double(b).promote_size()),
+                }
             }
             (Big(a), Big(b)) => {
-                Big(a.mod_floor(&b))
+                Big(a.mod_floor(&b)).demote_size()
             }
             _ => unreachable!()
         }
     }
 
+    /// The greatest common divisor, which is never negative.
     fn gcd(&self, other: &Self) -> Self {
         let (lhs, rhs) = ScalableInteger::max_size(self.clone(), other.clone());
         match (lhs, rhs) {
+            // The gcd of the minimum with itself or zero is its absolute value, which doesn't fit
+            (Single(a), Single(b)) if a == i64::MIN || b == i64::MIN => {
+                Single(a).promote_size().gcd(&Single(b).promote_size()).demote_size()
+            }
             (Single(a), Single(b)) => {
                 Single(a.gcd(&b))
             }
+            (Double(a), Double(b)) if a == i128::MIN || b == i128::MIN => {
+                Double(a).promote_size().gcd(&Double(b).promote_size())
+            }
             (Double(a), Double(b)) => {
                 Double(a.gcd(&b))
             }
             (Big(a), Big(b)) => {
-                Big(a.gcd(&b))
+                Big(a.gcd(&b)).demote_size()
             }
             _ => unreachable!()
         }
     }
 
     fn lcm(&self, other: &Self) -> Self {
-        let (lhs, rhs) = ScalableInteger::max_size(self.clone(), other.clone());
-        match (lhs, rhs) {
-            (Single(a), Single(b)) => {
-                Single(a.lcm(&b))
-            }
-            (Double(a), Double(b)) => {
-                Double(a.lcm(&b))
-            }
-            (Big(a), Big(b)) => {
-                Big(a.lcm(&b))
-            }
-            _ => unreachable!()
+        if self.is_zero() || other.is_zero() {
+            return Single(0);
         }
+
+        // The lcm of two i64 may not fit into an i64, so let Mul promote the result
+        let lcm = self.clone() / self.gcd(other) * other.clone();
+        if lcm < Single(0) { -lcm } else { lcm }
     }
 
+    /// Whether ```other``` divides ```self```. Only zero is a multiple of zero, like for ```i64```.
     fn is_multiple_of(&self, other: &Self) -> bool {
         let (lhs, rhs) = ScalableInteger::max_size(self.clone(), other.clone());
+        // The remainder is only missing for a zero divisor or for MIN % -1, which is zero
         match (lhs, rhs) {
             (Single(a), Single(b)) => {
-                a.is_multiple_of(&b)
+                a.checked_rem(b).map_or(b != 0 || a == 0, |rem| rem == 0)
             }
             (Double(a), Double(b)) => {
-                a.is_multiple_of(&b)
+                a.checked_rem(b).map_or(b != 0 || a == 0, |rem| rem == 0)
             }
             (Big(a), Big(b)) => {
                 a.is_multiple_of(&b)
@@ -434,22 +637,649 @@ impl Integer for ScalableInteger {
         !self.is_even()
     }
 
+    /// Truncating division and its remainder, like ```i64::div_rem```.
     fn div_rem(&self, other: &Self) -> (Self, Self) {
-        let (lhs, rhs) = ScalableInteger::max_size(self.clone(), other.clone());
-        match (lhs, rhs) {
+        match ScalableInteger::max_size(self.clone(), other.clone()) {
             (Single(a), Single(b)) => {
-                let (div, rem) = a.div_rem(&b);
-                (Single(div), Single(rem))
+                match a.checked_div(b) {
+                    Some(_) => {
+                        let (div, rem) = a.div_rem(&b);
+                        (Single(div), Single(rem))
+                    }
+                    None => Single(a).promote_size().div_rem(&Single(b).promote_size()),
+                }
             }
             (Double(a), Double(b)) => {
-                let (div, rem) = a.div_rem(&b);
-                (Double(div), Double(rem))
+                match a.checked_div(b) {
+                    Some(_) => {
+                        let (div, rem) = a.div_rem(&b);
+                        (Double(div).demote_size(), Double(rem).demote_size())
+                    }
+                    None => Double(a).promote_size().div_rem(&Double(b).promote_size()),
+                }
             }
             (Big(a), Big(b)) => {
                 let (div, rem) = a.div_rem(&b);
-                (Big(div), Big(rem))
+                (Big(div).demote_size(), Big(rem).demote_size())
             }
             _ => unreachable!()
         }
     }
 }
+
+impl Signed for ScalableInteger {
+    /// The absolute value, promoting ```i64::MIN``` and ```i128::MIN``` like ```Neg```.
+    fn abs(&self) -> Self {
+        if self.is_negative() { -self.clone() } else { self.clone() }
+    }
+
+    /// The difference ```self - other``` if it's positive, and zero otherwise.
+    fn abs_sub(&self, other: &Self) -> Self {
+        if self <= other { Self::zero() } else { self - other }
+    }
+
+    fn signum(&self) -> Self {
+        match self.cmp(&Self::zero()) {
+            Ordering::Less => Single(-1),
+            Ordering::Equal => Single(0),
+            Ordering::Greater => Single(1),
+        }
+    }
+
+    fn is_positive(&self) -> bool {
+        match self {
+            Single(n) => *n > 0,
+            Double(n) => *n > 0,
+            Big(n) => n.is_positive(),
+        }
+    }
+
+    fn is_negative(&self) -> bool {
+        match self {
+            Single(n) => *n < 0,
+            Double(n) => *n < 0,
+            Big(n) => n.is_negative(),
+        }
+    }
+}
+
+/// The convergents of the continued fraction of a rational, from the integer part to the
+/// rational itself.
+///
+/// Every convergent is closer to the rational than any fraction with a smaller denominator.
+/// Terms are taken with floor division, so negative rationals work like positive ones and an
+/// integer has a single convergent.
+///
+/// # Example:
+/// ```
+/// use tantalum_unit::prelude::*;
+/// use tantalum_unit::scalable_integer::convergents;
+///
+/// let convergents: Vec<_> = convergents(&ratio!(415, 93)).collect();
+/// assert_eq!(convergents, [int!(4), ratio!(9, 2), ratio!(58, 13), ratio!(415, 93)]);
+/// ```
+pub fn convergents(r: &BigRational) -> impl Iterator<Item = BigRational> {
+    let (mut numer, mut denom) = (r.numer().clone(), r.denom().clone());
+    // The last two convergents, starting with the conventional 1/0 and 0/1
+    let (mut h, mut h_previous) = (ScalableInteger::one(), ScalableInteger::zero());
+    let (mut k, mut k_previous) = (ScalableInteger::zero(), ScalableInteger::one());
+    std::iter::from_fn(move || {
+        if denom.is_zero() {
+            return None;
+        }
+        let term = numer.div_floor(&denom);
+        (numer, denom) = (denom.clone(), numer.clone() - term.clone() * denom.clone());
+        (h, h_previous) = (term.clone() * h.clone() + h_previous.clone(), h.clone());
+        (k, k_previous) = (term * k.clone() + k_previous.clone(), k.clone());
+        Some(BigRational::new_raw(h.clone(), k.clone()))
+    })
+}
+
+/// The rational closest to ```r``` whose denominator is at most ```max_denominator```.
+///
+/// The result is either a convergent of ```r``` or a semiconvergent between two of them. Of two
+/// equally close rationals the one with the smaller denominator is returned. Panics if
+/// ```max_denominator``` is less than one.
+///
+/// # Example:
+/// ```
+/// use tantalum_unit::prelude::*;
+/// use tantalum_unit::scalable_integer::best_approximation;
+///
+/// let pi = ratio!(3141592653589793i64, 1000000000000000i64);
+/// assert_eq!(best_approximation(&pi, &10.into()), ratio!(22, 7));
+/// assert_eq!(best_approximation(&pi, &1000.into()), ratio!(355, 113));
+/// ```
+pub fn best_approximation(r: &BigRational, max_denominator: &ScalableInteger) -> BigRational {
+    assert!(*max_denominator >= ScalableInteger::one(), "the maximum denominator must be at least one");
+    if r.denom() <= max_denominator {
+        return r.clone();
+    }
+
+    let mut previous = BigRational::from_integer(r.numer().div_floor(r.denom()));
+    let mut before_previous = BigRational::new_raw(ScalableInteger::one(), ScalableInteger::zero());
+    for convergent in convergents(r).skip(1) {
+        if convergent.denom() > max_denominator {
+            break;
+        }
+        before_previous = std::mem::replace(&mut previous, convergent);
+    }
+
+    // The semiconvergent with the largest allowed multiple of the last convergent
+    let multiple = (max_denominator.clone() - before_previous.denom().clone()).div_floor(previous.denom());
+    let semiconvergent = BigRational::new(
+        before_previous.numer().clone() + multiple.clone() * previous.numer().clone(),
+        before_previous.denom().clone() + multiple * previous.denom().clone(),
+    );
+    let distance = |candidate: &BigRational| {
+        let difference = candidate - r;
+        if difference < BigRational::zero() { -difference } else { difference }
+    };
+    match distance(&semiconvergent).cmp(&distance(&previous)) {
+        Ordering::Less => semiconvergent,
+        Ordering::Equal if semiconvergent.denom() < previous.denom() => semiconvergent,
+        _ => previous,
+    }
+}
+
+/// A lazy decimal expansion of a rational, see [decimal_digits].
+#[derive(Clone, Debug)]
+pub struct DecimalDigits {
+    negative: bool,
+    integer: Option<ScalableInteger>,
+    remainder: ScalableInteger,
+    denom: ScalableInteger,
+}
+
+impl DecimalDigits {
+    /// Whether the expanded rational is negative. The yielded digits are those of its absolute
+    /// value, so that values like -1/8 keep their sign despite an integer part of zero.
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// The bounds of the repetend as fractional digit positions, starting at zero for the first
+    /// digit after the decimal point. The end is exclusive. Returns ```None``` if the expansion
+    /// terminates.
+    ///
+    /// The repetend starts after as many digits as the powers of two and five in the denominator
+    /// require, and is as long as the order of ten modulo the rest of the denominator.
+    pub fn repetend(&self) -> Option<(usize, usize)> {
+        let (rest, twos) = self.denom.remove_factor(2);
+        let (rest, fives) = rest.remove_factor(5);
+        if rest.is_one() {
+            return None;
+        }
+        let start = twos.max(fives) as usize;
+        let ten = ScalableInteger::from(10);
+        let mut power = ten.clone() % rest.clone();
+        let mut period = 1;
+        while !power.is_one() {
+            power = power * ten.clone() % rest.clone();
+            period += 1;
+        }
+        Some((start, start + period))
+    }
+}
+
+impl Iterator for DecimalDigits {
+    type Item = ScalableInteger;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(integer) = self.integer.take() {
+            return Some(integer);
+        }
+        if self.remainder.is_zero() {
+            return None;
+        }
+        let (digit, remainder) = (self.remainder.clone() * 10.into()).div_rem(&self.denom);
+        self.remainder = remainder;
+        Some(digit)
+    }
+}
+
+/// The decimal expansion of the absolute value of a rational by long division, yielding the
+/// integer part and then the fractional digits one at a time.
+///
+/// The iterator ends after the last digit of a terminating expansion and never ends otherwise.
+///
+/// # Example:
+/// ```
+/// use tantalum_unit::prelude::*;
+/// use tantalum_unit::scalable_integer::{decimal_digits, ScalableInteger};
+///
+/// let digits: Vec<_> = decimal_digits(&ratio!(-25, 8)).collect();
+/// assert_eq!(digits, [3, 1, 2, 5].map(ScalableInteger::from));
+///
+/// let sevenths = decimal_digits(&ratio!(1, 7));
+/// assert_eq!(sevenths.repetend(), Some((0, 6)));
+/// let digits: Vec<_> = sevenths.skip(1).take(8).map(|d| d.to_string()).collect();
+/// assert_eq!(digits.concat(), "14285714");
+/// ```
+pub fn decimal_digits(r: &BigRational) -> DecimalDigits {
+    let negative = *r < BigRational::zero();
+    let numer = if negative { -r.numer().clone() } else { r.numer().clone() };
+    let (integer, remainder) = numer.div_rem(r.denom());
+    DecimalDigits { negative, integer: Some(integer), remainder, denom: r.denom().clone() }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+    use crate::{int, ratio};
+    use super::*;
+
+    #[test]
+    fn overflow_promotes() {
+        let result = Single(i64::MAX) + Single(1);
+        assert_eq!(result, Double(i64::MAX as i128 + 1));
+
+        let result = Single(i64::MAX) * Single(i64::MAX);
+        assert_eq!(result, Double(i64::MAX as i128 * i64::MAX as i128));
+
+        let result = Double(i128::MAX) + Single(1);
+        assert_eq!(result, Big(BigInt::from(i128::MAX) + 1));
+
+        let result = Single(i64::MIN) / Single(-1);
+        assert_eq!(result, Double(-(i64::MIN as i128)));
+    }
+
+    #[test]
+    fn remainder() {
+        // The sign follows the dividend, like i64::rem
+        for (a, b) in [(7, 3), (-7, 3), (7, -3), (-7, -3), (6, 3), (0, -5)] {
+            assert_eq!(Single(a) % Single(b), Single(a % b), "{a} % {b}");
+        }
+
+        // i64::MIN % -1 overflows in i64, as does i128::MIN % -1 in i128
+        assert!(matches!(Single(i64::MIN) % Single(-1), Single(0)));
+        assert!(matches!(Double(i128::MIN) % Single(-1), Single(0)));
+
+        // Mixed variants are computed at the larger size and demoted
+        assert!(matches!(Double(i128::MAX) % Single(10), Single(7)));
+        assert!(matches!(Single(-17) % Double(1 << 100), Single(-17)));
+        assert!(matches!(Big(BigInt::from(u128::MAX)) % Double(i128::MAX), Single(1)));
+        assert!(matches!(Single(i64::MIN) % Big(BigInt::from(-7)), Single(-1)));
+
+        let mut remainder = Single(-10);
+        remainder %= Double(4);
+        assert!(matches!(remainder, Single(-2)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn remainder_by_zero_panics() {
+        let _ = Single(1) % Single(0);
+    }
+
+    #[test]
+    fn parse_radix() {
+        assert!(matches!(ScalableInteger::from_str_radix("-ff", 16), Ok(Single(-255))));
+        assert!(matches!(ScalableInteger::from_str_radix("+101", 2), Ok(Single(5))));
+        assert!(matches!(ScalableInteger::from_str_radix("zz", 36), Ok(Single(1295))));
+
+        let double = ScalableInteger::from_str_radix("7fffffffffffffffffffffffffffffff", 16);
+        assert!(matches!(double, Ok(Double(i128::MAX))));
+
+        let huge = "f".repeat(64);
+        let parsed = ScalableInteger::from_str_radix(&huge, 16).unwrap();
+        assert!(matches!(&parsed, Big(_)));
+        assert_eq!(parsed, Big((BigInt::from(1) << 256) - 1));
+        let negative = ScalableInteger::from_str_radix(&format!("-{huge}"), 16).unwrap();
+        assert_eq!(negative, Big(BigInt::from(1) - (BigInt::from(1) << 256)));
+
+        // The smallest variant is used even for strings with many leading zeros
+        let padded = format!("{}42", "0".repeat(60));
+        assert!(matches!(ScalableInteger::from_str_radix(&padded, 10), Ok(Single(42))));
+    }
+
+    #[test]
+    fn parse_radix_errors() {
+        assert_eq!(ScalableInteger::from_str_radix("", 10), Err(ParseIntegerError::Empty));
+        assert_eq!(ScalableInteger::from_str_radix("-", 10), Err(ParseIntegerError::Empty));
+        assert_eq!(ScalableInteger::from_str_radix("12a", 10), Err(ParseIntegerError::InvalidDigit));
+        assert_eq!(ScalableInteger::from_str_radix("102", 2), Err(ParseIntegerError::InvalidDigit));
+        assert_eq!(ScalableInteger::from_str_radix("1_000", 10), Err(ParseIntegerError::InvalidDigit));
+        assert_eq!(ScalableInteger::from_str_radix(&format!("{}g", "f".repeat(64)), 16), Err(ParseIntegerError::InvalidDigit));
+        assert_eq!(ScalableInteger::from_str_radix("10", 37), Err(ParseIntegerError::InvalidRadix(37)));
+        assert_eq!(ScalableInteger::from_str_radix("10", 1), Err(ParseIntegerError::InvalidRadix(1)));
+        assert_eq!(ParseIntegerError::InvalidRadix(1).to_string(), "the radix 1 isn't between 2 and 36");
+    }
+
+    #[test]
+    fn floor_division() {
+        let variants = |n: i64| [Single(n), Double(n.into()), Big(n.into())];
+        for (a, b) in [(7, 2), (-7, 2), (7, -2), (-7, -2), (6, -3), (-1, 5), (0, -4)] {
+            for lhs in variants(a) {
+                for rhs in variants(b) {
+                    assert_eq!(lhs.div_floor(&rhs), Single(div_floor(a, b)), "{a} div_floor {b}");
+                    assert_eq!(lhs.mod_floor(&rhs), Single(a.mod_floor(&b)), "{a} mod_floor {b}");
+                    let (div, rem) = a.div_rem(&b);
+                    assert_eq!(lhs.div_rem(&rhs), (Single(div), Single(rem)), "{a} div_rem {b}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn floor_division_overflow_promotes() {
+        let quotient = Double(-(i64::MIN as i128));
+        assert_eq!(Single(i64::MIN).div_floor(&Single(-1)), quotient);
+        assert_eq!(Single(i64::MIN).mod_floor(&Single(-1)), Single(0));
+        assert_eq!(Single(i64::MIN).div_rem(&Single(-1)), (quotient, Single(0)));
+
+        let quotient = Big(-BigInt::from(i128::MIN));
+        assert_eq!(Double(i128::MIN).div_floor(&Single(-1)), quotient);
+        assert_eq!(Double(i128::MIN).mod_floor(&Double(-1)), Single(0));
+        assert_eq!(Double(i128::MIN).div_rem(&Single(-1)), (quotient, Single(0)));
+
+        // Results are demoted like those of the operators
+        assert!(matches!(Double(-(1 << 80)).div_floor(&Double(1 << 79)), Single(-2)));
+        assert!(matches!(Double(-(1 << 80) - 1).mod_floor(&Double(1 << 79)), Double(_)));
+        assert!(matches!(Double(-(1 << 80) - 1).div_rem(&Double(1 << 79)), (Single(-2), Single(-1))));
+    }
+
+    #[test]
+    fn multiples() {
+        let variants = |n: i64| [Single(n), Double(n.into()), Big(n.into())];
+        for (a, b, expected) in [(10, 5, true), (-10, 5, true), (10, -5, true), (10, 3, false), (-7, -2, false), (0, 7, true)] {
+            for lhs in variants(a) {
+                for rhs in variants(b) {
+                    assert_eq!(lhs.is_multiple_of(&rhs), expected, "{a} is_multiple_of {b}");
+                }
+            }
+        }
+
+        // Only zero is a multiple of zero
+        for lhs in variants(0) {
+            assert!(lhs.is_multiple_of(&Single(0)));
+        }
+        for lhs in variants(3) {
+            assert!(!lhs.is_multiple_of(&Double(0)));
+        }
+
+        // MIN % -1 overflows, but MIN is a multiple of -1
+        assert!(Single(i64::MIN).is_multiple_of(&Single(-1)));
+        assert!(Double(i128::MIN).is_multiple_of(&Double(-1)));
+        assert!(Big(BigInt::from(10).pow(40)).is_multiple_of(&Single(5)));
+        assert!(!Big(BigInt::from(10).pow(40) + 1).is_multiple_of(&Double(10)));
+    }
+
+    #[test]
+    fn negation_promotes() {
+        assert_eq!(-Single(i64::MIN), Double(-(i64::MIN as i128)));
+        assert_eq!(-Double(i128::MIN), Big(-BigInt::from(i128::MIN)));
+        assert!(matches!(-Single(5), Single(-5)));
+
+        // Negating back demotes to the smallest variant
+        assert!(matches!(-Double(-(i64::MIN as i128)), Single(i64::MIN)));
+        assert!(matches!(-Big(-BigInt::from(i128::MIN)), Double(i128::MIN)));
+
+        assert_eq!(Single(0) - Single(i64::MIN), Double(-(i64::MIN as i128)));
+        assert!(matches!(Single(-1) - Single(i64::MIN), Single(i64::MAX)));
+        assert!(matches!(Double(0) - Double(i128::MIN) - Double(i128::MAX), Single(1)));
+    }
+
+    #[test]
+    fn signed() {
+        assert_eq!(Single(i64::MIN).abs(), Double(-(i64::MIN as i128)));
+        assert_eq!(Double(i128::MIN).abs(), Big(-BigInt::from(i128::MIN)));
+        assert!(matches!(Double(-5).abs(), Single(5)));
+        assert!(matches!(Big(BigInt::from(-7)).signum(), Single(-1)));
+        assert!(matches!(Single(0).signum(), Single(0)));
+        assert!(Double(1 << 100).is_positive());
+        assert!(Big(-BigInt::from(u128::MAX)).is_negative());
+        assert!(!Single(0).is_positive() && !Single(0).is_negative());
+        assert_eq!(Single(3).abs_sub(&Single(i64::MIN)), Double(3 - i64::MIN as i128));
+        assert!(matches!(Single(-3).abs_sub(&Single(4)), Single(0)));
+
+        // The gcd of the minimum with itself or zero is its absolute value
+        assert_eq!(Single(i64::MIN).gcd(&Single(i64::MIN)), Double(-(i64::MIN as i128)));
+        assert_eq!(Single(0).gcd(&Single(i64::MIN)), Double(-(i64::MIN as i128)));
+        assert!(matches!(Single(i64::MIN).gcd(&Single(-6)), Single(2)));
+        assert_eq!(Double(i128::MIN).gcd(&Double(i128::MIN)), Big(-BigInt::from(i128::MIN)));
+        assert!(matches!(Double(i128::MIN).gcd(&Single(12)), Single(4)));
+
+        // Rationals over ScalableInteger get Signed as well
+        assert_eq!(ratio!(-3, 4).abs(), ratio!(3, 4));
+        assert_eq!(ratio!(5, -2).signum(), int!(-1));
+        assert!(ratio!(-1, 3).is_negative());
+        assert!(ratio!(-1, -3).is_positive());
+        assert_eq!(ratio!(i64::MIN, 3).abs(), BigRational::new(Double(-(i64::MIN as i128)), Single(3)));
+        assert_eq!(ratio!(i64::MIN, i64::MIN), int!(1));
+        assert_eq!(BigRational::new(Single(i64::MIN), Single(1)).abs(), BigRational::from_integer(Double(-(i64::MIN as i128))));
+        assert_eq!(ratio!(1, 2).abs_sub(&ratio!(-1, 4)), ratio!(3, 4));
+    }
+
+    #[test]
+    fn primitive_conversions() {
+        assert_eq!(Single(-5).to_i64(), Some(-5));
+        assert_eq!(Single(-5).to_u64(), None);
+        assert_eq!(Double(i64::MAX as i128 + 1).to_i64(), None);
+        assert_eq!(Double(i64::MAX as i128 + 1).to_u64(), Some(i64::MAX as u64 + 1));
+        assert_eq!(Double(i128::MIN).to_i128(), Some(i128::MIN));
+
+        // Values that only fit into the Big variant
+        let big = ScalableInteger::from(u128::MAX);
+        assert!(matches!(big, Big(_)));
+        assert_eq!(big.to_u128(), Some(u128::MAX));
+        assert_eq!(big.to_i128(), None);
+        assert_eq!(big.to_i64(), None);
+        assert_eq!((big.clone() * big.clone()).to_u128(), None);
+
+        // Large integers round to the nearest float, ties to even
+        let power = Big(BigInt::from(1) << 200);
+        assert_eq!((power.clone() + Single(1)).to_f64(), Some(2f64.powi(200)));
+        assert_eq!(Double((1 << 53) + 1).to_f64(), Some(2f64.powi(53)));
+        assert_eq!(Double((1 << 53) + 3).to_f64(), Some(2f64.powi(53) + 4.0));
+        assert_eq!(Big(BigInt::from(1) << 1024).to_f64(), Some(f64::INFINITY));
+        assert_eq!((-power).to_f64(), Some(-(2f64.powi(200))));
+
+        assert!(matches!(ScalableInteger::from_u64(u64::MAX), Some(Double(n)) if n == u64::MAX as i128));
+        assert!(matches!(ScalableInteger::from_u128(7), Some(Single(7))));
+        assert!(matches!(ScalableInteger::from_f64(-2.9), Some(Single(-2))));
+        assert!(matches!(ScalableInteger::from_f64(1e30), Some(Double(_))));
+        assert_eq!(ScalableInteger::from_f64(2f64.powi(200)), Some(Big(BigInt::from(1) << 200)));
+        assert_eq!(ScalableInteger::from_f64(f64::NAN), None);
+        assert_eq!(ScalableInteger::from_f64(f64::INFINITY), None);
+    }
+
+    #[test]
+    fn checked_arithmetic() {
+        use num::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub};
+
+        let values = [Single(i64::MIN), Single(-3), Double(i128::MAX), Double(i128::MIN), Big(BigInt::from(u128::MAX) * 5)];
+        for a in &values {
+            for b in &values {
+                let (x, y) = (a.clone().to_big_int(), b.clone().to_big_int());
+                assert_eq!(a.checked_add(b).map(ScalableInteger::to_big_int), Some(&x + &y));
+                assert_eq!(a.checked_sub(b).map(ScalableInteger::to_big_int), Some(&x - &y));
+                assert_eq!(a.checked_mul(b).map(ScalableInteger::to_big_int), Some(&x * &y));
+                assert_eq!(a.checked_div(b).map(ScalableInteger::to_big_int), Some(&x / &y));
+            }
+        }
+
+        assert!(matches!(Single(i64::MIN).checked_div(&Single(-1)), Some(Double(n)) if n == -(i64::MIN as i128)));
+        assert!(matches!(Double(i128::MAX).checked_sub(&Double(i128::MAX - 1)), Some(Single(1))));
+        assert_eq!(Single(1).checked_div(&Single(0)), None);
+        assert_eq!(Double(i128::MAX).checked_div(&Double(0)), None);
+        assert_eq!(Big(BigInt::from(u128::MAX)).checked_div(&Big(BigInt::zero())), None);
+    }
+
+    #[test]
+    fn results_are_demoted() {
+        let result = Double(i64::MAX as i128 + 1) - Single(1);
+        assert!(matches!(result, Single(i64::MAX)));
+
+        let result = Big(BigInt::from(i128::MAX) + 1) - Single(1);
+        assert!(matches!(result, Double(i128::MAX)));
+
+        let result = Big(BigInt::from(u128::MAX)) / Big(BigInt::from(u128::MAX));
+        assert!(matches!(result, Single(1)));
+
+        let (div, rem) = Big(BigInt::from(u128::MAX)).div_mod_floor(&Big(BigInt::from(u128::MAX) - 1));
+        assert!(matches!((div, rem), (Single(1), Single(1))));
+    }
+
+    #[test]
+    fn lcm_promotes() {
+        let result = Single(i64::MAX).lcm(&Single(i64::MAX - 1));
+        assert_eq!(result, Double(i64::MAX as i128 * (i64::MAX as i128 - 1)));
+
+        let result = Single(-4).lcm(&Double(6));
+        assert_eq!(result, Single(12));
+    }
+
+    #[test]
+    fn ordering_across_variants() {
+        assert!(Single(-1) < Double(0));
+        assert!(Double(-(1 << 100)) < Single(0));
+        assert!(Big(BigInt::from(-1)) < Single(0));
+        assert!(Single(i64::MAX) < Big(BigInt::from(u128::MAX)));
+        assert_eq!(Single(3).cmp(&Double(3)), Ordering::Equal);
+    }
+
+    #[test]
+    fn remove_factor() {
+        assert_eq!(Single(200).remove_factor(2), (Single(25), 3));
+        assert_eq!(Single(200).remove_factor(5), (Single(8), 2));
+        assert_eq!(Single(-7).remove_factor(2), (Single(-7), 0));
+        assert_eq!(Single(0).remove_factor(2), (Single(0), 0));
+        assert_eq!(Single(8).remove_factor(1), (Single(8), 0));
+        assert_eq!(Big(BigInt::from(5).pow(60) * 3).remove_factor(5), (Single(3), 60));
+    }
+
+    #[test]
+    fn equality_across_variants() {
+        assert_eq!(Single(42), Double(42));
+        assert_eq!(Big(BigInt::from(42)), Single(42));
+        assert_eq!(Double(i128::MAX), Big(BigInt::from(i128::MAX)));
+        assert_ne!(Single(-1), Big(BigInt::from(u128::MAX)));
+    }
+
+    #[test]
+    fn continued_fraction_convergents() {
+        let collect = |r: BigRational| convergents(&r).collect::<Vec<_>>();
+        assert_eq!(collect(ratio!(415, 93)), [int!(4), ratio!(9, 2), ratio!(58, 13), ratio!(415, 93)]);
+        assert_eq!(collect(ratio!(-7, 3)), [int!(-3), int!(-2), ratio!(-7, 3)]);
+        assert_eq!(collect(ratio!(3, 5)), [int!(0), int!(1), ratio!(1, 2), ratio!(3, 5)]);
+        assert_eq!(collect(int!(5)), [int!(5)]);
+        assert_eq!(collect(int!(-5)), [int!(-5)]);
+        assert_eq!(collect(int!(0)), [int!(0)]);
+    }
+
+    #[test]
+    fn best_approximations() {
+        let pi = ratio!(3141592653589793i64, 1000000000000000i64);
+        assert_eq!(best_approximation(&pi, &1.into()), int!(3));
+        assert_eq!(best_approximation(&pi, &7.into()), ratio!(22, 7));
+        assert_eq!(best_approximation(&pi, &100.into()), ratio!(311, 99));
+        assert_eq!(best_approximation(&pi, &1000.into()), ratio!(355, 113));
+        assert_eq!(best_approximation(&-pi, &1000.into()), ratio!(-355, 113));
+        assert_eq!(best_approximation(&ratio!(1, 3), &2.into()), ratio!(1, 2));
+        assert_eq!(best_approximation(&ratio!(1, 4), &3.into()), ratio!(1, 3));
+        assert_eq!(best_approximation(&ratio!(9, 10), &1.into()), int!(1));
+        assert_eq!(best_approximation(&int!(7), &1.into()), int!(7));
+    }
+
+    #[test]
+    #[should_panic]
+    fn best_approximation_without_denominators() {
+        best_approximation(&ratio!(1, 3), &0.into());
+    }
+
+    fn distance(a: &BigRational, b: &BigRational) -> BigRational {
+        let difference = a - b;
+        if difference < BigRational::zero() { -difference } else { difference }
+    }
+
+    /// The distance of the closest fraction with the denominator to ```r```.
+    fn closest_distance(r: &BigRational, denominator: i64) -> BigRational {
+        let denominator = ScalableInteger::from(denominator);
+        let below = (r.numer().clone() * denominator.clone()).div_floor(r.denom());
+        let candidates = [below.clone(), below + ScalableInteger::one()];
+        candidates.into_iter()
+            .map(|numer| distance(r, &BigRational::new(numer, denominator.clone())))
+            .min()
+            .unwrap()
+    }
+
+    proptest! {
+        #[test]
+        fn convergents_are_best_approximations(numer in -100_000i64..100_000, denom in 1i64..2_000) {
+            let r = BigRational::new(numer.into(), denom.into());
+            let mut count = 0;
+            for convergent in convergents(&r) {
+                let closest = distance(&r, &convergent);
+                let k = convergent.denom().clone().to_big_int().to_i64().unwrap();
+                for smaller in 1..k {
+                    prop_assert!(closest < closest_distance(&r, smaller), "{} vs denominator {}", convergent, smaller);
+                }
+                count += 1;
+            }
+            prop_assert!(count >= 1);
+            prop_assert_eq!(convergents(&r).last(), Some(r));
+        }
+
+        #[test]
+        fn best_approximation_is_closest(numer in -100_000i64..100_000, denom in 1i64..2_000, max in 1i64..60) {
+            let r = BigRational::new(numer.into(), denom.into());
+            let best = best_approximation(&r, &max.into());
+            prop_assert!(*best.denom() <= ScalableInteger::from(max));
+            let closest = (1..=max).map(|d| closest_distance(&r, d)).min().unwrap();
+            prop_assert_eq!(distance(&r, &best), closest);
+        }
+    }
+
+    fn digits(r: &BigRational, count: usize) -> Vec<ScalableInteger> {
+        decimal_digits(r).take(count).collect()
+    }
+
+    #[test]
+    fn terminating_decimal_digits() {
+        let eighth = decimal_digits(&ratio!(1, 8));
+        assert!(!eighth.is_negative());
+        assert_eq!(eighth.repetend(), None);
+        assert_eq!(digits(&ratio!(1, 8), 10), [0, 1, 2, 5].map(ScalableInteger::from));
+        assert_eq!(digits(&ratio!(1234, 10), 10), [123, 4].map(ScalableInteger::from));
+        assert_eq!(digits(&int!(42), 10), [42].map(ScalableInteger::from));
+        assert_eq!(digits(&int!(0), 10), [0].map(ScalableInteger::from));
+    }
+
+    #[test]
+    fn repeating_decimal_digits() {
+        let seventh = decimal_digits(&ratio!(1, 7));
+        assert_eq!(seventh.repetend(), Some((0, 6)));
+        assert_eq!(digits(&ratio!(1, 7), 13), [0, 1, 4, 2, 8, 5, 7, 1, 4, 2, 8, 5, 7].map(ScalableInteger::from));
+
+        assert_eq!(decimal_digits(&ratio!(1, 3)).repetend(), Some((0, 1)));
+        assert_eq!(decimal_digits(&ratio!(1, 6)).repetend(), Some((1, 2)));
+        assert_eq!(decimal_digits(&ratio!(7, 12)).repetend(), Some((2, 3)));
+        assert_eq!(digits(&ratio!(7, 12), 6), [0, 5, 8, 3, 3, 3].map(ScalableInteger::from));
+        assert_eq!(decimal_digits(&ratio!(1, 81)).repetend(), Some((0, 9)));
+    }
+
+    #[test]
+    fn negative_decimal_digits() {
+        let negative = decimal_digits(&ratio!(-1, 8));
+        assert!(negative.is_negative());
+        assert_eq!(negative.repetend(), None);
+        assert_eq!(digits(&ratio!(-1, 8), 10), [0, 1, 2, 5].map(ScalableInteger::from));
+        assert_eq!(digits(&ratio!(-22, 7), 4), [3, 1, 4, 2].map(ScalableInteger::from));
+        assert_eq!(decimal_digits(&ratio!(-22, 7)).repetend(), Some((0, 6)));
+    }
+
+    proptest! {
+        #[test]
+        fn repetend_repeats(numer in -1000i64..1000, denom in 1i64..500) {
+            let r = ratio!(numer, denom);
+            let expansion = decimal_digits(&r);
+            if let Some((start, end)) = expansion.repetend() {
+                let fraction: Vec<_> = expansion.skip(1).take(end + (end - start)).collect();
+                prop_assert_eq!(&fraction[start..end], &fraction[end..]);
+            } else {
+                prop_assert!(expansion.count() < 20);
+            }
+        }
+    }
+}
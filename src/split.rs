@@ -0,0 +1,125 @@
+//! Splitting a quantity into equal parts, exactly or in multiples of a granularity.
+//!
+//! # Example:
+//! ```
+//! use tantalum_unit::prelude::*;
+//!
+//! let parts = q!(1 L).divide_evenly(3).unwrap();
+//! assert_eq!(parts, vec![Quantity::new(ratio!(1, 3), Liter); 3]);
+//!
+//! let (parts, remainder) = q!(10 m).divide_with_remainder(3, &q!(1 m)).unwrap();
+//! assert_eq!(parts, vec![q!(3 m); 3]);
+//! assert_eq!(remainder, q!(1 m));
+//! ```
+
+use num::Zero;
+use crate::error::DivisionError;
+use crate::quantity::Quantity;
+use crate::rounding::{round, RoundingMode};
+use crate::scalable_integer::BigRational;
+
+impl Quantity {
+    /// Splits the Quantity into ```n``` equal parts, which sum exactly to the Quantity.
+    ///
+    /// Returns an error if ```n``` is zero.
+    pub fn divide_evenly(&self, n: u32) -> Result<Vec<Quantity>, DivisionError> {
+        if n == 0 {
+            return Err(DivisionError::ZeroParts);
+        }
+        let part = Quantity::new(&self.magnitude / BigRational::from_integer(n.into()), self.unit.clone());
+        Ok(vec![part; n as usize])
+    }
+
+    /// Splits the Quantity into ```n``` equal parts that are whole multiples of ```granularity```,
+    /// and the remainder left over.
+    ///
+    /// The parts are rounded toward zero, so the remainder has the sign of the Quantity and is
+    /// smaller than ```n``` times the granularity. The parts and the remainder are in the unit of
+    /// the Quantity and sum exactly to it.
+    ///
+    /// Returns an error if ```n``` is zero, if the granularity isn't positive or if it can't be
+    /// converted to the unit of the Quantity.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let (parts, remainder) = q!(100 m).divide_with_remainder(3, &q!(1 cm)).unwrap();
+    /// assert_eq!(parts, vec![Quantity::new(ratio!(3333, 100), Meter); 3]);
+    /// assert_eq!(remainder, Quantity::new(ratio!(1, 100), Meter));
+    /// ```
+    pub fn divide_with_remainder(&self, n: u32, granularity: &Quantity) -> Result<(Vec<Quantity>, Quantity), DivisionError> {
+        if n == 0 {
+            return Err(DivisionError::ZeroParts);
+        }
+        let step = granularity.clone()
+            .convert_to(self.unit.clone())
+            .map_err(|_| DivisionError::IncompatibleUnits { quantity: self.unit.clone(), granularity: granularity.unit.clone() })?
+            .magnitude;
+        if step <= BigRational::zero() {
+            return Err(DivisionError::NonPositiveGranularity);
+        }
+
+        let count = BigRational::from_integer(n.into());
+        let steps = round(&(&self.magnitude / &count / &step), RoundingMode::TowardZero);
+        let part = BigRational::from_integer(steps) * step;
+        let remainder = &self.magnitude - &part * count;
+        let parts = vec![Quantity::new(part, self.unit.clone()); n as usize];
+        Ok((parts, Quantity::new(remainder, self.unit.clone())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use num::Zero;
+    use crate::{int, ratio};
+    use crate::error::DivisionError;
+    use crate::quantity::Quantity;
+    use crate::scalable_integer::BigRational;
+    use crate::unit::Unit::*;
+
+    fn sum(parts: &[Quantity]) -> Quantity {
+        parts.iter().cloned().fold(Quantity::zero(), |sum, part| sum + part)
+    }
+
+    #[test]
+    fn divide_evenly() {
+        let liter = Quantity::from_i64_with_unit(1, Liter);
+        let parts = liter.divide_evenly(3).unwrap();
+        assert_eq!(parts, vec![Quantity::new(ratio!(1, 3), Liter); 3]);
+        assert_eq!(sum(&parts), liter);
+
+        let inches = Quantity::from_i64_with_unit(10, Inch);
+        let parts = inches.divide_evenly(4).unwrap();
+        assert_eq!(parts, vec![Quantity::new(ratio!(5, 2), Inch); 4]);
+        assert_eq!(sum(&parts), inches);
+
+        assert_eq!(inches.divide_evenly(1), Ok(vec![inches.clone()]));
+        assert_eq!(inches.divide_evenly(0), Err(DivisionError::ZeroParts));
+    }
+
+    #[test]
+    fn divide_with_remainder() {
+        let length = Quantity::from_i64_with_unit(100, Meter);
+        let centimeter = Quantity::from_i64_with_unit(1, Centi * Meter);
+        let (parts, remainder) = length.divide_with_remainder(3, &centimeter).unwrap();
+        assert_eq!(parts, vec![Quantity::new(ratio!(3333, 100), Meter); 3]);
+        assert_eq!(remainder, Quantity::new(ratio!(1, 100), Meter));
+        assert_eq!(sum(&parts) + remainder, length);
+
+        let negative = Quantity::from_i64_with_unit(-10, Meter);
+        let (parts, remainder) = negative.divide_with_remainder(4, &Quantity::from_i64_with_unit(1, Meter)).unwrap();
+        assert_eq!(parts, vec![Quantity::from_i64_with_unit(-2, Meter); 4]);
+        assert_eq!(remainder.magnitude, int!(-2));
+    }
+
+    #[test]
+    fn divide_with_remainder_errors() {
+        let length = Quantity::from_i64_with_unit(10, Meter);
+        let meter = Quantity::from_i64_with_unit(1, Meter);
+        assert_eq!(length.divide_with_remainder(0, &meter), Err(DivisionError::ZeroParts));
+        assert_eq!(length.divide_with_remainder(2, &Quantity::from_i64_with_unit(0, Meter)), Err(DivisionError::NonPositiveGranularity));
+        assert_eq!(length.divide_with_remainder(2, &Quantity::from_i64_with_unit(1, Second)),
+                   Err(DivisionError::IncompatibleUnits { quantity: Meter, granularity: Second }));
+    }
+}
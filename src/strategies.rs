@@ -0,0 +1,155 @@
+//! ```proptest``` strategies for units, integers, rationals and quantities.
+//!
+//! Generation is biased towards the cases that tend to break unit math: prefixes, affine
+//! temperature units and integers that don't fit into 128 bits. Compound units and powers are
+//! nested at most three levels deep and every generated rational has a nonzero denominator.
+
+use num::bigint::Sign;
+use num::{BigInt, Zero};
+use proptest::prelude::*;
+use crate::quantity::Quantity;
+use crate::scalable_integer::{BigRational, ScalableInteger};
+use crate::unit::Unit;
+use crate::unit::Unit::*;
+
+/// The named units that aren't SI or binary modifiers.
+fn base_units() -> Vec<Unit> {
+    Unit::NAMED_UNITS.iter().filter(|u| !u.is_modifier()).cloned().collect()
+}
+
+/// The SI and binary modifiers.
+fn modifiers() -> Vec<Unit> {
+    Unit::NAMED_UNITS.iter().filter(|u| u.is_modifier()).cloned().collect()
+}
+
+/// A named unit, optionally with a prefix, or a temperature unit with an offset.
+pub fn simple_unit() -> impl Strategy<Value=Unit> {
+    prop_oneof![
+        3 => prop::sample::select(base_units()),
+        2 => (prop::sample::select(modifiers()), prop::sample::select(base_units()))
+            .prop_map(|(prefix, unit)| prefix * unit),
+        1 => prop::sample::select(vec![Kelvin, Celsius, Fahrenheit]),
+    ]
+}
+
+/// A rational with a nonzero denominator.
+pub fn rational() -> impl Strategy<Value=BigRational> {
+    (any::<ScalableInteger>(), any::<ScalableInteger>())
+        .prop_map(|(numer, denom)| {
+            let denom = if denom.is_zero() { 1.into() } else { denom };
+            BigRational::new(numer, denom)
+        })
+}
+
+impl Arbitrary for ScalableInteger {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            4 => (-1000i64..1000).prop_map(ScalableInteger::from),
+            2 => any::<i64>().prop_map(ScalableInteger::from),
+            2 => any::<i128>().prop_map(ScalableInteger::from),
+            1 => (any::<bool>(), prop::collection::vec(any::<u32>(), 4..8))
+                .prop_map(|(negative, digits)| {
+                    let sign = if negative { Sign::Minus } else { Sign::Plus };
+                    BigInt::from_slice(sign, &digits).into()
+                }),
+        ].boxed()
+    }
+}
+
+impl Arbitrary for Unit {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        simple_unit()
+            .prop_recursive(3, 16, 4, |inner| {
+                prop_oneof![
+                    3 => (prop::collection::vec(inner.clone(), 0..4), prop::collection::vec(inner.clone(), 0..4))
+                        .prop_map(|(numerator, denominator)| Compound(numerator.into(), denominator.into())),
+                    1 => (inner, -3..=3).prop_map(|(base, exponent)| Power(Box::new(base), exponent)),
+                ]
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Quantity {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (rational(), any::<Unit>())
+            .prop_map(|(magnitude, unit)| Quantity::new(magnitude, unit))
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A quantity together with a unit it can be converted to without taking the reciprocal.
+    ///
+    /// The unit is simplified first, as canceling a nested unit can reorder its SI decomposition.
+    /// Temperatures in compound units like ```Kilo * Celsius``` don't convert, so they are skipped.
+    fn convertible() -> impl Strategy<Value=(Quantity, Unit)> {
+        (any::<Quantity>(), prop::sample::select(modifiers()), any::<bool>())
+            .prop_map(|(mut quantity, prefix, multiply)| {
+                quantity.unit = quantity.unit.simplify();
+                let target = if multiply {
+                    prefix * quantity.unit.clone()
+                } else {
+                    quantity.unit.clone() / prefix
+                };
+                (quantity, target)
+            })
+            .prop_filter("ambiguous temperature", |(quantity, target)| {
+                !quantity.unit.has_ambiguous_offset() && !target.has_ambiguous_offset()
+            })
+    }
+
+    /// A unit together with its decomposition into SI units, before or after reducing the prefixes
+    /// of derived units, in either order.
+    fn decomposed() -> impl Strategy<Value=(Unit, Unit)> {
+        (any::<Unit>(), any::<bool>(), any::<bool>())
+            .prop_map(|(unit, reduce, swap)| {
+                let si_unit = unit.clone().to_si_units().2;
+                let si_unit = if reduce { si_unit.to_si_units().2 } else { si_unit };
+                if swap { (si_unit, unit) } else { (unit, si_unit) }
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn compatible_units_convert((from, to) in decomposed(), magnitude in rational()) {
+            if from.is_compatible_with(&to) {
+                let quantity = Quantity::new(magnitude, from.clone());
+                prop_assert!(quantity.convert_to(to.clone()).is_ok(), "{:?} to {:?}", from, to);
+                prop_assert!(from.conversion_factors_to_many(&[to])[0].is_ok());
+            }
+        }
+
+        #[test]
+        fn generated_rationals_are_valid(value in rational()) {
+            prop_assert!(!value.denom().is_zero());
+        }
+
+        #[test]
+        fn conversion_round_trips((quantity, target) in convertible()) {
+            let result = quantity.clone()
+                .convert_to(target).unwrap()
+                .convert_to(quantity.unit.clone()).unwrap();
+            prop_assert_eq!(result, quantity);
+        }
+
+        #[test]
+        fn add_then_sub_is_identity((a, target) in convertible(), magnitude in rational()) {
+            let b = Quantity::new(magnitude, target);
+            let result = a.clone() + b.clone() - b;
+            prop_assert_eq!(result, a);
+        }
+    }
+}
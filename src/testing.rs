@@ -0,0 +1,189 @@
+//! Assertions for comparing quantities in tests.
+//!
+//! Both macros accept quantities in any two units of the same dimension and compare their exact
+//! magnitudes after converting the right quantity into the unit of the left one. On failure,
+//! they print both quantities with their exact and approximate magnitudes and their units.
+//!
+//! # Example:
+//! ```
+//! use tantalum_unit::prelude::*;
+//! use tantalum_unit::{assert_quantity_approx_eq, assert_quantity_eq};
+//!
+//! assert_quantity_eq!(q!(1 km), q!(1000 m));
+//! assert_quantity_eq!(Quantity::from_i64_with_unit(0, Celsius), q!(273.15 K));
+//! assert_quantity_approx_eq!(q!(1 mi), q!(1609 m), q!(1 m));
+//! ```
+//!
+//! Quantities of different dimensions never compare equal, even if ```convert_to``` could convert
+//! one into the reciprocal of the other:
+//! ```should_panic
+//! use tantalum_unit::prelude::*;
+//! use tantalum_unit::assert_quantity_eq;
+//!
+//! assert_quantity_eq!(q!(1 Hz), q!(1 s));
+//! ```
+
+use std::fmt::{Arguments, Write};
+use num::Zero;
+use crate::quantity::Quantity;
+use crate::scalable_integer::BigRational;
+use crate::unit::Unit;
+use crate::zero;
+
+/// Asserts that two quantities are equal after converting the right one into the unit of the left one.
+///
+/// Panics if the quantities are not equal or have different dimensions. Like ```assert_eq!```,
+/// an optional format string and arguments are added to the panic message.
+#[macro_export]
+macro_rules! assert_quantity_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::testing::__assert_quantity_eq(&$left, &$right, None)
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        $crate::testing::__assert_quantity_eq(&$left, &$right, Some(format_args!($($arg)+)))
+    };
+}
+
+/// Asserts that two quantities differ by at most a tolerance, which is itself a quantity.
+///
+/// The right quantity and the tolerance are converted into the unit of the left one. The tolerance
+/// is converted as a difference, so a tolerance of ```1 °C``` is the same as one of ```1 K```.
+/// Panics if the difference exceeds the tolerance, if the dimensions differ or if the tolerance is negative.
+#[macro_export]
+macro_rules! assert_quantity_approx_eq {
+    ($left:expr, $right:expr, $tolerance:expr $(,)?) => {
+        $crate::testing::__assert_quantity_approx_eq(&$left, &$right, &$tolerance, None)
+    };
+    ($left:expr, $right:expr, $tolerance:expr, $($arg:tt)+) => {
+        $crate::testing::__assert_quantity_approx_eq(&$left, &$right, &$tolerance, Some(format_args!($($arg)+)))
+    };
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn __assert_quantity_eq(left: &Quantity, right: &Quantity, message: Option<Arguments>) {
+    let converted = convert(right, &left.unit);
+    if converted.as_ref() != Some(&left.magnitude) {
+        fail("left == right", left, right, converted, None, message);
+    }
+}
+
+#[doc(hidden)]
+#[track_caller]
+pub fn __assert_quantity_approx_eq(left: &Quantity, right: &Quantity, tolerance: &Quantity, message: Option<Arguments>) {
+    let Some(bound) = convert_difference(tolerance, &left.unit) else {
+        panic!("the tolerance {tolerance:?} has a different dimension than {:?}", left.unit);
+    };
+    assert!(bound >= zero!(), "the tolerance {tolerance:?} is negative");
+
+    let converted = convert(right, &left.unit);
+    let within = converted.as_ref().is_some_and(|magnitude| {
+        let difference = magnitude - &left.magnitude;
+        difference <= bound && -difference <= bound
+    });
+    if !within {
+        fail("left ≈ right", left, right, converted, Some(tolerance), message);
+    }
+}
+
+#[track_caller]
+fn fail(comparison: &str, left: &Quantity, right: &Quantity, converted: Option<BigRational>, tolerance: Option<&Quantity>, message: Option<Arguments>) -> ! {
+    let mut report = format!("assertion `{comparison}` failed");
+    match message {
+        Some(message) => write!(report, ": {message}").unwrap(),
+        None if converted.is_none() => write!(report, ": cannot convert {} to {}", right.unit, left.unit).unwrap(),
+        None => {}
+    }
+
+    write!(report, "\n  left: {left:?}\n right: {right:?}").unwrap();
+    if let Some(magnitude) = converted.filter(|_| right.unit != left.unit) {
+        write!(report, " = {:?}", Quantity::new(magnitude, left.unit.clone())).unwrap();
+    }
+    if let Some(tolerance) = tolerance {
+        write!(report, "\n   tol: {tolerance:?}").unwrap();
+    }
+    panic!("{report}");
+}
+
+/// The magnitude of ```quantity``` in ```unit```, or ```None``` if their dimensions differ.
+fn convert(quantity: &Quantity, unit: &Unit) -> Option<BigRational> {
+    let (from_offset, from_slope) = si_affine(&quantity.unit, unit)?;
+    let (to_offset, to_slope) = si_affine(unit, unit)?;
+    Some((&quantity.magnitude + from_offset) * from_slope / to_slope - to_offset)
+}
+
+/// The magnitude of a difference of ```quantity``` in ```unit```, ignoring the units' offsets.
+fn convert_difference(quantity: &Quantity, unit: &Unit) -> Option<BigRational> {
+    let (_, from_slope) = si_affine(&quantity.unit, unit)?;
+    let (_, to_slope) = si_affine(unit, unit)?;
+    Some(&quantity.magnitude * from_slope / to_slope)
+}
+
+/// The offset and slope converting ```unit``` into SI base units, or ```None``` if it doesn't have
+/// the dimension of ```reference```.
+fn si_affine(unit: &Unit, reference: &Unit) -> Option<(BigRational, BigRational)> {
+    if !unit.has_same_dimension(reference) {
+        return None;
+    }
+    Some(unit.si_affine())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic::catch_unwind;
+    use crate::{q, ratio};
+    use crate::unit::KILOWATT_HOUR;
+    use crate::unit::Unit::*;
+    use super::*;
+
+    fn panic_message(f: impl FnOnce() + std::panic::UnwindSafe) -> String {
+        let payload = catch_unwind(f).expect_err("the assertion should fail");
+        payload.downcast_ref::<String>().cloned().unwrap()
+    }
+
+    #[test]
+    fn equal() {
+        assert_quantity_eq!(q!(1 km), q!(1000 m));
+        assert_quantity_eq!(q!(3.6 MJ), Quantity::from_i64_with_unit(1, KILOWATT_HOUR));
+        assert_quantity_eq!(q!(2 kg m^2/s^2), q!(2 J));
+        assert_quantity_eq!(q!(2 m^2 kg/s^2), q!(2 J), "order of {}", "components");
+        assert_quantity_eq!(Quantity::from_i64_with_unit(10, Celsius), q!(283.15 K));
+    }
+
+    #[test]
+    fn approx_equal() {
+        assert_quantity_approx_eq!(q!(1 in), q!(25 mm), q!(0.4 mm));
+        assert_quantity_approx_eq!(q!(25 mm), q!(1 in), q!(0.4 mm));
+        let celsius = Quantity::from_i64_with_unit(20, Celsius);
+        let fahrenheit = Quantity::new(ratio!(137, 2), Fahrenheit);
+        assert_quantity_approx_eq!(celsius, fahrenheit, q!(0.5 K));
+        assert_quantity_approx_eq!(q!(1 m), q!(1 m), q!(0 m));
+    }
+
+    #[test]
+    fn failure_messages() {
+        let message = panic_message(|| assert_quantity_eq!(q!(1 km), q!(999 m)));
+        assert_eq!(message, "assertion `left == right` failed\n  left: Quantity(1 km)\n right: Quantity(999 m) = Quantity(999/1000 ≈ 0.999 km)");
+
+        let message = panic_message(|| assert_quantity_eq!(q!(1 km), q!(1 kg)));
+        assert_eq!(message, "assertion `left == right` failed: cannot convert kg to km\n  left: Quantity(1 km)\n right: Quantity(1 kg)");
+
+        let message = panic_message(|| assert_quantity_eq!(q!(1 m), q!(2 m), "step {}", 3));
+        assert_eq!(message, "assertion `left == right` failed: step 3\n  left: Quantity(1 m)\n right: Quantity(2 m)");
+
+        let message = panic_message(|| assert_quantity_approx_eq!(q!(1 in), q!(2.5 cm), q!(0.01 cm)));
+        assert_eq!(message, "assertion `left ≈ right` failed\n  left: Quantity(1 in)\n right: Quantity(5/2 ≈ 2.5 cm) = Quantity(125/127 ≈ 0.984252 in)\n   tol: Quantity(1/100 ≈ 0.01 cm)");
+    }
+
+    #[test]
+    #[should_panic(expected = "different dimension")]
+    fn incompatible_tolerance() {
+        assert_quantity_approx_eq!(q!(1 m), q!(1 m), q!(1 s));
+    }
+
+    #[test]
+    #[should_panic(expected = "negative")]
+    fn negative_tolerance() {
+        assert_quantity_approx_eq!(q!(1 m), q!(1 m), q!(-1 m));
+    }
+}
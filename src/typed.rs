@@ -0,0 +1,316 @@
+//! Wrappers around ```Quantity``` that fix its dimension at compile time.
+//!
+//! Each wrapper can only be constructed from a quantity of its dimension, so an API taking a
+//! ```Length``` can't be passed a mass. The wrappers dereference to the inner ```Quantity```,
+//! and multiplying or dividing them yields the wrapper of the resulting dimension where one exists.
+//!
+//! # Example:
+//! ```
+//! use tantalum_unit::prelude::*;
+//! use tantalum_unit::typed::{Length, Time, Velocity};
+//! use tantalum_unit::unit::METER_PER_SECOND;
+//!
+//! let distance = Length::new(q!(100 m)).unwrap();
+//! let time = Time::new(q!(8 s)).unwrap();
+//!
+//! let speed: Velocity = distance / time;
+//! assert_eq!(speed.clone().into_quantity().convert_to(METER_PER_SECOND), Ok(q!(12.5 m/s)));
+//!
+//! assert!(Length::new(q!(3 kg)).is_err());
+//! ```
+
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, Deref, Div, Mul, Neg, Sub};
+use crate::error::ConversionError;
+use crate::quantity::Quantity;
+use crate::scalable_integer::BigRational;
+use crate::unit::Unit;
+use crate::unit::Unit::*;
+
+/// Returns an error unless ```quantity``` has the dimension of ```reference```.
+///
+/// Temperatures in compound units like ```Kilo * Celsius``` are rejected, since they can't be
+/// converted to any other unit.
+fn check_dimension(quantity: &Quantity, reference: Unit) -> Result<(), ConversionError> {
+    if quantity.unit.is_compatible_with(&reference) {
+        Ok(())
+    } else if quantity.unit.has_same_dimension(&reference) {
+        Err(ConversionError::AmbiguousOffset { from: quantity.unit.clone(), to: reference })
+    } else {
+        Err(ConversionError::IncompatibleUnits { from: quantity.unit.clone(), to: reference })
+    }
+}
+
+macro_rules! typed_quantities {
+    ($($name:ident, $dimension:literal, $reference:expr);* $(;)?) => {
+        $(
+            #[doc = concat!("A ```Quantity``` measuring ", $dimension, ", in any unit convertible to ```", stringify!($reference), "```.")]
+            #[derive(Clone, Debug, PartialEq, Eq)]
+            pub struct $name(Quantity);
+
+            impl $name {
+                #[doc = concat!("Wraps a quantity, returning an error if it doesn't measure ", $dimension, ".")]
+                pub fn new(quantity: Quantity) -> Result<Self, ConversionError> {
+                    check_dimension(&quantity, $reference)?;
+                    Ok(Self(quantity))
+                }
+
+                pub fn quantity(&self) -> &Quantity {
+                    &self.0
+                }
+
+                pub fn into_quantity(self) -> Quantity {
+                    self.0
+                }
+            }
+
+            impl Deref for $name {
+                type Target = Quantity;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.0
+                }
+            }
+
+            impl TryFrom<Quantity> for $name {
+                type Error = ConversionError;
+
+                fn try_from(quantity: Quantity) -> Result<Self, Self::Error> {
+                    Self::new(quantity)
+                }
+            }
+
+            impl From<$name> for Quantity {
+                fn from(value: $name) -> Self {
+                    value.0
+                }
+            }
+
+            impl Display for $name {
+                fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                    self.0.fmt(f)
+                }
+            }
+
+            // Both operands are compatible with the reference unit, so the conversion in Add and Sub can't fail
+            impl Add for $name {
+                type Output = $name;
+
+                fn add(self, rhs: Self) -> Self::Output {
+                    Self(self.0 + rhs.0)
+                }
+            }
+
+            impl Sub for $name {
+                type Output = $name;
+
+                fn sub(self, rhs: Self) -> Self::Output {
+                    Self(self.0 - rhs.0)
+                }
+            }
+
+            impl Neg for $name {
+                type Output = $name;
+
+                fn neg(self) -> Self::Output {
+                    Self(-self.0)
+                }
+            }
+
+            impl Mul<BigRational> for $name {
+                type Output = $name;
+
+                fn mul(self, rhs: BigRational) -> Self::Output {
+                    Self(self.0 * rhs)
+                }
+            }
+
+            impl Div<BigRational> for $name {
+                type Output = $name;
+
+                fn div(self, rhs: BigRational) -> Self::Output {
+                    Self(self.0 / rhs)
+                }
+            }
+
+            impl Div for $name {
+                type Output = BigRational;
+
+                /// The ratio of two quantities of the same dimension.
+                fn div(self, rhs: Self) -> Self::Output {
+                    let rhs = rhs.0.convert_to(self.0.unit.clone()).expect("quantities compatible with the reference unit are convertible");
+                    self.0.magnitude / rhs.magnitude
+                }
+            }
+        )*
+    };
+}
+
+/// Implements ```$lhs * $rhs = $output``` in both operand orders.
+macro_rules! typed_mul {
+    ($($lhs:ident * $rhs:ident = $output:ident);* $(;)?) => {
+        $(
+            impl Mul<$rhs> for $lhs {
+                type Output = $output;
+
+                fn mul(self, rhs: $rhs) -> Self::Output {
+                    $output(self.0 * rhs.0)
+                }
+            }
+
+            impl Mul<$lhs> for $rhs {
+                type Output = $output;
+
+                fn mul(self, rhs: $lhs) -> Self::Output {
+                    $output(self.0 * rhs.0)
+                }
+            }
+        )*
+    };
+}
+
+/// Implements ```$lhs / $rhs = $output```.
+macro_rules! typed_div {
+    ($($lhs:ident / $rhs:ident = $output:ident);* $(;)?) => {
+        $(
+            impl Div<$rhs> for $lhs {
+                type Output = $output;
+
+                fn div(self, rhs: $rhs) -> Self::Output {
+                    $output(self.0 / rhs.0)
+                }
+            }
+        )*
+    };
+}
+
+typed_quantities!(
+    Length, "a length", Meter;
+    Area, "an area", Meter * Meter;
+    Volume, "a volume", Meter * Meter * Meter;
+    Mass, "a mass", Gram;
+    Time, "a time", Second;
+    Frequency, "a frequency", Hertz;
+    Velocity, "a velocity", Meter / Second;
+    Acceleration, "an acceleration", Meter / (Second * Second);
+    Force, "a force", Newton;
+    Pressure, "a pressure", Pascal;
+    Energy, "an energy", Joule;
+    Power, "a power", Watt;
+    Temperature, "a temperature", Kelvin;
+    Current, "an electric current", Ampere;
+    Voltage, "a voltage", Volt;
+    Information, "an amount of information", Bit;
+);
+
+// Length * Length is implemented separately, since typed_mul would implement it twice
+impl Mul for Length {
+    type Output = Area;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Area(self.0 * rhs.0)
+    }
+}
+
+typed_mul!(
+    Area * Length = Volume;
+    Velocity * Time = Length;
+    Acceleration * Time = Velocity;
+    Mass * Acceleration = Force;
+    Force * Length = Energy;
+    Pressure * Area = Force;
+    Pressure * Volume = Energy;
+    Power * Time = Energy;
+    Voltage * Current = Power;
+);
+
+typed_div!(
+    Area / Length = Length;
+    Volume / Length = Area;
+    Volume / Area = Length;
+    Length / Time = Velocity;
+    Length / Velocity = Time;
+    Velocity / Time = Acceleration;
+    Velocity / Acceleration = Time;
+    Force / Mass = Acceleration;
+    Force / Acceleration = Mass;
+    Force / Area = Pressure;
+    Energy / Length = Force;
+    Energy / Force = Length;
+    Energy / Time = Power;
+    Energy / Power = Time;
+    Energy / Volume = Pressure;
+    Power / Voltage = Current;
+    Power / Current = Voltage;
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert_quantity_eq, int, q, ratio};
+    use super::*;
+
+    #[test]
+    fn constructors_check_dimension() {
+        assert!(Length::new(q!(5 km)).is_ok());
+        assert!(Length::new(q!(3 in)).is_ok());
+        assert!(Energy::new(q!(2 N m)).is_ok());
+        assert!(Energy::new(q!(2 kW h)).is_ok());
+        assert!(Energy::new(q!(2 kg m^2/s^2)).is_ok());
+        assert!(Power::new(q!(2 J/s)).is_ok());
+        assert!(Temperature::new(Quantity::from_i64_with_unit(20, Celsius)).is_ok());
+        assert!(Volume::new(q!(2 L)).is_ok());
+        assert!(Frequency::new(q!(50 1/s)).is_ok());
+
+        assert_eq!(Length::new(q!(3 kg)), Err(ConversionError::IncompatibleUnits { from: Kilo * Gram, to: Meter }));
+        assert!(Length::new(q!(3 1/m)).is_err());
+        assert!(Power::new(q!(1 J)).is_err());
+        assert!(Mass::try_from(q!(1 N)).is_err());
+
+        let kilo_celsius = Quantity::from_i64_with_unit(1, Kilo * Celsius);
+        assert_eq!(Temperature::new(kilo_celsius), Err(ConversionError::AmbiguousOffset { from: Kilo * Celsius, to: Kelvin }));
+    }
+
+    #[test]
+    fn mixed_compositions() {
+        let base_units = Energy::new(q!(1 kg m^2/s^2)).unwrap();
+        let joule = Energy::new(q!(1 J)).unwrap();
+        assert_quantity_eq!(q!(2 J), base_units.clone() + joule.clone());
+        assert_quantity_eq!(q!(0 J), joule.clone() - base_units.clone());
+        assert_eq!(base_units / joule, int!(1));
+
+        let celsius = Temperature::new(Quantity::from_i64_with_unit(1, Celsius)).unwrap();
+        let kelvin = Temperature::new(Quantity::from_i64_with_unit(1, Kelvin)).unwrap();
+        assert_eq!((kelvin.clone() + celsius.clone()).unit, Kelvin);
+        assert_eq!(kelvin / celsius, ratio!(20, 5483));
+    }
+
+    #[test]
+    fn cross_type_arithmetic() {
+        let speed = Length::new(q!(36 km)).unwrap() / Time::new(q!(1 h)).unwrap();
+        assert_quantity_eq!(q!(10 m/s), speed);
+
+        let energy = Power::new(q!(2 kW)).unwrap() * Time::new(q!(3 h)).unwrap();
+        assert_quantity_eq!(q!(6 kW h), energy);
+        let reversed = Time::new(q!(3 h)).unwrap() * Power::new(q!(2 kW)).unwrap();
+        assert_quantity_eq!(q!(6 kW h), reversed);
+
+        let force = Mass::new(q!(2 kg)).unwrap() * Acceleration::new(q!(3 m/s^2)).unwrap();
+        let work = force * Length::new(q!(5 m)).unwrap();
+        assert_quantity_eq!(q!(30 J), work);
+
+        let area = Length::new(q!(2 m)).unwrap() * Length::new(q!(3 m)).unwrap();
+        let side: Length = area / Length::new(q!(3 m)).unwrap();
+        assert_eq!(side.quantity(), &q!(2 m));
+    }
+
+    #[test]
+    fn same_type_arithmetic() {
+        let a = Length::new(q!(1 km)).unwrap();
+        let b = Length::new(q!(250 m)).unwrap();
+
+        assert_quantity_eq!(q!(1250 m), a.clone() + b.clone());
+        assert_quantity_eq!(q!(750 m), a.clone() - b.clone());
+        assert_eq!(a.clone() / b, int!(4));
+        assert_eq!((-(a * ratio!(1, 2))).magnitude, ratio!(-1, 2));
+    }
+}
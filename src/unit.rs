@@ -1,14 +1,63 @@
 //! A unit like ```Meter``` or ```Gallon/Hour```.
+use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
 use std::ops::{Div, DivAssign, Mul, MulAssign};
+use std::sync::OnceLock;
 use indexmap::IndexMap;
 use num::{Zero, One};
 use crate::unit::Unit::*;
 use crate::{define_units, int, one, ratio, zero};
+use crate::error::ConversionError;
+use crate::pow::magnitude_pow;
 use crate::scalable_integer::{BigRational};
+use crate::unit_list::UnitList;
+
+/// Size in bits above which ```to_si_units``` reduces the slope of a compound unit while accumulating it.
+const SLOPE_REDUCTION_BITS: u64 = 128;
+
+/// The named derived units ```Unit::to_named``` rewrites compounds to. When several share a
+/// dimension the first one wins, so ```1/s``` becomes ```Hertz``` rather than ```Baud``` and
+/// ```N*m``` becomes ```Joule```.
+const NAMED_DERIVED_UNITS: [Unit; 13] = [Newton, Joule, Watt, Pascal, Coulomb, Volt, Ohm, Siemens, Farad, Henry, Weber, Tesla, Hertz];
 
 /// A Unit that represents a dimensionless value.
-pub const UNITLESS: Unit = Compound(vec![], vec![]);
+pub const UNITLESS: Unit = Compound(UnitList::new(), UnitList::new());
+
+/// ```Kilo * Meter```
+pub const KILOMETER: Unit = Compound(UnitList::from_static(&[Kilo, Meter]), UnitList::new());
+/// ```Centi * Meter```
+pub const CENTIMETER: Unit = Compound(UnitList::from_static(&[Centi, Meter]), UnitList::new());
+/// ```Milli * Meter```
+pub const MILLIMETER: Unit = Compound(UnitList::from_static(&[Milli, Meter]), UnitList::new());
+/// ```Meter * Meter```
+pub const SQUARE_METER: Unit = Compound(UnitList::from_static(&[Meter, Meter]), UnitList::new());
+/// ```Meter * Meter * Meter```
+pub const CUBIC_METER: Unit = Compound(UnitList::from_static(&[Meter, Meter, Meter]), UnitList::new());
+/// ```Kilo * Gram```
+pub const KILOGRAM: Unit = Compound(UnitList::from_static(&[Kilo, Gram]), UnitList::new());
+/// ```Milli * Gram```
+pub const MILLIGRAM: Unit = Compound(UnitList::from_static(&[Milli, Gram]), UnitList::new());
+/// ```Milli * Liter```
+pub const MILLILITER: Unit = Compound(UnitList::from_static(&[Milli, Liter]), UnitList::new());
+/// ```Meter / Second```
+pub const METER_PER_SECOND: Unit = Compound(UnitList::from_static(&[Meter]), UnitList::from_static(&[Second]));
+/// ```Kilo * Meter / Hour```
+pub const KILOMETER_PER_HOUR: Unit = Compound(UnitList::from_static(&[Kilo, Meter]), UnitList::from_static(&[Hour]));
+/// ```Kilo * Watt```
+pub const KILOWATT: Unit = Compound(UnitList::from_static(&[Kilo, Watt]), UnitList::new());
+/// ```Kilo * Watt * Hour```
+pub const KILOWATT_HOUR: Unit = Compound(UnitList::from_static(&[Kilo, Watt, Hour]), UnitList::new());
+/// ```Meter / (Second * Second)```
+pub const METER_PER_SECOND_SQUARED: Unit = Compound(UnitList::from_static(&[Meter]), UnitList::from_static(&[Second, Second]));
+/// ```Mile / Hour```
+pub const MILE_PER_HOUR: Unit = Compound(UnitList::from_static(&[Mile]), UnitList::from_static(&[Hour]));
+/// ```Newton * Meter```
+pub const NEWTON_METER: Unit = Compound(UnitList::from_static(&[Newton, Meter]), UnitList::new());
+/// ```Kilo * Gram / (Meter * Meter * Meter)```
+pub const KILOGRAM_PER_CUBIC_METER: Unit = Compound(UnitList::from_static(&[Kilo, Gram]), UnitList::from_static(&[Meter, Meter, Meter]));
+/// ```Mole / Liter```, the unit of molar concentration
+pub const MOLAR: Unit = Compound(UnitList::from_static(&[Mole]), UnitList::from_static(&[Liter]));
 
 
 define_units!(
@@ -30,7 +79,7 @@ define_units!(
     // Temperature
     Kelvin, "kelvin", "K", zero!(), one!(), Kelvin;
     Celsius, "celsius", "°C", ratio!(5463, 20), one!(), Kelvin;
-    Fahrenheit, "fahrenheit", "°F", ratio!(45967, 100), ratio!(13889, 25000), Kelvin;
+    Fahrenheit, "fahrenheit", "°F", ratio!(45967, 100), ratio!(5, 9), Kelvin;
 
     // Area
     Hectare, "hectare", "ha", zero!(), int!(10000), Meter * Meter;
@@ -53,9 +102,12 @@ define_units!(
     CubicInch, "cubic inch", "in^3", zero!(), ratio!(2048383, 125000000000i64), Meter * Meter * Meter;
     CubicFeet, "cubic feet", "ft^3", zero!(), ratio!(55306341, 1953125000), Meter * Meter * Meter;
     CubicYard, "cubic yard", "yd^3", zero!(), ratio!(1493271207, 1953125000), Meter * Meter * Meter;
-    Pint, "pint", "pt", zero!(), ratio!(473176473, 1000000000000i64), Meter * Meter * Meter;
-    Quart, "quart", "qt", zero!(), ratio!(473176473, 500000000000i64), Meter * Meter * Meter;
-    Gallon, "gallon", "gal", zero!(), ratio!(473176473, 125000000000i64), Meter * Meter * Meter;
+    Pint, "pint", "pt", zero!(), ratio!(473176473, 1000000000000i64), Meter * Meter * Meter,
+        "the US liquid pint, unlike DryPint";
+    Quart, "quart", "qt", zero!(), ratio!(473176473, 500000000000i64), Meter * Meter * Meter,
+        "the US liquid quart";
+    Gallon, "gallon", "gal", zero!(), ratio!(473176473, 125000000000i64), Meter * Meter * Meter,
+        "the US liquid gallon of 231 cubic inches, unlike DryGallon";
 
     // Pressure
     Pascal, "pascal", "Pa", zero!(), one!(), (Kilo * Gram) / (Meter * Second * Second);
@@ -75,24 +127,25 @@ define_units!(
     // Magnetic flux
     Weber, "weber", "Wb", zero!(), one!(), (Kilo * Gram * Meter * Meter) / (Second * Second * Ampere);
 
-    // Length
+    // Length, with the astronomical unit of the IAU (2012) and a parsec of 648000/π astronomical
+    // units rounded to the meter
     Meter, "meter", "m", zero!(), one!(), Meter;
-    AU, "astronomical unit", "AU", zero!(), ratio!(149597870691i64, 1), Meter;
+    AU, "astronomical unit", "AU", zero!(), ratio!(149597870700i64, 1), Meter;
     Inch, "inch", "in", zero!(), ratio!(127, 5000), Meter;
     Feet, "feet", "ft", zero!(), ratio!(381, 1250), Meter;
     Yard, "yard", "yd", zero!(), ratio!(1143, 1250), Meter;
     Mile, "mile", "mi", zero!(), ratio!(201168, 125), Meter;
     NauticalMile, "nautical mile", "nmi", zero!(), ratio!(1852, 1), Meter;
     LightYear, "light year", "ly", zero!(), ratio!(9460730472580800i64, 1), Meter;
-    Parsec, "parsec", "pc", zero!(), ratio!(30857000000000000i64, 1), Meter;
+    Parsec, "parsec", "pc", zero!(), ratio!(30856775814913673i64, 1), Meter;
 
     // Electric Charge
     Coulomb, "coulomb", "C", zero!(), one!(), Second * Ampere;
 
-    // Mass
+    // Mass, with the international avoirdupois pound of 453.59237 grams
     Gram, "gram", "g", zero!(), one!(), Gram;
     Tonne, "tonne", "t", zero!(), ratio!(1000000, 1), Gram;
-    Dram, "dram", "dr", zero!(), ratio!(17718451953i64, 10_000_000_000i64), Gram;
+    Dram, "dram", "dr", zero!(), ratio!(45_359_237i64, 25_600_000i64), Gram;
     Ounce, "ounce", "oz", zero!(), ratio!(45_359_237i64, 1_600_000i64), Gram;
     Pound, "pound", "lb", zero!(), ratio!(45359237, 100_000), Gram;
 
@@ -104,74 +157,129 @@ define_units!(
     Minute, "minute", "min", zero!(), ratio!(60, 1), Second;
     Hour, "hour", "h", zero!(), ratio!(3600, 1), Second;
     Day, "day", "d", zero!(), ratio!(86400, 1), Second;
-    Month, "month", "mo", zero!(), ratio!(2629746, 1), Second;
-    Year, "year", "yr", zero!(), ratio!(31557600, 1), Second;
+    Month, "month", "mo", zero!(), ratio!(2629746, 1), Second,
+        "a twelfth of the Gregorian year of 365.2425 days";
+    Year, "year", "yr", zero!(), ratio!(31557600, 1), Second,
+        "the Julian year of 365.25 days, the same as JulianYear";
 
     // SI modifiers
-    Quecto, "quecto", "q", zero!(), ratio!(1, 1_000_000_000_000_000_000_000_000_000_000i128), UNITLESS;
-    Ronto, "ronto", "r", zero!(), ratio!(1, 1_000_000_000_000_000_000_000_000_000i128), UNITLESS;
-    Yocto, "yocto", "y", zero!(), ratio!(1, 1_000_000_000_000_000_000_000_000i128), UNITLESS;
-    Zepto, "zepto", "z", zero!(), ratio!(1, 1_000_000_000_000_000_000_000i128), UNITLESS;
-    Atto, "atto", "a", zero!(), ratio!(1, 1_000_000_000_000_000_000i128), UNITLESS;
-    Femto, "femto", "f", zero!(), ratio!(1, 1_000_000_000_000_000i64), UNITLESS;
-    Pico, "pico", "p", zero!(), ratio!(1, 1_000_000_000_000i64), UNITLESS;
-    Nano, "nano", "n", zero!(), ratio!(1, 1_000_000_000), UNITLESS;
-    Micro, "micro", "µ", zero!(), ratio!(1, 1_000_000), UNITLESS;
-    Milli, "milli", "m", zero!(), ratio!(1, 1_000), UNITLESS;
-    Centi, "centi", "c", zero!(), ratio!(1, 100), UNITLESS;
-    Deci, "deci", "d", zero!(), ratio!(1, 10), UNITLESS;
-    Hecto, "hecto", "h", zero!(), ratio!(100, 1), UNITLESS;
-    Kilo, "kilo", "k", zero!(), ratio!(1_000, 1), UNITLESS;
-    Mega, "mega", "M", zero!(), ratio!(1_000_000, 1), UNITLESS;
-    Giga, "giga", "G", zero!(), ratio!(1_000_000_000, 1), UNITLESS;
-    Tera, "tera", "T", zero!(), ratio!(1_000_000_000_000i64, 1), UNITLESS;
-    Peta, "peta", "P", zero!(), ratio!(1_000_000_000_000_000i64, 1), UNITLESS;
-    Exa, "exa", "E", zero!(), ratio!(1_000_000_000_000_000_000i128, 1), UNITLESS;
-    Zetta, "zetta", "Z", zero!(), ratio!(1_000_000_000_000_000_000_000i128, 1), UNITLESS;
-    Yotta, "yotta", "Y", zero!(), ratio!(1_000_000_000_000_000_000_000_000i128, 1), UNITLESS;
-    Ronna, "ronna", "R", zero!(), ratio!(1_000_000_000_000_000_000_000_000_000i128, 1), UNITLESS;
-    Quetta, "quetta", "Q", zero!(), ratio!(1_000_000_000_000_000_000_000_000_000_000i128, 1), UNITLESS;
+    #[modifier] Quecto, "quecto", "q", zero!(), ratio!(1, 1_000_000_000_000_000_000_000_000_000_000i128), UNITLESS;
+    #[modifier] Ronto, "ronto", "r", zero!(), ratio!(1, 1_000_000_000_000_000_000_000_000_000i128), UNITLESS;
+    #[modifier] Yocto, "yocto", "y", zero!(), ratio!(1, 1_000_000_000_000_000_000_000_000i128), UNITLESS;
+    #[modifier] Zepto, "zepto", "z", zero!(), ratio!(1, 1_000_000_000_000_000_000_000i128), UNITLESS;
+    #[modifier] Atto, "atto", "a", zero!(), ratio!(1, 1_000_000_000_000_000_000i128), UNITLESS;
+    #[modifier] Femto, "femto", "f", zero!(), ratio!(1, 1_000_000_000_000_000i64), UNITLESS;
+    #[modifier] Pico, "pico", "p", zero!(), ratio!(1, 1_000_000_000_000i64), UNITLESS;
+    #[modifier] Nano, "nano", "n", zero!(), ratio!(1, 1_000_000_000), UNITLESS;
+    #[modifier] Micro, "micro", "µ", zero!(), ratio!(1, 1_000_000), UNITLESS;
+    #[modifier] Milli, "milli", "m", zero!(), ratio!(1, 1_000), UNITLESS;
+    #[modifier] Centi, "centi", "c", zero!(), ratio!(1, 100), UNITLESS;
+    #[modifier] Deci, "deci", "d", zero!(), ratio!(1, 10), UNITLESS;
+    #[modifier] Hecto, "hecto", "h", zero!(), ratio!(100, 1), UNITLESS;
+    #[modifier] Kilo, "kilo", "k", zero!(), ratio!(1_000, 1), UNITLESS;
+    #[modifier] Mega, "mega", "M", zero!(), ratio!(1_000_000, 1), UNITLESS;
+    #[modifier] Giga, "giga", "G", zero!(), ratio!(1_000_000_000, 1), UNITLESS;
+    #[modifier] Tera, "tera", "T", zero!(), ratio!(1_000_000_000_000i64, 1), UNITLESS;
+    #[modifier] Peta, "peta", "P", zero!(), ratio!(1_000_000_000_000_000i64, 1), UNITLESS;
+    #[modifier] Exa, "exa", "E", zero!(), ratio!(1_000_000_000_000_000_000i128, 1), UNITLESS;
+    #[modifier] Zetta, "zetta", "Z", zero!(), ratio!(1_000_000_000_000_000_000_000i128, 1), UNITLESS;
+    #[modifier] Yotta, "yotta", "Y", zero!(), ratio!(1_000_000_000_000_000_000_000_000i128, 1), UNITLESS;
+    #[modifier] Ronna, "ronna", "R", zero!(), ratio!(1_000_000_000_000_000_000_000_000_000i128, 1), UNITLESS;
+    #[modifier] Quetta, "quetta", "Q", zero!(), ratio!(1_000_000_000_000_000_000_000_000_000_000i128, 1), UNITLESS;
 
     // IEC binary modifiers
-    Kibi, "kibi", "Ki", zero!(), ratio!(1024, 1), UNITLESS;
-    Mebi, "mebi", "Mi", zero!(), ratio!(1048576, 1), UNITLESS;
-    Gibi, "gibi", "Gi", zero!(), ratio!(1073741824, 1), UNITLESS;
-    Tebi, "tebi", "Ti", zero!(), ratio!(1099511627776i64, 1), UNITLESS;
-    Pebi, "pebi", "Pi", zero!(), ratio!(1125899906842624i64, 1), UNITLESS;
-    Exbi, "exbi", "Ei", zero!(), ratio!(1152921504606846976i64, 1), UNITLESS
+    #[modifier] Kibi, "kibi", "Ki", zero!(), ratio!(1024, 1), UNITLESS;
+    #[modifier] Mebi, "mebi", "Mi", zero!(), ratio!(1048576, 1), UNITLESS;
+    #[modifier] Gibi, "gibi", "Gi", zero!(), ratio!(1073741824, 1), UNITLESS;
+    #[modifier] Tebi, "tebi", "Ti", zero!(), ratio!(1099511627776i64, 1), UNITLESS;
+    #[modifier] Pebi, "pebi", "Pi", zero!(), ratio!(1125899906842624i64, 1), UNITLESS;
+    #[modifier] Exbi, "exbi", "Ei", zero!(), ratio!(1152921504606846976i64, 1), UNITLESS;
+
+    // Angle, where a degree is pi/180 rounded to 37 decimal places
+    Radian, "radian", "rad", zero!(), one!(), Radian;
+    Degree, "degree", "°", zero!(), ratio!(174532925199432957692369076848861271i128, 10_000_000_000_000_000_000_000_000_000_000_000_000i128), Radian,
+        "pi/180 radians with pi rounded to 37 decimal places, see Quantity::convert_angle for other precisions";
+
+    // Ratio
+    Percent, "percent", "%", zero!(), ratio!(1, 100), UNITLESS;
+
+    // US dry volume, where a bushel is exactly 35.23907016688 liters
+    Bushel, "bushel", "bu", zero!(), ratio!(220244188543i64, 6250000000000i64), Meter * Meter * Meter;
+    Peck, "peck", "pk", zero!(), ratio!(220244188543i64, 25000000000000i64), Meter * Meter * Meter;
+    DryGallon, "dry gallon", "dry_gal", zero!(), ratio!(220244188543i64, 50000000000000i64), Meter * Meter * Meter;
+    DryPint, "dry pint", "dry_pt", zero!(), ratio!(220244188543i64, 400000000000000i64), Meter * Meter * Meter;
+
+    // Sidereal time, with the sidereal day of the IERS Conventions (2010) and the sidereal year
+    // of 365.256363004 days at J2000.0 from the Astronomical Almanac
+    SiderealDay, "sidereal day", "sid_d", zero!(), ratio!(172328181, 2000), Second,
+        "86164.0905 seconds, the rotation of the Earth relative to the stars, per the IERS Conventions (2010)";
+    SiderealYear, "sidereal year", "sid_yr", zero!(), ratio!(2465480450277i64, 78125), Second,
+        "365.256363004 days, the orbit of the Earth relative to the stars at J2000.0";
+
+    // Year conventions, where Year is the Julian year for compatibility
+    JulianYear, "Julian year", "jul_yr", zero!(), ratio!(31557600, 1), Second,
+        "365.25 days, as used for light years and by the IAU";
+    TropicalYear, "tropical year", "trop_yr", zero!(), ratio!(3944615652i64, 125), Second,
+        "365.24219 days or 31556925.216 seconds, the mean tropical year at J2000.0";
+
+    // Information, with the word sizes named as on x86
+    Nibble, "nibble", "nib", zero!(), int!(4), Bit;
+    Word16, "word", "word", zero!(), int!(16), Bit, "a 16 bit word";
+    Word32, "double word", "dword", zero!(), int!(32), Bit, "a 32 bit word";
+    Word64, "quad word", "qword", zero!(), int!(64), Bit, "a 64 bit word";
+
+    // Symbol rate
+    Baud, "baud", "Bd", zero!(), one!(), UNITLESS / Second, "symbols per second, see Quantity::baud_to_bitrate";
+
+    // Pixels, which only relate to lengths through a density
+    Pixel, "pixel", "px", zero!(), one!(), Pixel,
+        "its own base unit, see Quantity::px_to_length for converting to lengths";
+    Dot, "dot", "dot", zero!(), one!(), Pixel, "a printed dot, counted as one pixel";
+    DotsPerInch, "dots per inch", "dpi", zero!(), ratio!(5000, 127), Pixel / Meter;
+    PixelsPerInch, "pixels per inch", "ppi", zero!(), ratio!(5000, 127), Pixel / Meter
 );
 
 impl Unit {
     /// Flattens nested Compound units without canceling units.
+    ///
+    /// Powers are kept as exponents, so ```s^-2``` becomes ```1/s^2```. The result is either a
+    /// named unit or a ```Compound``` of named units and powers of named units with positive
+    /// exponents. Exponents beyond the range of ```i32``` saturate.
     pub fn flatten(self) -> Self {
         use Unit::*;
 
-        match self {
-            Compound(numerator, denominator) => {
-                let mut flat_numerator = Vec::new();
-                let mut flat_denominator = Vec::new();
-
-                for unit in numerator {
-                    match unit.flatten() {
-                        Compound(inner_numerator, inner_denominator) => {
-                            flat_numerator.extend(inner_numerator);
-                            flat_denominator.extend(inner_denominator);
-                        }
-                        u => flat_numerator.push(u),
-                    }
-                }
-
-                for unit in denominator {
-                    match unit.flatten() {
-                        Compound(inner_numerator, inner_denominator) => {
-                            flat_numerator.extend(inner_denominator);
-                            flat_denominator.extend(inner_numerator);
-                        }
-                        simple_unit => flat_denominator.push(simple_unit),
+        /// Calls ```visit``` with every named unit in order, numerator first, and the exponent it
+        /// is raised to, which is negative in the denominator.
+        fn visit_named<'a>(unit: &'a Unit, mut visit: impl FnMut(&'a Unit, i64)) {
+            let mut stack = vec![(unit, 1i64)];
+            while let Some((unit, exponent)) = stack.pop() {
+                match unit {
+                    Compound(numerator, denominator) => {
+                        stack.extend(denominator.iter().rev().map(|u| (u, -exponent)));
+                        stack.extend(numerator.iter().rev().map(|u| (u, exponent)));
                     }
+                    Power(base, power) => stack.push((base, exponent.saturating_mul(i64::from(*power)))),
+                    _ if exponent == 0 => {}
+                    named => visit(named, exponent),
                 }
+            }
+        }
 
-                Compound(flat_numerator, flat_denominator)
+        match self {
+            Compound(..) if self.is_flat() => self,
+            Compound(..) | Power(..) => {
+                // Count the components first so that the output is only allocated once
+                let (mut numerator_len, mut denominator_len) = (0, 0);
+                visit_named(&self, |_, exponent| if exponent < 0 { denominator_len += 1 } else { numerator_len += 1 });
+
+                let mut flat_numerator = Vec::with_capacity(numerator_len);
+                let mut flat_denominator = Vec::with_capacity(denominator_len);
+                visit_named(&self, |named, exponent| {
+                    let side = if exponent < 0 { &mut flat_denominator } else { &mut flat_numerator };
+                    side.push(Unit::power_of(named.clone(), saturate(exponent.unsigned_abs())));
+                });
+
+                Compound(flat_numerator.into(), flat_denominator.into())
             }
             u => u,
         }
@@ -179,11 +287,127 @@ impl Unit {
 
     /// Returns the unit in the form of ```(numerator, denominator)```
     pub fn to_fraction(self) -> (Vec<Unit>, Vec<Unit>) {
+        let (n, d) = self.into_components();
+        (n.into_vec(), d.into_vec())
+    }
+
+    /// Like ```to_fraction``` but keeps the shared component lists.
+    pub(crate) fn into_components(self) -> (UnitList, UnitList) {
         use Unit::*;
 
         match self {
             Compound(n, d) => (n, d),
-            u => (vec![u], vec![]),
+            Power(..) => self.flatten().into_components(),
+            u => (vec![u].into(), UnitList::new()),
+        }
+    }
+
+    /// How often every named unit occurs in the numerator and in the denominator of the flattened
+    /// unit, as ```(index, count)``` pairs sorted by index.
+    ///
+    /// Powers multiply the counts instead of being expanded, so large exponents are cheap.
+    fn factor_counts(&self) -> [Vec<(usize, i128)>; 2] {
+        fn add(unit: &Unit, multiplier: i128, counts: &mut [Vec<(usize, i128)>; 2]) {
+            match unit {
+                Compound(numerator, denominator) => {
+                    numerator.iter().for_each(|u| add(u, multiplier, counts));
+                    denominator.iter().for_each(|u| add(u, -multiplier, counts));
+                }
+                Power(base, exponent) => add(base, multiplier.saturating_mul(i128::from(*exponent)), counts),
+                _ if multiplier == 0 => {}
+                named => {
+                    let index = named.index().expect("only compounds and powers have no index");
+                    let side = &mut counts[usize::from(multiplier < 0)];
+                    match side.iter_mut().find(|(i, _)| *i == index) {
+                        Some((_, count)) => *count = count.saturating_add(multiplier.abs()),
+                        None => side.push((index, multiplier.abs())),
+                    }
+                }
+            }
+        }
+
+        let mut counts = [vec![], vec![]];
+        add(self, 1, &mut counts);
+        counts.iter_mut().for_each(|side| side.sort_unstable());
+        counts
+    }
+
+    /// Checks if a compound unit only contains named units and positive powers of named units,
+    /// which is the form ```flatten``` returns.
+    fn is_flat(&self) -> bool {
+        match self {
+            Compound(numerator, denominator) => numerator.iter().chain(denominator.iter()).all(|u| match u {
+                Compound(..) => false,
+                Power(base, exponent) => *exponent > 0 && base.index().is_some(),
+                _ => true,
+            }),
+            Power(..) => false,
+            _ => true,
+        }
+    }
+
+    /// Checks if a compound unit only contains named units, without any powers.
+    fn has_only_named(&self) -> bool {
+        match self {
+            Compound(numerator, denominator) => numerator.iter().chain(denominator.iter()).all(|u| u.index().is_some()),
+            Power(..) => false,
+            _ => true,
+        }
+    }
+
+    /// The base and exponent of a component of a flat unit, which is a named unit or a power of
+    /// one. Other units are their own base with an exponent of one.
+    pub(crate) fn as_power(&self) -> (&Unit, i32) {
+        match self {
+            Power(base, exponent) if base.index().is_some() => (base, *exponent),
+            u => (u, 1),
+        }
+    }
+
+    /// The component of a flat unit for a named unit raised to a positive exponent.
+    fn power_of(named: Unit, exponent: i32) -> Unit {
+        if exponent == 1 {
+            named
+        } else {
+            Power(Box::new(named), exponent)
+        }
+    }
+
+    /// Appends the components of a unit raised to ```exponent``` to the given numerator and
+    /// denominator as named units and powers of them, swapping the sides for negative exponents.
+    fn push_power_into(&self, exponent: i32, numerator: &mut Vec<Unit>, denominator: &mut Vec<Unit>) {
+        match self {
+            Compound(n, d) => {
+                n.iter().for_each(|u| u.push_power_into(exponent, numerator, denominator));
+                d.iter().for_each(|u| u.push_power_into(exponent, denominator, numerator));
+            }
+            Power(base, power) => base.push_power_into(power.saturating_mul(exponent), numerator, denominator),
+            _ if exponent == 0 => {}
+            named => {
+                let side = if exponent < 0 { denominator } else { numerator };
+                side.push(Unit::power_of(named.clone(), exponent.saturating_abs()));
+            }
+        }
+    }
+
+    /// The slope of a component of a flat unit, see ```as_power```.
+    fn component_slope(&self) -> BigRational {
+        let (named, exponent) = self.as_power();
+        let slope = named.named_si_units().1;
+        if exponent == 1 || slope.is_one() {
+            slope
+        } else {
+            magnitude_pow(slope, exponent)
+        }
+    }
+
+    /// Reduces an unreduced slope once its parts grow past ```SLOPE_REDUCTION_BITS```, so the final
+    /// gcd never runs on astronomically large integers.
+    fn bound_slope(slope: BigRational) -> BigRational {
+        if slope.numer().bits() > SLOPE_REDUCTION_BITS || slope.denom().bits() > SLOPE_REDUCTION_BITS {
+            slope.reduced()
+        } else {
+            slope
         }
     }
 
@@ -191,92 +415,557 @@ impl Unit {
     ///
     /// # Example:
     /// ```
-    /// # use tantalum_unit::c;
-    /// # use tantalum_unit::unit::Unit;
-    /// use tantalum_unit::unit::Unit::*;
+    /// use tantalum_unit::prelude::*;
     ///
     /// let unit = (Meter * Second) / Second; // ms/s
     /// let simplified = unit.simplify();
     ///
     /// assert_eq!(simplified, Meter);
     /// ```
-    pub fn simplify(mut self) -> Self {
+    pub fn simplify(self) -> Self {
         use Unit::*;
 
-        self = self.flatten();
+        match self.flatten() {
+            Compound(mut num, mut denom) => {
+                Unit::combine(&mut num, &mut denom);
+                if denom.is_empty() && num.len() == 1 {
+                    num[0].clone()
+                } else {
+                    Compound(num, denom)
+                }
+            }
+            u => u
+        }
+    }
+
+    /// Merges repeated factors of a flat unit into powers and cancels factors of the numerator
+    /// against the denominator, only copying shared lists if something changes.
+    ///
+    /// A prefix stays with the unit after it, so ```km*m``` isn't merged into ```k*m^2```.
+    fn combine(numerator: &mut UnitList, denominator: &mut UnitList) {
+        // The net exponent of every factor, in the order they first appear
+        let mut exponents = IndexMap::with_capacity(numerator.len() + denominator.len());
+        let (mut factors, mut components) = (0, 0);
+        for (units, sign) in [(&**numerator, 1), (&**denominator, -1)] {
+            for ((prefix, unit), exponent) in prefixed_factors(units) {
+                factors += 1;
+                components += 1 + usize::from(prefix.is_some());
+                *exponents.entry((prefix.cloned(), unit.clone())).or_insert(0i64) += sign * i64::from(exponent);
+            }
+        }
+        if exponents.len() == factors && components == numerator.len() + denominator.len() {
+            return;
+        }
+
+        let (mut new_numerator, mut new_denominator) = (vec![], vec![]);
+        for ((prefix, unit), exponent) in exponents {
+            let side = if exponent < 0 { &mut new_denominator } else { &mut new_numerator };
+            if exponent != 0 {
+                let exponent = saturate(exponent.unsigned_abs());
+                side.extend(prefix.map(|prefix| Unit::power_of(prefix, exponent)));
+                side.push(Unit::power_of(unit, exponent));
+            }
+        }
+        *numerator = new_numerator.into();
+        *denominator = new_denominator.into();
+    }
+
+    /// Simplifies the unit, expanding derived units like ```Hertz``` or ```Newton``` into their SI
+    /// definitions if that lets components cancel.
+    ///
+    /// Only units that equal their SI definition are expanded, so the unit keeps its scale. If
+    /// nothing cancels, the unit is only simplified. A result that is exactly a named derived
+    /// unit is rewritten to it, like in ```to_named```.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// assert_eq!((Hertz * Second).normalize(), UNITLESS);
+    /// assert_eq!((Newton / Pascal).normalize(), Meter * Meter);
+    /// assert_eq!((Watt * Second).normalize(), Joule);
+    /// assert_eq!((Kilo * Meter / Hour).normalize(), Kilo * Meter / Hour);
+    /// ```
+    pub fn normalize(self) -> Unit {
+        match self.expand_and_cancel(|unit| unit.offset().is_zero() && unit.slope().is_one()) {
+            Some((_, unit)) => unit.to_named(),
+            None => self.simplify(),
+        }
+    }
+
+    /// Expands the components selected by ```expand``` into their SI definitions and cancels the
+    /// result. Returns the product of the slopes of the expanded components with the simplified
+    /// unit, or ```None``` if nothing cancels.
+    pub(crate) fn expand_and_cancel(&self, expand: impl Fn(&Unit) -> bool) -> Option<(BigRational, Unit)> {
+        let (numerator, denominator) = self.clone().flatten().into_components();
+        let mut slope = one!();
+        let mut new_numerator = Vec::with_capacity(numerator.len());
+        let mut new_denominator = Vec::with_capacity(denominator.len());
+
+        // The unit is flat, so every component is a named unit or a power of one
+        for unit in numerator.iter() {
+            let (named, exponent) = unit.as_power();
+            if expand(named) {
+                slope *= unit.component_slope();
+                named.named_si_units().2.push_power_into(exponent, &mut new_numerator, &mut new_denominator);
+            } else {
+                new_numerator.push(unit.clone());
+            }
+        }
+        for unit in denominator.iter() {
+            let (named, exponent) = unit.as_power();
+            if expand(named) {
+                slope /= unit.component_slope();
+                named.named_si_units().2.push_power_into(exponent, &mut new_denominator, &mut new_numerator);
+            } else {
+                new_denominator.push(unit.clone());
+            }
+        }
+
+        let (mut numerator, mut denominator): (UnitList, UnitList) = (new_numerator.into(), new_denominator.into());
+        let expanded_exponents = exponent_sum(&numerator) + exponent_sum(&denominator);
+        Unit::combine(&mut numerator, &mut denominator);
+        if exponent_sum(&numerator) + exponent_sum(&denominator) == expanded_exponents {
+            return None;
+        }
+        Some((slope, Compound(numerator, denominator).simplify()))
+    }
+
+    /// Removes all SI and binary modifiers from the unit and returns their combined factor with
+    /// the remaining unit, which is simplified.
+    ///
+    /// Modifiers in the denominator divide the factor.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// assert_eq!((Kilo * Meter / (Milli * Second)).strip_prefixes(), (ratio!(1_000_000, 1), Meter / Second));
+    /// assert_eq!(Meter.strip_prefixes(), (ratio!(1, 1), Meter));
+    /// ```
+    pub fn strip_prefixes(self) -> (BigRational, Unit) {
+        let (numerator, denominator) = self.flatten().to_fraction();
+
+        let mut factor = one!();
+        let mut new_num = vec![];
+        let mut new_denom = vec![];
+
+        for unit in numerator {
+            if unit.as_power().0.is_modifier() {
+                factor *= unit.component_slope();
+            } else {
+                new_num.push(unit);
+            }
+        }
+
+        for unit in denominator {
+            if unit.as_power().0.is_modifier() {
+                factor /= unit.component_slope();
+            } else {
+                new_denom.push(unit);
+            }
+        }
+
+        (factor, Compound(new_num.into(), new_denom.into()).simplify())
+    }
 
+    /// The exponents of the SI units a unit is made of, indexed like ```NAMED_UNITS```.
+    ///
+    /// Two units measure the same dimension exactly when their exponents are equal, regardless
+    /// of the order of their components. The exponents of shared compound units are cached.
+    pub(crate) fn si_exponents(&self) -> Vec<i32> {
         match self {
-            Compound(ref mut num, ref mut denom) => {
-                let mut i = 0;
-                while i < num.len() {
-                    if let Some(pos) = denom.iter().position(|d| d == &num[i]) {
-                        num.remove(i);
-                        denom.remove(pos);
-                    } else {
-                        i += 1;
+            Compound(numerator, denominator) => numerator.exponents().iter()
+                .zip(denominator.exponents().iter())
+                .map(|(n, d)| n.saturating_sub(*d))
+                .collect(),
+            Power(base, exponent) => base.si_exponents().into_iter().map(|e| e.saturating_mul(*exponent)).collect(),
+            named => named.named_exponents().to_vec(),
+        }
+    }
+
+    /// Whether the units measure the same dimension, like comparing ```si_exponents``` but
+    /// without allocating.
+    pub(crate) fn has_same_dimension(&self, other: &Unit) -> bool {
+        self.dimension_matches(other, 1)
+    }
+
+    /// Checks if quantities in this unit can be added to or converted into quantities in ```other```,
+    /// comparing the SI units both are made of. Prefixes, slopes and offsets are ignored.
+    ///
+    /// Unlike ```convert_to```, reciprocal units like ```Ohm``` and ```Siemens``` are not compatible.
+    /// Temperatures in compound units like ```Kilo * Celsius``` aren't compatible with any unit,
+    /// since ```convert_to``` rejects them as ambiguous.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// assert!((Kilo * Watt * Hour).is_compatible_with(&Joule));
+    /// assert!(Fahrenheit.is_compatible_with(&Kelvin));
+    /// assert!(!Meter.is_compatible_with(&Second));
+    /// ```
+    pub fn is_compatible_with(&self, other: &Unit) -> bool {
+        self.has_same_dimension(other) && !self.has_ambiguous_offset() && !other.has_ambiguous_offset()
+    }
+
+    /// Whether the unit measures the reciprocal dimension of ```other```, like ```Second``` and ```Hertz```.
+    pub(crate) fn has_reciprocal_dimension(&self, other: &Unit) -> bool {
+        self.dimension_matches(other, -1)
+    }
+
+    /// Whether the exponents of the unit are those of ```other``` multiplied by ```sign```.
+    fn dimension_matches(&self, other: &Unit, sign: i32) -> bool {
+        fn parts(unit: &Unit) -> (Cow<'_, [i32]>, Cow<'_, [i32]>) {
+            match unit {
+                Compound(numerator, denominator) => (numerator.exponents(), denominator.exponents()),
+                Power(..) => (Cow::Owned(unit.si_exponents()), Cow::Borrowed(&[])),
+                named => (Cow::Borrowed(named.named_exponents()), Cow::Borrowed(&[])),
+            }
+        }
+        let at = |exponents: &[i32], i: usize| i64::from(exponents.get(i).copied().unwrap_or(0));
+
+        let ((a_numerator, a_denominator), (b_numerator, b_denominator)) = (parts(self), parts(other));
+        (0..Unit::NAMED_UNITS.len()).all(|i| {
+            at(&a_numerator, i) - at(&a_denominator, i) == i64::from(sign) * (at(&b_numerator, i) - at(&b_denominator, i))
+        })
+    }
+
+    /// The exponents of a named unit, computed once for all named units.
+    fn named_exponents(&self) -> &'static [i32] {
+        static TABLE: OnceLock<Vec<Vec<i32>>> = OnceLock::new();
+        let table = TABLE.get_or_init(|| {
+            Unit::NAMED_UNITS.iter().map(|unit| {
+                let mut exponents = vec![0; Unit::NAMED_UNITS.len()];
+                let named_index = |u: &Unit| u.index().expect("flat units only contain named units");
+
+                // Derived units are defined in terms of kilograms, so a second pass is needed to reduce the prefix
+                let (_, _, si_unit) = unit.clone().to_si_units();
+                match si_unit.to_si_units().2.flatten() {
+                    Compound(numerator, denominator) => {
+                        for (u, exponent) in numerator.iter().map(Unit::as_power) {
+                            exponents[named_index(u)] += exponent;
+                        }
+                        for (u, exponent) in denominator.iter().map(Unit::as_power) {
+                            exponents[named_index(u)] -= exponent;
+                        }
                     }
+                    u => exponents[named_index(&u)] += 1,
                 }
-                if denom.is_empty() {
-                    if num.len() == 1 {
-                        num[0].clone()
-                    } else {
-                        Compound(num.clone(), vec![])
-                    }
+                exponents
+            }).collect()
+        });
+        &table[self.index().expect("named_exponents called on a compound unit")]
+    }
+
+    /// The named units that measure the same dimension as this unit, in declaration order.
+    ///
+    /// Modifiers are left out, since they only scale other units.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// assert_eq!(Second.units_for_dimension(), [Second, Minute, Hour, Day, Month, Year, SiderealDay, SiderealYear, JulianYear, TropicalYear]);
+    /// assert!((Kilo * Meter / Hour).units_for_dimension().is_empty());
+    /// ```
+    pub fn units_for_dimension(&self) -> Vec<Unit> {
+        Unit::NAMED_UNITS.iter()
+            .filter(|u| !u.is_modifier() && u.has_same_dimension(self))
+            .cloned()
+            .collect()
+    }
+
+    /// A unit both units convert to without growing the denominators of whole magnitudes.
+    ///
+    /// The rule is:
+    /// 1. If both units have the same slope, like ```Celsius``` and ```Kelvin```, the unit itself.
+    /// 2. If one unit is a whole multiple of the other, the finer unit. A mile is 1,609,344
+    ///    millimeters, so whole miles stay whole in millimeters.
+    /// 3. Otherwise the SI unit of the dimension, as returned by ```to_si_units```.
+    ///
+    /// Returns an error if the units measure different dimensions.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// assert_eq!(Mile.common_unit(&(Milli * Meter)), Ok(Milli * Meter));
+    /// assert_eq!(Inch.common_unit(&(Centi * Meter)), Ok(Meter));
+    /// ```
+    pub fn common_unit(&self, other: &Unit) -> Result<Unit, ConversionError> {
+        if !self.has_same_dimension(other) {
+            return Err(ConversionError::IncompatibleUnits { from: self.clone(), to: other.clone() });
+        }
+        let ratio = self.si_affine().1 / other.si_affine().1;
+        if ratio.is_one() || ratio.recip().is_integer() {
+            Ok(self.clone())
+        } else if ratio.is_integer() {
+            Ok(other.clone())
+        } else {
+            Ok(self.clone().to_si_units().2)
+        }
+    }
+
+    /// Units to suggest when this unit can't be converted to ```to```, at most ```limit``` of them.
+    ///
+    /// If the unit is off by one power of time, like ```Joule``` for ```Watt```, the unit divided
+    /// or multiplied by ```Second``` comes first, followed by the named units of the dimension of
+    /// ```to```. Otherwise the suggestions are the other named units this unit converts to.
+    pub(crate) fn conversion_suggestions(&self, to: &Unit, limit: usize) -> Vec<Unit> {
+        let per_second = self.clone() / Second;
+        let times_second = self.clone() * Second;
+        let mut suggestions = if per_second.has_same_dimension(to) {
+            let mut units = vec![per_second];
+            units.extend(to.units_for_dimension());
+            units
+        } else if times_second.has_same_dimension(to) {
+            let mut units = vec![times_second];
+            units.extend(to.units_for_dimension());
+            units
+        } else {
+            self.units_for_dimension().into_iter().filter(|unit| unit != self).collect()
+        };
+        suggestions.truncate(limit);
+        suggestions
+    }
+
+    /// The factors that convert a magnitude in this unit to each of the targets, decomposing this
+    /// unit into SI units only once.
+    ///
+    /// A factor ignores the offsets of units like ```Celsius```, so it converts differences rather
+    /// than absolute values. Targets that ```Quantity::convert_to``` only reaches through the
+    /// reciprocal have no factor and return an error like incompatible targets.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let factors = Hour.conversion_factors_to_many(&[Minute, Milli * Second, Meter]);
+    ///
+    /// assert_eq!(factors[0], Ok(ratio!(60, 1)));
+    /// assert_eq!(factors[1], Ok(ratio!(3_600_000, 1)));
+    /// assert!(factors[2].is_err());
+    /// ```
+    pub fn conversion_factors_to_many(&self, targets: &[Unit]) -> Vec<Result<BigRational, ConversionError>> {
+        let (_, slope) = self.si_affine();
+        targets.iter()
+            .map(|to| {
+                if self.has_same_dimension(to) {
+                    Ok(slope.clone() / to.si_affine().1)
                 } else {
-                    Compound(num.clone(), denom.clone())
+                    Err(ConversionError::IncompatibleUnits { from: self.clone(), to: to.clone() })
                 }
+            })
+            .collect()
+    }
+
+    /// Checks if the unit is a compound unit measuring a temperature that contains an affine unit,
+    /// like ```Kilo * Celsius```. Compound units convert differences while a lone ```Celsius``` is
+    /// an absolute temperature, so it's unclear which one these units measure.
+    pub(crate) fn has_ambiguous_offset(&self) -> bool {
+        match self.clone().flatten() {
+            Compound(numerator, denominator) if numerator.len() != 1 || !denominator.is_empty() || numerator[0].index().is_none() => {
+                numerator.iter().chain(denominator.iter()).any(|u| !u.as_power().0.offset().is_zero())
+                    && self.has_same_dimension(&Kelvin)
             }
-            u => u
+            _ => false,
         }
     }
 
-    /// Checks if the unit is an SI or binary modifier like Micro or Kibi.
-    pub fn is_modifier(&self) -> bool {
-        match self {
-            Yocto => true,
-            Zepto => true,
-            Atto => true,
-            Femto => true,
-            Pico => true,
-            Nano => true,
-            Micro => true,
-            Milli => true,
-            Centi => true,
-            Deci => true,
-            Hecto => true,
-            Kilo => true,
-            Mega => true,
-            Giga => true,
-            Tera => true,
-            Peta => true,
-            Exa => true,
-            Zetta => true,
-            Yotta => true,
-            Kibi => true,
-            Mebi => true,
-            Gibi => true,
-            Tebi => true,
-            Pebi => true,
-            Exbi => true,
-
-            _ => false
-        }
-    }
-
-    /// Checks if the unit represents a dimensionless value.
+    /// The offset and slope converting this unit into SI base units.
+    ///
+    /// Unlike ```to_si_units``` the slope also applies the prefixes left over in the SI units of
+    /// derived units like ```Joule```, so it doesn't depend on how the unit is composed.
+    pub(crate) fn si_affine(&self) -> (BigRational, BigRational) {
+        let (offset, slope, si_unit) = self.clone().to_si_units();
+        let (prefix_offset, prefix_slope, _) = si_unit.to_si_units();
+        (offset + prefix_offset / &slope, slope * prefix_slope)
+    }
+
+    /// A string identifying the dimension of the unit in SI base units, for grouping and
+    /// deduplicating units.
+    ///
+    /// The key lists every base unit with a nonzero exponent as ```symbol^exponent```, sorted by
+    /// symbol in byte order and separated by ```·```. Prefixes are dropped, so ```Watt``` and
+    /// ```Kilo * Watt``` have the key ```g^1·m^2·s^-3```. Unitless units have an empty key.
+    /// The format is stable across versions.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// assert_eq!(Watt.canonical_key(), "g^1·m^2·s^-3");
+    /// assert_eq!((Joule / Second).canonical_key(), (Kilo * Watt).canonical_key());
+    /// assert_eq!(Percent.canonical_key(), "");
+    /// ```
+    pub fn canonical_key(&self) -> String {
+        let (numerator, denominator) = self.clone().to_si_units().2.to_si_units().2.to_fraction();
+        let mut exponents = std::collections::BTreeMap::new();
+        for (unit, sign) in numerator.iter().map(|u| (u, 1)).chain(denominator.iter().map(|u| (u, -1))) {
+            let (named, exponent) = unit.as_power();
+            *exponents.entry(named.symbol()).or_insert(0i64) += sign * i64::from(exponent);
+        }
+        exponents.iter()
+            .filter(|(_, exponent)| **exponent != 0)
+            .map(|(symbol, exponent)| format!("{symbol}^{exponent}"))
+            .collect::<Vec<_>>()
+            .join("·")
+    }
+
+    /// Rewrites a compound unit made of base units to the named derived unit of its dimension,
+    /// like ```kg*m^2/s^3``` to ```Watt```.
+    ///
+    /// The unit is only rewritten if it is exactly the named unit, so ```kg*m^2/s^3``` becomes
+    /// ```Watt``` but ```g*m^2/s^3``` stays as it is. ```Quantity::with_named_unit``` rewrites
+    /// those as well, by folding the factor into the magnitude. Named units and compounds whose
+    /// dimension has no named unit are returned unchanged. Which unit wins when several share a
+    /// dimension is fixed: ```Joule``` over ```N*m``` and ```Hertz``` over ```Baud```.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// assert_eq!((Kilo * Gram * Meter * Meter / (Second * Second * Second)).to_named(), Watt);
+    /// assert_eq!((Newton * Meter).to_named(), Joule);
+    /// assert_eq!((Meter / Second).to_named(), Meter / Second);
+    /// ```
+    pub fn to_named(self) -> Unit {
+        match self.named_equivalent() {
+            Some(named) if self.si_affine() == named.si_affine() => named,
+            _ => self,
+        }
+    }
+
+    /// The named derived unit with the dimension of this compound unit, or ```None``` for named
+    /// units and dimensions without one.
+    pub(crate) fn named_equivalent(&self) -> Option<Unit> {
+        if !matches!(self, Compound(..) | Power(..)) {
+            return None;
+        }
+        NAMED_DERIVED_UNITS.into_iter().find(|named| self.has_same_dimension(named))
+    }
+
+    /// The symbol of the unit with the powers of prefixed units in parentheses, e.g. ```(km)^2```
+    /// where ```symbol``` returns ```km^2```.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let unit = Meter / (Milli * Second * Milli * Second * Milli * Second);
+    /// assert_eq!(unit.symbol(), "m/ms^3");
+    /// assert_eq!(unit.parenthesized_symbol(), "m/(ms)^3");
+    /// ```
+    pub fn parenthesized_symbol(&self) -> Cow<'static, str> {
+        self.symbol_with(true, false)
+    }
+
+    /// The symbol of the simplified unit with its components in a fixed order, so that equal
+    /// units render identically however they were built.
+    ///
+    /// The SI base units come first, in the order ```kg```, ```m```, ```s```, ```A```, ```K```,
+    /// ```mol```, ```cd```, followed by the other units sorted by their symbols. Prefixes stay
+    /// attached to their unit.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// let power = Meter * Meter * Kilo * Gram / (Second * Second * Second);
+    /// assert_eq!(power.symbol(), "m^2*kg/s^3");
+    /// assert_eq!(power.canonical_symbol(), "kg*m^2/s^3");
+    /// ```
+    pub fn canonical_symbol(&self) -> String {
+        self.clone().simplify().symbol_with(false, true).into_owned()
+    }
+
+    /// Renders the symbol with the powers of prefixed units parenthesized and the components sorted
+    /// canonically, see ```format_symbols```.
+    pub(crate) fn symbol_with(&self, parenthesize: bool, sorted: bool) -> Cow<'static, str> {
+        let Compound(numerator, denominator) = self else {
+            return match self {
+                Power(..) => self.clone().flatten().symbol_with(parenthesize, sorted),
+                named => named.symbol(),
+            };
+        };
+        let numerator = format_symbols(numerator, parenthesize, sorted);
+        let denominator = format_symbols(denominator, parenthesize, sorted);
+        Cow::Owned(fraction_symbol(&numerator, &denominator))
+    }
+
+    /// Checks if the unit represents a dimensionless value, i.e. if it cancels to ```UNITLESS```.
+    ///
+    /// Units like ```m/m``` or ```k/k``` are unitless even if they were never simplified, while
+    /// a bare modifier like ```Kilo``` isn't, since it scales the value.
+    ///
+    /// # Example:
+    /// ```
+    /// use tantalum_unit::prelude::*;
+    ///
+    /// assert!(Compound(vec![Meter].into(), vec![Meter].into()).is_unitless());
+    /// assert!(!Kilo.is_unitless());
+    /// ```
     pub fn is_unitless(&self) -> bool {
+        match self {
+            Compound(numerator, denominator) if numerator.is_empty() && denominator.is_empty() => true,
+            Compound(..) | Power(..) => self.clone().simplify().is_strictly_unitless(),
+            _ => false,
+        }
+    }
+
+    /// Checks if the unit is exactly ```UNITLESS```, without canceling units like ```m/m```.
+    pub fn is_strictly_unitless(&self) -> bool {
         *self == UNITLESS
     }
 }
 
+/// Units are equal if they flatten to the same named units with the same exponents, regardless of
+/// their order. So ```m*s``` equals ```s*m``` and ```Power(Box::new(Meter), 3)``` equals a compound
+/// of three meters, but ```m/m``` isn't ```UNITLESS```, since nothing is canceled.
+impl PartialEq for Unit {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Compound(n1, d1), Compound(n2, d2)) if self.has_only_named() && other.has_only_named() => n1 == n2 && d1 == d2,
+            (Compound(..) | Power(..), _) | (_, Compound(..) | Power(..)) => self.factor_counts() == other.factor_counts(),
+            _ => self.index() == other.index(),
+        }
+    }
+}
+
+impl Eq for Unit {}
+
+/// Hashes the flattened components like ```PartialEq``` compares them.
+impl Hash for Unit {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self.index() {
+            // The counts of a named unit, without allocating them
+            Some(index) => {
+                [(index, 1i128)].hash(state);
+                <[(usize, i128)]>::hash(&[], state);
+            }
+            None => {
+                for side in self.factor_counts() {
+                    side.hash(state);
+                }
+            }
+        }
+    }
+}
+
 impl Mul for Unit {
     type Output = Unit;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        let (mut numer1, mut denom1) = self.to_fraction();
-        let (mut numer2, mut denom2) = rhs.to_fraction();
-        numer1.append(&mut numer2);
-        denom1.append(&mut denom2);
+        // With both operands flat, the result is flat and simplify only has to cancel
+        let (mut numer1, mut denom1) = self.flatten().into_components();
+        match rhs.flatten() {
+            Compound(numer2, denom2) => {
+                numer1.make_mut().extend_from_slice(&numer2);
+                denom1.make_mut().extend_from_slice(&denom2);
+            }
+            u => numer1.make_mut().push(u),
+        }
         Compound(numer1, denom1).simplify()
     }
 }
@@ -291,11 +980,16 @@ impl Div for Unit {
     type Output = Unit;
 
     fn div(self, rhs: Self) -> Self::Output {
-        let (mut numer1, mut denom1) = self.to_fraction();
-        let (mut numer2, mut denom2) = rhs.to_fraction();
+        // With both operands flat, the result is flat and simplify only has to cancel
+        let (mut numer1, mut denom1) = self.flatten().into_components();
         // Multiply with the reciprocal
-        numer1.append(&mut denom2);
-        denom1.append(&mut numer2);
+        match rhs.flatten() {
+            Compound(numer2, denom2) => {
+                numer1.make_mut().extend_from_slice(&denom2);
+                denom1.make_mut().extend_from_slice(&numer2);
+            }
+            u => denom1.make_mut().push(u),
+        }
         Compound(numer1, denom1).simplify()
     }
 }
@@ -306,17 +1000,503 @@ impl DivAssign for Unit {
     }
 }
 
+/// Renders the symbols of a list of units, combining repeated units into powers. E.g. ```["m^2", "s"]```.
+///
+/// A modifier is paired with the unit after it, so the power of a prefixed unit applies to the
+/// prefix as well: ```km^2```, or ```(km)^2``` if ```parenthesize``` is set. If ```sorted``` is
+/// set, the units are ordered like in ```Unit::canonical_symbol``` instead of by their first occurrence.
+pub(crate) fn format_symbols(units: &[Unit], parenthesize: bool, sorted: bool) -> Vec<String> {
+    let mut counts = IndexMap::new();
+    for (factor, count) in prefixed_factors(units) {
+        *counts.entry(factor).or_insert(0) += i64::from(count);
+    }
+    if sorted {
+        counts.sort_by_cached_key(|(prefix, unit), _| (canonical_rank(unit), unit.symbol(), prefix.map(Unit::symbol)));
+    }
+
+    counts.into_iter()
+        .map(|((prefix, unit), count)| {
+            let symbol = match prefix {
+                Some(prefix) => format!("{}{}", prefix.symbol(), unit.symbol()),
+                None => unit.symbol().into_owned(),
+            };
+            match count {
+                1 => symbol,
+                _ if parenthesize && prefix.is_some() => format!("({})^{}", symbol, count),
+                _ => format!("{}^{}", symbol, count),
+            }
+        })
+        .collect()
+}
+
+/// Writes the symbols of a compound unit the way ```FromStr``` reads them back: the factors of
+/// the numerator are separated by ```*``` and every factor of the denominator follows a ```/```,
+/// like ```kg*m/s^2``` or ```J/kg/K```.
+///
+/// Juxtaposed factors would be ambiguous, e.g. ```m*s``` with a millisecond, and a single ```/```
+/// only divides by the factor directly after it.
+pub(crate) fn write_fraction<W: std::fmt::Write>(out: &mut W, numerator: &[String], denominator: &[String]) -> std::fmt::Result {
+    if numerator.is_empty() && !denominator.is_empty() {
+        out.write_char('1')?;
+    }
+    for (i, symbol) in numerator.iter().enumerate() {
+        if i > 0 {
+            out.write_char('*')?;
+        }
+        out.write_str(symbol)?;
+    }
+    for symbol in denominator {
+        out.write_char('/')?;
+        out.write_str(symbol)?;
+    }
+    Ok(())
+}
+
+/// The symbol of a compound unit, see ```write_fraction```.
+pub(crate) fn fraction_symbol(numerator: &[String], denominator: &[String]) -> String {
+    let mut symbol = String::new();
+    write_fraction(&mut symbol, numerator, denominator).expect("writing to a String can't fail");
+    symbol
+}
+
+/// The position of a unit in canonical symbols, where the SI base units come first.
+fn canonical_rank(unit: &Unit) -> usize {
+    const BASE_UNITS: [Unit; 7] = [Gram, Meter, Second, Ampere, Kelvin, Mole, Candela];
+    BASE_UNITS.iter().position(|base| base == unit).unwrap_or(BASE_UNITS.len())
+}
+
+/// The sums of the SI exponents of a list of units, see ```Unit::si_exponents```.
+pub(crate) fn sum_exponents(units: &[Unit]) -> Vec<i32> {
+    let mut exponents = vec![0i32; Unit::NAMED_UNITS.len()];
+    for unit in units {
+        for (sum, exponent) in exponents.iter_mut().zip(unit.si_exponents()) {
+            *sum = sum.saturating_add(exponent);
+        }
+    }
+    exponents
+}
+
+/// Splits a flat list of units into factors with their exponents. A modifier is paired with the
+/// unit after it, for as many powers as both have, and the rest of either is a factor of its own.
+fn prefixed_factors(units: &[Unit]) -> Vec<((Option<&Unit>, &Unit), i32)> {
+    let mut factors = Vec::with_capacity(units.len());
+    let mut units = units.iter().map(Unit::as_power).peekable();
+    while let Some((unit, count)) = units.next() {
+        match units.peek() {
+            Some(&(next, next_count)) if unit.is_modifier() && !next.is_modifier() => {
+                units.next();
+                let paired = count.min(next_count);
+                if count > paired {
+                    factors.push(((None, unit), count - paired));
+                }
+                factors.push(((Some(unit), next), paired));
+                if next_count > paired {
+                    factors.push(((None, next), next_count - paired));
+                }
+            }
+            _ => factors.push(((None, unit), count)),
+        }
+    }
+    factors
+}
+
+/// The sum of the exponents of the components of a flat unit.
+fn exponent_sum(units: &[Unit]) -> i64 {
+    units.iter().map(|u| i64::from(u.as_power().1)).sum()
+}
+
+/// Clamps the magnitude of an exponent to the range of ```i32```.
+fn saturate(exponent: u64) -> i32 {
+    i32::try_from(exponent).unwrap_or(i32::MAX)
+}
+
+/// Renders the names of a list of units, e.g. "square meter second ".
+pub(crate) fn format_names(units: &[Unit]) -> String {
+    let mut counts = IndexMap::new();
+    for (unit, count) in units.iter().map(Unit::as_power) {
+        let mut name = unit.name().into_owned();
+        if !unit.is_modifier() { name += " "; }
+        *counts.entry(name).or_insert(0) += i64::from(count);
+    }
+
+    counts
+        .into_iter()
+        .map(|(name, count)| {
+            if count > 3 {
+                format!("{} to the {}", name, count)
+            } else if count == 3 {
+                format!("cubic {}", name)
+            } else if count == 2 {
+                format!("square {}", name)
+            } else {
+                name
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("")
+}
+
 impl Display for Unit {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.symbol())
+        match self {
+            // Write the cached parts directly instead of building a new String
+            Compound(n, d) => write_fraction(f, &n.symbols(), &d.symbols()),
+            u => f.write_str(&u.symbol()),
+        }
     }
 }
 
 
 #[cfg(test)]
 mod tests {
+    use num::BigInt;
     use super::*;
     use crate::c;
+    use crate::quantity::Quantity;
+
+    const CONST_UNITS: [Unit; 2] = [KILOMETER, Compound(UnitList::from_static(&[Mega, Joule]), UnitList::new())];
+    static STATIC_UNIT: Unit = KILOMETER_PER_HOUR;
+
+    #[test]
+    fn constants() {
+        assert_eq!(KILOMETER, Kilo * Meter);
+        assert_eq!(CENTIMETER, Centi * Meter);
+        assert_eq!(MILLIMETER, Milli * Meter);
+        assert_eq!(SQUARE_METER, Meter * Meter);
+        assert_eq!(CUBIC_METER, Meter * Meter * Meter);
+        assert_eq!(KILOGRAM, Kilo * Gram);
+        assert_eq!(MILLIGRAM, Milli * Gram);
+        assert_eq!(MILLILITER, Milli * Liter);
+        assert_eq!(METER_PER_SECOND, Meter / Second);
+        assert_eq!(KILOMETER_PER_HOUR, Kilo * Meter / Hour);
+        assert_eq!(KILOWATT, Kilo * Watt);
+        assert_eq!(KILOWATT_HOUR, Kilo * Watt * Hour);
+        assert_eq!(METER_PER_SECOND_SQUARED, Meter / (Second * Second));
+        assert_eq!(MILE_PER_HOUR, Mile / Hour);
+        assert_eq!(NEWTON_METER, Newton * Meter);
+        assert_eq!(KILOGRAM_PER_CUBIC_METER, (Kilo * Gram) / (Meter * Meter * Meter));
+        assert_eq!(MOLAR, Mole / Liter);
+
+        assert_eq!(CONST_UNITS[1], Mega * Joule);
+        assert_eq!(STATIC_UNIT.symbol(), "km/h");
+
+        let constants = [KILOMETER, CENTIMETER, MILLIMETER, SQUARE_METER, CUBIC_METER, KILOGRAM, MILLIGRAM, MILLILITER,
+            METER_PER_SECOND, KILOMETER_PER_HOUR, KILOWATT, KILOWATT_HOUR, METER_PER_SECOND_SQUARED, MILE_PER_HOUR,
+            NEWTON_METER, KILOGRAM_PER_CUBIC_METER, MOLAR];
+        for constant in constants {
+            assert_eq!(constant.clone().simplify(), constant);
+        }
+    }
+
+    #[test]
+    fn convert_with_constants() {
+        let speed = Quantity::from_i64_with_unit(1, METER_PER_SECOND);
+        assert_eq!(speed.convert_to(KILOMETER_PER_HOUR), Ok(Quantity::new(ratio!(18, 5), KILOMETER_PER_HOUR)));
+
+        let concentration = Quantity::from_i64_with_unit(250, Milli * Mole / Liter);
+        assert_eq!(concentration.convert_to(MOLAR).unwrap().magnitude, ratio!(1, 4));
+
+        let torque = Quantity::from_i64_with_unit(3, Joule);
+        assert_eq!(torque.convert_to(NEWTON_METER).unwrap().magnitude, int!(3));
+    }
+
+    #[test]
+    fn conversion_suggestions() {
+        use crate::error::ConversionError;
+
+        // Energy instead of power
+        assert_eq!(Joule.conversion_suggestions(&Watt, 3), [Joule / Second, Watt]);
+        assert_eq!((Kilo * Watt).conversion_suggestions(&Joule, 3), [Kilo * Watt * Second, Joule]);
+        // Distance instead of speed
+        assert_eq!((Kilo * Meter).conversion_suggestions(&(Meter / Second), 3), [Kilo * Meter / Second]);
+        // Mass instead of force
+        assert_eq!((Kilo * Gram).conversion_suggestions(&Newton, 3), [Gram, Tonne, Dram]);
+        assert_eq!(Gram.conversion_suggestions(&Newton, 10), [Tonne, Dram, Ounce, Pound]);
+        assert!(Ohm.conversion_suggestions(&Siemens, 3).is_empty());
+
+        let error = ConversionError::IncompatibleUnits { from: Kilo * Gram, to: Newton };
+        assert_eq!(error.to_string(), "cannot convert kg to N, did you mean g, t or dr?");
+        let error = ConversionError::IncompatibleUnits { from: Ohm, to: Siemens };
+        assert_eq!(error.to_string(), "cannot convert Ω to S");
+        assert!(ConversionError::Overflow.suggestions().is_empty());
+    }
+
+    #[test]
+    fn units_for_dimension() {
+        assert_eq!(Meter.units_for_dimension(), [Meter, AU, Inch, Feet, Yard, Mile, NauticalMile, LightYear, Parsec]);
+        assert_eq!((Kilo * Meter).units_for_dimension(), Meter.units_for_dimension());
+        assert_eq!(Celsius.units_for_dimension(), Kelvin.units_for_dimension());
+        assert!(Joule.units_for_dimension().contains(&Joule));
+        assert!((Kilo * Gram * Meter * Meter / (Second * Second)).units_for_dimension().contains(&Joule));
+        assert!(!Ohm.units_for_dimension().contains(&Siemens));
+    }
+
+    #[test]
+    fn strip_prefixes_big() {
+        assert_eq!((Yotta * Meter).strip_prefixes(), (int!(1_000_000_000_000_000_000_000_000_i128), Meter));
+        assert_eq!((Zetta * Meter).strip_prefixes(), (int!(1_000_000_000_000_000_000_000_i128), Meter));
+        assert_eq!((Exa * Meter).strip_prefixes(), (int!(1_000_000_000_000_000_000_i128), Meter));
+        assert_eq!((Peta * Meter).strip_prefixes(), (int!(1_000_000_000_000_000_i128), Meter));
+        assert_eq!((Tera * Meter).strip_prefixes(), (int!(1_000_000_000_000_i128), Meter));
+        assert_eq!((Giga * Meter).strip_prefixes(), (int!(1_000_000_000_i128), Meter));
+        assert_eq!((Mega * Meter).strip_prefixes(), (int!(1_000_000_i128), Meter));
+        assert_eq!((Kilo * Meter).strip_prefixes(), (int!(1_000), Meter));
+        assert_eq!((Hecto * Meter).strip_prefixes(), (int!(100), Meter));
+        assert_eq!(Meter.strip_prefixes(), (int!(1), Meter));
+        assert_eq!((Kibi * Byte).strip_prefixes(), (int!(1024), Byte));
+    }
+
+    #[test]
+    fn strip_prefixes_small() {
+        assert_eq!((Yocto * Meter).strip_prefixes(), (ratio!(1, 1_000_000_000_000_000_000_000_000_i128), Meter));
+        assert_eq!((Zepto * Meter).strip_prefixes(), (ratio!(1, 1_000_000_000_000_000_000_000_i128), Meter));
+        assert_eq!((Atto * Meter).strip_prefixes(), (ratio!(1, 1_000_000_000_000_000_000_i128), Meter));
+        assert_eq!((Femto * Meter).strip_prefixes(), (ratio!(1, 1_000_000_000_000_000_i128), Meter));
+        assert_eq!((Pico * Meter).strip_prefixes(), (ratio!(1, 1_000_000_000_000_i128), Meter));
+        assert_eq!((Nano * Meter).strip_prefixes(), (ratio!(1, 1_000_000_000_i128), Meter));
+        assert_eq!((Micro * Meter).strip_prefixes(), (ratio!(1, 1_000_000_i128), Meter));
+        assert_eq!((Milli * Meter).strip_prefixes(), (ratio!(1, 1_000), Meter));
+        assert_eq!((Centi * Meter).strip_prefixes(), (ratio!(1, 100), Meter));
+        assert_eq!((Deci * Meter).strip_prefixes(), (ratio!(1, 10), Meter));
+    }
+
+    #[test]
+    fn strip_prefixes_mixed() {
+        assert_eq!(((Yotta * Meter) / Zetta).strip_prefixes(), (int!(1_000), Meter));
+        assert_eq!((Meter / (Kilo * Second)).strip_prefixes(), (ratio!(1, 1_000), Meter / Second));
+        assert_eq!(c!(c!(Kilo, Watt, Hour; Milli, Meter); c!(Mega, Second; Micro)).strip_prefixes(), (ratio!(1, 1_000_000), c!(Watt, Hour; Meter, Second)));
+        assert_eq!((Kilo * Milli).strip_prefixes(), (int!(1), UNITLESS));
+    }
+
+    #[test]
+    fn to_named() {
+        let mass = Kilo * Gram;
+        let acceleration = Meter / (Second * Second);
+        assert_eq!((mass.clone() * acceleration.clone()).to_named(), Newton);
+        assert_eq!((Newton * Meter / Second).to_named(), Watt);
+        assert_eq!((mass * Meter * Meter / (Second * Second * Second * Ampere)).to_named(), Volt);
+        assert_eq!((Second * Ampere).to_named(), Coulomb);
+        assert_eq!((UNITLESS / Second).to_named(), Hertz);
+        assert_eq!((UNITLESS / Ohm).to_named(), Siemens);
+
+        // Units that aren't exactly the named unit, or have no named unit, are unchanged
+        assert_eq!((Gram * acceleration.clone()).to_named(), Gram * acceleration);
+        assert_eq!((Meter / Second).to_named(), Meter / Second);
+        assert_eq!(Gallon.to_named(), Gallon);
+        assert_eq!(Baud.to_named(), Baud);
+    }
+
+    #[test]
+    fn canonical_key() {
+        // Snapshots of the documented format, which must not change
+        let snapshots = [
+            (Meter, "m^1"),
+            (Kilo * Gram, "g^1"),
+            (Newton, "g^1·m^1·s^-2"),
+            (Watt, "g^1·m^2·s^-3"),
+            (Volt, "A^-1·g^1·m^2·s^-3"),
+            (Hertz, "s^-1"),
+            (Celsius, "K^1"),
+            (UNITLESS, ""),
+        ];
+        for (unit, key) in snapshots {
+            assert_eq!(unit.canonical_key(), key, "{}", unit.symbol());
+        }
+
+        // Prefixed and reordered constructions of the same unit share a key
+        let equivalent = [
+            vec![Watt, Kilo * Watt, Joule / Second, (Newton * Meter) / Second, (Meter * Newton) / (Milli * Second), Volt * Ampere],
+            vec![Meter / Second, Kilo * Meter / Hour, Mile / Hour, UNITLESS / (Second / Meter)],
+            vec![Meter * Second, Second * Meter, (Kilo * Meter) * (Milli * Second)],
+            vec![Liter, Gallon, Meter * Meter * Meter, (Centi * Meter) * Meter * (Milli * Meter)],
+        ];
+        for units in equivalent {
+            for unit in &units {
+                assert_eq!(unit.canonical_key(), units[0].canonical_key(), "{}", unit.symbol());
+            }
+        }
+        assert_ne!(Meter.canonical_key(), Second.canonical_key());
+    }
+
+    #[test]
+    fn offset_and_slope() {
+        for unit in Unit::NAMED_UNITS {
+            let (offset, slope, _) = unit.clone().to_si_units();
+            assert_eq!((unit.offset(), unit.slope()), (offset, slope), "{}", unit.symbol());
+        }
+
+        let units = [Kilo * Meter / Hour, Celsius * Meter, UNITLESS / Inch, (Joule / Second) / (Milli * Watt)];
+        for unit in units {
+            let (offset, slope, _) = unit.clone().to_si_units();
+            assert_eq!((unit.offset(), unit.slope()), (offset, slope), "{}", unit.symbol());
+        }
+        assert_eq!((Kilo * Meter / Hour).slope(), ratio!(5, 18));
+        assert_eq!((Celsius * Meter).offset(), zero!());
+    }
+
+    #[test]
+    fn conversion_factors() {
+        let targets = [Inch, Kilo * Meter, Second, UNITLESS / Meter];
+        let factors = Meter.conversion_factors_to_many(&targets);
+        assert_eq!(factors[0], Ok(ratio!(5000, 127)));
+        assert_eq!(factors[1], Ok(ratio!(1, 1000)));
+        assert_eq!(factors[2], Err(ConversionError::IncompatibleUnits { from: Meter, to: Second }));
+        assert!(factors[3].is_err());
+
+        // Factors convert differences, so they ignore the offset of Celsius
+        assert_eq!(Celsius.conversion_factors_to_many(&[Kelvin, Fahrenheit])[0], Ok(one!()));
+
+        // Each factor agrees with converting a quantity of one unit
+        let targets = [Watt, Milli * Watt, (Kilo * Coulomb * Volt) / Second, (Newton * Meter) / Second];
+        for (factor, to) in (Joule / Second).conversion_factors_to_many(&targets).into_iter().zip(targets) {
+            let converted = Quantity::from_unit(Joule / Second).convert_to(to).unwrap();
+            assert_eq!(factor, Ok(converted.magnitude));
+        }
+    }
+
+    #[test]
+    fn dry_volume() {
+        let bushel = Quantity::from_unit(Bushel);
+        assert_eq!(bushel.clone().convert_to(Peck).unwrap().magnitude, int!(4));
+        assert_eq!(bushel.clone().convert_to(DryGallon).unwrap().magnitude, int!(8));
+        assert_eq!(bushel.clone().convert_to(DryPint).unwrap().magnitude, int!(64));
+        assert_eq!(bushel.convert_to(Liter).unwrap().magnitude, ratio!(3523907016688i64, 100000000000i64));
+        assert_eq!(Quantity::from_unit(DryGallon).convert_to(Liter).unwrap().magnitude, ratio!(440488377086i64, 100000000000i64));
+
+        // A dry pint is about 16% larger than a liquid pint
+        let ratio = Quantity::from_unit(DryPint).convert_to(Pint).unwrap().magnitude;
+        assert_eq!(ratio, ratio!(220244188543i64, 189270589200i64));
+        assert!(ratio > one!());
+    }
+
+    #[test]
+    fn sidereal_time() {
+        assert_eq!(Quantity::from_unit(SiderealDay).convert_to(Second).unwrap().magnitude, ratio!(861640905, 10000));
+        assert_eq!(Quantity::from_unit(SiderealYear).convert_to(Day).unwrap().magnitude, ratio!(365256363004i64, 1000000000));
+
+        // A solar day is 3 minutes and 55.9095 seconds longer than a sidereal day
+        let difference = Quantity::from_unit(Day) - Quantity::from_unit(SiderealDay).convert_to(Day).unwrap();
+        let difference = difference.convert_to(Second).unwrap();
+        assert_eq!(difference.magnitude, ratio!(3 * 60 * 10000 + 559095, 10000));
+
+        // The sidereal year is about 20 minutes longer than the Julian year
+        let year = Quantity::from_unit(SiderealYear).convert_to(Year).unwrap().magnitude;
+        assert_eq!(year, ratio!(365256363004i64, 365250000000i64));
+    }
+
+    #[test]
+    fn year_conventions() {
+        let julian = Quantity::from_unit(JulianYear);
+        assert_eq!(julian.clone().convert_to(Year).unwrap().magnitude, one!());
+        assert_eq!(julian.clone().convert_to(Day).unwrap().magnitude, ratio!(36525, 100));
+        let tropical = Quantity::from_unit(TropicalYear);
+        assert_eq!(tropical.clone().convert_to(Day).unwrap().magnitude, ratio!(36524219, 100000));
+        assert_eq!(tropical.clone().convert_to(Second).unwrap().magnitude, ratio!(31556925216i64, 1000));
+
+        // The Julian year is 674.784 seconds longer than the tropical year
+        let difference = (julian - tropical.convert_to(JulianYear).unwrap()).convert_to(Second).unwrap();
+        assert_eq!(difference.magnitude, ratio!(674784, 1000));
+        let tropical_years = Quantity::from_unit(Year).convert_to(TropicalYear).unwrap().magnitude;
+        assert_eq!(tropical_years, ratio!(36525000, 36524219));
+    }
+
+    #[test]
+    fn information_units() {
+        assert_eq!(Quantity::from_i64_with_unit(2, Nibble).convert_to(Byte).unwrap().magnitude, int!(1));
+        assert_eq!(Quantity::from_unit(Kibi * Byte).convert_to(Word32).unwrap().magnitude, int!(256));
+        assert_eq!(Quantity::from_unit(Word64).convert_to(Word16).unwrap().magnitude, int!(4));
+        assert_eq!(Quantity::from_unit(Kibi * Word32).convert_to(Kibi * Byte).unwrap().magnitude, int!(4));
+
+        for unit in [Nibble, Word16, Word32, Word64, Kibi * Word32] {
+            let quantity = Quantity::from_i64_with_unit(3, unit.clone());
+            assert_eq!(quantity.to_string().parse::<Quantity>(), Ok(quantity), "{}", unit);
+        }
+    }
+
+    #[test]
+    fn baud() {
+        assert_eq!(Quantity::from_unit(Baud).convert_to(Hertz).unwrap().magnitude, one!());
+        assert_eq!(Quantity::from_unit(Mega * Baud).convert_to(Kilo * Baud).unwrap().magnitude, int!(1000));
+        assert_eq!("9.6 kBd".parse(), Ok(Quantity::new(ratio!(96, 10), Kilo * Baud)));
+        assert_eq!("2 MBd".parse::<Quantity>().unwrap().unit.symbol(), "MBd");
+    }
+
+    #[test]
+    fn pixels() {
+        assert_eq!(Quantity::from_i64_with_unit(3, Dot).convert_to(Pixel).unwrap().magnitude, int!(3));
+        assert_eq!(Quantity::from_unit(DotsPerInch).convert_to(Pixel / Inch).unwrap().magnitude, one!());
+        assert_eq!(Quantity::from_unit(PixelsPerInch).convert_to(Pixel / (Centi * Meter)).unwrap().magnitude, ratio!(50, 127));
+        assert_eq!("300 dpi".parse(), Ok(Quantity::from_i64_with_unit(300, DotsPerInch)));
+        assert_eq!("96 ppi".parse(), Ok(Quantity::from_i64_with_unit(96, PixelsPerInch)));
+        assert_eq!("1920 px".parse(), Ok(Quantity::from_i64_with_unit(1920, Pixel)));
+
+        // Pixels have no length without a density
+        assert!(Quantity::from_unit(Pixel).convert_to(Meter).is_err());
+        assert!(!Pixel.has_same_dimension(&Meter));
+    }
+
+    #[test]
+    fn common_unit() {
+        assert_eq!(Mile.common_unit(&(Milli * Meter)), Ok(Milli * Meter));
+        assert_eq!((Milli * Meter).common_unit(&Mile), Ok(Milli * Meter));
+        assert_eq!(Byte.common_unit(&Bit), Ok(Bit));
+        assert_eq!((Kibi * Byte).common_unit(&Word32), Ok(Word32));
+        assert_eq!(Celsius.common_unit(&Kelvin), Ok(Celsius));
+        assert_eq!(Inch.common_unit(&(Centi * Meter)), Ok(Meter));
+        assert_eq!((Kilo * Watt * Hour).common_unit(&Joule), Ok(Joule));
+        assert_eq!(Newton.common_unit(&(Pound * Meter / (Second * Second))), Ok(Kilo * Gram * Meter / (Second * Second)));
+        assert_eq!(Meter.common_unit(&Second), Err(ConversionError::IncompatibleUnits { from: Meter, to: Second }));
+        assert!(Second.common_unit(&Hertz).is_err());
+    }
+
+    #[test]
+    fn exact_definitions() {
+        let convert = |magnitude: BigRational, from: Unit, to: Unit| Quantity::new(magnitude, from).convert_to(to).unwrap().magnitude;
+        let one = |from: Unit, to: Unit| convert(one!(), from, to);
+
+        // The international yard and pound agreement of 1959
+        assert_eq!(one(Inch, Centi * Meter), ratio!(254, 100));
+        assert_eq!(one(Feet, Meter), ratio!(3048, 10_000));
+        assert_eq!(one(Yard, Meter), ratio!(9144, 10_000));
+        assert_eq!(one(Mile, Meter), ratio!(1_609_344, 1000));
+        assert_eq!(one(Pound, Gram), ratio!(45_359_237, 100_000));
+        assert_eq!(one(Ounce, Pound), ratio!(1, 16));
+        assert_eq!(one(Dram, Pound), ratio!(1, 256));
+        assert_eq!(one(NauticalMile, Meter), int!(1852));
+
+        // US customary volumes are defined in cubic inches
+        assert_eq!(one(CubicInch, Meter * Meter * Meter), num::pow(ratio!(254, 10_000), 3));
+        assert_eq!(one(Gallon, CubicInch), int!(231));
+        assert_eq!(one(DryGallon, CubicInch), ratio!(2_688_025, 10_000));
+        assert_eq!(one(CubicFeet, CubicInch), int!(1728));
+
+        // Degrees Fahrenheit are 5/9 kelvin, with water freezing at 32 °F
+        assert_eq!(convert(int!(32), Fahrenheit, Celsius), zero!());
+        assert_eq!(convert(int!(212), Fahrenheit, Kelvin), ratio!(37_315, 100));
+        assert_eq!(convert(int!(-40), Fahrenheit, Celsius), int!(-40));
+        assert_eq!(one(Kelvin, Fahrenheit) - convert(zero!(), Kelvin, Fahrenheit), ratio!(9, 5));
+
+        // The IAU 2012 astronomical unit, the light year in Julian years and the parsec of 2015,
+        // which is 648000/π AU rounded to the meter
+        assert_eq!(one(AU, Meter), int!(149_597_870_700i64));
+        assert_eq!(one(JulianYear, Day), ratio!(36_525, 100));
+        assert_eq!(one(LightYear, Meter), int!(299_792_458) * one(JulianYear, Second));
+        let pi = BigInt::parse_bytes(b"3141592653589793238462643383279502884", 10).unwrap();
+        let scale = BigInt::from(10).pow(36);
+        let parsec = BigInt::from(149_597_870_700i64) * 648_000 * scale * 2 / pi;
+        assert_eq!(one(Parsec, Meter), BigRational::from_integer(crate::scalable_integer::ScalableInteger::from((parsec + 1) / 2)));
+
+        // A month is a twelfth of a Gregorian year
+        assert_eq!(one(Month, Day), ratio!(3_652_425, 120_000));
+    }
+
+    #[test]
+    fn definition_note() {
+        assert!(Year.definition_note().unwrap().contains("JulianYear"));
+        assert!(TropicalYear.definition_note().unwrap().contains("365.24219"));
+        assert_eq!(Second.definition_note(), None);
+        assert_eq!((Meter / Year).definition_note(), None);
+    }
 
     #[test]
     fn flatten() {
@@ -331,6 +1511,9 @@ mod tests {
 
         let result = (((Watt / Joule) / Second) / (Meter / Second)).flatten();
         assert_eq!(result, (Watt * Second) / (Joule * Second * Meter));
+
+        let result = c!(c!(c!(Watt; Joule); Second), Ampere; c!(Meter; c!(Second, Kelvin; Mole))).flatten();
+        assert_eq!(result, c!(Watt, Ampere, Second, Kelvin; Joule, Second, Meter, Mole));
     }
 
     #[test]
@@ -381,6 +1564,295 @@ mod tests {
         assert_eq!(result, c!(Watt; Joule, Meter));
     }
 
+    #[test]
+    fn to_si_units_nested() {
+        let (offset, slope, unit) = (((Kilo * Watt * Hour) / (Meter * Meter)) / (Kelvin * Day)).to_si_units();
+        assert_eq!(offset, zero!());
+        assert_eq!(slope, ratio!(125, 3));
+        assert_eq!(unit, c!(Kilo, Gram; Second, Second, Kelvin, Second));
+
+        let unit = c!(c!(Kilo, Watt, Hour; Meter, Meter); c!(Kelvin, Day; Mile, Gallon, Pound));
+        let (_, slope, unit) = unit.to_si_units();
+        assert_eq!(slope, ratio!(89951113568815944291u128, 781250000000000i64));
+        assert_eq!(unit, c!(Kilo, Gram, Meter, Meter, Meter, Meter, Gram; Second, Second, Kelvin, Second));
+
+        let unit = c!(c!(Mega, Joule; Gallon); c!(Milli, Second; c!(Inch; Year)));
+        let (_, slope, unit) = unit.to_si_units();
+        assert_eq!(slope, ratio!(31250000000000i64, 146971593153i64));
+        assert_eq!(unit, c!(Kilo, Gram; Second, Second, Second, Second));
+    }
+
+    #[test]
+    fn to_si_units_affine() {
+        assert_eq!(Celsius.to_si_units(), (ratio!(5463, 20), one!(), Kelvin));
+        assert_eq!(Compound(vec![Celsius].into(), vec![].into()).to_si_units(), (ratio!(5463, 20), one!(), Kelvin));
+
+        // Inside compound units Celsius is a temperature difference
+        assert_eq!((Celsius / Hour).to_si_units(), (zero!(), ratio!(1, 3600), Kelvin / Second));
+        let (offset, slope, unit) = (Joule / Celsius).to_si_units();
+        assert_eq!((offset, slope), (zero!(), one!()));
+        assert!(unit.has_same_dimension(&(Joule / Kelvin)));
+        assert_eq!((Fahrenheit / Hour).to_si_units().0, zero!());
+
+        let warming = Quantity::from_i64_with_unit(36, Celsius / Hour).convert_to(Kelvin / Second).unwrap();
+        assert_eq!(warming.magnitude, ratio!(1, 100));
+    }
+
+    #[test]
+    fn to_si_units_bounds_slope() {
+        // Forty prefixes would produce a 1200-bit slope if it was never reduced along the way
+        let numerator = vec![Quetta; 20];
+        let denominator = vec![Quecto; 20];
+        let (_, slope, unit) = Compound(numerator.into(), denominator.into()).to_si_units();
+        assert_eq!(slope, int!(BigInt::from(10).pow(1200)));
+        assert_eq!(unit, UNITLESS);
+    }
+
+    #[test]
+    fn symbol() {
+        assert_eq!(Meter.symbol(), "m");
+        assert_eq!((Kilo * Watt * Hour).symbol(), "kW*h");
+        assert_eq!((Meter / (Second * Second)).symbol(), "m/s^2");
+        assert_eq!((UNITLESS / Second).symbol(), "1/s");
+        assert_eq!(UNITLESS.symbol(), "");
+        assert_eq!(((Volt * Ampere) / Second).to_string(), "V*A/s");
+        assert_eq!((Joule / (Kilo * Gram * Kelvin)).symbol(), "J/kg/K");
+        assert_eq!((UNITLESS / (Meter * Second)).symbol(), "1/m/s");
+    }
+
+    #[test]
+    fn symbol_prefixes_and_units() {
+        assert_eq!((Milli * Meter).symbol(), "mm");
+        assert_eq!((Milli * Second).symbol(), "ms");
+        assert_eq!((Meter * Second).symbol(), "m*s");
+        assert_eq!((Milli * Meter * Second).symbol(), "mm*s");
+        assert_eq!((Milli * Meter * Meter).symbol(), "mm*m");
+        assert_eq!((Meter * Milli * Meter).symbol(), "m*mm");
+        assert_eq!((Meter * Meter * Second).symbol(), "m^2*s");
+        assert_eq!((Kilo * Gram * Meter / Second).symbol(), "kg*m/s");
+        assert_eq!((Tesla * Meter).to_string(), "T*m");
+        assert_eq!((Newton * Meter).to_string(), "N*m");
+        assert_eq!((Meter * Second).canonical_symbol(), "m*s");
+    }
+
+    #[test]
+    fn symbol_round_trip() {
+        let units = [
+            Kilo * Gram / (Meter * Second * Second),
+            Milli * Meter * Second,
+            Kilo * Gram * Meter / Second,
+            Newton / (Milli * Meter * Meter),
+            Meter * Second,
+            Tesla * Meter,
+            Joule / (Kilo * Gram * Kelvin),
+            UNITLESS / (Meter * Second),
+            Kilo * Watt * Hour,
+            KILOMETER * KILOMETER / (Hour * Second),
+            Meter / (Milli * Second * Milli * Second * Milli * Second),
+        ];
+        for unit in units {
+            let symbol = unit.to_string();
+            assert_eq!(symbol.parse::<Unit>(), Ok(unit.clone()), "{symbol}");
+            let quantity = Quantity::from_i64_with_unit(3, unit.clone());
+            assert_eq!(format!("3 {symbol}").parse::<Quantity>(), Ok(quantity), "{symbol}");
+            assert_eq!(unit.canonical_symbol().parse::<Unit>(), Ok(unit.simplify()));
+        }
+    }
+
+    #[test]
+    fn prefixed_powers() {
+        assert_eq!((KILOMETER * KILOMETER).symbol(), "km^2");
+        assert_eq!((KILOMETER * KILOMETER).parenthesized_symbol(), "(km)^2");
+        assert_eq!((KILOMETER * Meter).symbol(), "km*m");
+
+        let per_cubic_millisecond = UNITLESS / (Milli * Second * Milli * Second * Milli * Second);
+        assert_eq!(per_cubic_millisecond.symbol(), "1/ms^3");
+        assert_eq!(per_cubic_millisecond.parenthesized_symbol(), "1/(ms)^3");
+
+        let pressure = Kilo * Gram / (Meter * Second * Second);
+        assert_eq!(pressure.symbol(), "kg/m/s^2");
+        assert_eq!(pressure.parenthesized_symbol(), "kg/m/s^2");
+        assert_eq!((Kilo * Gram * Kilo * Gram).to_string(), "kg^2");
+        assert_eq!((Milli * Meter).symbol(), "mm");
+        assert_eq!(Compound(vec![Kilo].into(), vec![].into()).symbol(), "k");
+        assert_eq!(Compound(vec![Kilo, Mega, Meter].into(), vec![].into()).symbol(), "k*Mm");
+        assert_eq!(Meter.parenthesized_symbol(), "m");
+    }
+
+    #[test]
+    fn dimensions() {
+        let velocity = Kilo * Meter / Hour;
+        assert!(velocity.has_same_dimension(&(Meter / Second)));
+        assert!(velocity.has_same_dimension(&Compound(vec![Meter, Meter].into(), vec![Meter, Second].into())));
+        assert!(!velocity.has_same_dimension(&Meter));
+        assert!(Joule.has_same_dimension(&(Kilo * Gram * Meter * Meter / (Second * Second))));
+        assert!(Hertz.has_reciprocal_dimension(&Second));
+        assert!(Ohm.has_reciprocal_dimension(&Siemens));
+        assert!(!Ohm.has_same_dimension(&Siemens));
+        assert!(UNITLESS.has_same_dimension(&Kilo));
+        assert!(KILOMETER.has_same_dimension(&Meter));
+
+        // Products are new compounds with their own cache
+        let area = velocity.clone() * Hour * Meter;
+        assert_eq!(area.si_exponents(), (Meter * Meter).si_exponents());
+        assert!(area.has_same_dimension(&Hectare));
+        assert!((area / Meter).has_same_dimension(&Meter));
+        assert!(velocity.has_same_dimension(&(Meter / Second)));
+    }
+
+    #[test]
+    fn is_compatible_with() {
+        assert!((Kilo * Watt * Hour).is_compatible_with(&Joule));
+        assert!(Joule.is_compatible_with(&(Kilo * Watt * Hour)));
+        assert!((Newton * Meter).is_compatible_with(&(Meter * Newton)));
+        assert!(Celsius.is_compatible_with(&Fahrenheit));
+        assert!(Power(Box::new(Meter), 3).is_compatible_with(&Liter));
+        assert!(UNITLESS.is_compatible_with(&Percent));
+        assert!(!Meter.is_compatible_with(&Second));
+        assert!(!Ohm.is_compatible_with(&Siemens));
+        assert!(!Watt.is_compatible_with(&Joule));
+        assert!(!(Kilo * Celsius).is_compatible_with(&Kelvin));
+        assert!(!Kelvin.is_compatible_with(&(Kilo * Celsius)));
+
+        // Compatible units convert, however they are composed
+        let pairs = [
+            (Kilo * Gram * Meter * Meter / (Second * Second), Joule),
+            (Joule, Kilo * Gram * Meter * Meter / (Second * Second)),
+            (Newton * Meter, Gram * Meter * Meter / (Second * Second)),
+            (Kilo * Watt * Hour, Mega * Joule),
+            (Volt * Ampere, Kilo * Gram * Meter * Meter / (Second * Second * Second)),
+            (Pascal * Liter, Joule),
+        ];
+        for (from, to) in pairs {
+            assert!(from.is_compatible_with(&to), "{from:?} {to:?}");
+            assert!(Quantity::from_i64_with_unit(1, from.clone()).convert_to(to.clone()).is_ok(), "{from:?} {to:?}");
+            assert!(from.conversion_factors_to_many(std::slice::from_ref(&to))[0].is_ok());
+        }
+    }
+
+    #[test]
+    fn canonical_symbol() {
+        let kg = || Kilo * Gram;
+        let constructions = [
+            kg() * Meter * Meter / (Second * Second * Second),
+            Meter * Meter * kg() / (Second * Second * Second),
+            Meter * kg() * Meter / (Second * Second * Second),
+            (Meter / Second) * (Meter / Second) * (kg() / Second),
+            Compound(vec![Meter / Second, kg()].into(), vec![Second * Second / Meter].into()),
+            Compound(vec![Meter, Meter, Hertz, kg()].into(), vec![Second, Second, Second, Hertz].into()),
+        ];
+        for unit in constructions {
+            assert_eq!(unit.canonical_symbol(), "kg*m^2/s^3", "{unit}");
+        }
+
+        assert_eq!((Volt * Ampere / Second).canonical_symbol(), (Ampere * Volt / Second).canonical_symbol());
+        assert_eq!((Hour * Kilo * Watt).canonical_symbol(), "kW*h");
+        assert_eq!((Mole / (Kilo * Meter * Liter)).canonical_symbol(), "mol/km/L");
+        assert_eq!((Meter / Meter).canonical_symbol(), "");
+        assert_eq!(Joule.canonical_symbol(), "J");
+    }
+
+    #[test]
+    fn name() {
+        assert_eq!(Meter.name(), "meter");
+        assert_eq!((Kilo * Meter / Hour).name(), "kilometer per hour");
+        assert_eq!((Meter * Meter * Meter).name(), "cubic meter");
+        assert_eq!((UNITLESS / Second).name(), "reciprocal second");
+        assert_eq!(UNITLESS.name(), "");
+    }
+
+    #[test]
+    fn order_insensitive_equality() {
+        assert_eq!(Meter * Second, Second * Meter);
+        assert_eq!(Compound(vec![Meter, Second].into(), vec![].into()), Compound(vec![Second, Meter].into(), vec![].into()));
+        assert_eq!(Kilo * Gram * Meter / (Second * Ampere), Meter * (Kilo * Gram) / (Ampere * Second));
+        assert_eq!((Newton * Meter).to_si_units(), (Meter * Newton).to_si_units());
+        assert_ne!(Meter * Second, Meter / Second);
+
+        // Equal units still render in the order they were built
+        assert_eq!((Volt * Ampere).symbol(), "V*A");
+        assert_eq!((Ampere * Volt).symbol(), "A*V");
+
+        let quantity = Quantity::from_i64_with_unit(3, Kilo * Watt * Hour);
+        let converted = quantity.clone().convert_to(Hour * Kilo * Watt).unwrap();
+        assert_eq!(converted.magnitude, int!(3));
+        let converted = quantity.convert_to(Second * Newton * Meter / Second).unwrap();
+        assert_eq!(converted.magnitude, int!(10_800_000));
+    }
+
+    #[test]
+    fn powers() {
+        let cubic_meter = Power(Box::new(Meter), 3);
+        let per_square_second = Power(Box::new(Second), -2);
+
+        assert_eq!(cubic_meter.clone().flatten(), Meter * Meter * Meter);
+        assert_eq!(cubic_meter, Meter * Meter * Meter);
+        assert_eq!(cubic_meter, CUBIC_METER);
+        assert_eq!(per_square_second, UNITLESS / (Second * Second));
+        assert_eq!(Power(Box::new(Joule), 0), UNITLESS);
+        assert_eq!(Power(Box::new(Meter), 1), Meter);
+        assert_ne!(cubic_meter, SQUARE_METER);
+        assert_ne!(per_square_second, Second * Second);
+        assert_eq!(Compound(vec![Power(Box::new(Meter), 2), Second].into(), vec![].into()), Meter * Second * Meter);
+
+        // Exponents are multiplied rather than expanded
+        let huge = Power(Box::new(Meter), 1 << 30);
+        assert_eq!(huge, Power(Box::new(Power(Box::new(Meter), 1 << 15)), 1 << 15));
+        assert_ne!(huge, Power(Box::new(Meter), i32::MAX));
+        assert_eq!(huge.symbol(), "m^1073741824");
+        assert_eq!(huge.clone().to_si_units(), (zero!(), one!(), huge.clone()));
+        assert_eq!(huge.clone().flatten(), Compound(vec![huge.clone()].into(), vec![].into()));
+        assert_eq!((huge.clone() / huge.clone()).simplify(), UNITLESS);
+        assert!(matches!(huge.clone() * Meter, Power(_, 1073741825)));
+
+        // Repeated factors are merged into powers, keeping prefixes with their units
+        assert!(matches!(Meter * Meter * Meter, Power(_, 3)));
+        assert_eq!((Meter * Meter * Meter).to_fraction(), (vec![Power(Box::new(Meter), 3)], vec![]));
+        assert_eq!((Joule / Second / Second).to_fraction(), (vec![Joule], vec![Power(Box::new(Second), 2)]));
+        assert_eq!((Kilo * Meter * Kilo * Meter).to_fraction(), (vec![Power(Box::new(Kilo), 2), Power(Box::new(Meter), 2)], vec![]));
+        assert_eq!((Kilo * Meter * Kilo * Gram).symbol(), "km*kg");
+        assert_eq!((Meter * Milli * Meter * Meter).symbol(), "m^2*mm");
+        assert_eq!(per_square_second.clone().flatten(), UNITLESS / (Second * Second));
+        assert_eq!(Power(Box::new(Meter / Second), 2).flatten(), (Meter * Meter) / (Second * Second));
+        assert_eq!(Power(Box::new(Joule), 0).flatten(), UNITLESS);
+        assert_eq!((Meter * per_square_second.clone()).simplify(), METER_PER_SECOND_SQUARED);
+        assert_eq!((cubic_meter.clone() / Meter).simplify(), SQUARE_METER);
+
+        assert_eq!(cubic_meter.symbol(), "m^3");
+        assert_eq!(per_square_second.symbol(), "1/s^2");
+        assert_eq!((Joule * per_square_second.clone()).to_string(), "J/s^2");
+        assert_eq!((Joule / (Second * Second)).to_string(), "J/s^2");
+        assert_eq!(Power(Box::new(KILOMETER), 2).parenthesized_symbol(), "(km)^2");
+        assert_eq!(cubic_meter.name(), "cubic meter");
+
+        assert_eq!(cubic_meter.clone().to_si_units(), (zero!(), one!(), CUBIC_METER));
+        assert_eq!(Power(Box::new(Kilo * Meter), 2).slope(), int!(1_000_000));
+        assert!(cubic_meter.has_same_dimension(&Liter));
+        assert!(per_square_second.has_same_dimension(&(Hertz * Hertz)));
+        assert!(Power(Box::new(Meter), 0).is_unitless());
+        assert!(!cubic_meter.is_modifier());
+        assert_eq!(cubic_meter.definition_note(), None);
+
+        let volume = Quantity::from_i64_with_unit(2, cubic_meter).convert_to(Liter).unwrap();
+        assert_eq!(volume.magnitude, int!(2000));
+    }
+
+    #[test]
+    fn normalize() {
+        assert_eq!((Hertz * Second).normalize(), UNITLESS);
+        assert_eq!((Second * Hertz).normalize(), UNITLESS);
+        assert_eq!((Ohm * Siemens).normalize(), UNITLESS);
+        assert_eq!((Newton / Pascal).normalize(), Meter * Meter);
+        assert_eq!((Joule / Meter).normalize(), Newton);
+        assert_eq!((Coulomb / Second).normalize(), Ampere);
+        assert_eq!((Meter * Meter).normalize(), Meter * Meter);
+        assert_eq!((Joule / Second).normalize(), Joule / Second);
+
+        // Units with a scale aren't expanded, as that would change the unit
+        assert_eq!((Hertz * Hour).normalize(), Hertz * Hour);
+        assert_eq!(Compound(vec![Meter, Meter].into(), vec![Meter].into()).normalize(), Meter);
+    }
+
     #[test]
     fn to_fraction() {
         let result = Gram.to_fraction();
@@ -389,4 +1861,28 @@ mod tests {
         let result = (Meter / Second).to_fraction();
         assert_eq!(result, (vec![Meter], vec![Second]));
     }
+
+    #[test]
+    fn is_unitless() {
+        let meter_per_meter = Compound(vec![Meter].into(), vec![Meter].into());
+        assert!(meter_per_meter.is_unitless());
+        assert!(!meter_per_meter.is_strictly_unitless());
+
+        let velocity = Compound(vec![Meter].into(), vec![Second].into());
+        assert!(Compound(vec![velocity.clone()].into(), vec![velocity].into()).is_unitless());
+        assert!(Compound(vec![Kilo, Meter].into(), vec![Kilo, Meter].into()).is_unitless());
+        assert!(Compound(vec![Kilo].into(), vec![Kilo].into()).is_unitless());
+        assert!(UNITLESS.is_unitless() && UNITLESS.is_strictly_unitless());
+
+        assert!(!Kilo.is_unitless());
+        assert!(!Compound(vec![Kilo, Meter].into(), vec![Meter].into()).is_unitless());
+        assert!(!(Liter / (Meter * Meter * Meter)).is_unitless());
+        assert!(!Meter.is_unitless());
+    }
+
+    #[test]
+    fn quantity_is_unitless() {
+        use crate::quantity::Quantity;
+        assert!(Quantity::from_i64_with_unit(3, Compound(vec![Meter].into(), vec![Meter].into())).is_unitless());
+    }
 }
@@ -0,0 +1,287 @@
+//! Cheaply cloneable storage for the components of a compound ```Unit```.
+
+use std::borrow::Cow;
+use std::fmt::{Debug, Formatter};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::ops::Deref;
+use std::sync::{Arc, OnceLock};
+use crate::unit::{format_names, format_symbols, sum_exponents, Unit};
+
+/// The numerator or denominator of a ```Unit::Compound```.
+///
+/// Cloning a ```UnitList``` never copies its units: the list is either a ```&'static``` slice
+/// (which keeps constants like ```UNITLESS``` possible) or a shared, reference counted ```Vec```
+/// that is copied on write. Shared lists cache their rendered symbols and names as well as their
+/// dimension, which is safe because a list is never modified while it is shared. The caches don't
+/// take part in ```PartialEq``` and ```Hash```, so units are sound keys of hash maps. Clippy's
+/// ```mutable_key_type``` lint can't tell, so list ```tantalum_unit::unit_list::UnitList``` under
+/// ```ignore-interior-mutability``` in ```clippy.toml``` when using units or quantities as keys.
+///
+/// # Example:
+/// ```
+/// use tantalum_unit::prelude::*;
+/// use tantalum_unit::unit_list::UnitList;
+///
+/// let list: UnitList = vec![Kilo, Meter].into();
+/// let copy = list.clone(); // Only bumps a reference count
+///
+/// assert_eq!(copy, list);
+/// assert_eq!(&copy[..], &[Kilo, Meter]);
+/// ```
+#[derive(Clone)]
+pub struct UnitList(Storage);
+
+#[derive(Clone)]
+enum Storage {
+    Static(&'static [Unit]),
+    Shared(Arc<Shared>),
+}
+
+/// A shared list with its caches, which are left out of ```PartialEq``` and ```Hash``` of the list.
+#[derive(Clone)]
+struct Shared {
+    units: Vec<Unit>,
+    symbols: OnceLock<Vec<String>>,
+    name: OnceLock<String>,
+    exponents: OnceLock<Vec<i32>>,
+}
+
+impl Shared {
+    fn new(units: Vec<Unit>) -> Self {
+        Self {
+            units,
+            symbols: OnceLock::new(),
+            name: OnceLock::new(),
+            exponents: OnceLock::new(),
+        }
+    }
+}
+
+impl UnitList {
+    /// An empty list.
+    pub const fn new() -> Self {
+        Self(Storage::Static(&[]))
+    }
+
+    /// Constructs a list borrowing the given units, usable in ```const``` contexts.
+    pub const fn from_static(units: &'static [Unit]) -> Self {
+        Self(Storage::Static(units))
+    }
+
+    pub fn as_slice(&self) -> &[Unit] {
+        match &self.0 {
+            Storage::Static(units) => units,
+            Storage::Shared(shared) => &shared.units,
+        }
+    }
+
+    /// Returns a mutable reference to the units, copying them first if they are shared.
+    pub fn make_mut(&mut self) -> &mut Vec<Unit> {
+        if let Storage::Static(units) = self.0 {
+            self.0 = Storage::Shared(Arc::new(Shared::new(units.to_vec())));
+        }
+
+        match &mut self.0 {
+            Storage::Shared(shared) => {
+                let shared = Arc::make_mut(shared);
+                // The caller may change the units, so the cached renderings can't be kept
+                shared.symbols = OnceLock::new();
+                shared.name = OnceLock::new();
+                shared.exponents = OnceLock::new();
+                &mut shared.units
+            }
+            Storage::Static(_) => unreachable!(),
+        }
+    }
+
+    /// Returns the units as a ```Vec```, only copying them if they are shared.
+    pub fn into_vec(self) -> Vec<Unit> {
+        match self.0 {
+            Storage::Static(units) => units.to_vec(),
+            Storage::Shared(shared) => Arc::unwrap_or_clone(shared).units,
+        }
+    }
+
+    /// The symbols of the factors in the list, e.g. ```["m^2", "s"]```, see ```format_symbols```.
+    pub(crate) fn symbols(&self) -> Cow<'_, [String]> {
+        match &self.0 {
+            Storage::Static(units) => Cow::Owned(format_symbols(units, false, false)),
+            Storage::Shared(shared) => Cow::Borrowed(shared.symbols.get_or_init(|| format_symbols(&shared.units, false, false))),
+        }
+    }
+
+    /// The sums of the SI exponents of the units in the list, indexed like ```Unit::NAMED_UNITS```.
+    pub(crate) fn exponents(&self) -> Cow<'_, [i32]> {
+        match &self.0 {
+            Storage::Static(units) => Cow::Owned(sum_exponents(units)),
+            Storage::Shared(shared) => Cow::Borrowed(shared.exponents.get_or_init(|| sum_exponents(&shared.units))),
+        }
+    }
+
+    /// The names of the units in the list, e.g. "square meter second ".
+    pub(crate) fn name(&self) -> Cow<'_, str> {
+        match &self.0 {
+            Storage::Static(units) => Cow::Owned(format_names(units)),
+            Storage::Shared(shared) => Cow::Borrowed(shared.name.get_or_init(|| format_names(&shared.units))),
+        }
+    }
+}
+
+impl Default for UnitList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for UnitList {
+    type Target = [Unit];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl From<Vec<Unit>> for UnitList {
+    fn from(units: Vec<Unit>) -> Self {
+        if units.is_empty() {
+            Self::new()
+        } else {
+            Self(Storage::Shared(Arc::new(Shared::new(units))))
+        }
+    }
+}
+
+impl FromIterator<Unit> for UnitList {
+    fn from_iter<T: IntoIterator<Item=Unit>>(iter: T) -> Self {
+        iter.into_iter().collect::<Vec<_>>().into()
+    }
+}
+
+impl IntoIterator for UnitList {
+    type Item = Unit;
+    type IntoIter = std::vec::IntoIter<Unit>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_vec().into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a UnitList {
+    type Item = &'a Unit;
+    type IntoIter = std::slice::Iter<'a, Unit>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+
+/// Lists are equal if they contain the same units regardless of their order, as the components of
+/// a compound unit are multiplied. So ```m*s``` equals ```s*m```.
+impl PartialEq for UnitList {
+    fn eq(&self, other: &Self) -> bool {
+        let (units, others) = (self.as_slice(), other.as_slice());
+        if units == others {
+            return true;
+        }
+        if units.len() != others.len() {
+            return false;
+        }
+
+        // The lists are short, so matching every unit with an unmatched equal one is cheap
+        let mut matched = vec![false; others.len()];
+        units.iter().all(|unit| {
+            match others.iter().zip(&matched).position(|(other, &matched)| !matched && other == unit) {
+                Some(position) => {
+                    matched[position] = true;
+                    true
+                }
+                None => false,
+            }
+        })
+    }
+}
+
+impl Eq for UnitList {}
+
+/// Hashes the units independently of their order, consistent with ```PartialEq```.
+impl Hash for UnitList {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let combined = self.iter().fold(0u64, |combined, unit| {
+            let mut hasher = DefaultHasher::new();
+            unit.hash(&mut hasher);
+            combined.wrapping_add(hasher.finish())
+        });
+        state.write_usize(self.len());
+        state.write_u64(combined);
+    }
+}
+
+impl Debug for UnitList {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::unit::Unit::*;
+
+    fn hash(list: &UnitList) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        list.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn static_and_shared_are_equal() {
+        static UNITS: [Unit; 2] = [Kilo, Meter];
+        let a = UnitList::from_static(&UNITS);
+        let b: UnitList = vec![Kilo, Meter].into();
+
+        assert_eq!(a, b);
+        assert_eq!(hash(&a), hash(&b));
+        assert_eq!(hash(&UnitList::new()), hash(&vec![].into()));
+    }
+
+    #[test]
+    fn order_is_ignored() {
+        let a: UnitList = vec![Meter, Second, Meter].into();
+        let b: UnitList = vec![Second, Meter, Meter].into();
+        assert_eq!(a, b);
+        assert_eq!(hash(&a), hash(&b));
+
+        assert_ne!(a, vec![Second, Second, Meter].into());
+        assert_ne!(a, vec![Meter, Second].into());
+        assert_ne!(UnitList::from(vec![Meter, Meter]), vec![Meter, Meter, Meter].into());
+    }
+
+    #[test]
+    fn make_mut_copies_on_write() {
+        let mut a: UnitList = vec![Meter].into();
+        let b = a.clone();
+        a.make_mut().push(Second);
+
+        assert_eq!(&a[..], &[Meter, Second]);
+        assert_eq!(&b[..], &[Meter]);
+
+        let mut c = UnitList::new();
+        c.make_mut().push(Gram);
+        assert_eq!(&c[..], &[Gram]);
+    }
+
+    #[test]
+    fn make_mut_invalidates_cache() {
+        let mut a: UnitList = vec![Meter].into();
+        assert_eq!(&a.symbols()[..], ["m"]);
+
+        a.make_mut().push(Meter);
+        assert_eq!(&a.symbols()[..], ["m^2"]);
+        assert_eq!(a.name(), "square meter ");
+
+        let mut b: UnitList = vec![Meter].into();
+        assert_eq!(b.exponents(), Meter.si_exponents());
+        b.make_mut().push(Second);
+        assert_eq!(b.exponents(), (Meter * Second).si_exponents());
+    }
+}
@@ -1,41 +1,65 @@
 #[macro_export]
 macro_rules! define_units {
-    ($($name:ident, $display_name:expr, $symbol:expr, $offset:expr, $slope:expr, $si_units:expr);*) => {
+    (@note) => { None };
+    (@note $note:expr) => { Some($note) };
+    (@modifier) => { false };
+    (@modifier modifier) => { true };
+    ($($(#[$category:ident])? $name:ident, $display_name:expr, $symbol:expr, $offset:expr, $slope:expr, $si_units:expr $(, $note:expr)?);*) => {
         /// A Unit of measurement.
         ///
         /// # Example:
         /// ```
-        /// # use tantalum_unit::c;
-        /// # use tantalum_unit::unit::Unit;
+        /// use tantalum_unit::prelude::*;
+        ///
         /// // Simple units
         /// let meter = Unit::Meter;
         /// let year = Unit::Year;
         ///
         /// // More complex units can be created using Unit::Compound
-        /// let joule_per_second = Unit::Compound(vec![Unit::Joule], vec![Unit::Second]);
-        /// let kilo_meter = Unit::Compound(vec![Unit::Kilo, Unit::Meter], vec![]);
+        /// let joule_per_second = Unit::Compound(vec![Unit::Joule].into(), vec![Unit::Second].into());
+        /// let kilo_meter = Unit::Compound(vec![Unit::Kilo, Unit::Meter].into(), vec![].into());
         ///
         /// // Or by multiplying/dividing units
-        /// use tantalum_unit::unit::Unit::*;
-        ///
         /// let joule_per_second = Joule / Second;
         /// let kilo_meter = Kilo * Meter;
+        ///
+        /// // Powers of units can be written with an exponent
+        /// let cubic_meter = Unit::Power(Box::new(Meter), 3);
         /// ```
-        #[derive(Clone, Debug, PartialEq, Hash, Eq)]
+        #[derive(Clone, Debug)]
         pub enum Unit {
             $($name,)*
             /// Represents a Unit as a fraction in the form
             /// ```
-            /// # use tantalum_unit::unit::Unit::Compound;
-            /// # let (numerator, denominator) = (vec![], vec![]);
+            /// # use tantalum_unit::prelude::*;
+            /// # let (numerator, denominator) = (vec![].into(), vec![].into());
             /// Compound(numerator, denominator);
             /// ```
-            Compound(Vec<Unit>, Vec<Unit>),
+            ///
+            /// Both lists are shared, so cloning a compound unit is cheap.
+            Compound($crate::unit_list::UnitList, $crate::unit_list::UnitList),
+            /// A unit raised to an integer power, like ```m^3``` or ```s^-2```.
+            ///
+            /// The exponent is kept as a count, so large powers stay cheap. ```flatten``` turns a
+            /// power into a ```Compound``` of powers of named units, and multiplying a unit with
+            /// itself merges the factors into a power. ```Power(Box::new(Meter), 3)``` is displayed
+            /// as ```m^3``` and compares equal to ```Meter * Meter * Meter```.
+            Power(Box<Unit>, i32),
         }
 
+        /// The positions of the named units in ```Unit::NAMED_UNITS```.
+        #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+        enum UnitIndex { $($name,)* }
+
         impl Unit {
+            /// Every named unit, in declaration order.
+            pub const NAMED_UNITS: &'static [Unit] = &[$(Unit::$name,)*];
+
             /// Converts a Unit to its SI representation, removing prefixes and returning offset and slope.
             ///
+            /// Compound units containing affine units like ```Celsius``` convert temperature
+            /// differences, so their offset is zero, see ```offset```.
+            ///
             /// # Returns:
             /// ```
             /// # let (offset, slope, unit) = (0,0,0);
@@ -44,42 +68,128 @@ macro_rules! define_units {
             ///
             /// # Example:
             /// ```
-            /// # use tantalum_unit::unit::Unit;
+            /// use tantalum_unit::prelude::*;
             /// let temperature = Unit::Celsius;
             /// let (offset, slope, unit) = temperature.to_si_units();
             /// // Returns (273.15, 1.0, Unit::Kelvin) because Celsius is defined as C = K + 273.15
             /// ```
-            pub fn to_si_units(mut self) -> (BigRational, BigRational, Unit) {
-                use crate::scalable_integer::BigRational;
+            pub fn to_si_units(self) -> (BigRational, BigRational, Unit) {
+                match self.flatten() {
+                    Compound(numerator, denominator) => {
+                        let unit = Compound(numerator.clone(), denominator.clone());
+                        let (offset, slope) = (unit.offset(), unit.slope());
+                        let mut new_numerator = Vec::with_capacity(numerator.len());
+                        let mut new_denominator = Vec::with_capacity(denominator.len());
 
-                self = self.flatten();
-                match self {
-                    $(Unit::$name => ($offset, $slope, $si_units),)*
+                        // The unit is flat, so every component is a named unit or a power of one
+                        for (u, exponent) in numerator.iter().map(Unit::as_power) {
+                            u.named_si_units().2.push_power_into(exponent, &mut new_numerator, &mut new_denominator);
+                        }
+                        for (u, exponent) in denominator.iter().map(Unit::as_power) {
+                            u.named_si_units().2.push_power_into(exponent, &mut new_denominator, &mut new_numerator);
+                        }
+
+                        (offset, slope, Compound(new_numerator.into(), new_denominator.into()).simplify())
+                    }
+                    u => u.named_si_units(),
+                }
+            }
 
+            /// The offset of the unit in its SI representation, as returned by ```to_si_units```.
+            ///
+            /// Only a lone affine unit like ```Celsius``` has an offset. Inside a compound unit like
+            /// ```Celsius / Hour``` or ```Joule / Celsius``` it measures a temperature difference, so
+            /// compound units convert by their slope alone and have no offset.
+            ///
+            /// # Example:
+            /// ```
+            /// use tantalum_unit::prelude::*;
+            ///
+            /// assert_eq!(Celsius.offset(), ratio!(5463, 20));
+            /// assert_eq!((Celsius / Hour).offset(), ratio!(0, 1));
+            /// assert_eq!((Kilo * Meter).offset(), ratio!(0, 1));
+            /// ```
+            pub fn offset(&self) -> BigRational {
+                match self.clone().flatten() {
+                    Compound(numerator, denominator) if numerator.len() == 1 && denominator.is_empty() && numerator[0].index().is_some() => {
+                        numerator[0].named_si_units().0
+                    }
+                    Compound(..) => zero!(),
+                    u => u.named_si_units().0,
+                }
+            }
+
+            /// The slope of the unit in its SI representation, as returned by ```to_si_units```.
+            /// For compound units it is the product of the slopes of the numerator divided by the
+            /// product of the slopes of the denominator.
+            ///
+            /// # Example:
+            /// ```
+            /// use tantalum_unit::prelude::*;
+            ///
+            /// assert_eq!(Inch.slope(), ratio!(127, 5000));
+            /// assert_eq!((Kilo * Meter / Hour).slope(), ratio!(5, 18));
+            /// ```
+            pub fn slope(&self) -> BigRational {
+                match self.clone().flatten() {
                     Compound(numerator, denominator) => {
-                        let mut offset = zero!();
                         let mut slope = one!();
-                        let mut new_numerator = Vec::new();
-                        let mut new_denominator = Vec::new();
 
-                        for u in numerator {
-                            let (n_offset, n_slope, n_unit) = u.to_si_units();
-                            offset += n_offset;
+                        // The unit is flat, so every component is a named unit or a power of one
+                        for u in numerator.iter() {
+                            let n_slope = u.component_slope();
                             // Multiply by the new slope without reducing the fraction
-                            slope = BigRational::new_raw(slope.numer().clone() * n_slope.numer().clone(), slope.denom().clone() * n_slope.denom().clone());
-                            new_numerator.push(n_unit);
+                            slope = BigRational::new_raw(slope.numer() * n_slope.numer(), slope.denom() * n_slope.denom());
+                            slope = Unit::bound_slope(slope);
                         }
 
-                        for u in denominator {
-                            let (n_offset, n_slope, n_unit) = u.to_si_units();
-                            offset += n_offset;
+                        for u in denominator.iter() {
+                            let n_slope = u.component_slope();
                             // Divide by the new slope without reducing the fraction
                             slope = BigRational::new_raw(slope.numer() * n_slope.denom(), slope.denom() * n_slope.numer());
-                            new_denominator.push(n_unit);
+                            slope = Unit::bound_slope(slope);
                         }
 
-                        (offset, slope.reduced(), Compound(new_numerator, new_denominator).simplify())
+                        slope.reduced()
                     }
+                    u => u.named_si_units().1,
+                }
+            }
+
+            /// The symbols of ```NAMED_UNITS```, in the same order.
+            pub(crate) const SYMBOLS: &'static [&'static str] = &[$($symbol,)*];
+
+            /// The position of a named unit in ```NAMED_UNITS```, or ```None``` for compound units.
+            pub(crate) fn index(&self) -> Option<usize> {
+                match self {
+                    $(Unit::$name => Some(UnitIndex::$name as usize),)*
+                    Compound(..) | Power(..) => None,
+                }
+            }
+
+            /// The named unit at ```index``` in ```NAMED_UNITS```, usable in ```const``` contexts.
+            pub(crate) const fn from_index(index: usize) -> Unit {
+                $(if index == UnitIndex::$name as usize {
+                    return Unit::$name;
+                })*
+                panic!("unit index out of range")
+            }
+
+            /// The SI representation of a single named unit, computed once per unit.
+            fn named_si_units(&self) -> (BigRational, BigRational, Unit) {
+                static TABLE: std::sync::OnceLock<Vec<(BigRational, BigRational, Unit)>> = std::sync::OnceLock::new();
+                let table = TABLE.get_or_init(|| vec![$(($offset, $slope, $si_units),)*]);
+
+                let index = self.index().expect("named_si_units called on a compound unit");
+                table[index].clone()
+            }
+
+            /// Checks if the unit is an SI or binary modifier like Micro or Kibi, which are the units
+            /// marked with ```#[modifier]``` in ```define_units!```.
+            pub const fn is_modifier(&self) -> bool {
+                match self {
+                    $(Unit::$name => $crate::define_units!(@modifier $($category)?),)*
+                    Unit::Compound(..) | Unit::Power(..) => false,
                 }
             }
 
@@ -87,116 +197,62 @@ macro_rules! define_units {
             ///
             /// This method respects the order in which units are added to a compound unit.
             /// ```
-            /// # use tantalum_unit::c;
-            /// # use tantalum_unit::unit::Unit;
-            /// use tantalum_unit::unit::Unit::*;
+            /// use tantalum_unit::prelude::*;
             ///
-            /// ((Volt * Ampere) / Second).symbol(); // Returns VA/s
-            /// ((Ampere * Volt) / Second).symbol(); // Returns AV/s
+            /// ((Volt * Ampere) / Second).symbol(); // Returns V*A/s
+            /// ((Ampere * Volt) / Second).symbol(); // Returns A*V/s
             /// ```
-            pub fn symbol(&self) -> String {
+            ///
+            /// Named units don't allocate and the rendering of shared compound units is cached.
+            pub fn symbol(&self) -> Cow<'static, str> {
                 use Unit::*;
                 match self {
-                    // force
-                    $($name => $symbol.to_owned(),)*
-                    Compound(n, d) => {
-                        if n.is_empty() & &d.is_empty() {
-                            "".to_owned()
-                        } else {
-                            fn count_units(units: &[Unit]) -> IndexMap<String, usize> {
-                                let mut counts = IndexMap::new();
-                                for unit in units {
-                                    let symbol = unit.symbol();
-                                    *counts.entry(symbol).or_insert(0) += 1;
-                                }
-                                counts
-                            }
-
-                            fn format_units(counts: IndexMap<String, usize>) -> String {
-                                counts
-                                    .into_iter()
-                                    .map(|(symbol, count)| {
-                                        if count > 1 {
-                                            format!("{}^{}", symbol, count)
-                                        } else {
-                                            symbol
-                                        }
-                                    })
-                                    .collect::<Vec<String>>()
-                                    .join("")
-                            }
-
-                            let numerator_counts = count_units(n);
-                            let denominator_counts = count_units(d);
-
-                            let numerator = format_units(numerator_counts);
-                            let denominator = format_units(denominator_counts);
-
-                            if numerator.is_empty() {
-                                format!("1/{}", denominator)
-                            } else if denominator.is_empty() {
-                                numerator
-                            } else {
-                                format!("{}/{}", numerator, denominator)
-                            }
-                        }
-                    }
+                    $($name => Cow::Borrowed($symbol),)*
+                    Compound(n, d) => Cow::Owned($crate::unit::fraction_symbol(&n.symbols(), &d.symbols())),
+                    Power(..) => Cow::Owned(self.clone().flatten().symbol().into_owned()),
                 }
             }
 
-            pub fn name(&self) -> String {
+            /// Returns how a named unit is defined, for units whose definition follows one of several
+            /// conventions. E.g. which year ```Year``` is.
+            ///
+            /// Compound units and units with a single accepted definition have no note.
+            /// ```
+            /// use tantalum_unit::prelude::*;
+            ///
+            /// assert!(Year.definition_note().unwrap().contains("Julian"));
+            /// assert_eq!(Meter.definition_note(), None);
+            /// ```
+            pub fn definition_note(&self) -> Option<&'static str> {
                 use Unit::*;
                 match self {
-                    $($name => $display_name.to_owned(),)*
+                    $($name => $crate::define_units!(@note $($note)?),)*
+                    Compound(..) | Power(..) => None,
+                }
+            }
 
+            /// Returns the name of a unit. E.g "meter" for Meter or "kilometer per hour" for km/h.
+            pub fn name(&self) -> Cow<'static, str> {
+                use Unit::*;
+                match self {
+                    $($name => Cow::Borrowed($display_name),)*
                     Compound(n, d) => {
-                        if n.is_empty() & &d.is_empty() {
-                            "".to_owned()
+                        let (numerator, denominator) = (n.name(), d.name());
+
+                        let name = if numerator.is_empty() && denominator.is_empty() {
+                            return Cow::Borrowed("");
+                        } else if numerator.is_empty() {
+                            format!("reciprocal {}", denominator)
+                        } else if denominator.is_empty() {
+                            numerator.into_owned()
                         } else {
-                            fn count_units(units: &[Unit]) -> IndexMap<String, usize> {
-                                let mut counts = IndexMap::new();
-                                for unit in units {
-                                    let mut name = unit.name();
-                                    if !unit.is_modifier() { name += " "; }
-                                    *counts.entry(name).or_insert(0) += 1;
-                                }
-                                counts
-                            }
-
-                            fn format_units(counts: IndexMap<String, usize>) -> String {
-                                counts
-                                    .into_iter()
-                                    .map(|(symbol, count)| {
-                                        if count > 3 {
-                                            format!("{} to the {}", symbol, count)
-                                        } else if count == 3 {
-                                            format!("cubic {}", symbol)
-                                        } else if count == 2 {
-                                            format!("square {}", symbol)
-                                        } else {
-                                            symbol
-                                        }
-                                    })
-                                    .collect::<Vec<String>>()
-                                    .join("")
-                            }
-
-                            let numerator_counts = count_units(n);
-                            let denominator_counts = count_units(d);
-
-                            let numerator = format_units(numerator_counts);
-                            let denominator = format_units(denominator_counts);
-
-                            if numerator.is_empty() {
-                                format!("reciprocal {}", denominator)
-                            } else if denominator.is_empty() {
-                                numerator
-                            } else {
-                                format!("{}per {}", numerator, denominator)
-                            }
-                        }
+                            format!("{}per {}", numerator, denominator)
+                        };
+
+                        Cow::Owned(name.trim_end().to_owned())
                     }
-                }.trim_end().to_owned()
+                    Power(..) => Cow::Owned(self.clone().flatten().name().into_owned()),
+                }
             }
         }
     };
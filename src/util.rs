@@ -1,11 +1,12 @@
 //! Helper macros for constructing ```BigRational``` and ```Unit```.
 
+use crate::unit::Unit;
+
 /// Constructs a ```BigRational``` from two ints.
 ///
 /// # Example:
 /// ```
-/// # use tantalum_unit::ratio;
-/// # use tantalum_unit::scalable_integer::BigRational;
+/// use tantalum_unit::prelude::*;
 /// let value: BigRational = ratio!(17, 3); // For the fraction 17/3
 /// ```
 #[macro_export]
@@ -33,40 +34,99 @@ macro_rules! one {
 
 /// A helper for constructing compound units.
 ///
+/// Components before the ```;``` form the numerator and components after it the denominator.
+/// A component can be raised to an integer power with ```^```, which repeats it. A negative
+/// power moves the component to the other side of the fraction.
+///
 /// # Example:
 /// ```
-/// # use tantalum_unit::c;
-/// # use tantalum_unit::unit::Unit;
-/// # use tantalum_unit::unit::Unit::{Joule, Second, Volt, Ampere};
+/// use tantalum_unit::prelude::*;
 /// let joules_per_second: Unit = c!(Joule; Second);
 /// let volt_ampere_second: Unit = c!(Volt, Ampere, Second;);
 /// let per_ampere: Unit = c!(;Ampere);
+///
+/// let acceleration: Unit = c!(Meter; Second^2);
+/// assert_eq!(acceleration, c!(Meter; Second, Second));
+/// assert_eq!(c!(Kilo, Gram, Meter^2; Second^3), c!(Kilo, Gram, Meter, Meter; Second, Second, Second));
+/// assert_eq!(c!(Meter^-1;), c!(; Meter));
 /// ```
 #[macro_export]
 macro_rules! c {
-    () => {
-        Unit::Compound(vec![], vec![])
+    // The tokens of the current component are collected in brackets until a ',', ';' or '^' ends it
+    (@numerator $n:ident $d:ident [$($component:tt)+] ^ $exponent:literal $($rest:tt)*) => {
+        $crate::util::__push_power(&mut $n, &mut $d, $($component)+, $exponent);
+        $crate::c!(@numerator_separator $n $d $($rest)*);
+    };
+    (@numerator $n:ident $d:ident [$($component:tt)+] , $($rest:tt)*) => {
+        $crate::util::__push_power(&mut $n, &mut $d, $($component)+, 1);
+        $crate::c!(@numerator $n $d [] $($rest)*);
+    };
+    (@numerator $n:ident $d:ident [$($component:tt)+] ; $($rest:tt)*) => {
+        $crate::util::__push_power(&mut $n, &mut $d, $($component)+, 1);
+        $crate::c!(@denominator $n $d [] $($rest)*);
+    };
+    (@numerator $n:ident $d:ident [] ; $($rest:tt)*) => {
+        $crate::c!(@denominator $n $d [] $($rest)*);
+    };
+    (@numerator $n:ident $d:ident [$($component:tt)+]) => {
+        $crate::util::__push_power(&mut $n, &mut $d, $($component)+, 1);
+    };
+    (@numerator $n:ident $d:ident []) => {};
+    (@numerator $n:ident $d:ident [$($component:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::c!(@numerator $n $d [$($component)* $next] $($rest)*);
     };
 
-    ($($a:expr),*;) => {
-        Unit::Compound(vec![$($a),*], vec![])
+    (@numerator_separator $n:ident $d:ident , $($rest:tt)*) => {
+        $crate::c!(@numerator $n $d [] $($rest)*);
+    };
+    (@numerator_separator $n:ident $d:ident ; $($rest:tt)*) => {
+        $crate::c!(@denominator $n $d [] $($rest)*);
     };
+    (@numerator_separator $n:ident $d:ident) => {};
 
-    (; $($b:expr),*) => {
-        Unit::Compound(vec![], vec![$($b),*])
+    (@denominator $n:ident $d:ident [$($component:tt)+] ^ $exponent:literal $($rest:tt)*) => {
+        $crate::util::__push_power(&mut $d, &mut $n, $($component)+, $exponent);
+        $crate::c!(@denominator_separator $n $d $($rest)*);
+    };
+    (@denominator $n:ident $d:ident [$($component:tt)+] , $($rest:tt)*) => {
+        $crate::util::__push_power(&mut $d, &mut $n, $($component)+, 1);
+        $crate::c!(@denominator $n $d [] $($rest)*);
+    };
+    (@denominator $n:ident $d:ident [$($component:tt)+]) => {
+        $crate::util::__push_power(&mut $d, &mut $n, $($component)+, 1);
+    };
+    (@denominator $n:ident $d:ident []) => {};
+    (@denominator $n:ident $d:ident [$($component:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::c!(@denominator $n $d [$($component)* $next] $($rest)*);
     };
 
-    ($($a:expr),*; $($b:expr),*) => {
-        Unit::Compound(vec![$($a),*], vec![$($b),*])
+    (@denominator_separator $n:ident $d:ident , $($rest:tt)*) => {
+        $crate::c!(@denominator $n $d [] $($rest)*);
     };
+    (@denominator_separator $n:ident $d:ident) => {};
+
+    ($($tokens:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut numerator: Vec<$crate::unit::Unit> = Vec::new();
+        #[allow(unused_mut)]
+        let mut denominator: Vec<$crate::unit::Unit> = Vec::new();
+        $crate::c!(@numerator numerator denominator [] $($tokens)*);
+        $crate::unit::Unit::Compound(numerator.into(), denominator.into())
+    }};
+}
+
+/// Adds ```exponent``` copies of ```unit``` to ```side```, or to ```other``` if the exponent is negative.
+#[doc(hidden)]
+pub fn __push_power(side: &mut Vec<Unit>, other: &mut Vec<Unit>, unit: Unit, exponent: i32) {
+    let target = if exponent < 0 { other } else { side };
+    target.extend(std::iter::repeat_n(unit, exponent.unsigned_abs() as usize));
 }
 
 /// Constructs a ```BigRational``` from an int.
 ///
 /// # Example:
 /// ```
-/// # use tantalum_unit::scalable_integer::BigRational;
-/// # use tantalum_unit::int;
+/// use tantalum_unit::prelude::*;
 /// let eight: BigRational = int!(8);
 /// ```
 #[macro_export]
@@ -76,3 +136,137 @@ macro_rules! int {
     };
 }
 
+
+/// Constructs a ```Quantity``` from a number literal followed by a unit expression.
+///
+/// The number may be an integer, a decimal like ```3.5``` or ```1e-3```, or a fraction like
+/// ```1/2```, and is converted exactly. The unit uses the syntax described in the ```parse```
+/// module and is checked at compile time, so an unknown unit is a compile error.
+///
+/// # Example:
+/// ```
+/// use tantalum_unit::prelude::*;
+///
+/// assert_eq!(q!(5 km), Quantity::new(ratio!(5, 1), Kilo * Meter));
+/// assert_eq!(q!(3.5 m/s^2), Quantity::new(ratio!(7, 2), Meter / (Second * Second)));
+/// assert_eq!(q!(1/2 L), Quantity::new(ratio!(1, 2), Liter));
+/// assert_eq!(q!(-40 K), Quantity::new(ratio!(-40, 1), Kelvin));
+/// assert_eq!(q!(42), Quantity::from_i64(42));
+/// ```
+///
+/// Unknown units, malformed exponents and unsupported literals don't compile:
+/// ```compile_fail
+/// let _ = tantalum_unit::q!(5 furlongs);
+/// ```
+/// ```compile_fail
+/// let _ = tantalum_unit::q!(5 m/s^0);
+/// ```
+/// ```compile_fail
+/// let _ = tantalum_unit::q!(5 m/);
+/// ```
+/// ```compile_fail
+/// let _ = tantalum_unit::q!(5u8 m);
+/// ```
+/// ```compile_fail
+/// let _ = tantalum_unit::q!(1/0 m);
+/// ```
+#[macro_export]
+macro_rules! q {
+    (- $($rest:tt)+) => {
+        -$crate::q!($($rest)+)
+    };
+
+    ($numerator:literal / $denominator:literal $($unit:tt)*) => {{
+        const NUMERATOR: (i128, i32) = $crate::parse::__decimal_literal(stringify!($numerator));
+        const DENOMINATOR: (i128, i32) = $crate::parse::__decimal_literal(stringify!($denominator));
+        const _: () = assert!(DENOMINATOR.0 != 0, "the denominator is zero");
+
+        $crate::quantity::Quantity::new(
+            $crate::parse::__decimal(NUMERATOR.0, NUMERATOR.1) / $crate::parse::__decimal(DENOMINATOR.0, DENOMINATOR.1),
+            $crate::unit!($($unit)*),
+        )
+    }};
+
+    ($value:literal $($unit:tt)*) => {{
+        const VALUE: (i128, i32) = $crate::parse::__decimal_literal(stringify!($value));
+        $crate::quantity::Quantity::new($crate::parse::__decimal(VALUE.0, VALUE.1), $crate::unit!($($unit)*))
+    }};
+}
+
+/// Constructs a ```Unit``` from a unit expression, which is checked at compile time.
+///
+/// The expression uses the syntax described in the ```parse``` module and can be given as a
+/// string literal or as tokens. Symbols that aren't valid Rust tokens, like ```°C```, need the string form.
+/// The result is the same unit that parsing the expression with ```FromStr``` returns.
+///
+/// # Example:
+/// ```
+/// use tantalum_unit::prelude::*;
+/// use tantalum_unit::unit;
+///
+/// assert_eq!(unit!("kg*m/s^2"), Kilo * Gram * Meter / (Second * Second));
+/// assert_eq!(unit!(kg m / s^2), Kilo * Gram * Meter / (Second * Second));
+/// assert_eq!(unit!("°C"), Celsius);
+/// assert_eq!(unit!(km/h), "km/h".parse().unwrap());
+/// ```
+///
+/// Unknown symbols don't compile, and the error names the symbol:
+/// ```compile_fail
+/// let _ = tantalum_unit::unit!(m / fortnight);
+/// ```
+/// ```compile_fail
+/// let _ = tantalum_unit::unit!("kg*m/s^");
+/// ```
+#[macro_export]
+macro_rules! unit {
+    ($source:literal) => {
+        $crate::__unit_from_source!($source)
+    };
+
+    ($($unit:tt)*) => {
+        $crate::__unit_from_source!(stringify!($($unit)*))
+    };
+}
+
+/// Builds a ```Unit``` from a constant unit expression, validating it at compile time.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __unit_from_source {
+    ($source:expr) => {{
+        const SOURCE: &str = $source;
+        const SIZES: (usize, usize) = $crate::parse::__unit_sizes(SOURCE);
+        static UNITS: ([$crate::unit::Unit; SIZES.0], [$crate::unit::Unit; SIZES.1]) = $crate::parse::__unit_components(SOURCE);
+        $crate::parse::__unit(&UNITS.0, &UNITS.1)
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::unit::Unit::*;
+    use crate::unit::UNITLESS;
+
+    #[test]
+    fn compound_exponents() {
+        assert_eq!(c!(Meter^3; Second^2), c!(Meter, Meter, Meter; Second, Second));
+        assert_eq!(c!(Kilo, Gram, Meter^2; Second^3), (Kilo * Gram * Meter * Meter) / (Second * Second * Second));
+        assert_eq!(c!(Meter^1; Second^1), c!(Meter; Second));
+        assert_eq!(c!(Kilo^12;), Compound(vec![Kilo; 12].into(), vec![].into()));
+        assert_eq!(c!(Second^0, Meter;), c!(Meter;));
+        assert_eq!(c!(Meter^2), c!(Meter, Meter;));
+    }
+
+    #[test]
+    fn compound_negative_exponents() {
+        assert_eq!(c!(Meter^-1;), c!(; Meter));
+        assert_eq!(c!(Kilo, Meter, Hour^-1;), c!(Kilo, Meter; Hour));
+        assert_eq!(c!(Ampere; Second^-2, Volt), c!(Ampere, Second, Second; Volt));
+    }
+
+    #[test]
+    fn compound_nested() {
+        assert_eq!(c!(c!(Meter; Second)^2; Second), c!(c!(Meter; Second), c!(Meter; Second); Second));
+        assert_eq!(c!(Kilo * Meter, Meter; Hour), Compound(vec![Kilo * Meter, Meter].into(), vec![Hour].into()));
+        assert_eq!(c!(), UNITLESS);
+        assert_eq!(c!(;), UNITLESS);
+    }
+}